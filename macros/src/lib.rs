@@ -0,0 +1,93 @@
+//! Attribute macros for [`assert_fs`](https://docs.rs/assert_fs).
+//!
+//! Use through `assert_fs`, with its `macros` feature, rather than directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+/// Run a test with a fresh `TempDir`.
+///
+/// The test takes the fixture as its only argument, either borrowed, `temp: &TempDir`, or
+/// owned, `temp: TempDir`.  The macro adds `#[test]`, creates the directory, and keeps it if the
+/// test panics, printing its path (see `TempDir::persist_on_failure`).  Otherwise, a borrowed
+/// fixture is closed once the test returns, failing the test if it can't be removed.
+///
+/// ```rust,ignore
+/// use assert_fs::prelude::*;
+/// use assert_fs::TempDir;
+///
+/// #[assert_fs::fixture]
+/// fn writes_report(temp: &TempDir) {
+///     temp.child("report.txt").write_str("ok").unwrap();
+///     temp.child("report.txt").assert("ok");
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn fixture(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let item = syn::parse_macro_input!(item as syn::ItemFn);
+    match expand(args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    args: proc_macro2::TokenStream,
+    item: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if !args.is_empty() {
+        return Err(syn::Error::new(args.span(), "`fixture` takes no arguments"));
+    }
+    let syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item;
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "`fixture` tests can't be `async`",
+        ));
+    }
+    let param = match (sig.inputs.len(), sig.inputs.first()) {
+        (1, Some(syn::FnArg::Typed(param))) => param,
+        _ => {
+            return Err(syn::Error::new(
+                sig.inputs.span(),
+                "`fixture` tests take one argument, `&TempDir` or `TempDir`",
+            ))
+        }
+    };
+    let borrowed = matches!(*param.ty, syn::Type::Reference(_));
+    // Added below, tolerate it being spelled out
+    let attrs: Vec<_> = attrs
+        .into_iter()
+        .filter(|attr| !attr.path().is_ident("test"))
+        .collect();
+
+    let name = &sig.ident;
+    let output = &sig.output;
+    let inputs = &sig.inputs;
+    let call = if borrowed {
+        quote! {
+            let result = inner(&temp);
+            temp.close().unwrap();
+            result
+        }
+    } else {
+        quote! { inner(temp) }
+    };
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #output {
+            fn inner(#inputs) #output #block
+
+            let temp = ::assert_fs::TempDir::new().unwrap().persist_on_failure();
+            #call
+        }
+    })
+}
@@ -0,0 +1,187 @@
+use std::future::Future;
+use std::path;
+
+use crate::fixture;
+
+use super::IntoPathPredicate;
+
+/// Assert the state of files within [`TempDir`] without blocking the async runtime.
+///
+/// Like [`PathAssert::assert`][super::PathAssert::assert], with the predicate evaluated on
+/// Tokio's blocking thread pool.  A failed assertion panics the awaiting task.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use predicates::prelude::*;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let input_file = temp.child("foo.txt");
+/// input_file.write_str_async("").await.unwrap();
+///
+/// input_file.assert_async("").await;
+/// temp.child("bar.txt")
+///     .assert_async(predicate::path::missing())
+///     .await;
+///
+/// temp.close().unwrap();
+/// # });
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertAsync {
+    /// Assert the state of files within [`TempDir`].
+    ///
+    /// Accepts the same short-hands as [`PathAssert::assert`][super::PathAssert::assert].
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the condition is not satisfied
+    ///
+    /// [`TempDir`]: crate::TempDir
+    fn assert_async<I, P>(&self, pred: I) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static;
+
+    /// Assert the state of files within [`TempDir`] is reached, re-checking according to
+    /// `retry`.
+    ///
+    /// Like [`PathAssert::assert_eventually`][super::PathAssert::assert_eventually], for output
+    /// written by a background task or a daemon.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the condition is still not satisfied once `retry` gives up, showing the
+    /// last observed state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::Retry;
+    /// use assert_fs::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let writer = temp.child("server.log");
+    /// std::thread::spawn(move || writer.write_str("listening").unwrap());
+    ///
+    /// let retry = Retry::poll(Duration::from_secs(10), Duration::from_millis(50));
+    /// temp.child("server.log")
+    ///     .assert_eventually_async("listening", &retry)
+    ///     .await;
+    ///
+    /// temp.close().unwrap();
+    /// # });
+    /// ```
+    ///
+    /// [`TempDir`]: crate::TempDir
+    fn assert_eventually_async<I, P>(
+        &self,
+        pred: I,
+        retry: &fixture::Retry,
+    ) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static;
+}
+
+impl PathAssertAsync for fixture::TempDir {
+    fn assert_async<I, P>(&self, pred: I) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static,
+    {
+        assert_async(self.path(), pred.into_path())
+    }
+
+    fn assert_eventually_async<I, P>(
+        &self,
+        pred: I,
+        retry: &fixture::Retry,
+    ) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static,
+    {
+        assert_eventually_async(self.path(), pred.into_path(), retry)
+    }
+}
+
+impl PathAssertAsync for fixture::NamedTempFile {
+    fn assert_async<I, P>(&self, pred: I) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static,
+    {
+        assert_async(self.path(), pred.into_path())
+    }
+
+    fn assert_eventually_async<I, P>(
+        &self,
+        pred: I,
+        retry: &fixture::Retry,
+    ) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static,
+    {
+        assert_eventually_async(self.path(), pred.into_path(), retry)
+    }
+}
+
+impl PathAssertAsync for fixture::ChildPath {
+    fn assert_async<I, P>(&self, pred: I) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static,
+    {
+        assert_async(self.path(), pred.into_path())
+    }
+
+    fn assert_eventually_async<I, P>(
+        &self,
+        pred: I,
+        retry: &fixture::Retry,
+    ) -> impl Future<Output = ()> + Send
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path> + Send + 'static,
+    {
+        assert_eventually_async(self.path(), pred.into_path(), retry)
+    }
+}
+
+fn assert_async<P>(path: &path::Path, pred: P) -> impl Future<Output = ()> + Send
+where
+    P: predicates_core::Predicate<path::Path> + Send + 'static,
+{
+    let path = path.to_owned();
+    blocking(move || super::assert(&path, pred))
+}
+
+fn assert_eventually_async<P>(
+    path: &path::Path,
+    pred: P,
+    retry: &fixture::Retry,
+) -> impl Future<Output = ()> + Send
+where
+    P: predicates_core::Predicate<path::Path> + Send + 'static,
+{
+    let path = path.to_owned();
+    let retry = retry.clone();
+    blocking(move || super::assert_eventually(&path, pred, &retry))
+}
+
+/// Run `check` on the blocking thread pool, resuming its panic in the awaiting task.
+async fn blocking<F>(check: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    if let Err(err) = tokio::task::spawn_blocking(check).await {
+        std::panic::resume_unwind(err.into_panic());
+    }
+}
@@ -0,0 +1,100 @@
+use std::path;
+
+use crate::fixture;
+
+/// Assert on the extended attributes of files within [`TempDir`] (requires the `xattr`
+/// feature).
+///
+/// See [`PathXattr`][crate::fixture::PathXattr] for how attributes map to each platform.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let file = temp.child("photo.jpg");
+/// file.touch().unwrap();
+///
+/// if file.set_xattr("user.origin", b"camera").is_ok() {
+///     file.assert_no_xattr("user.comment");
+/// }
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertXattr {
+    /// Assert the attribute `name` is set to `expected`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the attribute is missing, can't be read, or has a different value.
+    #[track_caller]
+    fn assert_xattr(&self, name: &str, expected: &[u8]) -> &Self;
+
+    /// Assert the attribute `name` isn't set.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the attribute is set or can't be read.
+    #[track_caller]
+    fn assert_no_xattr(&self, name: &str) -> &Self;
+}
+
+impl PathAssertXattr for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_xattr(&self, name: &str, expected: &[u8]) -> &Self {
+        assert_xattr(self.path(), name, Some(expected));
+        self
+    }
+
+    #[track_caller]
+    fn assert_no_xattr(&self, name: &str) -> &Self {
+        assert_xattr(self.path(), name, None);
+        self
+    }
+}
+
+impl PathAssertXattr for fixture::ChildPath {
+    #[track_caller]
+    fn assert_xattr(&self, name: &str, expected: &[u8]) -> &Self {
+        assert_xattr(self.path(), name, Some(expected));
+        self
+    }
+
+    #[track_caller]
+    fn assert_no_xattr(&self, name: &str) -> &Self {
+        assert_xattr(self.path(), name, None);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_xattr(path: &path::Path, name: &str, expected: Option<&[u8]>) {
+    let actual = match fixture::xattr(path, name) {
+        Ok(actual) => actual,
+        Err(err) => panic!("{}", err),
+    };
+    if actual.as_deref() != expected {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected extended attribute\n{}={}\n{}={}\n{}={}\n{}={}",
+            palette.key.paint("name"),
+            palette.value.paint(name),
+            palette.key.paint("expected"),
+            palette.value.paint(describe(expected)),
+            palette.key.paint("actual"),
+            palette.value.paint(describe(actual.as_deref())),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
+
+fn describe(value: Option<&[u8]>) -> String {
+    match value {
+        Some(value) => format!("{:?}", String::from_utf8_lossy(value)),
+        None => "<unset>".to_owned(),
+    }
+}
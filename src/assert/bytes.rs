@@ -0,0 +1,468 @@
+use std::fmt::Write;
+use std::io;
+use std::io::Read;
+use std::path;
+
+use crate::fixture;
+
+/// Bytes shown per hex dump row.
+const ROW: usize = 16;
+
+/// Rows shown around the first difference.
+const CONTEXT_ROWS: usize = 2;
+
+/// Bytes compared at a time when streaming, a multiple of [`ROW`].
+const CHUNK: usize = 64 * 1024;
+
+/// Assert the exact binary content of files within [`TempDir`].
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let output = temp.child("header.bin");
+/// output.write_binary(b"\x89PNG\r\n\x1a\n").unwrap();
+///
+/// output.assert_bytes(b"\x89PNG\r\n\x1a\n");
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertBytes {
+    /// Assert the file's content is `expected`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read or its content differs, showing both lengths and a
+    /// hex dump of the expected and actual content around the first difference.
+    #[track_caller]
+    fn assert_bytes<B>(&self, expected: B) -> &Self
+    where
+        B: AsRef<[u8]>;
+
+    /// Assert the file's content is the same as the file at `other`.
+    ///
+    /// Both are read a chunk at a time, so files of any size are compared in constant memory.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either file can't be read or their content differs, showing the first
+    /// differing offset and a hex dump of both around it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let original = temp.child("original.bin");
+    /// original.write_random_bytes(1 << 20, 7).unwrap();
+    /// let restored = temp.child("restored.bin");
+    /// // e.g. the code under test restores a backup
+    /// restored.write_file(original.path()).unwrap();
+    ///
+    /// restored.assert_same_content_as(original.path());
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_same_content_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the file's content is everything read from `expected`, like a decompressor or a
+    /// generator too large to hold in memory.
+    ///
+    /// Both are read a chunk at a time, so content of any size is compared in constant memory.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file or `expected` can't be read or their content differs, showing the
+    /// first differing offset and a hex dump of both around it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let zeros = temp.child("zeros.bin");
+    /// zeros.write_zeros(1 << 20).unwrap();
+    ///
+    /// zeros.assert_content_from(std::io::repeat(0).take(1 << 20));
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_content_from<R>(&self, expected: R) -> &Self
+    where
+        R: Read;
+}
+
+impl PathAssertBytes for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_bytes<B>(&self, expected: B) -> &Self
+    where
+        B: AsRef<[u8]>,
+    {
+        assert_bytes(self.path(), expected.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_content_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_content_as(self.path(), other.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_content_from<R>(&self, expected: R) -> &Self
+    where
+        R: Read,
+    {
+        assert_content_from(self.path(), expected, None);
+        self
+    }
+}
+
+impl PathAssertBytes for fixture::ChildPath {
+    #[track_caller]
+    fn assert_bytes<B>(&self, expected: B) -> &Self
+    where
+        B: AsRef<[u8]>,
+    {
+        assert_bytes(self.path(), expected.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_content_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_content_as(self.path(), other.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_content_from<R>(&self, expected: R) -> &Self
+    where
+        R: Read,
+    {
+        assert_content_from(self.path(), expected, None);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_bytes(path: &path::Path, expected: &[u8]) {
+    let actual = match std::fs::read(path) {
+        Ok(actual) => actual,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    let offset = match first_difference(expected, &actual) {
+        Some(offset) => offset,
+        None => return,
+    };
+    let palette = crate::Palette::current();
+    crate::report::fail_with(
+        format!(
+            "Unexpected file content, differs at offset {:#x}\n{}={} bytes\n{}={} bytes\n{}={}\n{}:\n{}{}:\n{}",
+            offset,
+            palette.key.paint("expected"),
+            expected.len(),
+            palette.key.paint("actual"),
+            actual.len(),
+            palette.key.paint("path"),
+            palette.value.paint(path.display()),
+            palette.key.paint("expected"),
+            hex_window(expected, offset),
+            palette.key.paint("actual"),
+            hex_window(&actual, offset),
+        ),
+        vec![("expected", expected.to_owned()), ("actual", actual)],
+    );
+}
+
+#[track_caller]
+fn assert_same_content_as(path: &path::Path, other: &path::Path) {
+    let expected = match std::fs::File::open(other) {
+        Ok(expected) => expected,
+        Err(err) => panic!("Failed to read `{}`: {}", other.display(), err),
+    };
+    assert_content_from(path, expected, Some(other));
+}
+
+#[track_caller]
+fn assert_content_from<R>(path: &path::Path, mut expected: R, expected_path: Option<&path::Path>)
+where
+    R: Read,
+{
+    let mut actual = match std::fs::File::open(path) {
+        Ok(actual) => actual,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    let difference = match stream_difference(&mut expected, &mut actual) {
+        Ok(Some(difference)) => difference,
+        Ok(None) => return,
+        Err(err) => panic!("Failed to compare `{}`: {}", path.display(), err),
+    };
+    let palette = crate::Palette::current();
+    let mut message = format!(
+        "Unexpected file content, differs at offset {:#x}\n",
+        difference.offset
+    );
+    if let Some(expected_path) = expected_path {
+        let _ = writeln!(
+            message,
+            "{}={}",
+            palette.key.paint("expected path"),
+            palette.value.paint(expected_path.display())
+        );
+    }
+    let _ = write!(
+        message,
+        "{}={}\n{}:\n{}{}:\n{}",
+        palette.key.paint("path"),
+        palette.value.paint(path.display()),
+        palette.key.paint("expected"),
+        difference.expected.dump(difference.offset),
+        palette.key.paint("actual"),
+        difference.actual.dump(difference.offset),
+    );
+    crate::report::fail(message);
+}
+
+/// Where two streams first differ, with the content of each around it.
+struct Difference {
+    offset: u64,
+    expected: Window,
+    actual: Window,
+}
+
+/// Content read from a stream, starting at `base`.
+struct Window {
+    base: u64,
+    data: Vec<u8>,
+    /// Whether the stream ends with `data`.
+    complete: bool,
+}
+
+impl Window {
+    fn new(base: u64, tail: &[u8], chunk: &[u8]) -> Self {
+        let mut data = tail.to_vec();
+        data.extend_from_slice(chunk);
+        Self {
+            base,
+            data,
+            complete: chunk.len() < CHUNK,
+        }
+    }
+
+    fn dump(&self, offset: u64) -> String {
+        hex_rows(
+            &self.data,
+            self.base,
+            (offset - self.base) as usize,
+            self.complete,
+        )
+    }
+}
+
+/// Compare `expected` and `actual` a chunk at a time, keeping the rows before each chunk for
+/// context.
+fn stream_difference(
+    expected: &mut dyn Read,
+    actual: &mut dyn Read,
+) -> io::Result<Option<Difference>> {
+    let context = CONTEXT_ROWS * ROW;
+    let mut expected_chunk = vec![0; CHUNK];
+    let mut actual_chunk = vec![0; CHUNK];
+    let mut expected_tail = Vec::with_capacity(context);
+    let mut actual_tail = Vec::with_capacity(context);
+    let mut pos = 0u64;
+    loop {
+        let expected_len = fill(expected, &mut expected_chunk)?;
+        let actual_len = fill(actual, &mut actual_chunk)?;
+        let (expected_chunk, actual_chunk) =
+            (&expected_chunk[..expected_len], &actual_chunk[..actual_len]);
+        if let Some(index) = first_difference(expected_chunk, actual_chunk) {
+            let base = pos - expected_tail.len() as u64;
+            return Ok(Some(Difference {
+                offset: pos + index as u64,
+                expected: Window::new(base, &expected_tail, expected_chunk),
+                actual: Window::new(base, &actual_tail, actual_chunk),
+            }));
+        }
+        if expected_len < CHUNK {
+            return Ok(None);
+        }
+        pos += CHUNK as u64;
+        expected_tail.clear();
+        expected_tail.extend_from_slice(&expected_chunk[CHUNK - context..]);
+        actual_tail.clear();
+        actual_tail.extend_from_slice(&actual_chunk[CHUNK - context..]);
+    }
+}
+
+/// Read into `buf` until it's full or `reader` ends, returning how much was read.
+fn fill(reader: &mut dyn Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(len)
+}
+
+/// The offset of the first byte that differs, including where one side ends early.
+fn first_difference(expected: &[u8], actual: &[u8]) -> Option<usize> {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(e, a)| e != a)
+        .or_else(|| {
+            if expected.len() != actual.len() {
+                Some(expected.len().min(actual.len()))
+            } else {
+                None
+            }
+        })
+}
+
+/// A hex dump of the rows of `data` around `offset`, marking the row containing it.
+fn hex_window(data: &[u8], offset: usize) -> String {
+    hex_rows(data, 0, offset, true)
+}
+
+/// Like [`hex_window`], for `data` read from `base` on, which is the end of the content if
+/// `complete`.
+///
+/// `base` is a multiple of [`ROW`], so rows line up with those of the whole content.
+fn hex_rows(data: &[u8], base: u64, offset: usize, complete: bool) -> String {
+    let row = offset / ROW;
+    let first = row.saturating_sub(CONTEXT_ROWS) * ROW;
+    let last = ((row + CONTEXT_ROWS + 1) * ROW).min(data.len());
+    let end = base + data.len() as u64;
+    let mut dump = String::new();
+    if first >= data.len() {
+        let _ = writeln!(dump, "  {:08x}  <end of content>", end);
+        return dump;
+    }
+    for (i, chunk) in data[first..last].chunks(ROW).enumerate() {
+        let start = first + i * ROW;
+        let marker = if start / ROW == row { '>' } else { ' ' };
+        let mut hex = String::new();
+        for (j, byte) in chunk.iter().enumerate() {
+            if j == ROW / 2 {
+                hex.push(' ');
+            }
+            let _ = write!(hex, "{:02x} ", byte);
+        }
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(
+            dump,
+            "{} {:08x}  {:<49} |{}|",
+            marker,
+            base + start as u64,
+            hex,
+            ascii
+        );
+    }
+    if complete && last == data.len() {
+        let _ = writeln!(dump, "  {:08x}  <end of content>", end);
+    }
+    dump
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_difference_offsets() {
+        assert_eq!(first_difference(b"abc", b"abc"), None);
+        assert_eq!(first_difference(b"abc", b"abd"), Some(2));
+        assert_eq!(first_difference(b"abc", b"ab"), Some(2));
+        assert_eq!(first_difference(b"", b"a"), Some(0));
+    }
+
+    #[test]
+    fn hex_window_marks_difference() {
+        let data: Vec<u8> = (0..100).collect();
+        let dump = hex_window(&data, 50);
+        let lines: Vec<_> = dump.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("  00000010  10 11"), "{}", dump);
+        assert!(lines[2].starts_with("> 00000030  30 31"), "{}", dump);
+        assert!(lines[4].starts_with("  00000050  50 51"), "{}", dump);
+    }
+
+    #[test]
+    fn hex_window_past_end() {
+        let dump = hex_window(b"ab", 2);
+        let lines: Vec<_> = dump.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("> 00000000  61 62 "), "{}", dump);
+        assert!(lines[0].ends_with(" |ab|"), "{}", dump);
+        assert_eq!(lines[1], "  00000002  <end of content>");
+        assert_eq!(hex_window(b"", 0), "  00000000  <end of content>\n");
+    }
+
+    #[test]
+    fn stream_difference_across_chunks() {
+        let expected = vec![7u8; CHUNK * 2 + 10];
+        let mut actual = expected.clone();
+        assert!(stream_difference(&mut &expected[..], &mut &actual[..])
+            .unwrap()
+            .is_none());
+
+        actual[CHUNK + 3] = 0;
+        let difference = stream_difference(&mut &expected[..], &mut &actual[..])
+            .unwrap()
+            .unwrap();
+        assert_eq!(difference.offset, (CHUNK + 3) as u64);
+        let dump = difference.actual.dump(difference.offset);
+        let lines: Vec<_> = dump.lines().collect();
+        assert_eq!(lines.len(), 5, "{}", dump);
+        assert!(
+            lines[0].starts_with(&format!("  {:08x}", CHUNK - 2 * ROW)),
+            "{}",
+            dump
+        );
+        assert!(
+            lines[2].starts_with(&format!("> {:08x}  07 07 07 00", CHUNK)),
+            "{}",
+            dump
+        );
+
+        let difference = stream_difference(&mut &expected[..], &mut &expected[..CHUNK * 2][..])
+            .unwrap()
+            .unwrap();
+        assert_eq!(difference.offset, (CHUNK * 2) as u64);
+        assert!(difference
+            .actual
+            .dump(difference.offset)
+            .ends_with(&format!("  {:08x}  <end of content>\n", CHUNK * 2)));
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::io;
+use std::path;
+
+use sha2::Digest;
+
+use crate::fixture;
+
+/// Assert the checksum of files within [`TempDir`].
+///
+/// Large outputs can be checked without embedding their content in the test.  Files are
+/// streamed, rather than read into memory.
+///
+/// Requires the `checksum` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let output = temp.child("dist/app.txt");
+/// output.write_str("abc").unwrap();
+///
+/// output.assert_sha256("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+/// output.assert_md5("900150983cd24fb0d6963f7d28e17f72");
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertDigest {
+    /// Assert the file's SHA-256 is `expected`, in hex.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read or its digest differs.
+    #[track_caller]
+    fn assert_sha256(&self, expected: &str) -> &Self;
+
+    /// Assert the file's MD5 is `expected`, in hex.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read or its digest differs.
+    #[track_caller]
+    fn assert_md5(&self, expected: &str) -> &Self;
+}
+
+impl PathAssertDigest for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_sha256(&self, expected: &str) -> &Self {
+        assert_digest::<sha2::Sha256>(self.path(), "sha256", expected);
+        self
+    }
+
+    #[track_caller]
+    fn assert_md5(&self, expected: &str) -> &Self {
+        assert_digest::<md5::Md5>(self.path(), "md5", expected);
+        self
+    }
+}
+
+impl PathAssertDigest for fixture::ChildPath {
+    #[track_caller]
+    fn assert_sha256(&self, expected: &str) -> &Self {
+        assert_digest::<sha2::Sha256>(self.path(), "sha256", expected);
+        self
+    }
+
+    #[track_caller]
+    fn assert_md5(&self, expected: &str) -> &Self {
+        assert_digest::<md5::Md5>(self.path(), "md5", expected);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_digest<D>(path: &path::Path, algorithm: &str, expected: &str)
+where
+    D: Digest + io::Write,
+{
+    let mut hasher = D::new();
+    let hashed = fs::File::open(path).and_then(|mut file| io::copy(&mut file, &mut hasher));
+    if let Err(err) = hashed {
+        panic!("Failed to read `{}`: {}", path.display(), err);
+    }
+    let actual = crate::hex::encode(&hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected file {}\n{}={}\n{}={}\n{}={}",
+            algorithm,
+            palette.key.paint("expected"),
+            palette.value.paint(expected),
+            palette.key.paint("actual"),
+            palette.value.paint(actual),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
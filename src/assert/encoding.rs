@@ -0,0 +1,164 @@
+use std::fmt;
+use std::path;
+
+use crate::fixture;
+
+/// A text encoding, as detected from a file's content.
+///
+/// See [`PathAssertEncoding`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// 7-bit ASCII.
+    Ascii,
+    /// UTF-8 without a byte-order mark.
+    ///
+    /// As ASCII is a subset of UTF-8, ASCII content also satisfies this encoding.
+    Utf8,
+    /// UTF-8 with a byte-order mark.
+    Utf8Bom,
+    /// UTF-16, little-endian, with a byte-order mark.
+    Utf16Le,
+    /// UTF-16, big-endian, with a byte-order mark.
+    Utf16Be,
+    /// A single-byte encoding like ISO-8859-1 or Windows-1252: not valid UTF-8 and no NUL bytes.
+    ///
+    /// As ASCII is a subset of these encodings, ASCII content also satisfies this encoding.
+    Latin1,
+    /// Content that doesn't look like any of the other encodings.
+    Binary,
+}
+
+impl Encoding {
+    /// Detect the encoding of `data`.
+    pub fn detect(data: &[u8]) -> Self {
+        if data.starts_with(b"\xEF\xBB\xBF") {
+            Encoding::Utf8Bom
+        } else if data.starts_with(b"\xFF\xFE") {
+            Encoding::Utf16Le
+        } else if data.starts_with(b"\xFE\xFF") {
+            Encoding::Utf16Be
+        } else if data.contains(&0) {
+            Encoding::Binary
+        } else if data.is_ascii() {
+            Encoding::Ascii
+        } else if std::str::from_utf8(data).is_ok() {
+            Encoding::Utf8
+        } else {
+            Encoding::Latin1
+        }
+    }
+
+    fn accepts(self, detected: Self) -> bool {
+        match self {
+            Encoding::Utf8 | Encoding::Latin1 => detected == self || detected == Encoding::Ascii,
+            _ => detected == self,
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Encoding::Ascii => "ASCII",
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf8Bom => "UTF-8 with BOM",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Latin1 => "single-byte (Latin-1)",
+            Encoding::Binary => "binary",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Assert the text encoding of files within [`TempDir`].
+///
+/// Detection is heuristic: it relies on byte-order marks and on whether the content decodes as
+/// UTF-8 or ASCII.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::assert::Encoding;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let output = temp.child("output.txt");
+/// output.write_binary(b"\xEF\xBB\xBFcaf\xC3\xA9").unwrap();
+///
+/// output.assert_encoding(Encoding::Utf8Bom);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertEncoding {
+    /// Assert the file's content is in the given encoding.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read or is detected as a different encoding.
+    #[track_caller]
+    fn assert_encoding(&self, encoding: Encoding) -> &Self;
+}
+
+impl PathAssertEncoding for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_encoding(&self, encoding: Encoding) -> &Self {
+        assert_encoding(self.path(), encoding);
+        self
+    }
+}
+
+impl PathAssertEncoding for fixture::ChildPath {
+    #[track_caller]
+    fn assert_encoding(&self, encoding: Encoding) -> &Self {
+        assert_encoding(self.path(), encoding);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_encoding(path: &path::Path, encoding: Encoding) {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    let actual = Encoding::detect(&data);
+    if !encoding.accepts(actual) {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected file encoding\n{}={}\n{}={}\n{}={}",
+            palette.key.paint("expected"),
+            palette.value.paint(encoding),
+            palette.key.paint("actual"),
+            palette.value.paint(actual),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect() {
+        assert_eq!(Encoding::detect(b"hello"), Encoding::Ascii);
+        assert_eq!(Encoding::detect(b"caf\xC3\xA9"), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"\xEF\xBB\xBFhello"), Encoding::Utf8Bom);
+        assert_eq!(Encoding::detect(b"\xFF\xFEh\0"), Encoding::Utf16Le);
+        assert_eq!(Encoding::detect(b"\xFE\xFF\0h"), Encoding::Utf16Be);
+        assert_eq!(Encoding::detect(b"caf\xE9"), Encoding::Latin1);
+        assert_eq!(Encoding::detect(b"\x7FELF\0\0"), Encoding::Binary);
+    }
+
+    #[test]
+    fn ascii_is_accepted_by_supersets() {
+        assert!(Encoding::Utf8.accepts(Encoding::Ascii));
+        assert!(Encoding::Latin1.accepts(Encoding::Ascii));
+        assert!(!Encoding::Ascii.accepts(Encoding::Utf8));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fs;
+use std::path;
+
+use sha2::Digest;
+
+use crate::fixture;
+
+/// Environment variable that, when set to `overwrite`, updates golden references instead of
+/// asserting against them.
+pub const GOLDEN_ENV: &str = "ASSERT_FS_GOLDEN";
+
+/// Assert files against golden references stored outside the repository.
+///
+/// Large binary goldens bloat a repository's history.  Instead, a small manifest holding the
+/// hash and size of the expected content is committed, while the content itself lives in a
+/// content-addressed cache directory, e.g. one restored by CI or synced from shared storage.
+///
+/// When the [`GOLDEN_ENV`] environment variable is set to `overwrite`, the assertion records
+/// the actual content instead: it's stored in the cache and the manifest is rewritten.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let cache = temp.child("cache");
+/// let manifest = temp.child("goldens/render.png.ref");
+/// # std::fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+/// # std::fs::write(
+/// #     manifest.path(),
+/// #     "sha256 e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\nsize 0\n",
+/// # )
+/// # .unwrap();
+/// let output = temp.child("render.png");
+/// output.touch().unwrap();
+///
+/// output.assert_golden_ref(&manifest, &cache);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertGolden {
+    /// Assert the file's content matches the golden reference in `manifest`.
+    ///
+    /// The expected content, if present in `cache_dir`, is exported as an artifact on failure;
+    /// see [`report`][crate::report].
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the manifest is missing or the content differs from it.
+    #[track_caller]
+    fn assert_golden_ref<M, C>(&self, manifest: M, cache_dir: C) -> &Self
+    where
+        M: AsRef<path::Path>,
+        C: AsRef<path::Path>;
+}
+
+impl PathAssertGolden for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_golden_ref<M, C>(&self, manifest: M, cache_dir: C) -> &Self
+    where
+        M: AsRef<path::Path>,
+        C: AsRef<path::Path>,
+    {
+        assert_golden_ref(self.path(), manifest.as_ref(), cache_dir.as_ref());
+        self
+    }
+}
+
+impl PathAssertGolden for fixture::ChildPath {
+    #[track_caller]
+    fn assert_golden_ref<M, C>(&self, manifest: M, cache_dir: C) -> &Self
+    where
+        M: AsRef<path::Path>,
+        C: AsRef<path::Path>,
+    {
+        assert_golden_ref(self.path(), manifest.as_ref(), cache_dir.as_ref());
+        self
+    }
+}
+
+/// The hash and size of a golden file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Reference {
+    sha256: String,
+    size: u64,
+}
+
+impl Reference {
+    fn of(data: &[u8]) -> Self {
+        Self {
+            sha256: crate::hex::encode(&sha2::Sha256::digest(data)),
+            size: data.len() as u64,
+        }
+    }
+
+    fn parse(manifest: &str) -> Option<Self> {
+        let mut sha256 = None;
+        let mut size = None;
+        for line in manifest.lines() {
+            match line.trim().split_once(' ') {
+                Some(("sha256", value)) => sha256 = Some(value.trim().to_ascii_lowercase()),
+                Some(("size", value)) => size = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            sha256: sha256?,
+            size: size?,
+        })
+    }
+
+    fn render(&self) -> String {
+        format!("sha256 {}\nsize {}\n", self.sha256, self.size)
+    }
+
+    /// Where the content lives within `cache_dir`.
+    fn cached(&self, cache_dir: &path::Path) -> path::PathBuf {
+        cache_dir.join(&self.sha256[..2]).join(&self.sha256)
+    }
+}
+
+fn overwrite() -> bool {
+    std::env::var_os(GOLDEN_ENV).as_deref() == Some(std::ffi::OsStr::new("overwrite"))
+}
+
+#[track_caller]
+fn assert_golden_ref(path: &path::Path, manifest: &path::Path, cache_dir: &path::Path) {
+    let actual =
+        fs::read(path).unwrap_or_else(|err| panic!("Failed to read `{}`: {}", path.display(), err));
+    let actual_ref = Reference::of(&actual);
+
+    if overwrite() {
+        let cached = actual_ref.cached(cache_dir);
+        let stored = cached
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&cached, &actual))
+            .and_then(|_| match manifest.parent() {
+                Some(parent) => fs::create_dir_all(parent),
+                None => Ok(()),
+            })
+            .and_then(|_| fs::write(manifest, actual_ref.render()));
+        if let Err(err) = stored {
+            panic!("Failed to update golden `{}`: {}", manifest.display(), err);
+        }
+        return;
+    }
+
+    let palette = crate::Palette::current();
+    let expected_ref = match fs::read_to_string(manifest)
+        .ok()
+        .and_then(|m| Reference::parse(&m))
+    {
+        Some(expected_ref) => expected_ref,
+        None => crate::report::fail(format!(
+            "Missing golden reference, run with `{}=overwrite` to record it\n{}={}\n{}={}",
+            GOLDEN_ENV,
+            palette.key.paint("manifest"),
+            palette.value.paint(manifest.display()),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        )),
+    };
+    if expected_ref != actual_ref {
+        let mut artifacts = vec![("actual", actual)];
+        if let Ok(expected) = fs::read(expected_ref.cached(cache_dir)) {
+            artifacts.push(("expected", expected));
+        }
+        crate::report::fail_with(
+            format!(
+                "Unexpected golden content\n{}={} ({} bytes)\n{}={} ({} bytes)\n{}={}\n{}={}",
+                palette.key.paint("expected"),
+                palette.value.paint(&expected_ref.sha256),
+                expected_ref.size,
+                palette.key.paint("actual"),
+                palette.value.paint(&actual_ref.sha256),
+                actual_ref.size,
+                palette.key.paint("manifest"),
+                palette.value.paint(manifest.display()),
+                palette.key.paint("path"),
+                palette.value.paint(path.display())
+            ),
+            artifacts,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reference_round_trip() {
+        let reference = Reference::of(b"hello\n");
+        assert_eq!(reference.size, 6);
+        assert_eq!(Reference::parse(&reference.render()), Some(reference));
+    }
+
+    #[test]
+    fn reference_incomplete() {
+        assert_eq!(Reference::parse("size 6\n"), None);
+    }
+}
@@ -0,0 +1,602 @@
+use std::fmt;
+use std::io::Read;
+use std::path;
+
+use crate::fixture;
+
+/// A file format, identified by its magic bytes.
+///
+/// See [`PathAssertKind`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileKind {
+    /// PNG image
+    Png,
+    /// JPEG image
+    Jpeg,
+    /// GIF image
+    Gif,
+    /// PDF document
+    Pdf,
+    /// Zip archive
+    Zip,
+    /// Tar archive
+    Tar,
+    /// Gzip-compressed data
+    Gzip,
+    /// Zstandard-compressed data
+    Zstd,
+    /// ELF executable
+    Elf,
+    /// WebAssembly module
+    Wasm,
+    /// Any format, identified by its MIME type (e.g. `"image/webp"`).
+    Mime(&'static str),
+    /// Text, like source code, HTML or JSON: UTF-8 without NUL bytes.
+    ///
+    /// Only the first 8 KiB are checked.
+    Text,
+    /// Anything that isn't [`FileKind::Text`].
+    Binary,
+}
+
+impl FileKind {
+    /// The MIME type of the file format.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            FileKind::Png => "image/png",
+            FileKind::Jpeg => "image/jpeg",
+            FileKind::Gif => "image/gif",
+            FileKind::Pdf => "application/pdf",
+            FileKind::Zip => "application/zip",
+            FileKind::Tar => "application/x-tar",
+            FileKind::Gzip => "application/gzip",
+            FileKind::Zstd => "application/zstd",
+            FileKind::Elf => "application/x-executable",
+            FileKind::Wasm => "application/wasm",
+            FileKind::Mime(mime) => mime,
+            FileKind::Text => "text/plain",
+            FileKind::Binary => "application/octet-stream",
+        }
+    }
+
+    /// Whether the file at `path` is of this format.
+    pub(crate) fn matches(self, path: &path::Path) -> std::io::Result<bool> {
+        match self {
+            FileKind::Text => is_text(path),
+            FileKind::Binary => Ok(!is_text(path)?),
+            kind => Ok(Self::sniff(path)? == Some(kind.mime_type())),
+        }
+    }
+
+    /// The MIME type of the file at `path`, falling back to text or binary.
+    pub(crate) fn detect(path: &path::Path) -> std::io::Result<&'static str> {
+        if let Some(mime) = Self::sniff(path)? {
+            return Ok(mime);
+        }
+        let kind = if is_text(path)? {
+            FileKind::Text
+        } else {
+            FileKind::Binary
+        };
+        Ok(kind.mime_type())
+    }
+
+    /// Detect the MIME type of the file at `path`, if it is recognized.
+    pub(crate) fn sniff(path: &path::Path) -> std::io::Result<Option<&'static str>> {
+        infer::get_from_path(path).map(|kind| kind.map(|k| k.mime_type()))
+    }
+}
+
+impl FileKind {
+    /// Why the file at `path`, already known to be of this format, is malformed, if it is.
+    fn validate(self, path: &path::Path) -> std::io::Result<Option<String>> {
+        let problem = match self {
+            FileKind::Png => validate_png(&std::fs::read(path)?),
+            FileKind::Jpeg => ends_with(path, b"\xFF\xD9", 0, "missing end of image marker")?,
+            FileKind::Gif => ends_with(path, b"\x3B", 0, "missing trailer")?,
+            // Trailing whitespace and comments are common after the marker
+            FileKind::Pdf => ends_with(path, b"%%EOF", 1024, "missing `%%EOF` marker")?,
+            FileKind::Text => {
+                let data = std::fs::read(path)?;
+                match std::str::from_utf8(&data) {
+                    Ok(_) if data.contains(&0) => Some("contains NUL bytes".to_owned()),
+                    Ok(_) => None,
+                    Err(err) => Some(format!("invalid UTF-8: {}", err)),
+                }
+            }
+            #[cfg(feature = "archive-gzip")]
+            FileKind::Gzip => decompresses(flate2::read::MultiGzDecoder::new(std::fs::File::open(
+                path,
+            )?)),
+            #[cfg(feature = "archive-zstd")]
+            FileKind::Zstd => match zstd::stream::read::Decoder::new(std::fs::File::open(path)?) {
+                Ok(decoder) => decompresses(decoder),
+                Err(err) => Some(err.to_string()),
+            },
+            #[cfg(feature = "archive-tar")]
+            FileKind::Tar => validate_tar(std::fs::File::open(path)?),
+            #[cfg(feature = "archive-zip")]
+            FileKind::Zip => validate_zip(std::fs::File::open(path)?),
+            _ => None,
+        };
+        Ok(problem)
+    }
+}
+
+/// `problem`, unless the file ends with `marker`, or has it within its last `window` bytes.
+fn ends_with(
+    path: &path::Path,
+    marker: &[u8],
+    window: usize,
+    problem: &str,
+) -> std::io::Result<Option<String>> {
+    let data = std::fs::read(path)?;
+    let tail = &data[data.len().saturating_sub(marker.len() + window)..];
+    let found = if window == 0 {
+        tail.ends_with(marker)
+    } else {
+        tail.windows(marker.len()).any(|w| w == marker)
+    };
+    Ok(if found {
+        None
+    } else {
+        Some(problem.to_owned())
+    })
+}
+
+fn validate_png(data: &[u8]) -> Option<String> {
+    let mut rest = match data.get(8..) {
+        Some(rest) => rest,
+        None => return Some("truncated signature".to_owned()),
+    };
+    let mut first = true;
+    loop {
+        if rest.len() < 12 {
+            return Some("truncated before the `IEND` chunk".to_owned());
+        }
+        let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        if rest.len() - 12 < len {
+            return Some("truncated chunk".to_owned());
+        }
+        let chunk_type = &rest[4..8];
+        let name = String::from_utf8_lossy(chunk_type).into_owned();
+        if first && chunk_type != b"IHDR" {
+            return Some(format!("first chunk is `{}`, not `IHDR`", name));
+        }
+        first = false;
+        let crc_at = 8 + len;
+        let expected = u32::from_be_bytes([
+            rest[crc_at],
+            rest[crc_at + 1],
+            rest[crc_at + 2],
+            rest[crc_at + 3],
+        ]);
+        if crc32(&rest[4..crc_at]) != expected {
+            return Some(format!("bad checksum in `{}` chunk", name));
+        }
+        rest = &rest[crc_at + 4..];
+        if chunk_type == b"IEND" {
+            return if rest.is_empty() {
+                None
+            } else {
+                Some(format!("{} bytes after the `IEND` chunk", rest.len()))
+            };
+        }
+    }
+}
+
+/// The CRC-32 used by PNG, zip and gzip.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(any(feature = "archive-gzip", feature = "archive-zstd"))]
+fn decompresses<R: Read>(mut reader: R) -> Option<String> {
+    std::io::copy(&mut reader, &mut std::io::sink())
+        .err()
+        .map(|err| err.to_string())
+}
+
+#[cfg(feature = "archive-tar")]
+fn validate_tar(file: std::fs::File) -> Option<String> {
+    let mut archive = tar::Archive::new(file);
+    let entries = match archive.entries() {
+        Ok(entries) => entries,
+        Err(err) => return Some(err.to_string()),
+    };
+    for entry in entries {
+        let result = entry.and_then(|mut entry| std::io::copy(&mut entry, &mut std::io::sink()));
+        if let Err(err) = result {
+            return Some(err.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(feature = "archive-zip")]
+fn validate_zip(file: std::fs::File) -> Option<String> {
+    let mut archive = match zip::ZipArchive::new(file) {
+        Ok(archive) => archive,
+        Err(err) => return Some(err.to_string()),
+    };
+    for i in 0..archive.len() {
+        // Reading to the end checks the entry's checksum
+        let result = archive
+            .by_index(i)
+            .map_err(std::io::Error::from)
+            .and_then(|mut entry| std::io::copy(&mut entry, &mut std::io::sink()));
+        if let Err(err) = result {
+            return Some(err.to_string());
+        }
+    }
+    None
+}
+
+/// How much of a file to inspect when deciding if it's text.
+const TEXT_SNIFF_LEN: u64 = 8 * 1024;
+
+fn is_text(path: &path::Path) -> std::io::Result<bool> {
+    let mut head = Vec::new();
+    std::fs::File::open(path)?
+        .take(TEXT_SNIFF_LEN)
+        .read_to_end(&mut head)?;
+    let text = !head.contains(&0)
+        && match std::str::from_utf8(&head) {
+            Ok(_) => true,
+            // Cut off mid-character
+            Err(err) => err.error_len().is_none(),
+        };
+    Ok(text)
+}
+
+impl fmt::Display for FileKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.mime_type().fmt(f)
+    }
+}
+
+/// Assert the format of files within [`TempDir`], using their magic bytes.
+///
+/// Only the leading bytes of the file are checked and not whether the rest of the file is
+/// well-formed.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::assert::FileKind;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let image = temp.child("image.png");
+/// image.write_binary(b"\x89PNG\r\n\x1a\n").unwrap();
+///
+/// image.assert_file_kind(FileKind::Png);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertKind {
+    /// Assert the file is of the given format.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read or is of a different format.
+    #[doc(alias = "assert_file_type")]
+    #[track_caller]
+    fn assert_file_kind(&self, kind: FileKind) -> &Self;
+
+    /// Assert the file is of the given format and, as far as can be checked, well-formed.
+    ///
+    /// For generated images and archives, where the exact bytes don't matter.  Beyond the magic
+    /// bytes, this checks:
+    /// - [`FileKind::Png`]: every chunk's checksum, from `IHDR` through `IEND`.
+    /// - [`FileKind::Jpeg`], [`FileKind::Gif`], [`FileKind::Pdf`]: the end marker, to catch
+    ///   truncated output.
+    /// - [`FileKind::Gzip`], [`FileKind::Zstd`], [`FileKind::Tar`], [`FileKind::Zip`]: that the
+    ///   whole archive decompresses, with the corresponding `archive-*` feature.
+    /// - [`FileKind::Text`]: that the whole file is UTF-8 without NUL bytes.
+    ///
+    /// Other formats are only checked by their magic bytes.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read, is of a different format, or is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::assert::FileKind;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let notes = temp.child("notes.txt");
+    /// notes.write_str("caf\u{e9}\n").unwrap();
+    ///
+    /// notes.assert_well_formed(FileKind::Text);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_well_formed(&self, kind: FileKind) -> &Self;
+}
+
+impl PathAssertKind for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_file_kind(&self, kind: FileKind) -> &Self {
+        assert_file_kind(self.path(), kind);
+        self
+    }
+
+    #[track_caller]
+    fn assert_well_formed(&self, kind: FileKind) -> &Self {
+        assert_well_formed(self.path(), kind);
+        self
+    }
+}
+
+impl PathAssertKind for fixture::ChildPath {
+    #[track_caller]
+    fn assert_file_kind(&self, kind: FileKind) -> &Self {
+        assert_file_kind(self.path(), kind);
+        self
+    }
+
+    #[track_caller]
+    fn assert_well_formed(&self, kind: FileKind) -> &Self {
+        assert_well_formed(self.path(), kind);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_file_kind(path: &path::Path, kind: FileKind) {
+    let matches = match kind.matches(path) {
+        Ok(matches) => matches,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    if !matches {
+        let actual = FileKind::detect(path).unwrap_or("unknown");
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected file kind\n{}={}\n{}={}\n{}={}",
+            palette.key.paint("expected"),
+            palette.value.paint(kind),
+            palette.key.paint("actual"),
+            palette.value.paint(actual),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
+
+#[track_caller]
+fn assert_well_formed(path: &path::Path, kind: FileKind) {
+    assert_file_kind(path, kind);
+    let problem = match kind.validate(path) {
+        Ok(problem) => problem,
+        Err(err) => Some(err.to_string()),
+    };
+    if let Some(problem) = problem {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Malformed file\n{}={}\n{}={}\n{}={}",
+            palette.key.paint("kind"),
+            palette.value.paint(kind),
+            palette.key.paint("error"),
+            palette.value.paint(problem),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
+
+/// Find and assert on files within [`TempDir`] by their format, rather than their extension.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::assert::FileKind;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.child("docs/index.html").write_str("<html></html>").unwrap();
+/// temp.child("docs/logo.png").write_binary(b"\x89PNG\r\n\x1a\n").unwrap();
+///
+/// assert_eq!(temp.find_by_kind(FileKind::Png).len(), 1);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathFindKind {
+    /// All files of the given format, in sorted order.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if a file can't be read.
+    #[track_caller]
+    fn find_by_kind(&self, kind: FileKind) -> Vec<fixture::ChildPath>;
+
+    /// Assert every file matching the glob `pattern` is of the given format.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if any matching file is of a different format, listing them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::assert::FileKind;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("docs/index.html").write_str("<html></html>").unwrap();
+    ///
+    /// temp.child("docs").assert_all_of_kind("**/*", FileKind::Text);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_all_of_kind(&self, pattern: &str, kind: FileKind) -> &Self;
+
+    /// Assert no file matching the glob `pattern` is of the given format.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if any matching file is of the format, listing them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::assert::FileKind;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("docs/index.html").write_str("<html></html>").unwrap();
+    ///
+    /// temp.child("docs").assert_none_of_kind("**/*", FileKind::Binary);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_none_of_kind(&self, pattern: &str, kind: FileKind) -> &Self;
+}
+
+impl PathFindKind for fixture::TempDir {
+    #[track_caller]
+    fn find_by_kind(&self, kind: FileKind) -> Vec<fixture::ChildPath> {
+        find_by_kind(self.path(), kind)
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_all_of_kind(&self, pattern: &str, kind: FileKind) -> &Self {
+        assert_glob_kind(self.path(), pattern, kind, true);
+        self
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_none_of_kind(&self, pattern: &str, kind: FileKind) -> &Self {
+        assert_glob_kind(self.path(), pattern, kind, false);
+        self
+    }
+}
+
+impl PathFindKind for fixture::ChildPath {
+    #[track_caller]
+    fn find_by_kind(&self, kind: FileKind) -> Vec<fixture::ChildPath> {
+        find_by_kind(self.path(), kind)
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_all_of_kind(&self, pattern: &str, kind: FileKind) -> &Self {
+        assert_glob_kind(self.path(), pattern, kind, true);
+        self
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_none_of_kind(&self, pattern: &str, kind: FileKind) -> &Self {
+        assert_glob_kind(self.path(), pattern, kind, false);
+        self
+    }
+}
+
+#[track_caller]
+fn find_by_kind(root: &path::Path, kind: FileKind) -> Vec<fixture::ChildPath> {
+    walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| match kind.matches(entry.path()) {
+            Ok(matches) => matches,
+            Err(err) => panic!("Failed to read `{}`: {}", entry.path().display(), err),
+        })
+        .map(|entry| fixture::ChildPath::new(entry.into_path()))
+        .collect()
+}
+
+#[cfg(feature = "glob")]
+#[track_caller]
+fn assert_glob_kind(root: &path::Path, pattern: &str, kind: FileKind, expected: bool) {
+    let mut offenders = Vec::new();
+    for rel in super::glob_files(root, &[pattern]) {
+        let path = root.join(&rel);
+        let matches = match kind.matches(&path) {
+            Ok(matches) => matches,
+            Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+        };
+        if matches != expected {
+            let actual = FileKind::detect(&path).unwrap_or("unknown");
+            offenders.push(format!("{} ({})", super::rel_display(&rel), actual));
+        }
+    }
+    if !offenders.is_empty() {
+        let expected = if expected {
+            format!("all {}", kind)
+        } else {
+            format!("no {}", kind)
+        };
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected file kinds\n{}={}\n{}={}\n{}={}",
+            palette.key.paint("expected"),
+            palette.value.paint(expected),
+            palette.key.paint("offenders"),
+            palette.value.paint(offenders.join(", ")),
+            palette.key.paint("path"),
+            palette.value.paint(root.display())
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&crc32(&chunk[4..]).to_be_bytes());
+        chunk
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn validate_png_walks_chunks() {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 0, 0, 0, 0]));
+        png.extend(chunk(b"IEND", b""));
+        assert_eq!(validate_png(&png), None);
+
+        let truncated = &png[..png.len() - 4];
+        assert!(validate_png(truncated).is_some());
+
+        let mut corrupt = png.clone();
+        corrupt[20] ^= 0xFF;
+        assert!(validate_png(&corrupt).unwrap().contains("checksum"));
+    }
+}
@@ -0,0 +1,100 @@
+use std::path;
+
+use crate::fixture;
+
+/// Assert whether files within [`TempDir`] are locked (requires the `lock` feature).
+///
+/// See [`FileLock`][crate::fixture::FileLock] for which locks are seen.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let lock_file = temp.child("app.lock");
+/// let guard = lock_file.lock_shared().unwrap();
+/// lock_file.assert_locked();
+///
+/// drop(guard);
+/// lock_file.assert_unlocked();
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertLock {
+    /// Assert something holds a lock on the file, shared or exclusive.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file is missing, isn't locked, or the lock can't be checked.
+    #[track_caller]
+    fn assert_locked(&self) -> &Self;
+
+    /// Assert nothing holds a lock on the file, e.g. once the code under test released it.
+    ///
+    /// A missing file can't be locked, so passes.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file is locked, or the lock can't be checked.
+    #[track_caller]
+    fn assert_unlocked(&self) -> &Self;
+}
+
+impl PathAssertLock for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_locked(&self) -> &Self {
+        assert_locked(self.path(), true);
+        self
+    }
+
+    #[track_caller]
+    fn assert_unlocked(&self) -> &Self {
+        assert_locked(self.path(), false);
+        self
+    }
+}
+
+impl PathAssertLock for fixture::ChildPath {
+    #[track_caller]
+    fn assert_locked(&self) -> &Self {
+        assert_locked(self.path(), true);
+        self
+    }
+
+    #[track_caller]
+    fn assert_unlocked(&self) -> &Self {
+        assert_locked(self.path(), false);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_locked(path: &path::Path, expected: bool) {
+    let actual = match fixture::is_locked(path) {
+        Ok(actual) => actual,
+        Err(err) => panic!("{}", err),
+    };
+    if actual != expected {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected lock state\n{}={}\n{}={}\n{}={}",
+            palette.key.paint("expected"),
+            palette.value.paint(describe(expected)),
+            palette.key.paint("actual"),
+            palette.value.paint(describe(actual)),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
+
+fn describe(locked: bool) -> &'static str {
+    if locked {
+        "locked"
+    } else {
+        "unlocked"
+    }
+}
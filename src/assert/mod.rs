@@ -0,0 +1,2794 @@
+//! Filesystem assertions.
+//!
+//! See [`PathAssert`] and, for directories not managed by `assert_fs`, [`DirAssert`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use assert_fs::prelude::*;
+//! use predicates::prelude::*;
+//!
+//! let temp = assert_fs::TempDir::new().unwrap();
+//! let input_file = temp.child("foo.txt");
+//! input_file.touch().unwrap();
+//!
+//! // ... do something with input_file ...
+//!
+//! input_file.assert("");
+//! temp.child("bar.txt").assert(predicate::path::missing());
+//!
+//! temp.close().unwrap();
+//! ```
+//!
+
+use std::fmt;
+use std::path;
+
+use predicates::path::PredicateFileContentExt;
+use predicates::str::PredicateStrExt;
+use predicates_tree::CaseTreeExt;
+
+use crate::fixture;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "xattr")]
+mod attrs;
+mod bytes;
+#[cfg(feature = "checksum")]
+mod digest;
+mod encoding;
+mod golden;
+#[cfg(feature = "sniff")]
+mod kind;
+#[cfg(feature = "lock")]
+mod lock;
+mod names;
+mod similar;
+mod sorted;
+#[cfg(any(
+    feature = "structured-json",
+    feature = "structured-toml",
+    feature = "structured-yaml"
+))]
+mod structured;
+mod tree;
+
+#[cfg(feature = "async")]
+pub use self::asynchronous::*;
+#[cfg(feature = "xattr")]
+pub use self::attrs::*;
+pub use self::bytes::*;
+#[cfg(feature = "checksum")]
+pub use self::digest::*;
+pub use self::encoding::*;
+pub use self::golden::*;
+#[cfg(feature = "sniff")]
+pub use self::kind::*;
+#[cfg(feature = "lock")]
+pub use self::lock::*;
+pub use self::names::*;
+pub use self::similar::*;
+pub use self::sorted::*;
+#[cfg(any(
+    feature = "structured-json",
+    feature = "structured-toml",
+    feature = "structured-yaml"
+))]
+pub use self::structured::*;
+pub use self::tree::*;
+
+/// Assert the state of files within [`TempDir`].
+///
+/// This uses [`IntoPathPredicate`] to provide short-hands for common cases, accepting:
+/// - `Predicate<Path>` for validating a path.
+/// - `Predicate<str>` for validating the content of the file.
+/// - `Predicate<[u8]>` for validating the raw content of the file.
+/// - `&Path` which must have the same file content.
+/// - `&[u8]` or `&str` representing the content of the file.
+///
+/// See [`predicates`] for more predicates.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use predicates::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let input_file = temp.child("foo.txt");
+/// input_file.touch().unwrap();
+///
+/// // ... do something with input_file ...
+///
+/// input_file.assert("");
+/// temp.child("bar.txt").assert(predicate::path::missing());
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: super::TempDir
+pub trait PathAssert {
+    /// Assert the state of files within [`TempDir`].
+    ///
+    /// This uses [`IntoPathPredicate`] to provide short-hands for common cases, accepting:
+    /// - `Predicate<Path>` for validating a path.
+    /// - `Predicate<str>` for validating the content of the file.
+    /// - `Predicate<[u8]>` for validating the raw content of the file.
+    /// - `&Path` which must have the same file content.
+    /// - `&[u8]` or `&str` representing the content of the file.
+    ///
+    /// See [`predicates`] for more predicates.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the condition is not satisfied
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use predicates::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input_file = temp.child("foo.txt");
+    /// input_file.touch().unwrap();
+    ///
+    /// // ... do something with input_file ...
+    ///
+    /// input_file.assert("");
+    /// // or
+    /// input_file.assert(predicate::str::is_empty());
+    ///
+    /// temp.child("bar.txt").assert(predicate::path::missing());
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    /// [`TempDir`]: super::TempDir
+    #[track_caller]
+    fn assert<I, P>(&self, pred: I) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>;
+
+    /// Assert the state of files within [`TempDir`] is reached, re-checking according to
+    /// `retry`.
+    ///
+    /// For output written by a background process or thread.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the condition is still not satisfied once `retry` gives up.
+    ///
+    /// The default finds the path through [`PathAssert::assert`], with a predicate that always
+    /// passes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::Retry;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let writer = temp.child("server.log");
+    /// std::thread::spawn(move || writer.write_str("listening").unwrap());
+    ///
+    /// temp.child("server.log").assert_eventually("listening", &Retry::default());
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    /// [`TempDir`]: super::TempDir
+    #[track_caller]
+    fn assert_eventually<I, P>(&self, pred: I, retry: &fixture::Retry) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        let seen = std::cell::RefCell::new(None);
+        self.assert(predicates::function::function(|path: &path::Path| {
+            *seen.borrow_mut() = Some(path.to_owned());
+            true
+        }));
+        let path = seen
+            .into_inner()
+            .expect("`assert` to evaluate the predicate");
+        assert_eventually(&path, pred, retry);
+        self
+    }
+}
+
+impl PathAssert for fixture::TempDir {
+    #[track_caller]
+    fn assert<I, P>(&self, pred: I) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        assert(self.path(), pred);
+        self
+    }
+
+    #[track_caller]
+    fn assert_eventually<I, P>(&self, pred: I, retry: &fixture::Retry) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        assert_eventually(self.path(), pred, retry);
+        self
+    }
+}
+
+impl PathAssert for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert<I, P>(&self, pred: I) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        assert(self.path(), pred);
+        self
+    }
+
+    #[track_caller]
+    fn assert_eventually<I, P>(&self, pred: I, retry: &fixture::Retry) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        assert_eventually(self.path(), pred, retry);
+        self
+    }
+}
+
+impl PathAssert for fixture::ChildPath {
+    #[track_caller]
+    fn assert<I, P>(&self, pred: I) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        assert(self.path(), pred);
+        self
+    }
+
+    #[track_caller]
+    fn assert_eventually<I, P>(&self, pred: I, retry: &fixture::Retry) -> &Self
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        assert_eventually(self.path(), pred, retry);
+        self
+    }
+}
+
+/// Assert on a directory not managed by `assert_fs`.
+///
+/// End-to-end harnesses often manage their own output directories.  `DirAssert` exposes the same
+/// child, assertion, and snapshot API used with [`TempDir`] for any path, without cleaning it up.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::assert::DirAssert;
+/// use predicates::prelude::*;
+///
+/// let out = DirAssert::new("tests/fixture");
+/// out.child("hello.txt").assert("hello\n");
+/// out.child("missing.txt").assert(predicate::path::missing());
+/// ```
+///
+/// [`TempDir`]: super::TempDir
+pub struct DirAssert {
+    root: fixture::ChildPath,
+}
+
+impl DirAssert {
+    /// Wrap an existing directory.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<path::PathBuf>,
+    {
+        Self {
+            root: fixture::ChildPath::new(path),
+        }
+    }
+
+    /// Access the path.
+    pub fn path(&self) -> &path::Path {
+        self.root.path()
+    }
+}
+
+impl AsRef<path::Path> for DirAssert {
+    fn as_ref(&self) -> &path::Path {
+        self.path()
+    }
+}
+
+impl std::ops::Deref for DirAssert {
+    type Target = fixture::ChildPath;
+    #[inline]
+    fn deref(&self) -> &fixture::ChildPath {
+        &self.root
+    }
+}
+
+#[track_caller]
+fn assert<I, P>(path: &path::Path, pred: I)
+where
+    I: IntoPathPredicate<P>,
+    P: predicates_core::Predicate<path::Path>,
+{
+    let pred = pred.into_path();
+    if let Some(case) = pred.find_case(false, path) {
+        let palette = crate::Palette::current();
+        let mut artifacts = Vec::new();
+        if let Some(expected) = find_parameter(&pred, "original") {
+            artifacts.push(("expected", expected.into_bytes()));
+        }
+        if let Ok(actual) = std::fs::read(path) {
+            artifacts.push(("actual", actual));
+        }
+        crate::report::fail_with(
+            format!(
+                "Unexpected file, failed {}\n{}={}{}",
+                case.tree(),
+                palette.key.paint("path"),
+                palette.value.paint(path.display()),
+                missing_context(path)
+            ),
+            artifacts,
+        );
+    }
+}
+
+#[track_caller]
+fn assert_eventually<I, P>(path: &path::Path, pred: I, retry: &fixture::Retry)
+where
+    I: IntoPathPredicate<P>,
+    P: predicates_core::Predicate<path::Path>,
+{
+    let pred = pred.into_path();
+    let mut attempts = retry.start();
+    while !predicates_core::Predicate::eval(&pred, path) {
+        if !attempts.next() {
+            // Report the final state
+            assert(path, pred);
+            return;
+        }
+    }
+}
+
+/// Look up a parameter of `pred`, or of its children, like the expected value of a diff.
+///
+/// Formatted with `{:#}`, for the full value of parameters summarized in failure messages.
+fn find_parameter(
+    pred: &dyn predicates_core::reflection::PredicateReflection,
+    name: &str,
+) -> Option<String> {
+    if let Some(param) = pred.parameters().find(|p| p.name() == name) {
+        return Some(format!("{:#}", param.value()));
+    }
+    pred.children()
+        .find_map(|child| find_parameter(child.value(), name))
+}
+
+/// Assert on the metadata of files within [`TempDir`], without reading their content.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let marker = temp.child("done.marker");
+/// marker.touch().unwrap();
+///
+/// marker.assert_empty_file();
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: super::TempDir
+pub trait PathAssertMetadata {
+    /// Assert the path is a file with no content.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing, not a file, or the file is not empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let marker = temp.child("done.marker");
+    /// marker.touch().unwrap();
+    ///
+    /// marker.assert_empty_file();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_empty_file(&self) -> &Self;
+
+    /// Assert the path is a file with content or a directory with entries.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("output.log");
+    /// output.write_str("Started").unwrap();
+    ///
+    /// output.assert_non_empty();
+    /// temp.assert_non_empty();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_non_empty(&self) -> &Self;
+
+    /// Assert nothing exists at the path.
+    ///
+    /// A symlink counts as existing, even if its target doesn't.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path exists.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    ///
+    /// temp.child("core.dump").assert_missing();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_missing(&self) -> &Self;
+
+    /// Assert the path has exactly `n` hard links.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or has a different number of hard links.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let original = temp.child("original.bin");
+    /// original.write_str("shared").unwrap();
+    /// temp.child("link.bin").hard_link_to(original.path()).unwrap();
+    ///
+    /// original.assert_nlink(2);
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_nlink(&self, n: u64) -> &Self;
+
+    /// Assert the path is a file taking less space on disk than its length, i.e. it has holes.
+    ///
+    /// Compares the blocks allocated to the file with its logical size, so a filesystem without
+    /// sparse file support, or one that compresses, may give misleading results.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing, not a file, or has as many bytes allocated as its
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use assert_fs::fixture::SparseLayout;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let image = temp.child("disk.img");
+    /// image
+    ///     .write_sparse(&SparseLayout::new(64 << 20).data(0, b"MBR"))
+    ///     .unwrap();
+    ///
+    /// // e.g. after copying `image` with the tool under test
+    /// image.assert_sparse();
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_sparse(&self) -> &Self;
+
+    /// Assert the path refers to the same underlying file as `other`, e.g. a hard link or
+    /// the result of a rename rather than a copy.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either path is missing or they are different files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let original = temp.child("original.bin");
+    /// original.write_str("shared").unwrap();
+    /// let link = temp.child("link.bin");
+    /// link.hard_link_to(original.path()).unwrap();
+    ///
+    /// link.assert_same_file_as(original.path());
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the path refers to a different underlying file than `other`, e.g. a copy.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either path is missing or they are the same file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let original = temp.child("original.bin");
+    /// original.write_str("shared").unwrap();
+    /// let copy = temp.child("copy.bin");
+    /// copy.write_file(original.path()).unwrap();
+    ///
+    /// copy.assert_not_same_file_as(original.path());
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_not_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the path resolves to the same location as `other`, once symlinks and `.` and `..`
+    /// components are resolved in both.
+    ///
+    /// Unlike comparing paths as strings, this holds through symlinked directories, like macOS's
+    /// `/tmp` to `/private/tmp`.  Unlike
+    /// [`PathAssertMetadata::assert_same_file_as`], a hard link is a different path.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either path is missing or they resolve to different locations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("etc/app.toml");
+    /// config.touch().unwrap();
+    ///
+    /// // e.g. a path printed by the code under test
+    /// let printed = temp.path().join("etc/../etc/./app.toml");
+    /// config.assert_same_path(&printed);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_same_path<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert no file or directory in the tree is group or world writable.
+    ///
+    /// See [`PathAssertMetadata::assert_no_mode_bits`] to check other permissions.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or any entry is group or world writable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use std::os::unix::fs::PermissionsExt;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let secret = temp.child("secret.key");
+    /// secret.write_str("hunter2").unwrap();
+    /// std::fs::set_permissions(secret.path(), std::fs::Permissions::from_mode(0o600)).unwrap();
+    ///
+    /// secret.assert_no_world_writable();
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_world_writable(&self) -> &Self;
+
+    /// Assert no file or directory in the tree has any of the permission bits in `mask` set.
+    ///
+    /// Symlinks are not checked, as their own permissions are meaningless.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or any entry has a bit of `mask` set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use std::os::unix::fs::PermissionsExt;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let secret = temp.child("secret.key");
+    /// secret.write_str("hunter2").unwrap();
+    /// std::fs::set_permissions(secret.path(), std::fs::Permissions::from_mode(0o600)).unwrap();
+    ///
+    /// // Not accessible to anyone but the owner
+    /// secret.assert_no_mode_bits(0o077);
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_mode_bits(&self, mask: u32) -> &Self;
+
+    /// Assert this process has no open file descriptors for the path or anything within it.
+    ///
+    /// Leaked descriptors otherwise only show up when cleanup fails confusingly, e.g. with
+    /// "directory not empty" on NFS or a locked file on other platforms.  Descriptors are found
+    /// through `/proc/self/fd`, so this requires procfs, e.g. Linux.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if `/proc/self/fd` can't be read or a descriptor is open, listing each one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(target_os = "linux")] {
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("app.log");
+    /// log.touch().unwrap();
+    /// let file = std::fs::File::open(log.path()).unwrap();
+    /// drop(file);
+    ///
+    /// temp.assert_no_open_handles();
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_open_handles(&self) -> &Self;
+
+    /// Assert the path's permission bits, including setuid, setgid, and sticky, are `mode`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or has different permission bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let script = temp.child("run.sh").write_script("#!/bin/sh\n").unwrap();
+    ///
+    /// script.assert_mode(0o755);
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_mode(&self, mode: u32) -> &Self;
+
+    /// Assert the path is a named pipe (FIFO).
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or of another type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let pipe = temp.child("events");
+    /// pipe.mkfifo(0o600).unwrap();
+    ///
+    /// pipe.assert_is_fifo();
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_fifo(&self) -> &Self;
+
+    /// Assert the path is a unix socket.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or of another type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let socket = temp.child("daemon.sock");
+    /// let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+    ///
+    /// socket.assert_is_socket();
+    ///
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_socket(&self) -> &Self;
+
+    /// Assert the path is a file, following symlinks.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or of another type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("output.txt");
+    /// output.touch().unwrap();
+    ///
+    /// output.assert_is_file();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_is_file(&self) -> &Self;
+
+    /// Assert the path is a directory, following symlinks.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or of another type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("cache").create_dir_all().unwrap();
+    ///
+    /// temp.child("cache").assert_is_dir();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_is_dir(&self) -> &Self;
+
+    /// Assert the path is a symlink, whether or not its target exists.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or not a symlink.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let current = temp.child("current");
+    /// current.symlink_to_dir("releases/v2").unwrap();
+    ///
+    /// current.assert_is_symlink();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_is_symlink(&self) -> &Self;
+
+    /// Assert the path is a symlink whose target is exactly `target`, as stored in the link.
+    ///
+    /// The target isn't resolved, so a relative link only matches the same relative `target`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing, not a symlink, or links elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let current = temp.child("current");
+    /// current.symlink_to_dir("releases/v2").unwrap();
+    ///
+    /// current.assert_is_symlink_to("releases/v2");
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_is_symlink_to<P>(&self, target: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the path is a file that can be executed.
+    ///
+    /// On Unix, any execute bit must be set; on Windows, the extension must be one of
+    /// `.exe`, `.com`, `.bat` or `.cmd`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing, not a file, or not executable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let script = temp.child("bin/hello").write_script("echo hello").unwrap();
+    ///
+    /// script.assert_is_executable();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_is_executable(&self) -> &Self;
+
+    /// Assert the path is a file with no content or a directory with no entries.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or not empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("spool").create_dir_all().unwrap();
+    ///
+    /// temp.child("spool").assert_is_empty();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_is_empty(&self) -> &Self;
+
+    /// Assert the path was modified after `other`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either path is missing or the path's modification time is not later.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("main.c");
+    /// input.touch_at(UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+    /// let output = temp.child("main.o");
+    /// output.touch_at(UNIX_EPOCH + Duration::from_secs(120)).unwrap();
+    ///
+    /// output.assert_newer_than(&input);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_newer_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the path was modified before `other`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either path is missing or the path's modification time is not earlier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("main.c");
+    /// input.touch_at(UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+    /// let output = temp.child("main.o");
+    /// output.touch_at(UNIX_EPOCH + Duration::from_secs(120)).unwrap();
+    ///
+    /// input.assert_older_than(&output);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_older_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the path's size is exactly `bytes`.
+    ///
+    /// The size of a directory is the total length of the files within it, recursively, like
+    /// [`TempDir::total_size`][crate::TempDir::total_size].
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or has a different size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("cache/a.bin").write_binary(&[0; 100]).unwrap();
+    /// temp.child("cache/b.bin").write_binary(&[0; 24]).unwrap();
+    ///
+    /// temp.child("cache/a.bin").assert_size(100);
+    /// temp.child("cache").assert_size(124);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_size(&self, bytes: u64) -> &Self;
+
+    /// Assert the path's size is within `min..=max` bytes.
+    ///
+    /// See [`PathAssertMetadata::assert_size`] for how directories are measured.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or its size is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("app.log");
+    /// log.write_str("started\n").unwrap();
+    ///
+    /// log.assert_size_between(1, 1024);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_size_between(&self, min: u64, max: u64) -> &Self;
+}
+
+impl PathAssertMetadata for fixture::TempDir {
+    #[track_caller]
+    fn assert_empty_file(&self) -> &Self {
+        assert_empty_file(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_non_empty(&self) -> &Self {
+        assert_non_empty(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_missing(&self) -> &Self {
+        assert_missing(self.path());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_nlink(&self, n: u64) -> &Self {
+        assert_nlink(self.path(), n);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_sparse(&self) -> &Self {
+        assert_sparse(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_file_as(self.path(), other.as_ref(), true);
+        self
+    }
+
+    #[track_caller]
+    fn assert_not_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_file_as(self.path(), other.as_ref(), false);
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_path<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_path(self.path(), other.as_ref());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_world_writable(&self) -> &Self {
+        assert_no_mode_bits(self.path(), 0o022);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_mode_bits(&self, mask: u32) -> &Self {
+        assert_no_mode_bits(self.path(), mask);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_open_handles(&self) -> &Self {
+        assert_no_open_handles(self.path());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_mode(&self, mode: u32) -> &Self {
+        assert_mode(self.path(), mode);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_fifo(&self) -> &Self {
+        assert_file_type(self.path(), "named pipe", |t| {
+            std::os::unix::fs::FileTypeExt::is_fifo(&t)
+        });
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_socket(&self) -> &Self {
+        assert_file_type(self.path(), "socket", |t| {
+            std::os::unix::fs::FileTypeExt::is_socket(&t)
+        });
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_file(&self) -> &Self {
+        assert_file_type(self.path(), "file", |t| t.is_file());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_dir(&self) -> &Self {
+        assert_file_type(self.path(), "directory", |t| t.is_dir());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_symlink(&self) -> &Self {
+        symlink_metadata(self.path(), "symlink");
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_symlink_to<P>(&self, target: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_is_symlink_to(self.path(), target.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_executable(&self) -> &Self {
+        assert_is_executable(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_empty(&self) -> &Self {
+        assert_is_empty(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_newer_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_modified_order(self.path(), other.as_ref(), std::cmp::Ordering::Greater);
+        self
+    }
+
+    #[track_caller]
+    fn assert_older_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_modified_order(self.path(), other.as_ref(), std::cmp::Ordering::Less);
+        self
+    }
+
+    #[track_caller]
+    fn assert_size(&self, bytes: u64) -> &Self {
+        assert_size_between(self.path(), bytes, bytes);
+        self
+    }
+
+    #[track_caller]
+    fn assert_size_between(&self, min: u64, max: u64) -> &Self {
+        assert_size_between(self.path(), min, max);
+        self
+    }
+}
+
+impl PathAssertMetadata for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_empty_file(&self) -> &Self {
+        assert_empty_file(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_non_empty(&self) -> &Self {
+        assert_non_empty(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_missing(&self) -> &Self {
+        assert_missing(self.path());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_nlink(&self, n: u64) -> &Self {
+        assert_nlink(self.path(), n);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_sparse(&self) -> &Self {
+        assert_sparse(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_file_as(self.path(), other.as_ref(), true);
+        self
+    }
+
+    #[track_caller]
+    fn assert_not_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_file_as(self.path(), other.as_ref(), false);
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_path<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_path(self.path(), other.as_ref());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_world_writable(&self) -> &Self {
+        assert_no_mode_bits(self.path(), 0o022);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_mode_bits(&self, mask: u32) -> &Self {
+        assert_no_mode_bits(self.path(), mask);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_open_handles(&self) -> &Self {
+        assert_no_open_handles(self.path());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_mode(&self, mode: u32) -> &Self {
+        assert_mode(self.path(), mode);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_fifo(&self) -> &Self {
+        assert_file_type(self.path(), "named pipe", |t| {
+            std::os::unix::fs::FileTypeExt::is_fifo(&t)
+        });
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_socket(&self) -> &Self {
+        assert_file_type(self.path(), "socket", |t| {
+            std::os::unix::fs::FileTypeExt::is_socket(&t)
+        });
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_file(&self) -> &Self {
+        assert_file_type(self.path(), "file", |t| t.is_file());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_dir(&self) -> &Self {
+        assert_file_type(self.path(), "directory", |t| t.is_dir());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_symlink(&self) -> &Self {
+        symlink_metadata(self.path(), "symlink");
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_symlink_to<P>(&self, target: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_is_symlink_to(self.path(), target.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_executable(&self) -> &Self {
+        assert_is_executable(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_empty(&self) -> &Self {
+        assert_is_empty(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_newer_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_modified_order(self.path(), other.as_ref(), std::cmp::Ordering::Greater);
+        self
+    }
+
+    #[track_caller]
+    fn assert_older_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_modified_order(self.path(), other.as_ref(), std::cmp::Ordering::Less);
+        self
+    }
+
+    #[track_caller]
+    fn assert_size(&self, bytes: u64) -> &Self {
+        assert_size_between(self.path(), bytes, bytes);
+        self
+    }
+
+    #[track_caller]
+    fn assert_size_between(&self, min: u64, max: u64) -> &Self {
+        assert_size_between(self.path(), min, max);
+        self
+    }
+}
+
+impl PathAssertMetadata for fixture::ChildPath {
+    #[track_caller]
+    fn assert_empty_file(&self) -> &Self {
+        assert_empty_file(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_non_empty(&self) -> &Self {
+        assert_non_empty(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_missing(&self) -> &Self {
+        assert_missing(self.path());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_nlink(&self, n: u64) -> &Self {
+        assert_nlink(self.path(), n);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_sparse(&self) -> &Self {
+        assert_sparse(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_file_as(self.path(), other.as_ref(), true);
+        self
+    }
+
+    #[track_caller]
+    fn assert_not_same_file_as<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_file_as(self.path(), other.as_ref(), false);
+        self
+    }
+
+    #[track_caller]
+    fn assert_same_path<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_same_path(self.path(), other.as_ref());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_world_writable(&self) -> &Self {
+        assert_no_mode_bits(self.path(), 0o022);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_mode_bits(&self, mask: u32) -> &Self {
+        assert_no_mode_bits(self.path(), mask);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_no_open_handles(&self) -> &Self {
+        assert_no_open_handles(self.path());
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_mode(&self, mode: u32) -> &Self {
+        assert_mode(self.path(), mode);
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_fifo(&self) -> &Self {
+        assert_file_type(self.path(), "named pipe", |t| {
+            std::os::unix::fs::FileTypeExt::is_fifo(&t)
+        });
+        self
+    }
+
+    #[cfg(unix)]
+    #[track_caller]
+    fn assert_is_socket(&self) -> &Self {
+        assert_file_type(self.path(), "socket", |t| {
+            std::os::unix::fs::FileTypeExt::is_socket(&t)
+        });
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_file(&self) -> &Self {
+        assert_file_type(self.path(), "file", |t| t.is_file());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_dir(&self) -> &Self {
+        assert_file_type(self.path(), "directory", |t| t.is_dir());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_symlink(&self) -> &Self {
+        symlink_metadata(self.path(), "symlink");
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_symlink_to<P>(&self, target: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_is_symlink_to(self.path(), target.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_executable(&self) -> &Self {
+        assert_is_executable(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_is_empty(&self) -> &Self {
+        assert_is_empty(self.path());
+        self
+    }
+
+    #[track_caller]
+    fn assert_newer_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_modified_order(self.path(), other.as_ref(), std::cmp::Ordering::Greater);
+        self
+    }
+
+    #[track_caller]
+    fn assert_older_than<P>(&self, other: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_modified_order(self.path(), other.as_ref(), std::cmp::Ordering::Less);
+        self
+    }
+
+    #[track_caller]
+    fn assert_size(&self, bytes: u64) -> &Self {
+        assert_size_between(self.path(), bytes, bytes);
+        self
+    }
+
+    #[track_caller]
+    fn assert_size_between(&self, min: u64, max: u64) -> &Self {
+        assert_size_between(self.path(), min, max);
+        self
+    }
+}
+
+#[track_caller]
+fn metadata(path: &path::Path) -> std::fs::Metadata {
+    match path.metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => metadata_failure(path, "readable metadata", &err),
+    }
+}
+
+#[track_caller]
+fn metadata_failure(path: &path::Path, expected: &str, actual: &dyn fmt::Display) -> ! {
+    let palette = crate::Palette::current();
+    crate::report::fail(format!(
+        "Unexpected file, expected {}\n{}={}\n{}={}{}",
+        expected,
+        palette.key.paint("actual"),
+        palette.value.paint(actual),
+        palette.key.paint("path"),
+        palette.value.paint(path.display()),
+        missing_context(path)
+    ));
+}
+
+/// How many entries to list when a path is missing.
+const MISSING_CONTEXT_ENTRIES: usize = 50;
+
+/// A listing of the nearest existing ancestor of `path` when it's missing, to show what is there
+/// instead.
+///
+/// Only paths within a [`TempDir`][fixture::TempDir] are listed, to bound the cost.
+fn missing_context(path: &path::Path) -> String {
+    match path.symlink_metadata() {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        _ => return String::new(),
+    }
+    let root = match fixture::fixture_root(path) {
+        Some(root) => root,
+        None => return String::new(),
+    };
+    let ancestor = path
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| ancestor.starts_with(&root))
+        .find(|ancestor| ancestor.is_dir());
+    match ancestor {
+        Some(ancestor) => {
+            let palette = crate::Palette::current();
+            let listing = fixture::render_listing(ancestor, MISSING_CONTEXT_ENTRIES);
+            format!(
+                "\n{}={}",
+                palette.key.paint("existing"),
+                palette.value.paint(listing.trim_end())
+            )
+        }
+        None => String::new(),
+    }
+}
+
+#[track_caller]
+fn assert_empty_file(path: &path::Path) {
+    let metadata = metadata(path);
+    if !metadata.is_file() {
+        metadata_failure(path, "empty file", &"not a file");
+    }
+    if metadata.len() != 0 {
+        metadata_failure(path, "empty file", &format!("{} bytes", metadata.len()));
+    }
+}
+
+#[track_caller]
+fn assert_missing(path: &path::Path) {
+    match path.symlink_metadata() {
+        Ok(metadata) => metadata_failure(path, "missing", &file_type_name(metadata.file_type())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => metadata_failure(path, "missing", &err),
+    }
+}
+
+#[track_caller]
+fn assert_non_empty(path: &path::Path) {
+    let metadata = metadata(path);
+    if metadata.is_dir() {
+        let mut entries = match path.read_dir() {
+            Ok(entries) => entries,
+            Err(err) => metadata_failure(path, "non-empty directory", &err),
+        };
+        if entries.next().is_none() {
+            metadata_failure(path, "non-empty directory", &"no entries");
+        }
+    } else if metadata.len() == 0 {
+        metadata_failure(path, "non-empty file", &"0 bytes");
+    }
+}
+
+#[cfg(unix)]
+#[track_caller]
+fn assert_nlink(path: &path::Path, n: u64) {
+    use std::os::unix::fs::MetadataExt;
+
+    let nlink = metadata(path).nlink();
+    if nlink != n {
+        metadata_failure(
+            path,
+            &format!("{} hard links", n),
+            &format!("{} hard links", nlink),
+        );
+    }
+}
+
+#[cfg(unix)]
+#[track_caller]
+fn assert_sparse(path: &path::Path) {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = metadata(path);
+    if !metadata.is_file() {
+        metadata_failure(path, "sparse file", &file_type_name(metadata.file_type()));
+    }
+    // `blocks` is always in 512-byte units, whatever the filesystem's block size
+    let allocated = metadata.blocks() * 512;
+    if allocated >= metadata.len() {
+        metadata_failure(
+            path,
+            "sparse file",
+            &format!("{} bytes allocated for {} bytes", allocated, metadata.len()),
+        );
+    }
+}
+
+/// A description of `file_type`, for failure messages.
+fn file_type_name(file_type: std::fs::FileType) -> &'static str {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        if file_type.is_fifo() {
+            return "named pipe";
+        } else if file_type.is_socket() {
+            return "socket";
+        } else if file_type.is_block_device() || file_type.is_char_device() {
+            return "device";
+        }
+    }
+    if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "directory"
+    } else if file_type.is_file() {
+        "file"
+    } else {
+        "unknown"
+    }
+}
+
+#[track_caller]
+fn assert_file_type<F>(path: &path::Path, expected: &str, is_expected: F)
+where
+    F: Fn(std::fs::FileType) -> bool,
+{
+    let file_type = metadata(path).file_type();
+    if !is_expected(file_type) {
+        metadata_failure(path, expected, &file_type_name(file_type));
+    }
+}
+
+/// The metadata of the symlink at `path`, failing unless it's a symlink.
+#[track_caller]
+fn symlink_metadata(path: &path::Path, expected: &str) -> std::fs::Metadata {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => metadata_failure(path, expected, &err),
+    };
+    if !metadata.file_type().is_symlink() {
+        metadata_failure(path, expected, &file_type_name(metadata.file_type()));
+    }
+    metadata
+}
+
+#[track_caller]
+fn assert_is_symlink_to(path: &path::Path, target: &path::Path) {
+    let expected = format!("symlink to `{}`", target.display());
+    symlink_metadata(path, &expected);
+    let actual = match std::fs::read_link(path) {
+        Ok(actual) => actual,
+        Err(err) => metadata_failure(path, &expected, &err),
+    };
+    if actual != target {
+        metadata_failure(
+            path,
+            &expected,
+            &format!("symlink to `{}`", actual.display()),
+        );
+    }
+}
+
+#[track_caller]
+fn assert_is_executable(path: &path::Path) {
+    let metadata = metadata(path);
+    if !metadata.is_file() {
+        metadata_failure(
+            path,
+            "executable file",
+            &file_type_name(metadata.file_type()),
+        );
+    }
+    #[cfg(unix)]
+    let executable = {
+        use std::os::unix::fs::PermissionsExt;
+
+        metadata.permissions().mode() & 0o111 != 0
+    };
+    #[cfg(not(unix))]
+    let executable = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            ["exe", "com", "bat", "cmd"]
+                .iter()
+                .any(|e| ext.eq_ignore_ascii_case(e))
+        })
+        .unwrap_or(false);
+    if !executable {
+        metadata_failure(path, "executable file", &"not executable");
+    }
+}
+
+#[track_caller]
+fn assert_is_empty(path: &path::Path) {
+    let metadata = metadata(path);
+    if metadata.is_dir() {
+        let mut entries = match path.read_dir() {
+            Ok(entries) => entries,
+            Err(err) => metadata_failure(path, "empty directory", &err),
+        };
+        if let Some(entry) = entries.next() {
+            let name = entry
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .unwrap_or_default();
+            metadata_failure(path, "empty directory", &format!("contains `{}`", name));
+        }
+    } else if metadata.len() != 0 {
+        metadata_failure(path, "empty file", &format!("{} bytes", metadata.len()));
+    }
+}
+
+#[track_caller]
+fn assert_same_file_as(path: &path::Path, other: &path::Path, expected: bool) {
+    let same = match same_file::is_same_file(path, other) {
+        Ok(same) => same,
+        Err(err) => metadata_failure(path, "readable file identity", &err),
+    };
+    if same != expected {
+        let expected = if expected {
+            format!("same file as `{}`", other.display())
+        } else {
+            format!("different file than `{}`", other.display())
+        };
+        let actual = if same { "same file" } else { "different file" };
+        metadata_failure(path, &expected, &actual);
+    }
+}
+
+#[track_caller]
+fn assert_same_path(path: &path::Path, other: &path::Path) {
+    let expected = format!("same path as `{}`", other.display());
+    let actual = match path.canonicalize() {
+        Ok(actual) => actual,
+        Err(err) => metadata_failure(path, &expected, &err),
+    };
+    let other = match other.canonicalize() {
+        Ok(other) => other,
+        Err(err) => metadata_failure(other, "existing path", &err),
+    };
+    if actual != other {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected path, expected {}\n{}={}\n{}={}\n{}={}",
+            expected,
+            palette.key.paint("expected"),
+            palette.value.paint(other.display()),
+            palette.key.paint("actual"),
+            palette.value.paint(actual.display()),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+}
+
+#[cfg(unix)]
+#[track_caller]
+fn assert_no_mode_bits(path: &path::Path, mask: u32) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let expected = format!("no mode bits in {:04o}", mask);
+    // Fail on a missing root, rather than vacuously passing
+    metadata(path);
+    let mut offenders = Vec::new();
+    for entry in crate::order::walk(path) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => metadata_failure(path, &expected, &err),
+        };
+        if entry.file_type().is_symlink() {
+            continue;
+        }
+        let mode = match entry.metadata() {
+            Ok(metadata) => metadata.permissions().mode(),
+            Err(err) => metadata_failure(entry.path(), &expected, &err),
+        };
+        if mode & mask != 0 {
+            offenders.push(format!("{:04o} {}", mode & 0o7777, entry.path().display()));
+        }
+    }
+    if !offenders.is_empty() {
+        metadata_failure(path, &expected, &offenders.join("\n"));
+    }
+}
+
+#[track_caller]
+fn modified(path: &path::Path) -> std::time::SystemTime {
+    match metadata(path).modified() {
+        Ok(modified) => modified,
+        Err(err) => metadata_failure(path, "readable modification time", &err),
+    }
+}
+
+#[track_caller]
+fn assert_size_between(path: &path::Path, min: u64, max: u64) {
+    let size = match fixture::total_size(path) {
+        Ok(size) => size,
+        Err(err) => metadata_failure(path, "readable size", &err),
+    };
+    if size < min || max < size {
+        let expected = if min == max {
+            format!("{} bytes", min)
+        } else {
+            format!("{} to {} bytes", min, max)
+        };
+        metadata_failure(path, &expected, &format!("{} bytes", size));
+    }
+}
+
+#[track_caller]
+fn assert_modified_order(path: &path::Path, other: &path::Path, expected: std::cmp::Ordering) {
+    let actual = modified(path);
+    let reference = modified(other);
+    if actual.cmp(&reference) != expected {
+        let relation = match expected {
+            std::cmp::Ordering::Greater => "newer than",
+            _ => "older than",
+        };
+        metadata_failure(
+            path,
+            &format!(
+                "modified {} `{}` ({:?})",
+                relation,
+                other.display(),
+                reference
+            ),
+            &format!("modified {:?}", actual),
+        );
+    }
+}
+
+#[cfg(unix)]
+#[track_caller]
+fn assert_mode(path: &path::Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let actual = metadata(path).permissions().mode() & 0o7777;
+    if actual != mode {
+        metadata_failure(
+            path,
+            &format!("mode {:04o}", mode),
+            &format!("mode {:04o}", actual),
+        );
+    }
+}
+
+#[cfg(unix)]
+#[track_caller]
+fn assert_no_open_handles(path: &path::Path) {
+    let expected = "no open file descriptors";
+    // Descriptors resolve to canonical paths, e.g. through a symlinked temp directory
+    let root = match path.canonicalize() {
+        Ok(root) => root,
+        Err(err) => metadata_failure(path, expected, &err),
+    };
+    let fds = match std::fs::read_dir("/proc/self/fd") {
+        Ok(fds) => fds,
+        Err(err) => metadata_failure(path, expected, &format!("/proc/self/fd: {}", err)),
+    };
+    let mut open = Vec::new();
+    for fd in fds.flatten() {
+        // Descriptors may close, like the one for this listing, between reading and resolving
+        if let Ok(target) = std::fs::read_link(fd.path()) {
+            if target.starts_with(&root) {
+                open.push(format!(
+                    "fd {} -> {}",
+                    fd.file_name().to_string_lossy(),
+                    target.display()
+                ));
+            }
+        }
+    }
+    if !open.is_empty() {
+        open.sort();
+        metadata_failure(path, expected, &open.join("\n"));
+    }
+}
+
+/// Assert on files sharing the same content within [`TempDir`].
+///
+/// Files are compared by a hash of their content.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.child("a.txt").write_str("one").unwrap();
+/// temp.child("b.txt").write_str("two").unwrap();
+///
+/// temp.assert_duplicates(&[]);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: super::TempDir
+pub trait PathAssertDuplicates {
+    /// Assert no two files matching the glob `pattern` have the same content.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if duplicates are found, listing each group of duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("a.txt").write_str("one").unwrap();
+    /// temp.child("b.txt").write_str("two").unwrap();
+    ///
+    /// temp.assert_no_duplicate_files("**/*");
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_no_duplicate_files(&self, pattern: &str) -> &Self;
+
+    /// Assert the files with the same content are exactly the `expected` groups.
+    ///
+    /// Paths are relative, `/`-separated, and compared regardless of order.  Files with unique
+    /// content are not part of any group.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the duplicate groups are different.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("a.txt").write_str("one").unwrap();
+    /// temp.child("copy/a.txt").write_str("one").unwrap();
+    /// temp.child("b.txt").write_str("two").unwrap();
+    ///
+    /// temp.assert_duplicates(&[&["a.txt", "copy/a.txt"]]);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_duplicates(&self, expected: &[&[&str]]) -> &Self;
+}
+
+impl PathAssertDuplicates for fixture::TempDir {
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_no_duplicate_files(&self, pattern: &str) -> &Self {
+        assert_no_duplicate_files(self.path(), pattern);
+        self
+    }
+
+    #[track_caller]
+    fn assert_duplicates(&self, expected: &[&[&str]]) -> &Self {
+        assert_duplicates(self.path(), expected);
+        self
+    }
+}
+
+impl PathAssertDuplicates for fixture::ChildPath {
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_no_duplicate_files(&self, pattern: &str) -> &Self {
+        assert_no_duplicate_files(self.path(), pattern);
+        self
+    }
+
+    #[track_caller]
+    fn assert_duplicates(&self, expected: &[&[&str]]) -> &Self {
+        assert_duplicates(self.path(), expected);
+        self
+    }
+}
+
+#[cfg(feature = "glob")]
+#[track_caller]
+fn assert_no_duplicate_files(root: &path::Path, pattern: &str) {
+    let groups = duplicate_groups(root, glob_files(root, &[pattern]));
+    if !groups.is_empty() {
+        let palette = crate::Palette::current();
+        crate::report::fail(format!(
+            "Unexpected duplicate files\n{}={}\n{}={}",
+            palette.key.paint("duplicates"),
+            palette.value.paint(format!("{:?}", groups)),
+            palette.key.paint("path"),
+            palette.value.paint(root.display())
+        ));
+    }
+}
+
+#[track_caller]
+fn assert_duplicates(root: &path::Path, expected: &[&[&str]]) {
+    let mut expected: Vec<Vec<String>> = expected
+        .iter()
+        .map(|group| {
+            let mut group: Vec<_> = group.iter().map(|p| (*p).to_owned()).collect();
+            group.sort();
+            group
+        })
+        .collect();
+    expected.sort();
+    let actual = duplicate_groups(root, all_files(root));
+    if actual != expected {
+        let palette = crate::Palette::current();
+        let artifacts = vec![
+            ("expected", format!("{:#?}\n", expected).into_bytes()),
+            ("actual", format!("{:#?}\n", actual).into_bytes()),
+        ];
+        crate::report::fail_with(
+            format!(
+                "Unexpected duplicate files\n{}={}\n{}={}\n{}={}",
+                palette.key.paint("expected"),
+                palette.value.paint(format!("{:?}", expected)),
+                palette.key.paint("actual"),
+                palette.value.paint(format!("{:?}", actual)),
+                palette.key.paint("path"),
+                palette.value.paint(root.display())
+            ),
+            artifacts,
+        );
+    }
+}
+
+#[track_caller]
+fn duplicate_groups(root: &path::Path, files: Vec<path::PathBuf>) -> Vec<Vec<String>> {
+    use sha2::Digest;
+
+    let mut by_digest = std::collections::BTreeMap::<_, Vec<String>>::new();
+    for rel in files {
+        // Read through the original `rel`, as the display form is lossy for non-UTF-8 names
+        let data = std::fs::read(root.join(&rel))
+            .unwrap_or_else(|err| panic!("Failed to read `{}`: {}", rel.display(), err));
+        by_digest
+            .entry(sha2::Sha256::digest(&data).to_vec())
+            .or_default()
+            .push(rel_display(&rel));
+    }
+    let mut groups: Vec<_> = by_digest
+        .into_values()
+        .filter(|group| 1 < group.len())
+        .map(|mut group| {
+            group.sort();
+            group
+        })
+        .collect();
+    groups.sort();
+    groups
+}
+
+/// Relative paths of all files under `root`, in sorted order.
+fn all_files(root: &path::Path) -> Vec<path::PathBuf> {
+    walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            entry
+                .path()
+                .strip_prefix(root)
+                .expect("entries to be under `root`")
+                .to_owned()
+        })
+        .collect()
+}
+
+/// Relative paths of files under `root` matching `patterns`, in sorted order.
+#[cfg(feature = "glob")]
+#[track_caller]
+fn glob_files<S>(root: &path::Path, patterns: &[S]) -> Vec<path::PathBuf>
+where
+    S: AsRef<str>,
+{
+    let walker = globwalk::GlobWalkerBuilder::from_patterns(root, patterns)
+        .build()
+        .unwrap_or_else(|err| panic!("Invalid glob: {}", err));
+    let mut files: Vec<_> = walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            entry
+                .path()
+                .strip_prefix(root)
+                .expect("entries to be under `root`")
+                .to_owned()
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn rel_display(rel: &path::Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Used by [`PathAssert`] to convert Self into the needed [`predicates_core::Predicate<Path>`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path;
+///
+/// use assert_fs::prelude::*;
+/// use predicates::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+///
+/// // ... do something with input_file ...
+///
+/// temp.child("bar.txt").assert(predicate::path::missing()); // Uses IntoPathPredicate
+///
+/// temp.close().unwrap();
+/// ```
+pub trait IntoPathPredicate<P>
+where
+    P: predicates_core::Predicate<path::Path>,
+{
+    /// The type of the predicate being returned.
+    type Predicate;
+
+    /// Convert to a predicate for testing a path.
+    fn into_path(self) -> P;
+}
+
+impl<P> IntoPathPredicate<P> for P
+where
+    P: predicates_core::Predicate<path::Path>,
+{
+    type Predicate = P;
+
+    fn into_path(self) -> Self::Predicate {
+        self
+    }
+}
+
+/// Keep `predicates` concrete Predicates out of our public API.
+/// [predicates_core::Predicate] used by [`IntoPathPredicate`] for bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let input_file = temp.child("foo.txt");
+/// input_file.touch().unwrap();
+///
+/// // ... do something with input_file ...
+///
+/// input_file.assert(b"" as &[u8]); // uses BytesContentPathPredicate
+///
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct BytesContentPathPredicate(
+    predicates::path::FileContentPredicate<predicates::ord::EqPredicate<&'static [u8]>>,
+);
+
+impl BytesContentPathPredicate {
+    pub(crate) fn new(value: &'static [u8]) -> Self {
+        let pred = predicates::ord::eq(value).from_file_path();
+        BytesContentPathPredicate(pred)
+    }
+}
+
+impl predicates_core::reflection::PredicateReflection for BytesContentPathPredicate {
+    fn parameters<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Parameter<'a>> + 'a> {
+        self.0.parameters()
+    }
+
+    /// Nested `Predicate`s of the current `Predicate`.
+    fn children<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Child<'a>> + 'a> {
+        self.0.children()
+    }
+}
+
+impl predicates_core::Predicate<path::Path> for BytesContentPathPredicate {
+    fn eval(&self, item: &path::Path) -> bool {
+        self.0.eval(item)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<predicates_core::reflection::Case<'a>> {
+        self.0.find_case(expected, variable)
+    }
+}
+
+impl fmt::Display for BytesContentPathPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl IntoPathPredicate<BytesContentPathPredicate> for &'static [u8] {
+    type Predicate = BytesContentPathPredicate;
+
+    fn into_path(self) -> Self::Predicate {
+        Self::Predicate::new(self)
+    }
+}
+
+/// Keep `predicates` concrete Predicates out of our public API.
+/// [predicates_core::Predicate] used by `IntoPathPredicate` for `str`.
+///
+/// # Example
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let input_file = temp.child("foo.txt");
+/// input_file.touch().unwrap();
+///
+/// // ... do something with input_file ...
+///
+/// input_file.assert(""); // Uses StrContentPathPredicate
+///
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct StrContentPathPredicate(predicates::path::FileContentPredicate<Utf8Text>);
+
+impl StrContentPathPredicate {
+    pub(crate) fn new(value: String) -> Self {
+        let pred = Utf8Text(LineEndingPredicate::new(value, false)).from_file_path();
+        StrContentPathPredicate(pred)
+    }
+
+    /// Compare the file's content to `value`, treating `\r\n` and `\n` as the same.
+    ///
+    /// For golden text that may be checked out, or generated, with either line ending.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use assert_fs::assert::StrContentPathPredicate;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("output.txt");
+    /// output.write_str("one\r\ntwo\r\n").unwrap();
+    ///
+    /// output.assert(StrContentPathPredicate::normalized("one\ntwo\n"));
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    pub fn normalized<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let pred = Utf8Text(LineEndingPredicate::new(value.into(), true)).from_file_path();
+        StrContentPathPredicate(pred)
+    }
+}
+
+/// Unchanged lines shown around each change in text content failures.
+const DIFF_CONTEXT: usize = 3;
+
+/// A diff against the expected text, optionally ignoring `\r\n` vs `\n`.
+///
+/// Failures show a unified diff of the changed lines, rather than both texts in full.
+#[derive(Debug, Clone)]
+struct LineEndingPredicate {
+    original: ExpectedText,
+    normalize: bool,
+}
+
+/// Expected text, summarized in failure messages as the diff shows what matters.
+///
+/// The alternate form, `{:#}`, is the full text.
+#[derive(Debug, Clone)]
+struct ExpectedText(String);
+
+impl fmt::Display for ExpectedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.0)
+        } else {
+            write!(
+                f,
+                "{} lines, {} bytes",
+                self.0.lines().count(),
+                self.0.len()
+            )
+        }
+    }
+}
+
+impl LineEndingPredicate {
+    fn new(value: String, normalize: bool) -> Self {
+        let original = if normalize {
+            normalize_line_endings(&value).into_owned()
+        } else {
+            value
+        };
+        Self {
+            original: ExpectedText(original),
+            normalize,
+        }
+    }
+
+    fn normalized<'s>(&self, variable: &'s str) -> std::borrow::Cow<'s, str> {
+        if self.normalize {
+            normalize_line_endings(variable)
+        } else {
+            std::borrow::Cow::Borrowed(variable)
+        }
+    }
+}
+
+impl predicates_core::reflection::PredicateReflection for LineEndingPredicate {
+    fn parameters<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Parameter<'a>> + 'a> {
+        let params = vec![predicates_core::reflection::Parameter::new(
+            "original",
+            &self.original,
+        )];
+        Box::new(params.into_iter())
+    }
+}
+
+impl predicates_core::Predicate<str> for LineEndingPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.normalized(variable) == self.original.0.as_str()
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &str,
+    ) -> Option<predicates_core::reflection::Case<'a>> {
+        let variable = self.normalized(variable);
+        let result = variable == self.original.0.as_str();
+        if result != expected {
+            return None;
+        }
+        let mut case = predicates_core::reflection::Case::new(Some(self), result);
+        if !result {
+            let diff = similar::unified_diff(&self.original.0, &variable, DIFF_CONTEXT, usize::MAX);
+            let diff = if diff.is_empty() {
+                "only line endings, or the final newline, differ".to_owned()
+            } else {
+                format!(
+                    "\n{}",
+                    similar::paint_diff(&diff, crate::Palette::current()).trim_end()
+                )
+            };
+            case = case.add_product(predicates_core::reflection::Product::new("diff", diff));
+        }
+        Some(case)
+    }
+}
+
+impl fmt::Display for LineEndingPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::current();
+        write!(
+            f,
+            "{} {} {}",
+            palette.key.paint("diff"),
+            palette.value.paint("original"),
+            palette.key.paint("var")
+        )
+    }
+}
+
+/// Like [`predicates::str::Utf8Predicate`], without the full text in failures, as the diff shows
+/// what matters.
+#[derive(Debug, Clone)]
+struct Utf8Text(LineEndingPredicate);
+
+impl predicates_core::reflection::PredicateReflection for Utf8Text {
+    fn children<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Child<'a>> + 'a> {
+        let params = vec![predicates_core::reflection::Child::new(
+            "predicate",
+            &self.0,
+        )];
+        Box::new(params.into_iter())
+    }
+}
+
+impl predicates_core::Predicate<[u8]> for Utf8Text {
+    fn eval(&self, variable: &[u8]) -> bool {
+        std::str::from_utf8(variable)
+            .map(|text| self.0.eval(text))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &[u8],
+    ) -> Option<predicates_core::reflection::Case<'a>> {
+        match (expected, std::str::from_utf8(variable)) {
+            (_, Ok(text)) => self.0.find_case(expected, text),
+            (true, Err(_)) => None,
+            (false, Err(err)) => Some(
+                predicates_core::reflection::Case::new(Some(self), false)
+                    .add_product(predicates_core::reflection::Product::new("error", err)),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Utf8Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// `text` with every `\r\n` replaced by `\n`.
+pub(crate) fn normalize_line_endings(text: &str) -> std::borrow::Cow<'_, str> {
+    if text.contains("\r\n") {
+        std::borrow::Cow::Owned(text.replace("\r\n", "\n"))
+    } else {
+        std::borrow::Cow::Borrowed(text)
+    }
+}
+
+impl predicates_core::reflection::PredicateReflection for StrContentPathPredicate {
+    fn parameters<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Parameter<'a>> + 'a> {
+        self.0.parameters()
+    }
+
+    /// Nested `Predicate`s of the current `Predicate`.
+    fn children<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Child<'a>> + 'a> {
+        self.0.children()
+    }
+}
+
+impl predicates_core::Predicate<path::Path> for StrContentPathPredicate {
+    fn eval(&self, item: &path::Path) -> bool {
+        self.0.eval(item)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<predicates_core::reflection::Case<'a>> {
+        self.0.find_case(expected, variable)
+    }
+}
+
+impl fmt::Display for StrContentPathPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl IntoPathPredicate<StrContentPathPredicate> for String {
+    type Predicate = StrContentPathPredicate;
+
+    fn into_path(self) -> Self::Predicate {
+        Self::Predicate::new(self)
+    }
+}
+
+impl<'s> IntoPathPredicate<StrContentPathPredicate> for &'s str {
+    type Predicate = StrContentPathPredicate;
+
+    fn into_path(self) -> Self::Predicate {
+        Self::Predicate::new(self.to_owned())
+    }
+}
+
+impl<'s> IntoPathPredicate<StrContentPathPredicate> for &'s String {
+    type Predicate = StrContentPathPredicate;
+
+    fn into_path(self) -> Self::Predicate {
+        Self::Predicate::new(self.to_owned())
+    }
+}
+
+/// Keep `predicates` concrete Predicates out of our public API.
+/// [predicates_core::Predicate] used by `IntoPathPredicate` for `str` predicates.
+///
+/// # Example
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use predicates::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let input_file = temp.child("foo.txt");
+/// input_file.touch().unwrap();
+///
+/// // ... do something with input_file ...
+///
+/// input_file.assert(predicate::str::is_empty()); // Uses StrPathPredicate
+///
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct StrPathPredicate<P: predicates_core::Predicate<str>>(
+    predicates::path::FileContentPredicate<predicates::str::Utf8Predicate<P>>,
+);
+
+impl<P> StrPathPredicate<P>
+where
+    P: predicates_core::Predicate<str>,
+{
+    pub(crate) fn new(value: P) -> Self {
+        let pred = value.from_utf8().from_file_path();
+        StrPathPredicate(pred)
+    }
+}
+
+impl<P> predicates_core::reflection::PredicateReflection for StrPathPredicate<P>
+where
+    P: predicates_core::Predicate<str>,
+{
+    fn parameters<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Parameter<'a>> + 'a> {
+        self.0.parameters()
+    }
+
+    /// Nested `Predicate`s of the current `Predicate`.
+    fn children<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Child<'a>> + 'a> {
+        self.0.children()
+    }
+}
+
+impl<P> predicates_core::Predicate<path::Path> for StrPathPredicate<P>
+where
+    P: predicates_core::Predicate<str>,
+{
+    fn eval(&self, item: &path::Path) -> bool {
+        self.0.eval(item)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<predicates_core::reflection::Case<'a>> {
+        self.0.find_case(expected, variable)
+    }
+}
+
+impl<P> fmt::Display for StrPathPredicate<P>
+where
+    P: predicates_core::Predicate<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<P> IntoPathPredicate<StrPathPredicate<P>> for P
+where
+    P: predicates_core::Predicate<str>,
+{
+    type Predicate = StrPathPredicate<P>;
+
+    fn into_path(self) -> Self::Predicate {
+        Self::Predicate::new(self)
+    }
+}
+
+/// Keep `predicates` concrete Predicates out of our public API.
+/// [predicates_core::Predicate] used by `IntoPathPredicate` for `[u8]` predicates.
+///
+/// # Example
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use predicates::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let input_file = temp.child("foo.bin");
+/// input_file.write_binary(b"\x00\x01").unwrap();
+///
+/// // ... do something with input_file ...
+///
+/// input_file.assert(predicate::function(|b: &[u8]| b.len() == 2)); // Uses BytesPathPredicate
+///
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct BytesPathPredicate<P: predicates_core::Predicate<[u8]>>(
+    predicates::path::FileContentPredicate<P>,
+);
+
+impl<P> BytesPathPredicate<P>
+where
+    P: predicates_core::Predicate<[u8]>,
+{
+    pub(crate) fn new(value: P) -> Self {
+        let pred = value.from_file_path();
+        BytesPathPredicate(pred)
+    }
+}
+
+impl<P> predicates_core::reflection::PredicateReflection for BytesPathPredicate<P>
+where
+    P: predicates_core::Predicate<[u8]>,
+{
+    fn parameters<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Parameter<'a>> + 'a> {
+        self.0.parameters()
+    }
+
+    /// Nested `Predicate`s of the current `Predicate`.
+    fn children<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = predicates_core::reflection::Child<'a>> + 'a> {
+        self.0.children()
+    }
+}
+
+impl<P> predicates_core::Predicate<path::Path> for BytesPathPredicate<P>
+where
+    P: predicates_core::Predicate<[u8]>,
+{
+    fn eval(&self, item: &path::Path) -> bool {
+        self.0.eval(item)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<predicates_core::reflection::Case<'a>> {
+        self.0.find_case(expected, variable)
+    }
+}
+
+impl<P> fmt::Display for BytesPathPredicate<P>
+where
+    P: predicates_core::Predicate<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<P> IntoPathPredicate<BytesPathPredicate<P>> for P
+where
+    P: predicates_core::Predicate<[u8]>,
+{
+    type Predicate = BytesPathPredicate<P>;
+
+    fn into_path(self) -> Self::Predicate {
+        Self::Predicate::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use predicates::prelude::*;
+
+    // Since IntoPathPredicate exists solely for conversion, test it under that scenario to ensure
+    // it works as expected.
+    fn convert_path<I, P>(pred: I) -> P
+    where
+        I: IntoPathPredicate<P>,
+        P: predicates_core::Predicate<path::Path>,
+    {
+        pred.into_path()
+    }
+
+    #[test]
+    fn into_path_from_pred() {
+        let pred = convert_path(predicate::eq(path::Path::new("hello.md")));
+        let case = pred.find_case(false, path::Path::new("hello.md"));
+        println!("Failing case: {:?}", case);
+        assert!(case.is_none());
+    }
+
+    #[test]
+    fn into_path_from_bytes() {
+        let pred = convert_path(b"hello\n" as &[u8]);
+        let case = pred.find_case(false, path::Path::new("tests/fixture/hello.txt"));
+        println!("Failing case: {:?}", case);
+        assert!(case.is_none());
+    }
+
+    #[test]
+    fn into_path_from_str() {
+        let pred = convert_path("hello\n");
+        let case = pred.find_case(false, path::Path::new("tests/fixture/hello.txt"));
+        println!("Failing case: {:?}", case);
+        assert!(case.is_none());
+    }
+
+    #[test]
+    fn into_path_from_bytes_pred() {
+        let pred = convert_path(predicate::function(|b: &[u8]| b == b"hello\n"));
+        let case = pred.find_case(false, path::Path::new("tests/fixture/hello.txt"));
+        println!("Failing case: {:?}", case);
+        assert!(case.is_none());
+    }
+}
@@ -0,0 +1,351 @@
+use std::ffi;
+use std::path;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::fixture;
+
+/// How to compare file names.
+///
+/// Some filesystems, like HFS+ and APFS on macOS, may store names in a different Unicode
+/// normalization form than they were created with, so a name written as NFC can be listed back
+/// as NFD.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NameMatching {
+    /// Names must be byte-for-byte identical.
+    Exact,
+    /// Names are equal if they are equivalent after Unicode (NFC) normalization.
+    Normalized,
+}
+
+impl NameMatching {
+    /// Whether `actual` is the same name as `expected`.
+    pub fn matches(self, expected: &ffi::OsStr, actual: &ffi::OsStr) -> bool {
+        match self {
+            NameMatching::Exact => expected == actual,
+            NameMatching::Normalized => self.key(expected) == self.key(actual),
+        }
+    }
+
+    /// A form of `name` that is identical for all names that match.
+    pub(crate) fn key(self, name: &ffi::OsStr) -> ffi::OsString {
+        match (self, name.to_str()) {
+            (NameMatching::Normalized, Some(name)) => name.nfc().collect::<String>().into(),
+            _ => name.to_owned(),
+        }
+    }
+}
+
+impl Default for NameMatching {
+    fn default() -> Self {
+        NameMatching::Exact
+    }
+}
+
+/// Assert entries exist within [`TempDir`], as listed by the filesystem.
+///
+/// Unlike [`predicates::path::exists`], which asks the filesystem to look up a path (which may
+/// ignore normalization or case), this compares against the names the filesystem actually
+/// stored.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::assert::NameMatching;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// // "café" with a precomposed "é" (NFC)
+/// temp.child("caf\u{e9}.txt").touch().unwrap();
+///
+/// // "café" with a combining accent (NFD)
+/// temp.assert_has_entry("cafe\u{301}.txt", NameMatching::Normalized);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertEntry {
+    /// Assert the relative path `name` exists, comparing each component with `matching`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if no matching entry is found.
+    #[track_caller]
+    fn assert_has_entry<P>(&self, name: P, matching: NameMatching) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the relative path exists that `encoded`, a `/` separated, percent-encoded URL
+    /// path, decodes to.
+    ///
+    /// For tools converting between paths and `file://` URLs.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if `encoded` is not valid percent-encoded UTF-8 or no matching entry is found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("docs/100% done #1.txt").touch().unwrap();
+    ///
+    /// temp.assert_has_url_entry("docs/100%25%20done%20%231.txt");
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_has_url_entry(&self, encoded: &str) -> &Self;
+}
+
+impl PathAssertEntry for fixture::TempDir {
+    #[track_caller]
+    fn assert_has_entry<P>(&self, name: P, matching: NameMatching) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_has_entry(self.path(), name.as_ref(), matching);
+        self
+    }
+
+    #[track_caller]
+    fn assert_has_url_entry(&self, encoded: &str) -> &Self {
+        assert_has_url_entry(self.path(), encoded);
+        self
+    }
+}
+
+impl PathAssertEntry for fixture::ChildPath {
+    #[track_caller]
+    fn assert_has_entry<P>(&self, name: P, matching: NameMatching) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_has_entry(self.path(), name.as_ref(), matching);
+        self
+    }
+
+    #[track_caller]
+    fn assert_has_url_entry(&self, encoded: &str) -> &Self {
+        assert_has_url_entry(self.path(), encoded);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_has_entry(root: &path::Path, name: &path::Path, matching: NameMatching) {
+    let mut current = root.to_owned();
+    for component in name.components() {
+        let expected = component.as_os_str();
+        let found = std::fs::read_dir(&current).ok().and_then(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name())
+                .find(|actual| matching.matches(expected, actual))
+        });
+        match found {
+            Some(actual) => current.push(actual),
+            None => {
+                let palette = crate::Palette::current();
+                crate::report::fail(format!(
+                    "Unexpected file, missing entry\n{}={}\n{}={:?}\n{}={}",
+                    palette.key.paint("entry"),
+                    palette.value.paint(name.display()),
+                    palette.key.paint("matching"),
+                    matching,
+                    palette.key.paint("path"),
+                    palette.value.paint(current.display())
+                ));
+            }
+        }
+    }
+}
+
+#[track_caller]
+fn assert_has_url_entry(root: &path::Path, encoded: &str) {
+    let mut name = path::PathBuf::new();
+    for segment in encoded.split('/').filter(|s| !s.is_empty()) {
+        match percent_decode(segment) {
+            Some(segment) => name.push(segment),
+            None => {
+                let palette = crate::Palette::current();
+                crate::report::fail(format!(
+                    "Invalid percent-encoded path\n{}={}",
+                    palette.key.paint("url"),
+                    palette.value.paint(encoded)
+                ));
+            }
+        }
+    }
+    assert_has_entry(root, &name, NameMatching::Exact);
+}
+
+/// Assert a path exists with exactly its name's case.
+///
+/// On case-insensitive filesystems, like the defaults on macOS and Windows, looking up
+/// `README.md` finds `readme.md`, so [`predicates::path::exists`] can't catch a tool writing the
+/// wrong case.  This compares each component against the names the filesystem actually stored.
+/// See [`TempDir::is_case_sensitive`] to check which kind of filesystem a fixture is on.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let readme = temp.child("docs/README.md");
+/// readme.touch().unwrap();
+///
+/// readme.assert_exact_case();
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir::is_case_sensitive`]: crate::TempDir::is_case_sensitive
+pub trait PathAssertExactCase {
+    /// Assert the path exists, with every component named exactly as given.
+    ///
+    /// Components within directories that can't be listed are only checked to exist.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the path is missing or exists under a different case, showing the name
+    /// found instead.
+    #[track_caller]
+    fn assert_exact_case(&self) -> &Self;
+}
+
+impl PathAssertExactCase for fixture::ChildPath {
+    #[track_caller]
+    fn assert_exact_case(&self) -> &Self {
+        assert_exact_case(self.path());
+        self
+    }
+}
+
+impl PathAssertExactCase for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_exact_case(&self) -> &Self {
+        assert_exact_case(self.path());
+        self
+    }
+}
+
+#[track_caller]
+fn assert_exact_case(path: &path::Path) {
+    let palette = crate::Palette::current();
+    if path.symlink_metadata().is_err() {
+        crate::report::fail(format!(
+            "Unexpected file, file does not exist\n{}={}",
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+    let mut current = path::PathBuf::new();
+    for component in path.components() {
+        let expected = component.as_os_str();
+        if let path::Component::Normal(_) = component {
+            let dir = if current.as_os_str().is_empty() {
+                path::Path::new(".")
+            } else {
+                current.as_path()
+            };
+            let names: Option<Vec<_>> = std::fs::read_dir(dir).ok().map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name())
+                    .collect()
+            });
+            if let Some(names) = names {
+                if !names.iter().any(|actual| actual == expected) {
+                    let found = names
+                        .iter()
+                        .find(|actual| same_ignoring_case(expected, actual))
+                        .map(|actual| actual.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    crate::report::fail(format!(
+                        "Unexpected file, name differs in case\n{}={}\n{}={}\n{}={}",
+                        palette.key.paint("path"),
+                        palette.value.paint(path.display()),
+                        palette.key.paint("expected"),
+                        palette.value.paint(expected.to_string_lossy()),
+                        palette.key.paint("actual"),
+                        palette.value.paint(found)
+                    ));
+                }
+            }
+        }
+        current.push(expected);
+    }
+}
+
+fn same_ignoring_case(expected: &ffi::OsStr, actual: &ffi::OsStr) -> bool {
+    match (expected.to_str(), actual.to_str()) {
+        (Some(expected), Some(actual)) => expected.to_lowercase() == actual.to_lowercase(),
+        _ => false,
+    }
+}
+
+/// Decode `%XX` escapes in `segment`, returning `None` for malformed escapes or UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut rest = segment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            let hex = std::str::from_utf8(hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        let nfc = ffi::OsStr::new("caf\u{e9}");
+        let nfd = ffi::OsStr::new("cafe\u{301}");
+        assert!(NameMatching::Normalized.matches(nfc, nfd));
+        assert!(!NameMatching::Exact.matches(nfc, nfd));
+        assert!(NameMatching::Exact.matches(nfc, nfc));
+    }
+
+    #[test]
+    fn same_ignoring_case_folds() {
+        assert!(same_ignoring_case(
+            ffi::OsStr::new("README.md"),
+            ffi::OsStr::new("readme.md")
+        ));
+        assert!(!same_ignoring_case(
+            ffi::OsStr::new("README.md"),
+            ffi::OsStr::new("readme.txt")
+        ));
+    }
+
+    #[test]
+    fn percent_decode_escapes() {
+        assert_eq!(
+            percent_decode("100%25%20done%20%231").unwrap(),
+            "100% done #1"
+        );
+        assert_eq!(percent_decode("caf%C3%A9").unwrap(), "caf\u{e9}");
+        assert_eq!(percent_decode("bad%2"), None);
+        assert_eq!(percent_decode("bad%zz"), None);
+        assert_eq!(percent_decode("bad%+1"), None);
+        assert_eq!(percent_decode("%ff"), None);
+    }
+}
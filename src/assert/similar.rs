@@ -0,0 +1,315 @@
+use std::path;
+
+use crate::fixture;
+
+/// Assert files within [`TempDir`] are nearly equal to expected text.
+///
+/// For outputs that vary slightly between runs, like embedded timestamps or the order of a few
+/// lines, where normalizing them away is impractical.
+///
+/// Content is compared line by line.  The difference ratio is the number of lines removed from
+/// and added to the expected text, over the total number of lines in both: `0.0` for equal
+/// content, `1.0` for content with no line in common.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let report = temp.child("report.txt");
+/// report
+///     .write_str("Report\ngenerated 2024-05-01 12:00:03\n1 passed\n2 passed\n3 passed\n")
+///     .unwrap();
+///
+/// // One of five lines changed: one removed, one added, out of ten
+/// report.assert_similar(
+///     "Report\ngenerated 2024-01-01 00:00:00\n1 passed\n2 passed\n3 passed\n",
+///     0.2,
+/// );
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertSimilar {
+    /// Assert the file's content differs from `expected` by at most `max_diff_ratio`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read as UTF-8 or differs by more than `max_diff_ratio`,
+    /// showing the measured ratio and the largest differing hunk.
+    #[track_caller]
+    fn assert_similar(&self, expected: &str, max_diff_ratio: f64) -> &Self;
+}
+
+impl PathAssertSimilar for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_similar(&self, expected: &str, max_diff_ratio: f64) -> &Self {
+        assert_similar(self.path(), expected, max_diff_ratio);
+        self
+    }
+}
+
+impl PathAssertSimilar for fixture::ChildPath {
+    #[track_caller]
+    fn assert_similar(&self, expected: &str, max_diff_ratio: f64) -> &Self {
+        assert_similar(self.path(), expected, max_diff_ratio);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_similar(path: &path::Path, expected: &str, max_diff_ratio: f64) {
+    let actual = match std::fs::read_to_string(path) {
+        Ok(actual) => actual,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    let expected_lines: Vec<_> = expected.lines().collect();
+    let actual_lines: Vec<_> = actual.lines().collect();
+    let edits = diff(&expected_lines, &actual_lines);
+    let total = expected_lines.len() + actual_lines.len();
+    let changed = edits.iter().filter(|e| !matches!(e, Edit::Same(_))).count();
+    let ratio = if total == 0 {
+        0.0
+    } else {
+        changed as f64 / total as f64
+    };
+    if ratio <= max_diff_ratio {
+        return;
+    }
+    let palette = crate::Palette::current();
+    crate::report::fail_with(
+        format!(
+            "Unexpected file content, too different\n{}={:.3}\n{}={:.3}\n{}={}\n{}:\n{}",
+            palette.key.paint("max_diff_ratio"),
+            max_diff_ratio,
+            palette.key.paint("diff_ratio"),
+            ratio,
+            palette.key.paint("path"),
+            palette.value.paint(path.display()),
+            palette.key.paint("largest hunk"),
+            largest_hunk(&edits),
+        ),
+        vec![
+            ("expected", expected.as_bytes().to_owned()),
+            ("actual", actual.into_bytes()),
+        ],
+    );
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Edit<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A shortest line-based edit script from `expected` to `actual`.
+///
+/// Quadratic in the number of lines between the common prefix and suffix.
+fn diff<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<Edit<'a>> {
+    let prefix = expected
+        .iter()
+        .zip(actual)
+        .take_while(|(e, a)| e == a)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+    let old = &expected[prefix..expected.len() - suffix];
+    let new = &actual[prefix..actual.len() - suffix];
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits: Vec<_> = expected[..prefix].iter().map(|l| Edit::Same(l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push(Edit::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push(Edit::Removed(old[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Added(new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|l| Edit::Same(l)),
+    );
+    edits
+}
+
+/// The longest run of changed lines, as `-`/`+` prefixed lines.
+fn largest_hunk(edits: &[Edit<'_>]) -> String {
+    let hunk = edits
+        .split(|e| matches!(e, Edit::Same(_)))
+        .max_by_key(|hunk| hunk.len())
+        .unwrap_or_default();
+    let mut rendered = String::new();
+    for edit in hunk {
+        let (marker, line) = match edit {
+            Edit::Removed(line) => ('-', line),
+            Edit::Added(line) => ('+', line),
+            Edit::Same(_) => unreachable!("hunks only contain changes"),
+        };
+        rendered.push(marker);
+        rendered.push_str(line);
+        rendered.push('\n');
+    }
+    rendered
+}
+
+/// A unified diff from `expected` to `actual`, with `context` unchanged lines around each hunk.
+///
+/// Output past `max_lines` lines is cut, noting how many were left out.
+pub(super) fn unified_diff(
+    expected: &str,
+    actual: &str,
+    context: usize,
+    max_lines: usize,
+) -> String {
+    let expected_lines: Vec<_> = expected.lines().collect();
+    let actual_lines: Vec<_> = actual.lines().collect();
+    let edits = diff(&expected_lines, &actual_lines);
+
+    // Keep the edits within `context` of a change
+    let mut shown = vec![false; edits.len()];
+    for (i, edit) in edits.iter().enumerate() {
+        if !matches!(edit, Edit::Same(_)) {
+            let start = i.saturating_sub(context);
+            let end = (i + context + 1).min(edits.len());
+            shown[start..end].iter_mut().for_each(|s| *s = true);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut old_line, mut new_line) = (1, 1);
+    let mut i = 0;
+    while i < edits.len() {
+        if !shown[i] {
+            match edits[i] {
+                Edit::Same(_) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+                Edit::Removed(_) => old_line += 1,
+                Edit::Added(_) => new_line += 1,
+            }
+            i += 1;
+            continue;
+        }
+        let end = shown[i..]
+            .iter()
+            .position(|s| !s)
+            .map_or(edits.len(), |n| i + n);
+        let hunk = &edits[i..end];
+        let old_len = hunk.iter().filter(|e| !matches!(e, Edit::Added(_))).count();
+        let new_len = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Removed(_)))
+            .count();
+        lines.push(format!(
+            "@@ -{},{} +{},{} @@",
+            old_line, old_len, new_line, new_len
+        ));
+        for edit in hunk {
+            lines.push(match edit {
+                Edit::Same(line) => format!(" {}", line),
+                Edit::Removed(line) => format!("-{}", line),
+                Edit::Added(line) => format!("+{}", line),
+            });
+        }
+        old_line += old_len;
+        new_line += new_len;
+        i = end;
+    }
+
+    let mut rendered = String::new();
+    for line in lines.iter().take(max_lines) {
+        rendered.push_str(line);
+        rendered.push('\n');
+    }
+    if max_lines < lines.len() {
+        rendered.push_str(&format!("... {} more lines\n", lines.len() - max_lines));
+    }
+    rendered
+}
+
+/// `diff`, from [`unified_diff`], colored when the terminal supports it.
+pub(super) fn paint_diff(diff: &str, palette: crate::Palette) -> String {
+    let mut painted = String::new();
+    for line in diff.lines() {
+        let line = if line.starts_with("@@") {
+            palette.key.paint(line).to_string()
+        } else if line.starts_with('-') {
+            palette.removed.paint(line).to_string()
+        } else if line.starts_with('+') {
+            palette.added.paint(line).to_string()
+        } else {
+            line.to_owned()
+        };
+        painted.push_str(&line);
+        painted.push('\n');
+    }
+    painted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diff_keeps_common_lines() {
+        let edits = diff(&["a", "b", "c", "d"], &["a", "x", "c", "d", "e"]);
+        assert_eq!(
+            edits,
+            [
+                Edit::Same("a"),
+                Edit::Removed("b"),
+                Edit::Added("x"),
+                Edit::Same("c"),
+                Edit::Same("d"),
+                Edit::Added("e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn largest_hunk_picks_longest_run() {
+        let edits = diff(&["a", "b", "c", "d", "e"], &["x", "b", "y", "z", "e"]);
+        assert_eq!(largest_hunk(&edits), "-c\n-d\n+y\n+z\n");
+    }
+
+    #[test]
+    fn unified_diff_shows_context_and_truncates() {
+        let expected = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let actual = "1\n2\nthree\n4\n5\n6\n7\neight\n";
+        assert_eq!(
+            unified_diff(expected, actual, 1, 100),
+            "@@ -2,3 +2,3 @@\n 2\n-3\n+three\n 4\n@@ -7,2 +7,2 @@\n 7\n-8\n+eight\n"
+        );
+        assert_eq!(
+            unified_diff(expected, actual, 1, 3),
+            "@@ -2,3 +2,3 @@\n 2\n-3\n... 6 more lines\n"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::cmp;
+use std::path;
+
+use crate::fixture;
+
+/// The order lines must be in, for [`PathAssertSorted::assert_sorted_lines`].
+///
+/// Lines are compared as strings, byte by byte, like [`slice::sort`] orders them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LineOrdering {
+    /// Each line is greater than or equal to the one before it.
+    Ascending,
+    /// Each line is strictly greater than the one before it, so lines are also unique.
+    AscendingUnique,
+    /// Each line is less than or equal to the one before it.
+    Descending,
+    /// Each line is strictly less than the one before it, so lines are also unique.
+    DescendingUnique,
+}
+
+impl LineOrdering {
+    /// Whether `next` may follow `prev`.
+    fn allows(self, prev: &str, next: &str) -> bool {
+        let ordering = prev.cmp(next);
+        match self {
+            LineOrdering::Ascending => {
+                matches!(ordering, cmp::Ordering::Less | cmp::Ordering::Equal)
+            }
+            LineOrdering::AscendingUnique => matches!(ordering, cmp::Ordering::Less),
+            LineOrdering::Descending => {
+                matches!(ordering, cmp::Ordering::Greater | cmp::Ordering::Equal)
+            }
+            LineOrdering::DescendingUnique => matches!(ordering, cmp::Ordering::Greater),
+        }
+    }
+}
+
+/// Assert the lines of files within [`TempDir`] are sorted.
+///
+/// For generated indexes, lockfiles, and manifests, whose output must be deterministic.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::assert::LineOrdering;
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let index = temp.child("index.txt");
+/// index.write_str("alpha\nbeta\ngamma\n").unwrap();
+///
+/// index.assert_sorted_lines(LineOrdering::AscendingUnique);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertSorted {
+    /// Assert the file's lines are in `ordering`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the file can't be read as UTF-8 or its lines aren't in `ordering`,
+    /// showing the first pair of lines out of order.
+    #[track_caller]
+    fn assert_sorted_lines(&self, ordering: LineOrdering) -> &Self;
+}
+
+impl PathAssertSorted for fixture::NamedTempFile {
+    #[track_caller]
+    fn assert_sorted_lines(&self, ordering: LineOrdering) -> &Self {
+        assert_sorted_lines(self.path(), ordering);
+        self
+    }
+}
+
+impl PathAssertSorted for fixture::ChildPath {
+    #[track_caller]
+    fn assert_sorted_lines(&self, ordering: LineOrdering) -> &Self {
+        assert_sorted_lines(self.path(), ordering);
+        self
+    }
+}
+
+#[track_caller]
+fn assert_sorted_lines(path: &path::Path, ordering: LineOrdering) {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    let (index, prev, next) = match first_unsorted(&content, ordering) {
+        Some(pair) => pair,
+        None => return,
+    };
+    let palette = crate::Palette::current();
+    crate::report::fail(format!(
+        "Unexpected file content, lines out of order\n{}={:?}\n{}={}\n{}={}: {:?}\n{}={}: {:?}",
+        palette.key.paint("ordering"),
+        ordering,
+        palette.key.paint("path"),
+        palette.value.paint(path.display()),
+        palette.key.paint("line"),
+        index + 1,
+        prev,
+        palette.key.paint("line"),
+        index + 2,
+        next,
+    ));
+}
+
+/// The first pair of adjacent lines out of `ordering`, with the 0-based index of the first.
+fn first_unsorted(content: &str, ordering: LineOrdering) -> Option<(usize, &str, &str)> {
+    let lines: Vec<_> = content.lines().collect();
+    lines
+        .windows(2)
+        .position(|pair| !ordering.allows(pair[0], pair[1]))
+        .map(|index| (index, lines[index], lines[index + 1]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_unsorted_respects_uniqueness() {
+        let content = "a\nb\nb\nc\n";
+        assert_eq!(first_unsorted(content, LineOrdering::Ascending), None);
+        assert_eq!(
+            first_unsorted(content, LineOrdering::AscendingUnique),
+            Some((1, "b", "b"))
+        );
+        assert_eq!(
+            first_unsorted(content, LineOrdering::Descending),
+            Some((0, "a", "b"))
+        );
+    }
+}
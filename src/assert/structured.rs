@@ -0,0 +1,444 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path;
+
+use crate::fixture;
+
+/// Assert the content of serialized files within [`TempDir`], ignoring formatting.
+///
+/// Both the file and `expected` are parsed and compared as trees of values, so key order,
+/// whitespace, and comments don't matter.  On failure, the first difference is shown with its
+/// location as a JSON pointer, like `/dependencies/0/version`.
+///
+/// Each format requires its feature: `structured-json`, `structured-toml`, or
+/// `structured-yaml`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "structured-json")]
+/// # {
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let manifest = temp.child("manifest.json");
+/// manifest.write_str(r#"{"version": 2, "name": "app"}"#).unwrap();
+///
+/// manifest.assert_json(r#"{ "name": "app", "version": 2 }"#);
+///
+/// temp.close().unwrap();
+/// # }
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertStructured {
+    /// Assert the file is JSON equal to `expected`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either side isn't valid JSON, or they differ.
+    #[cfg(feature = "structured-json")]
+    #[track_caller]
+    fn assert_json(&self, expected: &str) -> &Self;
+
+    /// Assert the file is TOML equal to `expected`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either side isn't valid TOML, or they differ.
+    #[cfg(feature = "structured-toml")]
+    #[track_caller]
+    fn assert_toml(&self, expected: &str) -> &Self;
+
+    /// Assert the file is YAML equal to `expected`.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if either side isn't valid YAML, or they differ.
+    #[cfg(feature = "structured-yaml")]
+    #[track_caller]
+    fn assert_yaml(&self, expected: &str) -> &Self;
+}
+
+impl PathAssertStructured for fixture::NamedTempFile {
+    #[cfg(feature = "structured-json")]
+    #[track_caller]
+    fn assert_json(&self, expected: &str) -> &Self {
+        assert_structured(self.path(), Format::Json, expected);
+        self
+    }
+
+    #[cfg(feature = "structured-toml")]
+    #[track_caller]
+    fn assert_toml(&self, expected: &str) -> &Self {
+        assert_structured(self.path(), Format::Toml, expected);
+        self
+    }
+
+    #[cfg(feature = "structured-yaml")]
+    #[track_caller]
+    fn assert_yaml(&self, expected: &str) -> &Self {
+        assert_structured(self.path(), Format::Yaml, expected);
+        self
+    }
+}
+
+impl PathAssertStructured for fixture::ChildPath {
+    #[cfg(feature = "structured-json")]
+    #[track_caller]
+    fn assert_json(&self, expected: &str) -> &Self {
+        assert_structured(self.path(), Format::Json, expected);
+        self
+    }
+
+    #[cfg(feature = "structured-toml")]
+    #[track_caller]
+    fn assert_toml(&self, expected: &str) -> &Self {
+        assert_structured(self.path(), Format::Toml, expected);
+        self
+    }
+
+    #[cfg(feature = "structured-yaml")]
+    #[track_caller]
+    fn assert_yaml(&self, expected: &str) -> &Self {
+        assert_structured(self.path(), Format::Yaml, expected);
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Format {
+    #[cfg(feature = "structured-json")]
+    Json,
+    #[cfg(feature = "structured-toml")]
+    Toml,
+    #[cfg(feature = "structured-yaml")]
+    Yaml,
+}
+
+impl Format {
+    fn parse(self, text: &str) -> Result<Node, String> {
+        match self {
+            #[cfg(feature = "structured-json")]
+            Format::Json => serde_json::from_str(text)
+                .map(Node::from_json)
+                .map_err(|e| e.to_string()),
+            #[cfg(feature = "structured-toml")]
+            Format::Toml => text
+                .parse::<toml::Table>()
+                .map(|table| Node::from_toml(toml::Value::Table(table)))
+                .map_err(|e| e.to_string()),
+            #[cfg(feature = "structured-yaml")]
+            Format::Yaml => serde_yaml::from_str(text)
+                .map(Node::from_yaml)
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "structured-json")]
+            Format::Json => write!(f, "JSON"),
+            #[cfg(feature = "structured-toml")]
+            Format::Toml => write!(f, "TOML"),
+            #[cfg(feature = "structured-yaml")]
+            Format::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// A parsed value, common to all formats.
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Null,
+    Bool(bool),
+    // Formatted, so integers and floats of any size compare exactly
+    Number(String),
+    String(String),
+    Array(Vec<Node>),
+    Object(BTreeMap<String, Node>),
+}
+
+impl Node {
+    #[cfg(feature = "structured-json")]
+    fn from_json(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Node::Null,
+            serde_json::Value::Bool(b) => Node::Bool(b),
+            serde_json::Value::Number(n) => match n.as_f64() {
+                Some(f) if n.is_f64() => Node::Number(format!("{:?}", f)),
+                _ => Node::Number(n.to_string()),
+            },
+            serde_json::Value::String(s) => Node::String(s),
+            serde_json::Value::Array(items) => {
+                Node::Array(items.into_iter().map(Node::from_json).collect())
+            }
+            serde_json::Value::Object(map) => Node::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k, Node::from_json(v)))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[cfg(feature = "structured-toml")]
+    fn from_toml(value: toml::Value) -> Self {
+        match value {
+            toml::Value::String(s) => Node::String(s),
+            toml::Value::Integer(i) => Node::Number(i.to_string()),
+            toml::Value::Float(f) => Node::Number(format!("{:?}", f)),
+            toml::Value::Boolean(b) => Node::Bool(b),
+            toml::Value::Datetime(d) => Node::String(d.to_string()),
+            toml::Value::Array(items) => {
+                Node::Array(items.into_iter().map(Node::from_toml).collect())
+            }
+            toml::Value::Table(table) => Node::Object(
+                table
+                    .into_iter()
+                    .map(|(k, v)| (k, Node::from_toml(v)))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[cfg(feature = "structured-yaml")]
+    fn from_yaml(value: serde_yaml::Value) -> Self {
+        match value {
+            serde_yaml::Value::Null => Node::Null,
+            serde_yaml::Value::Bool(b) => Node::Bool(b),
+            serde_yaml::Value::Number(n) => match n.as_f64() {
+                Some(f) if n.is_f64() => Node::Number(format!("{:?}", f)),
+                _ => Node::Number(n.to_string()),
+            },
+            serde_yaml::Value::String(s) => Node::String(s),
+            serde_yaml::Value::Sequence(items) => {
+                Node::Array(items.into_iter().map(Node::from_yaml).collect())
+            }
+            serde_yaml::Value::Mapping(map) => Node::Object(
+                map.into_iter()
+                    .map(|(k, v)| {
+                        let key = match Node::from_yaml(k) {
+                            Node::String(s) => s,
+                            other => other.to_string(),
+                        };
+                        (key, Node::from_yaml(v))
+                    })
+                    .collect(),
+            ),
+            serde_yaml::Value::Tagged(tagged) => Node::from_yaml(tagged.value),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Node::Null => "null",
+            Node::Bool(_) => "boolean",
+            Node::Number(_) => "number",
+            Node::String(_) => "string",
+            Node::Array(_) => "array",
+            Node::Object(_) => "object",
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Null => write!(f, "null"),
+            Node::Bool(b) => write!(f, "{}", b),
+            Node::Number(n) => write!(f, "{}", n),
+            Node::String(s) => write!(f, "{:?}", s),
+            Node::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Node::Object(map) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// The first difference between two trees.
+#[derive(Debug, PartialEq)]
+struct Difference {
+    pointer: String,
+    expected: Option<String>,
+    actual: Option<String>,
+}
+
+/// Find the first difference, visiting keys in order, with `pointer` the location of `expected`.
+fn first_difference(pointer: &str, expected: &Node, actual: &Node) -> Option<Difference> {
+    match (expected, actual) {
+        (Node::Array(expected), Node::Array(actual)) => {
+            for i in 0..expected.len().max(actual.len()) {
+                let pointer = format!("{}/{}", pointer, i);
+                match (expected.get(i), actual.get(i)) {
+                    (Some(e), Some(a)) => {
+                        if let Some(diff) = first_difference(&pointer, e, a) {
+                            return Some(diff);
+                        }
+                    }
+                    (e, a) => {
+                        return Some(Difference {
+                            pointer,
+                            expected: e.map(Node::to_string),
+                            actual: a.map(Node::to_string),
+                        })
+                    }
+                }
+            }
+            None
+        }
+        (Node::Object(expected), Node::Object(actual)) => {
+            let mut keys: Vec<_> = expected.keys().chain(actual.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let pointer = format!("{}/{}", pointer, escape_pointer(key));
+                match (expected.get(key), actual.get(key)) {
+                    (Some(e), Some(a)) => {
+                        if let Some(diff) = first_difference(&pointer, e, a) {
+                            return Some(diff);
+                        }
+                    }
+                    (e, a) => {
+                        return Some(Difference {
+                            pointer,
+                            expected: e.map(Node::to_string),
+                            actual: a.map(Node::to_string),
+                        })
+                    }
+                }
+            }
+            None
+        }
+        (expected, actual) if expected == actual => None,
+        (expected, actual) => {
+            let (expected, actual) = if expected.kind() == actual.kind() {
+                (expected.to_string(), actual.to_string())
+            } else {
+                (
+                    format!("{} {}", expected.kind(), expected),
+                    format!("{} {}", actual.kind(), actual),
+                )
+            };
+            Some(Difference {
+                pointer: pointer.to_owned(),
+                expected: Some(expected),
+                actual: Some(actual),
+            })
+        }
+    }
+}
+
+/// Escape a key for a JSON pointer (RFC 6901).
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[track_caller]
+fn assert_structured(path: &path::Path, format: Format, expected: &str) {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => panic!("Failed to read `{}`: {}", path.display(), err),
+    };
+    let expected = match format.parse(expected) {
+        Ok(expected) => expected,
+        Err(err) => panic!("Invalid expected {}: {}", format, err),
+    };
+    let palette = crate::Palette::current();
+    let actual = match format.parse(&content) {
+        Ok(actual) => actual,
+        Err(err) => crate::report::fail(format!(
+            "Unexpected file content, invalid {}\n{}={}\n{}={}",
+            format,
+            palette.key.paint("path"),
+            palette.value.paint(path.display()),
+            palette.key.paint("error"),
+            palette.value.paint(err)
+        )),
+    };
+    if let Some(diff) = first_difference("", &expected, &actual) {
+        let missing = || "(missing)".to_owned();
+        crate::report::fail(format!(
+            "Unexpected file content, {} differs\n{}={}\n{}={}\n{}={}\n{}={}",
+            format,
+            palette.key.paint("path"),
+            palette.value.paint(path.display()),
+            palette.key.paint("pointer"),
+            palette.value.paint(if diff.pointer.is_empty() {
+                "/"
+            } else {
+                diff.pointer.as_str()
+            }),
+            palette.key.paint("expected"),
+            palette.value.paint(diff.expected.unwrap_or_else(missing)),
+            palette.key.paint("actual"),
+            palette.value.paint(diff.actual.unwrap_or_else(missing))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn object(entries: &[(&str, Node)]) -> Node {
+        Node::Object(
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn first_difference_points_at_change() {
+        let expected = object(&[(
+            "deps",
+            Node::Array(vec![object(&[("a/b", Node::Number("1".into()))])]),
+        )]);
+        let actual = object(&[(
+            "deps",
+            Node::Array(vec![object(&[("a/b", Node::Number("2".into()))])]),
+        )]);
+        assert_eq!(first_difference("", &expected, &expected), None);
+        assert_eq!(
+            first_difference("", &expected, &actual),
+            Some(Difference {
+                pointer: "/deps/0/a~1b".into(),
+                expected: Some("1".into()),
+                actual: Some("2".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn first_difference_reports_missing_and_kind() {
+        let expected = object(&[("a", Node::Bool(true))]);
+        let actual = object(&[("b", Node::Bool(true))]);
+        let diff = first_difference("", &expected, &actual).unwrap();
+        assert_eq!(diff.pointer, "/a");
+        assert_eq!(diff.actual, None);
+
+        let diff = first_difference("", &Node::Null, &Node::String("x".into())).unwrap();
+        assert_eq!(diff.expected.unwrap(), "null null");
+        assert_eq!(diff.actual.unwrap(), "string \"x\"");
+    }
+}
@@ -0,0 +1,769 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path;
+
+use predicates_core::Predicate;
+use predicates_tree::CaseTreeExt;
+
+use crate::fixture;
+use crate::fixture::PathRenderTree;
+
+/// Environment variable that, when set to `overwrite`, updates tree snapshots and manifests
+/// instead of asserting against them.
+pub const SNAPSHOT_ENV: &str = "ASSERT_FS_SNAPSHOT";
+
+/// Assert a whole directory tree against an expected one.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let expected = temp.child("expected");
+/// expected.child("out/report.txt").write_str("ok\n").unwrap();
+/// let actual = temp.child("actual");
+/// actual.child("out/report.txt").write_str("ok\n").unwrap();
+///
+/// actual.assert_dir_eq(&expected);
+///
+/// temp.close().unwrap();
+/// ```
+///
+/// [`TempDir`]: crate::TempDir
+pub trait PathAssertTree {
+    /// Assert the directory has the same entries and file contents as `expected_root`.
+    ///
+    /// Symlinks are compared by their target, rather than followed.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the trees differ, listing missing entries, extra entries, and each file
+    /// whose content differs.  Small text files get an inline unified diff, bounded in length
+    /// and in the number of files, so most failures can be diagnosed from the message alone.
+    #[track_caller]
+    fn assert_dir_eq<P>(&self, expected_root: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert every file in the directory is matched by one of the glob `patterns`.
+    ///
+    /// A pattern matching a directory covers everything within it.  Directories themselves
+    /// aren't required to match.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if a pattern is invalid or any file is not matched, listing the unexpected
+    /// files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("expected.txt").touch().unwrap();
+    /// temp.child("logs/run.log").touch().unwrap();
+    /// temp.child("cache/index").touch().unwrap();
+    ///
+    /// temp.assert_only(&["expected.txt", "logs/*.log", "cache"]);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_only<S>(&self, patterns: &[S]) -> &Self
+    where
+        S: AsRef<str>;
+
+    /// Assert the directory matches the snapshot stored in the `.snap` file at `snapshot`.
+    ///
+    /// The snapshot is the directory [rendered][fixture::PathRenderTree::render_tree] with the
+    /// content hash of each file and the content of small text files, so it can be reviewed in
+    /// diffs.  When the [`SNAPSHOT_ENV`] environment variable is set to `overwrite`, the
+    /// snapshot is written instead.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the snapshot is missing or differs, showing a diff.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let out = temp.child("out");
+    /// out.child("report.txt").write_str("ok\n").unwrap();
+    /// let snap = temp.child("out.snap");
+    /// # let hash = "dc51b8c96c2d745df3bd5590d990230a482fd247123599548e0632fdbf97fc22";
+    /// # snap.write_str(&format!("report.txt sha256:{}\n  | ok\n", hash)).unwrap();
+    ///
+    /// out.assert_tree_snapshot(&snap);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_tree_snapshot<P>(&self, snapshot: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+
+    /// Assert the files in the directory are exactly those matched by the glob `patterns`.
+    ///
+    /// Like [`PathAssertTree::assert_only`], with every pattern, other than `!` exclusions,
+    /// also required to match something.  Plain paths are valid patterns, so this also checks
+    /// for an explicit list of files.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if a pattern is invalid, listing the patterns that matched nothing as
+    /// missing and the files not matched as extra.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("dist/app.js").touch().unwrap();
+    /// temp.child("dist/app.js.map").touch().unwrap();
+    /// temp.child("dist/index.html").touch().unwrap();
+    ///
+    /// temp.child("dist")
+    ///     .assert_matches_glob_set(&["index.html", "*.js", "*.js.map"]);
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_matches_glob_set<S>(&self, patterns: &[S]) -> &Self
+    where
+        S: AsRef<str>;
+
+    /// Assert the directory matches the manifest at `manifest`, written by
+    /// [`PathManifest::write_manifest`][fixture::PathManifest::write_manifest].
+    ///
+    /// Entries are compared by kind, size, content hash, symlink target, and, when both sides
+    /// record one, mode.  When the [`SNAPSHOT_ENV`] environment variable is set to
+    /// `overwrite`, the manifest is written instead.  A manifest within the directory is left
+    /// out.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if the manifest is missing or invalid, or the directory differs, listing the
+    /// added, removed, and changed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("out/report.txt").write_str("ok\n").unwrap();
+    /// temp.write_manifest(temp.child("expected.manifest")).unwrap();
+    ///
+    /// temp.assert_manifest(temp.child("expected.manifest"));
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    #[track_caller]
+    fn assert_manifest<P>(&self, manifest: P) -> &Self
+    where
+        P: AsRef<path::Path>;
+}
+
+impl PathAssertTree for fixture::TempDir {
+    #[track_caller]
+    fn assert_dir_eq<P>(&self, expected_root: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_dir_eq(self.path(), expected_root.as_ref());
+        self
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_only<S>(&self, patterns: &[S]) -> &Self
+    where
+        S: AsRef<str>,
+    {
+        assert_only(self.path(), patterns);
+        self
+    }
+
+    #[track_caller]
+    fn assert_tree_snapshot<P>(&self, snapshot: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_tree_snapshot(self, self.path(), snapshot.as_ref());
+        self
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_matches_glob_set<S>(&self, patterns: &[S]) -> &Self
+    where
+        S: AsRef<str>,
+    {
+        assert_matches_glob_set(self.path(), patterns);
+        self
+    }
+
+    #[track_caller]
+    fn assert_manifest<P>(&self, manifest: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_manifest(self.path(), manifest.as_ref());
+        self
+    }
+}
+
+impl PathAssertTree for fixture::ChildPath {
+    #[track_caller]
+    fn assert_dir_eq<P>(&self, expected_root: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_dir_eq(self.path(), expected_root.as_ref());
+        self
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_only<S>(&self, patterns: &[S]) -> &Self
+    where
+        S: AsRef<str>,
+    {
+        assert_only(self.path(), patterns);
+        self
+    }
+
+    #[track_caller]
+    fn assert_tree_snapshot<P>(&self, snapshot: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_tree_snapshot(self, self.path(), snapshot.as_ref());
+        self
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn assert_matches_glob_set<S>(&self, patterns: &[S]) -> &Self
+    where
+        S: AsRef<str>,
+    {
+        assert_matches_glob_set(self.path(), patterns);
+        self
+    }
+
+    #[track_caller]
+    fn assert_manifest<P>(&self, manifest: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        assert_manifest(self.path(), manifest.as_ref());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Dir,
+    File,
+    Symlink(path::PathBuf),
+}
+
+impl Node {
+    fn describe(&self) -> &'static str {
+        match self {
+            Node::Dir => "directory",
+            Node::File => "file",
+            Node::Symlink(_) => "symlink",
+        }
+    }
+}
+
+/// All entries under `root`, keyed by their path relative to it.
+#[track_caller]
+fn scan(root: &path::Path) -> BTreeMap<path::PathBuf, Node> {
+    match fs::metadata(root) {
+        Ok(metadata) if metadata.is_dir() => {}
+        Ok(_) => panic!("`{}` is not a directory", root.display()),
+        Err(err) => panic!("Failed to read `{}`: {}", root.display(), err),
+    }
+    let mut nodes = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root).min_depth(1) {
+        let entry =
+            entry.unwrap_or_else(|err| panic!("Failed to walk `{}`: {}", root.display(), err));
+        let node = if entry.file_type().is_symlink() {
+            let target = fs::read_link(entry.path()).unwrap_or_else(|err| {
+                panic!("Failed to read `{}`: {}", entry.path().display(), err)
+            });
+            Node::Symlink(target)
+        } else if entry.file_type().is_dir() {
+            Node::Dir
+        } else {
+            Node::File
+        };
+        let rel = entry
+            .path()
+            .strip_prefix(root)
+            .expect("walked paths are within the root")
+            .to_owned();
+        nodes.insert(rel, node);
+    }
+    nodes
+}
+
+/// Drop entries within directories that are already listed.
+fn top_level(paths: Vec<&path::PathBuf>) -> Vec<&path::PathBuf> {
+    let mut kept: Vec<&path::PathBuf> = Vec::new();
+    for path in paths {
+        // Sorted, so a parent directory always comes before its entries
+        if !kept.iter().any(|parent| path.starts_with(parent)) {
+            kept.push(path);
+        }
+    }
+    kept
+}
+
+/// Describe how `actual` differs from `expected`, if at all.
+///
+/// Small text files get an inline diff while `inline_diffs` remain.
+#[track_caller]
+fn compare_files(
+    actual: &path::Path,
+    expected: &path::Path,
+    inline_diffs: &mut usize,
+) -> Option<String> {
+    let read = |path: &path::Path| {
+        fs::read(path).unwrap_or_else(|err| panic!("Failed to read `{}`: {}", path.display(), err))
+    };
+    let actual = read(actual);
+    let expected = read(expected);
+    if actual == expected {
+        return None;
+    }
+    let (actual_len, expected_len) = (actual.len(), expected.len());
+    match (String::from_utf8(actual), String::from_utf8(expected)) {
+        (Ok(actual), Ok(expected)) => {
+            if 0 < *inline_diffs && is_small(&actual) && is_small(&expected) {
+                let diff = super::similar::unified_diff(
+                    &expected,
+                    &actual,
+                    INLINE_DIFF_CONTEXT,
+                    INLINE_DIFF_MAX_LINES,
+                );
+                // Empty when only line endings differ
+                if !diff.is_empty() {
+                    *inline_diffs -= 1;
+                    return Some(diff.trim_end().to_owned());
+                }
+            }
+            Some(format!(
+                "text content differs, {} bytes, expected {} bytes",
+                actual_len, expected_len
+            ))
+        }
+        _ => Some(format!(
+            "binary content differs, {} bytes, expected {} bytes",
+            actual_len, expected_len
+        )),
+    }
+}
+
+/// Text files up to this size get an inline diff in [`PathAssertTree::assert_dir_eq`] failures.
+const INLINE_DIFF_MAX_BYTES: usize = 16 * 1024;
+/// Text files up to this many lines get an inline diff, bounding the cost of diffing.
+const INLINE_DIFF_MAX_FILE_LINES: usize = 1000;
+/// At most this many files get an inline diff per failure.
+const INLINE_DIFF_MAX_FILES: usize = 10;
+/// Each inline diff is cut after this many lines.
+const INLINE_DIFF_MAX_LINES: usize = 40;
+/// Unchanged lines shown around each change.
+const INLINE_DIFF_CONTEXT: usize = 2;
+
+fn is_small(content: &str) -> bool {
+    content.len() <= INLINE_DIFF_MAX_BYTES && content.lines().count() <= INLINE_DIFF_MAX_FILE_LINES
+}
+
+#[track_caller]
+fn assert_dir_eq(path: &path::Path, expected_root: &path::Path) {
+    let actual = scan(path);
+    let expected = scan(expected_root);
+
+    let missing = top_level(
+        expected
+            .keys()
+            .filter(|rel| !actual.contains_key(*rel))
+            .collect(),
+    );
+    let extra = top_level(
+        actual
+            .keys()
+            .filter(|rel| !expected.contains_key(*rel))
+            .collect(),
+    );
+    let mut differing = Vec::new();
+    let mut inline_diffs = INLINE_DIFF_MAX_FILES;
+    for (rel, expected_node) in &expected {
+        let actual_node = match actual.get(rel) {
+            Some(actual_node) => actual_node,
+            None => continue,
+        };
+        let difference = match (actual_node, expected_node) {
+            (Node::Dir, Node::Dir) => None,
+            (Node::File, Node::File) => {
+                compare_files(&path.join(rel), &expected_root.join(rel), &mut inline_diffs)
+            }
+            (Node::Symlink(actual), Node::Symlink(expected)) if actual != expected => {
+                Some(format!(
+                    "links to `{}`, expected `{}`",
+                    actual.display(),
+                    expected.display()
+                ))
+            }
+            (Node::Symlink(_), Node::Symlink(_)) => None,
+            (actual_node, expected_node) => Some(format!(
+                "{}, expected {}",
+                actual_node.describe(),
+                expected_node.describe()
+            )),
+        };
+        if let Some(difference) = difference {
+            differing.push((rel, difference));
+        }
+    }
+
+    if missing.is_empty() && extra.is_empty() && differing.is_empty() {
+        return;
+    }
+    let palette = crate::Palette::current();
+    let list = |paths: &[&path::PathBuf]| {
+        paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut message = String::from("Unexpected directory tree");
+    if !missing.is_empty() {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("missing"),
+            palette.value.paint(list(&missing))
+        ));
+    }
+    if !extra.is_empty() {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("extra"),
+            palette.value.paint(list(&extra))
+        ));
+    }
+    for (rel, difference) in &differing {
+        message.push_str(&format!(
+            "\n{}={}\n{}",
+            palette.key.paint("differs"),
+            palette.value.paint(rel.display()),
+            difference
+        ));
+    }
+    message.push_str(&format!(
+        "\n{}={}\n{}={}",
+        palette.key.paint("expected"),
+        palette.value.paint(expected_root.display()),
+        palette.key.paint("path"),
+        palette.value.paint(path.display())
+    ));
+    crate::report::fail(message);
+}
+
+/// Paths, relative to `root`, matched by the glob `patterns`.
+#[cfg(feature = "glob")]
+#[track_caller]
+fn matched<S>(root: &path::Path, patterns: &[S]) -> std::collections::BTreeSet<path::PathBuf>
+where
+    S: AsRef<str>,
+{
+    let walker = globwalk::GlobWalkerBuilder::from_patterns(root, patterns)
+        .build()
+        .unwrap_or_else(|err| panic!("Invalid glob: {}", err));
+    walker
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(root)
+                .ok()
+                .map(path::Path::to_owned)
+        })
+        .collect()
+}
+
+/// Files under `path` not matched by the glob `patterns`, or within a matched directory.
+#[cfg(feature = "glob")]
+#[track_caller]
+fn unmatched_files<S>(path: &path::Path, patterns: &[S]) -> Vec<path::PathBuf>
+where
+    S: AsRef<str>,
+{
+    let covered = matched(path, patterns);
+    scan(path)
+        .into_iter()
+        .filter(|(_, node)| *node != Node::Dir)
+        .map(|(rel, _)| rel)
+        .filter(|rel| !rel.ancestors().any(|a| covered.contains(a)))
+        .collect()
+}
+
+#[cfg(feature = "glob")]
+fn list_patterns<S>(patterns: &[S]) -> String
+where
+    S: AsRef<str>,
+{
+    patterns
+        .iter()
+        .map(|p| p.as_ref())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(feature = "glob")]
+#[track_caller]
+fn assert_only<S>(path: &path::Path, patterns: &[S])
+where
+    S: AsRef<str>,
+{
+    let unexpected = unmatched_files(path, patterns);
+    if unexpected.is_empty() {
+        return;
+    }
+    let palette = crate::Palette::current();
+    let mut message = String::from("Unexpected files");
+    for rel in &unexpected {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("unexpected"),
+            palette.value.paint(rel.display())
+        ));
+    }
+    message.push_str(&format!(
+        "\n{}={}",
+        palette.key.paint("path"),
+        palette.value.paint(path.display())
+    ));
+    crate::report::fail(message);
+}
+
+#[cfg(feature = "glob")]
+#[track_caller]
+fn assert_matches_glob_set<S>(path: &path::Path, patterns: &[S])
+where
+    S: AsRef<str>,
+{
+    let extra = unmatched_files(path, patterns);
+    let missing: Vec<_> = patterns
+        .iter()
+        .map(|p| p.as_ref())
+        .filter(|p| !p.starts_with('!'))
+        .filter(|p| matched(path, &[p]).is_empty())
+        .collect();
+    if missing.is_empty() && extra.is_empty() {
+        return;
+    }
+    let palette = crate::Palette::current();
+    let mut message = String::from("Unexpected set of files");
+    for pattern in &missing {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("missing"),
+            palette.value.paint(pattern)
+        ));
+    }
+    for rel in &extra {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("extra"),
+            palette.value.paint(rel.display())
+        ));
+    }
+    message.push_str(&format!(
+        "\n{}={}\n{}={}",
+        palette.key.paint("expected"),
+        palette.value.paint(list_patterns(patterns)),
+        palette.key.paint("path"),
+        palette.value.paint(path.display())
+    ));
+    crate::report::fail(message);
+}
+
+/// Files up to this size are included in tree snapshots, when they are text.
+const SNAPSHOT_INLINE_BYTES: usize = 1024;
+
+#[track_caller]
+fn assert_tree_snapshot(tree: &dyn PathRenderTree, path: &path::Path, snapshot: &path::Path) {
+    let options = fixture::RenderOptions::new()
+        .hashes(true)
+        .inline_content(SNAPSHOT_INLINE_BYTES);
+    let actual = tree
+        .render_tree(options)
+        .unwrap_or_else(|err| panic!("Failed to render `{}`: {}", path.display(), err));
+
+    if std::env::var_os(SNAPSHOT_ENV).as_deref() == Some(std::ffi::OsStr::new("overwrite")) {
+        let stored = snapshot
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(snapshot, &actual));
+        if let Err(err) = stored {
+            panic!(
+                "Failed to update snapshot `{}`: {}",
+                snapshot.display(),
+                err
+            );
+        }
+        return;
+    }
+
+    let palette = crate::Palette::current();
+    let expected = match fs::read_to_string(snapshot) {
+        // Tolerate snapshots checked out with `\r\n`
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(_) => crate::report::fail(format!(
+            "Missing tree snapshot, run with `{}=overwrite` to record it\n{}={}\n{}={}",
+            SNAPSHOT_ENV,
+            palette.key.paint("snapshot"),
+            palette.value.paint(snapshot.display()),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        )),
+    };
+    let pred = predicates::str::diff(expected.clone());
+    if let Some(case) = pred.find_case(false, &actual) {
+        crate::report::fail_with(
+            format!(
+                "Unexpected directory tree, run with `{}=overwrite` to update the snapshot\n{}\n{}={}\n{}={}",
+                SNAPSHOT_ENV,
+                case.tree(),
+                palette.key.paint("snapshot"),
+                palette.value.paint(snapshot.display()),
+                palette.key.paint("path"),
+                palette.value.paint(path.display())
+            ),
+            vec![
+                ("expected", expected.into_bytes()),
+                ("actual", actual.into_bytes()),
+            ],
+        );
+    }
+}
+
+#[track_caller]
+fn assert_manifest(path: &path::Path, manifest: &path::Path) {
+    if std::env::var_os(SNAPSHOT_ENV).as_deref() == Some(std::ffi::OsStr::new("overwrite")) {
+        if let Err(err) = fixture::write_manifest(path, manifest) {
+            panic!(
+                "Failed to update manifest `{}`: {}",
+                manifest.display(),
+                err
+            );
+        }
+        return;
+    }
+
+    let palette = crate::Palette::current();
+    if !manifest.exists() {
+        crate::report::fail(format!(
+            "Missing manifest, run with `{}=overwrite` to record it\n{}={}\n{}={}",
+            SNAPSHOT_ENV,
+            palette.key.paint("manifest"),
+            palette.value.paint(manifest.display()),
+            palette.key.paint("path"),
+            palette.value.paint(path.display())
+        ));
+    }
+    let expected = fixture::read_manifest(manifest).unwrap_or_else(|err| panic!("{}", err));
+    let actual = fixture::tree_manifest(path, Some(manifest))
+        .unwrap_or_else(|err| panic!("Failed to scan `{}`: {}", path.display(), err));
+
+    let added: Vec<_> = actual
+        .keys()
+        .filter(|name| !expected.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    let removed: Vec<_> = expected
+        .keys()
+        .filter(|name| !actual.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    let changed: Vec<_> = expected
+        .iter()
+        .filter_map(|(name, expected_entry)| {
+            let differences = actual.get(name)?.differences(expected_entry);
+            if differences.is_empty() {
+                None
+            } else {
+                Some((name, differences.join(", ")))
+            }
+        })
+        .collect();
+    if added.is_empty() && removed.is_empty() && changed.is_empty() {
+        return;
+    }
+
+    let mut message = format!(
+        "Unexpected directory tree, run with `{}=overwrite` to update the manifest",
+        SNAPSHOT_ENV
+    );
+    if !added.is_empty() {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("added"),
+            palette.added.paint(added.join(", "))
+        ));
+    }
+    if !removed.is_empty() {
+        message.push_str(&format!(
+            "\n{}={}",
+            palette.key.paint("removed"),
+            palette.removed.paint(removed.join(", "))
+        ));
+    }
+    for (name, differences) in &changed {
+        message.push_str(&format!(
+            "\n{}={}\n{}",
+            palette.key.paint("changed"),
+            palette.value.paint(name),
+            differences
+        ));
+    }
+    message.push_str(&format!(
+        "\n{}={}\n{}={}",
+        palette.key.paint("manifest"),
+        palette.value.paint(manifest.display()),
+        palette.key.paint("path"),
+        palette.value.paint(path.display())
+    ));
+    crate::report::fail(message);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn top_level_collapses_children() {
+        let paths = [
+            path::PathBuf::from("a"),
+            path::PathBuf::from("a/b"),
+            path::PathBuf::from("a/b/c.txt"),
+            path::PathBuf::from("ab.txt"),
+        ];
+        let kept = top_level(paths.iter().collect());
+        assert_eq!(kept, vec![&paths[0], &paths[3]]);
+    }
+}
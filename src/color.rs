@@ -2,6 +2,8 @@
 pub(crate) struct Palette {
     pub(crate) key: styled::Style,
     pub(crate) value: styled::Style,
+    pub(crate) removed: styled::Style,
+    pub(crate) added: styled::Style,
 }
 
 impl Palette {
@@ -11,6 +13,8 @@ impl Palette {
             Self {
                 key: styled::Style(yansi::Style::new(yansi::Color::Blue).bold()),
                 value: styled::Style(yansi::Style::new(yansi::Color::Yellow).bold()),
+                removed: styled::Style(yansi::Style::new(yansi::Color::Red)),
+                added: styled::Style(yansi::Style::new(yansi::Color::Green)),
             }
         } else {
             Self::default()
@@ -0,0 +1,251 @@
+use std::fs;
+use std::io;
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::progress::Tracker;
+use super::ChildPath;
+use super::TempDir;
+
+/// Extract archives into [`TempDir`].
+///
+/// Keeping large fixtures as archives keeps the repository small.  The archive format is
+/// detected from the file extension:
+/// - `.tar` (requires the `archive-tar` feature)
+/// - `.tar.gz` / `.tgz` (requires the `archive-gzip` feature)
+/// - `.tar.zst` / `.tzst` (requires the `archive-zstd` feature)
+/// - `.zip` (requires the `archive-zip` feature)
+///
+/// Directory structure and, on Unix, permissions are preserved.  Entries that would be
+/// extracted outside of the target directory are skipped.  When an entry fails to extract, the
+/// error names it.
+///
+pub trait PathExtract {
+    /// Extract the archive at `archive` into the current path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.extract_from("tests/fixtures/corpus.tar.zst").unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    fn extract_from<P>(&self, archive: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>;
+
+    /// Extract an archive of `format`, read from `reader`, into the current path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use assert_fs::fixture::ArchiveFormat;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let format = ArchiveFormat::detect("corpus.tar.gz").unwrap();
+    /// let download = std::fs::File::open("target/downloads/corpus").unwrap();
+    /// temp.extract_from_reader(download, format).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    fn extract_from_reader<R>(&self, reader: R, format: ArchiveFormat) -> Result<(), FixtureError>
+    where
+        R: io::Read;
+}
+
+impl PathExtract for TempDir {
+    fn extract_from<P>(&self, archive: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        extract_from(self.path(), archive.as_ref())
+    }
+
+    fn extract_from_reader<R>(&self, reader: R, format: ArchiveFormat) -> Result<(), FixtureError>
+    where
+        R: io::Read,
+    {
+        extract_from_reader(self.path(), reader, format)
+    }
+}
+
+impl PathExtract for ChildPath {
+    fn extract_from<P>(&self, archive: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        extract_from(self.path(), archive.as_ref())
+    }
+
+    fn extract_from_reader<R>(&self, reader: R, format: ArchiveFormat) -> Result<(), FixtureError>
+    where
+        R: io::Read,
+    {
+        extract_from_reader(self.path(), reader, format)
+    }
+}
+
+/// Archive formats supported by [`PathExtract`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArchiveFormat {
+    /// Uncompressed tar.
+    #[cfg(feature = "archive-tar")]
+    Tar,
+    /// Gzip compressed tar.
+    #[cfg(feature = "archive-gzip")]
+    TarGz,
+    /// Zstandard compressed tar.
+    #[cfg(feature = "archive-zstd")]
+    TarZstd,
+    /// Zip.
+    #[cfg(feature = "archive-zip")]
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from the file extension of `archive`.
+    pub fn detect<P>(archive: P) -> Option<Self>
+    where
+        P: AsRef<path::Path>,
+    {
+        let name = archive.as_ref().file_name()?.to_str()?.to_ascii_lowercase();
+        #[cfg(feature = "archive-gzip")]
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            return Some(ArchiveFormat::TarGz);
+        }
+        #[cfg(feature = "archive-zstd")]
+        if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            return Some(ArchiveFormat::TarZstd);
+        }
+        #[cfg(feature = "archive-tar")]
+        if name.ends_with(".tar") {
+            return Some(ArchiveFormat::Tar);
+        }
+        #[cfg(feature = "archive-zip")]
+        if name.ends_with(".zip") {
+            return Some(ArchiveFormat::Zip);
+        }
+        None
+    }
+}
+
+fn extract_from(target: &path::Path, archive: &path::Path) -> Result<(), FixtureError> {
+    let format = ArchiveFormat::detect(archive).ok_or_else(|| {
+        let err = io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported archive format: `{}`", archive.display()),
+        );
+        FixtureError::new(FixtureKind::Extract).chain(err)
+    })?;
+    let file = fs::File::open(archive).chain(FixtureError::new(FixtureKind::ReadFile))?;
+    extract_from_reader(target, io::BufReader::new(file), format)
+}
+
+fn extract_from_reader<R>(
+    target: &path::Path,
+    reader: R,
+    format: ArchiveFormat,
+) -> Result<(), FixtureError>
+where
+    R: io::Read,
+{
+    record(OperationKind::Extract, target, None, || {
+        fs::create_dir_all(target).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        match format {
+            #[cfg(feature = "archive-tar")]
+            ArchiveFormat::Tar => unpack_tar(target, reader),
+            #[cfg(feature = "archive-gzip")]
+            ArchiveFormat::TarGz => unpack_tar(target, flate2::read::GzDecoder::new(reader)),
+            #[cfg(feature = "archive-zstd")]
+            ArchiveFormat::TarZstd => {
+                let decoder =
+                    zstd::Decoder::new(reader).chain(FixtureError::new(FixtureKind::Extract))?;
+                unpack_tar(target, decoder)
+            }
+            #[cfg(feature = "archive-zip")]
+            ArchiveFormat::Zip => unpack_zip(target, reader),
+        }
+    })
+}
+
+/// Name the archive entry that failed in `err`.
+fn entry_error(entry: &path::Path, err: io::Error) -> FixtureError {
+    let err = io::Error::new(err.kind(), format!("`{}`: {}", entry.display(), err));
+    FixtureError::new(FixtureKind::Extract).chain(err)
+}
+
+#[cfg(feature = "archive-tar")]
+fn unpack_tar<R>(target: &path::Path, reader: R) -> Result<(), FixtureError>
+where
+    R: io::Read,
+{
+    let mut progress = Tracker::new(OperationKind::Extract, target);
+    let mut archive = tar::Archive::new(reader);
+    archive.set_preserve_permissions(true);
+    for entry in archive
+        .entries()
+        .chain(FixtureError::new(FixtureKind::Extract))?
+    {
+        let mut entry = entry.chain(FixtureError::new(FixtureKind::Extract))?;
+        let name = entry
+            .path()
+            .map(|p| p.into_owned())
+            .chain(FixtureError::new(FixtureKind::Extract))?;
+        let bytes = entry.size();
+        entry
+            .unpack_in(target)
+            .map_err(|err| entry_error(&name, err))?;
+        if entry.header().entry_type().is_file() {
+            progress.file(bytes);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "archive-zip")]
+fn unpack_zip<R>(target: &path::Path, mut reader: R) -> Result<(), FixtureError>
+where
+    R: io::Read,
+{
+    // Zip's index is at the end, so the archive needs to be seekable
+    let mut data = Vec::new();
+    reader
+        .read_to_end(&mut data)
+        .chain(FixtureError::new(FixtureKind::ReadFile))?;
+    let mut archive = zip::ZipArchive::new(io::Cursor::new(data))
+        .chain(FixtureError::new(FixtureKind::Extract))?;
+    let mut progress = Tracker::new(OperationKind::Extract, target);
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .chain(FixtureError::new(FixtureKind::Extract))?;
+        let name = match entry.enclosed_name() {
+            Some(name) => name,
+            None => continue,
+        };
+        let path = target.join(&name);
+        let unpacked = if entry.is_dir() {
+            fs::create_dir_all(&path)
+        } else {
+            path.parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::File::create(&path))
+                .and_then(|mut file| io::copy(&mut entry, &mut file))
+                .map(|bytes| progress.file(bytes))
+        };
+        unpacked.map_err(|err| entry_error(&name, err))?;
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode))
+                .map_err(|err| entry_error(&name, err))?;
+        }
+    }
+    Ok(())
+}
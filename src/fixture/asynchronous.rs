@@ -0,0 +1,258 @@
+use std::future::Future;
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::NamedTempFile;
+#[cfg(feature = "glob")]
+use super::TempDir;
+
+/// Write files at [`ChildPath`] without blocking the async runtime.
+///
+/// Like [`FileWriteStr`][super::FileWriteStr] and [`FileWriteBin`][super::FileWriteBin],
+/// backed by [`tokio::fs`].
+///
+pub trait FileWriteAsync {
+    /// Write text to a file, creating any missing parent directories.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("etc/config.toml");
+    /// config.write_str_async("port = 8080\n").await.unwrap();
+    /// config.assert_async("port = 8080\n").await;
+    /// temp.close().unwrap();
+    /// # });
+    /// ```
+    fn write_str_async(&self, data: &str) -> impl Future<Output = Result<(), FixtureError>> + Send;
+
+    /// Write binary data to a file, creating any missing parent directories.
+    fn write_binary_async(
+        &self,
+        data: &[u8],
+    ) -> impl Future<Output = Result<(), FixtureError>> + Send;
+}
+
+impl FileWriteAsync for ChildPath {
+    fn write_str_async(&self, data: &str) -> impl Future<Output = Result<(), FixtureError>> + Send {
+        write_bytes(self.path(), OperationKind::WriteStr, data.as_bytes())
+    }
+
+    fn write_binary_async(
+        &self,
+        data: &[u8],
+    ) -> impl Future<Output = Result<(), FixtureError>> + Send {
+        write_bytes(self.path(), OperationKind::WriteBinary, data)
+    }
+}
+
+impl FileWriteAsync for NamedTempFile {
+    fn write_str_async(&self, data: &str) -> impl Future<Output = Result<(), FixtureError>> + Send {
+        write_bytes(self.path(), OperationKind::WriteStr, data.as_bytes())
+    }
+
+    fn write_binary_async(
+        &self,
+        data: &[u8],
+    ) -> impl Future<Output = Result<(), FixtureError>> + Send {
+        write_bytes(self.path(), OperationKind::WriteBinary, data)
+    }
+}
+
+/// Read files at [`ChildPath`] without blocking the async runtime.
+///
+/// Like [`FileRead`][super::FileRead], backed by [`tokio::fs`].  Errors name the file that
+/// couldn't be read.
+///
+pub trait FileReadAsync {
+    /// Read the file's content.
+    fn read_binary_async(&self) -> impl Future<Output = Result<Vec<u8>, FixtureError>> + Send;
+
+    /// Read the file's content as UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("out.txt");
+    /// output.write_str_async("done\n").await.unwrap();
+    /// assert_eq!(output.read_str_async().await.unwrap(), "done\n");
+    /// temp.close().unwrap();
+    /// # });
+    /// ```
+    fn read_str_async(&self) -> impl Future<Output = Result<String, FixtureError>> + Send;
+
+    /// Read the file's content as UTF-8 lines, without line endings.
+    fn read_lines_async(&self) -> impl Future<Output = Result<Vec<String>, FixtureError>> + Send;
+}
+
+impl FileReadAsync for ChildPath {
+    fn read_binary_async(&self) -> impl Future<Output = Result<Vec<u8>, FixtureError>> + Send {
+        read_binary(self.path())
+    }
+
+    fn read_str_async(&self) -> impl Future<Output = Result<String, FixtureError>> + Send {
+        read_str(self.path())
+    }
+
+    fn read_lines_async(&self) -> impl Future<Output = Result<Vec<String>, FixtureError>> + Send {
+        read_lines(self.path())
+    }
+}
+
+impl FileReadAsync for NamedTempFile {
+    fn read_binary_async(&self) -> impl Future<Output = Result<Vec<u8>, FixtureError>> + Send {
+        read_binary(self.path())
+    }
+
+    fn read_str_async(&self) -> impl Future<Output = Result<String, FixtureError>> + Send {
+        read_str(self.path())
+    }
+
+    fn read_lines_async(&self) -> impl Future<Output = Result<Vec<String>, FixtureError>> + Send {
+        read_lines(self.path())
+    }
+}
+
+/// Copy files into [`TempDir`] or [`ChildPath`] without blocking the async runtime.
+///
+/// Like [`PathCopy::copy_from`][super::PathCopy::copy_from], run on Tokio's blocking thread
+/// pool.
+///
+#[cfg(feature = "glob")]
+pub trait PathCopyAsync {
+    /// Copy files and directories into the current path from the `source` according to the glob
+    /// `patterns`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.copy_from_async(".", &["*.rs"]).await.unwrap();
+    /// temp.close().unwrap();
+    /// # });
+    /// ```
+    fn copy_from_async<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+    ) -> impl Future<Output = Result<(), FixtureError>> + Send
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>;
+}
+
+#[cfg(feature = "glob")]
+impl PathCopyAsync for TempDir {
+    fn copy_from_async<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+    ) -> impl Future<Output = Result<(), FixtureError>> + Send
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_from(self.path(), source.as_ref(), patterns)
+    }
+}
+
+#[cfg(feature = "glob")]
+impl PathCopyAsync for ChildPath {
+    fn copy_from_async<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+    ) -> impl Future<Output = Result<(), FixtureError>> + Send
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_from(self.path(), source.as_ref(), patterns)
+    }
+}
+
+fn write_bytes(
+    path: &path::Path,
+    kind: OperationKind,
+    data: &[u8],
+) -> impl Future<Output = Result<(), FixtureError>> + Send {
+    let path = path.to_owned();
+    let data = data.to_owned();
+    async move {
+        let result = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .chain(FixtureError::new(FixtureKind::CreateDir))?;
+            }
+            tokio::fs::write(&path, &data)
+                .await
+                .chain(FixtureError::new(FixtureKind::WriteFile))
+        }
+        .await;
+        // Recorded once done, so the operation log and `verify_setup` see async writes too
+        record(kind, &path, Some(data.len() as u64), || result)
+    }
+}
+
+fn read_binary(path: &path::Path) -> impl Future<Output = Result<Vec<u8>, FixtureError>> + Send {
+    let path = path.to_owned();
+    async move {
+        tokio::fs::read(&path)
+            .await
+            .map_err(|err| super::tools::read_error(&path, err))
+    }
+}
+
+fn read_str(path: &path::Path) -> impl Future<Output = Result<String, FixtureError>> + Send {
+    let path = path.to_owned();
+    async move {
+        tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|err| super::tools::read_error(&path, err))
+    }
+}
+
+fn read_lines(path: &path::Path) -> impl Future<Output = Result<Vec<String>, FixtureError>> + Send {
+    let read = read_str(path);
+    async move {
+        read.await
+            .map(|data| data.lines().map(str::to_owned).collect())
+    }
+}
+
+#[cfg(feature = "glob")]
+fn copy_from<S>(
+    target: &path::Path,
+    source: &path::Path,
+    patterns: &[S],
+) -> impl Future<Output = Result<(), FixtureError>> + Send
+where
+    S: AsRef<str>,
+{
+    let target = ChildPath::new(target);
+    let source = source.to_owned();
+    let patterns: Vec<String> = patterns.iter().map(|p| p.as_ref().to_owned()).collect();
+    async move {
+        let copy = tokio::task::spawn_blocking(move || {
+            super::PathCopy::copy_from(&target, &source, &patterns)
+        });
+        match copy.await {
+            Ok(result) => result,
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        }
+    }
+}
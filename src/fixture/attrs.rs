@@ -0,0 +1,124 @@
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::NamedTempFile;
+
+/// Set and read extended attributes of [`ChildPath`] or [`NamedTempFile`] (requires the
+/// `xattr` feature).
+///
+/// On Linux and macOS these are extended attributes; unprivileged processes on Linux are
+/// limited to the `user.` namespace, and some filesystems, like older `tmpfs`, don't support
+/// them.  On Windows, each attribute is an NTFS alternate data stream, `path:name`.
+///
+/// See [`PathAssertXattr`][crate::assert::PathAssertXattr] to check them.
+pub trait PathXattr {
+    /// Set the attribute `name` to `value`, replacing any existing value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let file = temp.child("photo.jpg");
+    /// file.touch().unwrap();
+    /// if file.set_xattr("user.origin", b"camera").is_ok() {
+    ///     file.assert_xattr("user.origin", b"camera");
+    /// }
+    /// temp.close().unwrap();
+    /// ```
+    fn set_xattr(&self, name: &str, value: &[u8]) -> Result<(), FixtureError>;
+
+    /// The value of the attribute `name`, if set.
+    fn xattr(&self, name: &str) -> Result<Option<Vec<u8>>, FixtureError>;
+}
+
+impl PathXattr for ChildPath {
+    fn set_xattr(&self, name: &str, value: &[u8]) -> Result<(), FixtureError> {
+        set_xattr(self.path(), name, value)
+    }
+
+    fn xattr(&self, name: &str) -> Result<Option<Vec<u8>>, FixtureError> {
+        xattr(self.path(), name)
+    }
+}
+
+impl PathXattr for NamedTempFile {
+    fn set_xattr(&self, name: &str, value: &[u8]) -> Result<(), FixtureError> {
+        set_xattr(self.path(), name, value)
+    }
+
+    fn xattr(&self, name: &str) -> Result<Option<Vec<u8>>, FixtureError> {
+        xattr(self.path(), name)
+    }
+}
+
+fn set_xattr(path: &path::Path, name: &str, value: &[u8]) -> Result<(), FixtureError> {
+    record(
+        OperationKind::SetXattr,
+        path,
+        Some(value.len() as u64),
+        || set(path, name, value).chain(FixtureError::new(FixtureKind::WriteFile)),
+    )
+}
+
+pub(crate) fn xattr(path: &path::Path, name: &str) -> Result<Option<Vec<u8>>, FixtureError> {
+    get(path, name)
+        .chain(FixtureError::new(FixtureKind::ReadFile))
+        .map_err(|err| err.with_path(path))
+}
+
+#[cfg(unix)]
+fn set(path: &path::Path, name: &str, value: &[u8]) -> std::io::Result<()> {
+    ::xattr::set(path, name, value)
+}
+
+#[cfg(unix)]
+fn get(path: &path::Path, name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    ::xattr::get(path, name)
+}
+
+#[cfg(windows)]
+fn set(path: &path::Path, name: &str, value: &[u8]) -> std::io::Result<()> {
+    std::fs::write(stream_path(path, name), value)
+}
+
+#[cfg(windows)]
+fn get(path: &path::Path, name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    match std::fs::read(stream_path(path, name)) {
+        Ok(value) => Ok(Some(value)),
+        // Only the stream is missing, not the file
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound && path.exists() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// The alternate data stream `name` of `path`.
+#[cfg(windows)]
+fn stream_path(path: &path::Path, name: &str) -> path::PathBuf {
+    let mut stream = path.as_os_str().to_owned();
+    stream.push(":");
+    stream.push(name);
+    stream.into()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn set(_path: &path::Path, _name: &str, _value: &[u8]) -> std::io::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn get(_path: &path::Path, _name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unsupported() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "extended attributes are unsupported on this platform",
+    )
+}
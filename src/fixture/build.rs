@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::mem;
+use std::path;
+
+use super::errors::*;
+use super::ChildPath;
+use super::FileWriteBin;
+use super::SymlinkToDir;
+use super::SymlinkToFile;
+
+/// Declare a tree of files and directories, created as it's declared.
+///
+/// Created with [`TempDir::build`][super::TempDir::build] or
+/// [`TempDir::with_layout`][super::TempDir::with_layout].
+///
+/// Failures don't stop the build; every failing path is collected into the one
+/// [`FixtureError`] returned at the end.
+#[derive(Debug)]
+pub struct TreeBuilder {
+    root: path::PathBuf,
+    failures: Vec<(path::PathBuf, FixtureError)>,
+}
+
+impl TreeBuilder {
+    pub(crate) fn new(root: &path::Path) -> Self {
+        Self {
+            root: root.to_owned(),
+            failures: Vec::new(),
+        }
+    }
+
+    /// Write a file at the relative `path`, creating parent directories as needed.
+    pub fn file<P, C>(&mut self, path: P, content: C) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+        C: AsRef<[u8]>,
+    {
+        let path = self.root.join(path);
+        if let Err(err) = ChildPath::new(&path).write_binary(content.as_ref()) {
+            self.failures.push((path, err));
+        }
+        self
+    }
+
+    /// Create an empty directory at the relative `path`, including any missing parents.
+    pub fn empty_dir<P>(&mut self, path: P) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+    {
+        self.dir(path, |d| d)
+    }
+
+    /// Create a directory at the relative `path`, declaring its entries with `f`.
+    ///
+    /// Use [`TreeBuilder::empty_dir`] for an empty directory.
+    pub fn dir<P, F>(&mut self, path: P, f: F) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+        F: FnOnce(&mut TreeBuilder) -> &mut TreeBuilder,
+    {
+        let path = self.root.join(path);
+        if let Err(err) = fs::create_dir_all(&path).chain(FixtureError::new(FixtureKind::CreateDir))
+        {
+            // Its entries would only fail the same way
+            self.failures.push((path, err));
+            return self;
+        }
+        let mut child = TreeBuilder {
+            root: path,
+            failures: mem::take(&mut self.failures),
+        };
+        f(&mut child);
+        self.failures = child.failures;
+        self
+    }
+
+    /// Create a symlink at the relative `path`, to the file at `target`.
+    pub fn symlink_to_file<P, T>(&mut self, path: P, target: T) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+        T: AsRef<path::Path>,
+    {
+        let path = self.root.join(path);
+        if let Err(err) = ChildPath::new(&path).symlink_to_file(target) {
+            self.failures.push((path, err));
+        }
+        self
+    }
+
+    /// Create a symlink at the relative `path`, to the directory at `target`.
+    pub fn symlink_to_dir<P, T>(&mut self, path: P, target: T) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+        T: AsRef<path::Path>,
+    {
+        let path = self.root.join(path);
+        if let Err(err) = ChildPath::new(&path).symlink_to_dir(target) {
+            self.failures.push((path, err));
+        }
+        self
+    }
+
+    /// Set the permission bits of the entry at the relative `path`.
+    ///
+    /// Declare this after the entry's content, as `mode` may forbid adding to it.
+    #[cfg(unix)]
+    pub fn mode<P>(&mut self, path: P, mode: u32) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = self.root.join(path);
+        if let Err(err) = fs::set_permissions(&path, fs::Permissions::from_mode(mode))
+            .chain(FixtureError::new(FixtureKind::Permissions))
+        {
+            self.failures.push((path, err));
+        }
+        self
+    }
+
+    /// Record a failure at the relative `path`.
+    #[cfg_attr(not(feature = "spec-json"), allow(dead_code))]
+    pub(crate) fn fail<P>(&mut self, path: P, err: FixtureError) -> &mut Self
+    where
+        P: AsRef<path::Path>,
+    {
+        self.failures.push((self.root.join(path), err));
+        self
+    }
+
+    pub(crate) fn finish(self) -> Result<(), FixtureError> {
+        match self.failures.first() {
+            Some((_, first)) => {
+                Err(FixtureError::new(first.kind()).chain(BuildFailures(self.failures)))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Every path that failed while building a tree.
+#[derive(Debug)]
+struct BuildFailures(Vec<(path::PathBuf, FixtureError)>);
+
+impl fmt::Display for BuildFailures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (path, err)) in self.0.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "`{}`: {}", path.display(), err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BuildFailures {}
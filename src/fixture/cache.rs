@@ -0,0 +1,93 @@
+use std::fs;
+use std::path;
+
+use super::errors::*;
+use super::CloneStrategy;
+use super::TempDir;
+
+/// A fixture tree built once per process, and cloned for each test that uses it.
+///
+/// Many tests start from the same tree.  Declare it once as a `static`; the first test to call
+/// [`FixtureCache::instantiate`] builds it, and every test gets its own cheap
+/// [clone][TempDir::clone_from], so tests may modify their copy freely.
+///
+/// The shared tree is kept in `assert_fs-cache` in Cargo's target directory, or the system's
+/// temporary directory when not running under Cargo.  As statics are never dropped, it's left
+/// behind when the process exits.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::{FixtureCache, TempDir};
+/// use assert_fs::prelude::*;
+///
+/// static PROJECT: FixtureCache = FixtureCache::new("project", |dir: &TempDir| {
+///     dir.child("Cargo.toml").write_str("[package]\n")?;
+///     dir.child("src/lib.rs").write_str("pub fn answer() -> u32 { 42 }\n")?;
+///     Ok(())
+/// });
+///
+/// let temp = PROJECT.instantiate().unwrap();
+/// temp.child("src/lib.rs").write_str("").unwrap();
+/// assert_ne!(temp.path(), PROJECT.path().unwrap());
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FixtureCache {
+    name: &'static str,
+    build: fn(&TempDir) -> Result<(), FixtureError>,
+    strategy: CloneStrategy,
+    tree: once_cell::sync::OnceCell<path::PathBuf>,
+}
+
+impl FixtureCache {
+    /// A fixture named `name`, populated by `build`.
+    ///
+    /// `name` prefixes the directories created for it, to recognize them.
+    pub const fn new(name: &'static str, build: fn(&TempDir) -> Result<(), FixtureError>) -> Self {
+        Self {
+            name,
+            build,
+            strategy: CloneStrategy::ReflinkOrCopy,
+            tree: once_cell::sync::OnceCell::new(),
+        }
+    }
+
+    /// Change how the tree is cloned for each test, [`CloneStrategy::ReflinkOrCopy`] by
+    /// default.
+    ///
+    /// Strategies using hard links are faster, but modifying a file in one test then modifies
+    /// the shared tree, and every other test's copy.
+    pub const fn strategy(mut self, strategy: CloneStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// The shared tree, building it if this is the first use.
+    ///
+    /// Don't modify it: it's what every test's copy is cloned from.
+    pub fn path(&self) -> Result<&path::Path, FixtureError> {
+        self.tree
+            .get_or_try_init(|| {
+                let parent = super::dir::target_dir()
+                    .unwrap_or_else(std::env::temp_dir)
+                    .join("assert_fs-cache");
+                fs::create_dir_all(&parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+                let temp = TempDir::builder()
+                    .prefix(format!("{}.", self.name))
+                    .parent(parent)
+                    .create()?;
+                (self.build)(&temp)?;
+                Ok(temp.into_persistent().path().to_owned())
+            })
+            .map(|tree| tree.as_path())
+    }
+
+    /// A new [`TempDir`] with a copy of the tree.
+    pub fn instantiate(&self) -> Result<TempDir, FixtureError> {
+        let tree = self.path()?;
+        let temp = TempDir::with_prefix(format!("{}.", self.name))?;
+        temp.clone_from(tree, self.strategy)?;
+        Ok(temp)
+    }
+}
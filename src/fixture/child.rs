@@ -13,6 +13,24 @@ use std::path;
 /// temp.close().unwrap();
 /// ```
 ///
+/// Implemented for both [`TempDir`][crate::TempDir] and [`ChildPath`], so fixture setup can be
+/// factored into helpers that work on any directory in the tree:
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// fn project(root: &impl PathChild) {
+///     root.child("Cargo.toml").write_str("[package]\n").unwrap();
+///     root.child("src/lib.rs").touch().unwrap();
+/// }
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// project(&temp);
+/// project(&temp.child("crates/nested"));
+/// temp.child("crates/nested/src/lib.rs").assert("");
+/// temp.close().unwrap();
+/// ```
+///
 pub trait PathChild {
     /// Access a path within the temp directory.
     ///
@@ -26,33 +44,217 @@ pub trait PathChild {
     /// println!("{}", temp.child("foo/bar.txt").path().display());
     /// temp.close().unwrap();
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `path` is absolute or uses `..` to escape the current directory.  See
+    /// [`PathChild::child_unchecked`] to opt-out.
     fn child<P>(&self, path: P) -> ChildPath
     where
         P: AsRef<path::Path>;
+
+    /// Access a path, without verifying it stays within the temp directory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let sibling = temp.child("foo").child_unchecked("../bar.txt");
+    /// println!("{}", sibling.path().display());
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    /// Defaults to [`PathChild::child`], for implementations that don't verify paths.
+    fn child_unchecked<P>(&self, path: P) -> ChildPath
+    where
+        P: AsRef<path::Path>,
+    {
+        self.child(path)
+    }
+
+    /// Access a path, that doesn't exist yet, within the temp directory, ending in `suffix`.
+    ///
+    /// The name is generated according to the fixture's
+    /// [`NamePolicy`][super::NamePolicy].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let upload = temp.child("uploads").unique_child(".bin");
+    /// upload.touch().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    fn unique_child(&self, suffix: &str) -> ChildPath {
+        let dir = self.child_unchecked("");
+        ChildPath::new(super::naming::unique_child(dir.path(), suffix))
+    }
+
+    /// Iterate over the existing paths within the current path matching the glob `pattern`.
+    ///
+    /// Entries are visited in name order, directories before their contents, unless
+    /// [turned off][crate::order].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use predicates::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("out/a.json").write_str("{}").unwrap();
+    /// temp.child("out/nested/b.json").write_str("{}").unwrap();
+    /// temp.child("out/c.txt").touch().unwrap();
+    ///
+    /// let mut count = 0;
+    /// for child in temp.children("out/**/*.json") {
+    ///     child.assert(predicate::str::starts_with("{"));
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 2);
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `pattern` is not a valid glob.
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn children(&self, pattern: &str) -> Children {
+        Children::new(self.child_unchecked("").path(), pattern)
+    }
 }
 
 impl PathChild for super::TempDir {
+    #[track_caller]
     fn child<P>(&self, path: P) -> ChildPath
+    where
+        P: AsRef<path::Path>,
+    {
+        let path = path.as_ref();
+        if let Err(reason) = validate_child(path) {
+            panic!("Invalid child path `{}`: {}", path.display(), reason);
+        }
+        self.child_unchecked(path)
+    }
+
+    fn child_unchecked<P>(&self, path: P) -> ChildPath
     where
         P: AsRef<path::Path>,
     {
         ChildPath::new(self.path().join(path.as_ref()))
     }
+
+    fn unique_child(&self, suffix: &str) -> ChildPath {
+        ChildPath::new(super::naming::unique_child(self.path(), suffix))
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn children(&self, pattern: &str) -> Children {
+        Children::new(self.path(), pattern)
+    }
 }
 
 impl PathChild for ChildPath {
+    #[track_caller]
     fn child<P>(&self, path: P) -> ChildPath
+    where
+        P: AsRef<path::Path>,
+    {
+        let path = path.as_ref();
+        if let Err(reason) = validate_child(path) {
+            panic!("Invalid child path `{}`: {}", path.display(), reason);
+        }
+        self.child_unchecked(path)
+    }
+
+    fn child_unchecked<P>(&self, path: P) -> ChildPath
     where
         P: AsRef<path::Path>,
     {
         ChildPath::new(self.path().join(path.as_ref()))
     }
+
+    fn unique_child(&self, suffix: &str) -> ChildPath {
+        ChildPath::new(super::naming::unique_child(self.path(), suffix))
+    }
+
+    #[cfg(feature = "glob")]
+    #[track_caller]
+    fn children(&self, pattern: &str) -> Children {
+        Children::new(self.path(), pattern)
+    }
+}
+
+/// Paths matching a glob, from [`PathChild::children`].
+#[cfg(feature = "glob")]
+pub struct Children {
+    walker: globwalk::GlobWalker,
+}
+
+#[cfg(feature = "glob")]
+impl Children {
+    #[track_caller]
+    fn new(root: &path::Path, pattern: &str) -> Self {
+        let walker = match crate::order::glob(root, &[pattern]).build() {
+            Ok(walker) => walker,
+            Err(err) => panic!("Invalid glob `{}`: {}", pattern, err),
+        };
+        Self { walker }
+    }
+}
+
+#[cfg(feature = "glob")]
+impl Iterator for Children {
+    type Item = ChildPath;
+
+    fn next(&mut self) -> Option<ChildPath> {
+        // Entries may disappear while walking; only yield what could be read.
+        self.walker
+            .by_ref()
+            .find_map(|entry| entry.ok())
+            .map(|entry| ChildPath::new(entry.into_path()))
+    }
+}
+
+#[cfg(feature = "glob")]
+impl std::fmt::Debug for Children {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Children").finish_non_exhaustive()
+    }
+}
+
+pub(super) fn validate_child(path: &path::Path) -> Result<(), &'static str> {
+    let mut depth = 0_usize;
+    for component in path.components() {
+        match component {
+            path::Component::Prefix(_) | path::Component::RootDir => {
+                return Err("must be relative");
+            }
+            path::Component::CurDir => {}
+            path::Component::ParentDir => {
+                depth = depth.checked_sub(1).ok_or("escapes the parent directory")?;
+            }
+            path::Component::Normal(_) => {
+                depth += 1;
+            }
+        }
+    }
+    Ok(())
 }
 
 /// A path within a [`TempDir`][crate::TempDir]
 ///
 /// See Trait Implementations.
 ///
+/// `ChildPath` is `Send + Sync` and cheap to clone, so it can be handed to worker threads.  It
+/// doesn't keep the directory alive; see [`TempDir::path_handle`][crate::TempDir::path_handle].
+///
 /// # Examples
 ///
 /// ```rust
@@ -68,6 +270,7 @@ impl PathChild for ChildPath {
 /// temp.close().unwrap();
 /// ```
 ///
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ChildPath {
     path: path::PathBuf,
 }
@@ -77,17 +280,151 @@ impl ChildPath {
     ///
     /// See trait implementations or [`PathChild`] for more details.
     ///
+    /// On Windows, absolute paths too long for `MAX_PATH` are converted to the extended-length
+    /// `\\?\` form, so deep fixtures work with every helper and [`std::fs`].  To test how the
+    /// program under test handles such paths, see [`names::long_path`][super::names::long_path].
+    ///
     pub fn new<P>(path: P) -> Self
     where
         P: Into<path::PathBuf>,
     {
-        Self { path: path.into() }
+        Self {
+            path: extended_length(path.into()),
+        }
     }
 
     /// Access the path.
     pub fn path(&self) -> &path::Path {
         &self.path
     }
+
+    /// Like [`Path::join`][std::path::Path::join], but remaining a `ChildPath`.
+    ///
+    /// Unlike [`PathChild::child`], `path` is not validated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let out = temp.child("out");
+    /// out.join("report.txt").touch().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    pub fn join<P>(&self, path: P) -> ChildPath
+    where
+        P: AsRef<path::Path>,
+    {
+        ChildPath::new(self.path.join(path))
+    }
+
+    /// Like [`Path::with_extension`][std::path::Path::with_extension], but remaining a
+    /// `ChildPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("input.md");
+    /// input.touch().unwrap();
+    /// input.with_extension("html").assert(predicates::path::missing());
+    /// temp.close().unwrap();
+    /// ```
+    pub fn with_extension<S>(&self, extension: S) -> ChildPath
+    where
+        S: AsRef<std::ffi::OsStr>,
+    {
+        ChildPath::new(self.path.with_extension(extension))
+    }
+
+    /// Like [`Path::with_file_name`][std::path::Path::with_file_name], but remaining a
+    /// `ChildPath`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("data/input.txt");
+    /// input.touch().unwrap();
+    /// input.with_file_name("output.txt").assert(predicates::path::missing());
+    /// temp.close().unwrap();
+    /// ```
+    pub fn with_file_name<S>(&self, file_name: S) -> ChildPath
+    where
+        S: AsRef<std::ffi::OsStr>,
+    {
+        ChildPath::new(self.path.with_file_name(file_name))
+    }
+
+    /// The path with symlinks, and `.` and `..` components, resolved, remaining a `ChildPath`.
+    ///
+    /// Compare canonical paths, rather than the paths of a [`TempDir`][super::TempDir] as
+    /// created, where the temp directory is reached through a symlink, like macOS's `/tmp` to
+    /// `/private/tmp`.  See also [`TempDir::new_canonical`][super::TempDir::new_canonical].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the path doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("data/input.txt");
+    /// input.touch().unwrap();
+    /// let canonical = input.canonicalized().unwrap();
+    /// assert_eq!(canonical.path(), input.path().canonicalize().unwrap());
+    /// temp.close().unwrap();
+    /// ```
+    pub fn canonicalized(&self) -> Result<ChildPath, super::FixtureError> {
+        use super::ResultChainExt;
+
+        self.path
+            .canonicalize()
+            .map(ChildPath::new)
+            .chain(super::FixtureError::new(super::FixtureKind::Walk))
+            .map_err(|err| err.with_path(&self.path))
+    }
+
+    /// The `file://` URL for the path.
+    ///
+    /// Special characters are percent-encoded and, on Windows, the drive letter becomes the first
+    /// URL path segment (`file:///C:/...`).
+    ///
+    /// Requires the `url` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let page = temp.child("my docs/index #1.html");
+    /// let url = page.to_file_url();
+    /// assert_eq!(url.scheme(), "file");
+    /// assert!(url.as_str().ends_with("/my%20docs/index%20%231.html"));
+    /// assert_eq!(url.to_file_path().unwrap(), page.path());
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the path is relative, or can't be expressed as a URL.
+    #[cfg(feature = "url")]
+    #[track_caller]
+    pub fn to_file_url(&self) -> url::Url {
+        match url::Url::from_file_path(&self.path) {
+            Ok(url) => url,
+            Err(()) => panic!("No `file://` URL for `{}`", self.path.display()),
+        }
+    }
 }
 
 impl AsRef<path::Path> for ChildPath {
@@ -103,3 +440,114 @@ impl std::ops::Deref for ChildPath {
         &self.path
     }
 }
+
+impl From<ChildPath> for path::PathBuf {
+    fn from(child: ChildPath) -> Self {
+        child.path
+    }
+}
+
+/// The longest path, in UTF-16 units, usable without the `\\?\` prefix: `MAX_PATH` less room
+/// for an 8.3 file name, the limit for directories.
+#[cfg(windows)]
+const MAX_SHORT_PATH_LEN: usize = 248;
+
+/// `path`, in the `\\?\` extended-length form on Windows if it's too long for the usual form.
+///
+/// The extended form is passed to the OS as-is, so `.` and `..` are resolved here.  Relative
+/// paths are left alone, as only absolute paths can be extended.
+#[cfg(windows)]
+pub(crate) fn extended_length(path: path::PathBuf) -> path::PathBuf {
+    use std::os::windows::ffi::OsStrExt;
+
+    if path.as_os_str().encode_wide().count() < MAX_SHORT_PATH_LEN {
+        return path;
+    }
+    let mut components = path.components();
+    let mut extended = match components.next() {
+        Some(path::Component::Prefix(prefix)) => match prefix.kind() {
+            path::Prefix::Disk(drive) => {
+                path::PathBuf::from(format!("\\\\?\\{}:\\", char::from(drive)))
+            }
+            path::Prefix::UNC(server, share) => {
+                let mut unc = std::ffi::OsString::from("\\\\?\\UNC\\");
+                unc.push(server);
+                unc.push("\\");
+                unc.push(share);
+                unc.push("\\");
+                path::PathBuf::from(unc)
+            }
+            // Already verbatim, or a device path
+            _ => return path,
+        },
+        _ => return path,
+    };
+    for component in components {
+        match component {
+            path::Component::Normal(name) => extended.push(name),
+            path::Component::ParentDir => {
+                extended.pop();
+            }
+            path::Component::Prefix(_) | path::Component::RootDir | path::Component::CurDir => {}
+        }
+    }
+    extended
+}
+
+#[cfg(not(windows))]
+pub(crate) fn extended_length(path: path::PathBuf) -> path::PathBuf {
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_child_relative() {
+        assert!(validate_child(path::Path::new("foo/bar.txt")).is_ok());
+        assert!(validate_child(path::Path::new("./foo/../bar.txt")).is_ok());
+    }
+
+    #[test]
+    fn validate_child_escapes() {
+        assert!(validate_child(path::Path::new("..")).is_err());
+        assert!(validate_child(path::Path::new("foo/../../bar.txt")).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "url")]
+    fn to_file_url_encodes() {
+        let root = std::env::temp_dir();
+        let url = ChildPath::new(root.join("100% done")).to_file_url();
+        assert!(url.as_str().starts_with("file:///"));
+        assert!(url.as_str().ends_with("/100%25%20done"));
+        assert_eq!(url.to_file_path().unwrap(), root.join("100% done"));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn extended_length_only_for_long_paths() {
+        let short = path::PathBuf::from(r"C:\Temp\a.txt");
+        assert_eq!(extended_length(short.clone()), short);
+
+        let name = "d".repeat(100);
+        let long = path::PathBuf::from(format!(r"C:\Temp\{0}\.\x\..\{0}\{0}", name));
+        assert_eq!(
+            extended_length(long),
+            path::PathBuf::from(format!(r"\\?\C:\Temp\{0}\{0}\{0}", name))
+        );
+
+        let unc = path::PathBuf::from(format!(r"\\server\share\{0}\{0}\{0}", name));
+        assert_eq!(
+            extended_length(unc),
+            path::PathBuf::from(format!(r"\\?\UNC\server\share\{0}\{0}\{0}", name))
+        );
+    }
+
+    #[test]
+    fn validate_child_absolute() {
+        let root = std::env::temp_dir();
+        assert!(validate_child(&root).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path;
+use std::sync;
+use std::time;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::NamedTempFile;
+
+/// Where a fixture gets the current time from.
+///
+/// Set for a fixture with [`TempDir::set_clock`][super::TempDir::set_clock], and used by
+/// [`FileTimestamp`] and [`PathSnapshot::snapshot`][super::PathSnapshot::snapshot].  Pinning
+/// the clock lets a suite describe timestamps relative to one base time, like "input is 2h
+/// older than output", without calling [`SystemTime::now`][std::time::SystemTime::now]
+/// throughout its tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use assert_fs::prelude::*;
+/// use assert_fs::fixture::Clock;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.set_clock(Clock::fixed(UNIX_EPOCH + Duration::from_secs(1_000_000_000)));
+/// temp.child("input.txt").touch_ago(Duration::from_secs(2 * 60 * 60)).unwrap();
+/// temp.child("output.txt").touch_ago(Duration::ZERO).unwrap();
+/// temp.close().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Clock {
+    kind: Kind,
+}
+
+#[derive(Clone)]
+enum Kind {
+    System,
+    Fixed(time::SystemTime),
+    Custom(sync::Arc<dyn Fn() -> time::SystemTime + Send + Sync>),
+}
+
+impl Clock {
+    /// The real time, from [`SystemTime::now`][std::time::SystemTime::now].
+    pub fn system() -> Self {
+        Self { kind: Kind::System }
+    }
+
+    /// Always `time`.
+    pub fn fixed(time: time::SystemTime) -> Self {
+        Self {
+            kind: Kind::Fixed(time),
+        }
+    }
+
+    /// Ask `now` for the time.
+    pub fn from_fn<F>(now: F) -> Self
+    where
+        F: Fn() -> time::SystemTime + Send + Sync + 'static,
+    {
+        Self {
+            kind: Kind::Custom(sync::Arc::new(now)),
+        }
+    }
+
+    /// The current time, according to this clock.
+    pub fn now(&self) -> time::SystemTime {
+        match self.kind {
+            Kind::System => time::SystemTime::now(),
+            Kind::Fixed(time) => time,
+            Kind::Custom(ref now) => now(),
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::System => f.write_str("Clock::system()"),
+            Kind::Fixed(time) => write!(f, "Clock::fixed({:?})", time),
+            Kind::Custom(_) => f.write_str("Clock::from_fn(..)"),
+        }
+    }
+}
+
+pub(crate) type Shared = sync::RwLock<Clock>;
+
+/// The current time, according to the innermost fixture containing `path`.
+pub(crate) fn now(path: &path::Path) -> time::SystemTime {
+    match super::context::innermost(path) {
+        Some(context) => context
+            .clock
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .now(),
+        None => time::SystemTime::now(),
+    }
+}
+
+/// Set the timestamps of files at [`ChildPath`].
+///
+pub trait FileTimestamp {
+    /// Set the modification time to `time`, creating an empty file if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("foo.txt");
+    /// input.touch_at(UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn touch_at(&self, time: time::SystemTime) -> Result<(), FixtureError>;
+
+    /// Set the modification time to `age` before the fixture's [`Clock`], creating an empty
+    /// file if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("stale.lock").touch_ago(Duration::from_secs(3600)).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn touch_ago(&self, age: time::Duration) -> Result<(), FixtureError>;
+
+    /// Set the modification time of the existing file or directory to `time`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("out.o");
+    /// output.write_str("stale").unwrap();
+    /// output.set_mtime(UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn set_mtime(&self, time: time::SystemTime) -> Result<(), FixtureError>;
+
+    /// Set the access and modification times of the existing file or directory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let input = temp.child("in.c");
+    /// input.touch().unwrap();
+    /// let time = UNIX_EPOCH + Duration::from_secs(60);
+    /// input.set_times(time, time).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn set_times(
+        &self,
+        accessed: time::SystemTime,
+        modified: time::SystemTime,
+    ) -> Result<(), FixtureError>;
+}
+
+impl FileTimestamp for ChildPath {
+    fn touch_at(&self, time: time::SystemTime) -> Result<(), FixtureError> {
+        touch_at(self.path(), time)
+    }
+
+    fn touch_ago(&self, age: time::Duration) -> Result<(), FixtureError> {
+        touch_ago(self.path(), age)
+    }
+
+    fn set_mtime(&self, time: time::SystemTime) -> Result<(), FixtureError> {
+        set_times(self.path(), None, time)
+    }
+
+    fn set_times(
+        &self,
+        accessed: time::SystemTime,
+        modified: time::SystemTime,
+    ) -> Result<(), FixtureError> {
+        set_times(self.path(), Some(accessed), modified)
+    }
+}
+
+impl FileTimestamp for NamedTempFile {
+    fn touch_at(&self, time: time::SystemTime) -> Result<(), FixtureError> {
+        touch_at(self.path(), time)
+    }
+
+    fn touch_ago(&self, age: time::Duration) -> Result<(), FixtureError> {
+        touch_ago(self.path(), age)
+    }
+
+    fn set_mtime(&self, time: time::SystemTime) -> Result<(), FixtureError> {
+        set_times(self.path(), None, time)
+    }
+
+    fn set_times(
+        &self,
+        accessed: time::SystemTime,
+        modified: time::SystemTime,
+    ) -> Result<(), FixtureError> {
+        set_times(self.path(), Some(accessed), modified)
+    }
+}
+
+fn touch_ago(path: &path::Path, age: time::Duration) -> Result<(), FixtureError> {
+    let time = now(path)
+        .checked_sub(age)
+        .ok_or_else(|| FixtureError::new(FixtureKind::Timestamp))?;
+    touch_at(path, time)
+}
+
+fn touch_at(path: &path::Path, time: time::SystemTime) -> Result<(), FixtureError> {
+    record(OperationKind::TouchAt, path, None, || {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        }
+        set_modified(path, time, true)
+    })
+}
+
+fn set_times(
+    path: &path::Path,
+    accessed: Option<time::SystemTime>,
+    modified: time::SystemTime,
+) -> Result<(), FixtureError> {
+    record(OperationKind::SetTimes, path, None, || {
+        let modified = filetime::FileTime::from_system_time(modified);
+        match accessed {
+            Some(accessed) => filetime::set_file_times(
+                path,
+                filetime::FileTime::from_system_time(accessed),
+                modified,
+            ),
+            None => filetime::set_file_mtime(path, modified),
+        }
+        .chain(FixtureError::new(FixtureKind::Timestamp))
+    })
+}
+
+/// Set the modification time of the file at `path`, optionally creating it.
+pub(crate) fn set_modified(
+    path: &path::Path,
+    time: time::SystemTime,
+    create: bool,
+) -> Result<(), FixtureError> {
+    if create {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+        {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(FixtureError::new(FixtureKind::WriteFile).chain(err)),
+        }
+    }
+    // Only touches the file's attributes, so read-only files can be updated too
+    filetime::set_file_mtime(path, filetime::FileTime::from_system_time(time))
+        .chain(FixtureError::new(FixtureKind::Timestamp))
+}
@@ -0,0 +1,194 @@
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path;
+use std::sync;
+use std::thread;
+
+use super::errors::*;
+use super::ChildPath;
+use super::TempDir;
+
+/// Write to a path from many threads at once.
+///
+/// Useful for testing the locking or atomicity claims of the code under test under contention.
+/// All threads are released together, to maximize overlap.
+///
+pub trait PathContend {
+    /// Append `lines` lines to the file at the current path from each of `threads` threads.
+    ///
+    /// Each line, `<thread> <n>\n`, is appended with a single write, so with atomic appends
+    /// every line reads back intact.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("shared.log");
+    /// let reports = log.append_concurrently(4, 100);
+    /// assert!(reports.iter().all(|r| r.succeeded() == 100));
+    ///
+    /// let content = std::fs::read_to_string(log.path()).unwrap();
+    /// assert_eq!(content.lines().count(), 400);
+    /// temp.close().unwrap();
+    /// ```
+    fn append_concurrently(&self, threads: usize, lines: usize) -> Vec<WriterReport>;
+
+    /// Race `threads` threads to exclusively create the files `0` to `files - 1` in the
+    /// directory at the current path.
+    ///
+    /// Each file should be won by exactly one thread; the others see a conflict.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let reports = temp.child("locks").create_concurrently(4, 10);
+    /// let created: usize = reports.iter().map(|r| r.succeeded()).sum();
+    /// assert_eq!(created, 10);
+    /// temp.close().unwrap();
+    /// ```
+    fn create_concurrently(&self, threads: usize, files: usize) -> Vec<WriterReport>;
+}
+
+impl PathContend for TempDir {
+    fn append_concurrently(&self, threads: usize, lines: usize) -> Vec<WriterReport> {
+        append_concurrently(self.path(), threads, lines)
+    }
+
+    fn create_concurrently(&self, threads: usize, files: usize) -> Vec<WriterReport> {
+        create_concurrently(self.path(), threads, files)
+    }
+}
+
+impl PathContend for ChildPath {
+    fn append_concurrently(&self, threads: usize, lines: usize) -> Vec<WriterReport> {
+        append_concurrently(self.path(), threads, lines)
+    }
+
+    fn create_concurrently(&self, threads: usize, files: usize) -> Vec<WriterReport> {
+        create_concurrently(self.path(), threads, files)
+    }
+}
+
+/// What one thread of a [`PathContend`] run achieved.
+#[derive(Debug)]
+pub struct WriterReport {
+    thread: usize,
+    succeeded: usize,
+    conflicts: usize,
+    errors: Vec<FixtureError>,
+}
+
+impl WriterReport {
+    fn new(thread: usize) -> Self {
+        Self {
+            thread,
+            succeeded: 0,
+            conflicts: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Index of the thread, from `0`.
+    pub fn thread(&self) -> usize {
+        self.thread
+    }
+
+    /// How many writes or creations succeeded.
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+
+    /// How many creations lost to another thread.
+    pub fn conflicts(&self) -> usize {
+        self.conflicts
+    }
+
+    /// Unexpected failures.
+    pub fn errors(&self) -> &[FixtureError] {
+        &self.errors
+    }
+}
+
+fn run<F>(threads: usize, op: F) -> Vec<WriterReport>
+where
+    F: Fn(&mut WriterReport) + Send + Sync + 'static,
+{
+    let op = sync::Arc::new(op);
+    let barrier = sync::Arc::new(sync::Barrier::new(threads));
+    let handles: Vec<_> = (0..threads)
+        .map(|thread| {
+            let op = op.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut report = WriterReport::new(thread);
+                barrier.wait();
+                op(&mut report);
+                report
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| match handle.join() {
+            Ok(report) => report,
+            Err(panic) => std::panic::resume_unwind(panic),
+        })
+        .collect()
+}
+
+fn append_concurrently(path: &path::Path, threads: usize, lines: usize) -> Vec<WriterReport> {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let path = path.to_owned();
+    run(threads, move |report| {
+        let mut file = match fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&path)
+            .chain(FixtureError::new(FixtureKind::WriteFile))
+        {
+            Ok(file) => file,
+            Err(err) => {
+                report.errors.push(err);
+                return;
+            }
+        };
+        for n in 0..lines {
+            let line = format!("{} {}\n", report.thread, n);
+            match file
+                .write_all(line.as_bytes())
+                .chain(FixtureError::new(FixtureKind::WriteFile))
+            {
+                Ok(()) => report.succeeded += 1,
+                Err(err) => report.errors.push(err),
+            }
+        }
+    })
+}
+
+fn create_concurrently(path: &path::Path, threads: usize, files: usize) -> Vec<WriterReport> {
+    let _ = fs::create_dir_all(path);
+    let path = path.to_owned();
+    run(threads, move |report| {
+        for n in 0..files {
+            let created = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path.join(n.to_string()));
+            match created {
+                Ok(_) => report.succeeded += 1,
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => report.conflicts += 1,
+                Err(err) => report
+                    .errors
+                    .push(FixtureError::new(FixtureKind::WriteFile).chain(err)),
+            }
+        }
+    })
+}
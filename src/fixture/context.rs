@@ -0,0 +1,53 @@
+use std::path;
+use std::sync;
+
+/// State a [`TempDir`][super::TempDir] shares with operations on paths within it.
+///
+/// Operations only see paths, so they look the state up by path, through the fixtures
+/// registered while they're alive.
+#[derive(Default)]
+pub(crate) struct Context {
+    pub(crate) root: path::PathBuf,
+    pub(crate) log: super::oplog::Log,
+    pub(crate) names: super::naming::Policy,
+    pub(crate) clock: super::clock::Shared,
+    pub(crate) progress: super::progress::Shared,
+    pub(crate) write_limit: super::throttle::Shared,
+    #[cfg(feature = "tracing")]
+    pub(crate) trace: super::trace::Shared,
+}
+
+type Registry = Vec<(path::PathBuf, sync::Weak<Context>)>;
+
+static REGISTRY: once_cell::sync::Lazy<sync::Mutex<Registry>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// Share the returned context with operations on paths under `root`, for as long as it's alive.
+pub(crate) fn register(root: &path::Path) -> sync::Arc<Context> {
+    let context = sync::Arc::new(Context {
+        root: root.to_owned(),
+        ..Default::default()
+    });
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|(_, context)| context.strong_count() != 0);
+    registry.push((root.to_owned(), sync::Arc::downgrade(&context)));
+    context
+}
+
+/// The contexts of all live fixtures containing `path`, outermost first.
+pub(crate) fn containing(path: &path::Path) -> Vec<sync::Arc<Context>> {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|(_, context)| context.strong_count() != 0);
+    let mut contexts: Vec<_> = registry
+        .iter()
+        .filter(|(root, _)| path.starts_with(root))
+        .filter_map(|(_, context)| context.upgrade())
+        .collect();
+    contexts.sort_by_key(|context| context.root.as_os_str().len());
+    contexts
+}
+
+/// The context of the innermost live fixture containing `path`.
+pub(crate) fn innermost(path: &path::Path) -> Option<sync::Arc<Context>> {
+    containing(path).pop()
+}
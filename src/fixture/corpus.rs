@@ -0,0 +1,120 @@
+use std::fs;
+use std::path;
+
+use sha2::Digest;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::progress::Tracker;
+use super::ChildPath;
+use super::TempDir;
+
+/// Manage a fuzzing corpus (libFuzzer / AFL style) within a [`TempDir`].
+///
+/// Each input is stored in its own file, named after the SHA-256 of its content, so duplicate
+/// inputs collapse into a single entry.
+///
+pub trait PathCorpus {
+    /// Write each input as a corpus entry, returning the paths of the written entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let corpus = temp.child("corpus");
+    /// let entries = corpus.write_corpus(&[b"GET /" as &[u8], b"POST /"]).unwrap();
+    /// assert_eq!(entries.len(), 2);
+    /// temp.close().unwrap();
+    /// ```
+    fn write_corpus<I, B>(&self, inputs: I) -> Result<Vec<ChildPath>, FixtureError>
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>;
+
+    /// Read back every entry of the corpus, e.g. after the fuzzer minimized it.
+    ///
+    /// Entries are returned sorted by file name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let corpus = temp.child("corpus");
+    /// corpus.write_corpus(&[b"GET /" as &[u8], b"GET /"]).unwrap();
+    /// assert_eq!(corpus.read_corpus().unwrap(), vec![b"GET /".to_vec()]);
+    /// temp.close().unwrap();
+    /// ```
+    fn read_corpus(&self) -> Result<Vec<Vec<u8>>, FixtureError>;
+}
+
+impl PathCorpus for TempDir {
+    fn write_corpus<I, B>(&self, inputs: I) -> Result<Vec<ChildPath>, FixtureError>
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        write_corpus(self.path(), inputs)
+    }
+
+    fn read_corpus(&self) -> Result<Vec<Vec<u8>>, FixtureError> {
+        read_corpus(self.path())
+    }
+}
+
+impl PathCorpus for ChildPath {
+    fn write_corpus<I, B>(&self, inputs: I) -> Result<Vec<ChildPath>, FixtureError>
+    where
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        write_corpus(self.path(), inputs)
+    }
+
+    fn read_corpus(&self) -> Result<Vec<Vec<u8>>, FixtureError> {
+        read_corpus(self.path())
+    }
+}
+
+fn write_corpus<I, B>(root: &path::Path, inputs: I) -> Result<Vec<ChildPath>, FixtureError>
+where
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]>,
+{
+    record(OperationKind::WriteCorpus, root, None, || {
+        let mut progress = Tracker::new(OperationKind::WriteCorpus, root);
+        fs::create_dir_all(root).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        let mut entries = Vec::new();
+        for input in inputs {
+            let input = input.as_ref();
+            let name = format!("{:x}", sha2::Sha256::digest(input));
+            let entry = root.join(name);
+            fs::write(&entry, input).chain(FixtureError::new(FixtureKind::WriteFile))?;
+            progress.file(input.len() as u64);
+            entries.push(ChildPath::new(entry));
+        }
+        Ok(entries)
+    })
+}
+
+fn read_corpus(root: &path::Path) -> Result<Vec<Vec<u8>>, FixtureError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(root).chain(FixtureError::new(FixtureKind::Walk))? {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        let file_type = entry
+            .file_type()
+            .chain(FixtureError::new(FixtureKind::Walk))?;
+        if file_type.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| fs::read(path).chain(FixtureError::new(FixtureKind::ReadFile)))
+        .collect()
+}
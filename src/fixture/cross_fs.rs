@@ -0,0 +1,124 @@
+use std::path;
+
+use super::errors::*;
+use super::TempDir;
+
+/// A directory on a different filesystem to try first in
+/// [`TempDir::new_on_different_fs_than`], e.g. a mount set up by CI for the purpose.
+pub const OTHER_FS_ENV: &str = "ASSERT_FS_OTHER_FS_DIR";
+
+impl TempDir {
+    /// Attempts to make a temporary directory on a different filesystem than `other`.
+    ///
+    /// This is for testing what happens when files move across filesystems, like
+    /// [`std::fs::rename`] failing with `EXDEV` ("Invalid cross-device link"), where code
+    /// should fall back to copying.
+    ///
+    /// The directory named by [`OTHER_FS_ENV`] is tried first, then the usual places for
+    /// temporary files, like the system temp directory, `/dev/shm` and `/var/tmp`, and
+    /// Cargo's target directory.  On Unix, filesystems are told apart by device id; on
+    /// Windows, by drive or network share, so other drive letters are tried too.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an [`Unsupported`][std::io::ErrorKind::Unsupported] cause when none of the
+    /// candidates is on a different filesystem, which tests should treat as a reason to skip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// let source = tmp_dir.child("data.bin");
+    /// source.write_binary(b"payload").unwrap();
+    ///
+    /// let other = match TempDir::new_on_different_fs_than(&tmp_dir) {
+    ///     Ok(other) => other,
+    ///     Err(err) => {
+    ///         println!("skipping: {}", err);
+    ///         return;
+    ///     }
+    /// };
+    /// let err = std::fs::rename(&source, other.child("data.bin")).unwrap_err();
+    /// println!("{}", err);
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// other.close().unwrap();
+    /// ```
+    pub fn new_on_different_fs_than<P>(other: P) -> Result<Self, FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        let other = other.as_ref();
+        let other_fs = filesystem(other)
+            .chain(FixtureError::new(FixtureKind::CreateDir))
+            .map_err(|err| err.with_path(other))?;
+        for candidate in candidates() {
+            let temp = match Self::new_in(&candidate) {
+                Ok(temp) => temp,
+                Err(_) => continue,
+            };
+            // Checked on the new directory, as `candidate` may be a mount point itself
+            match filesystem(temp.path()) {
+                Ok(fs) if fs != other_fs => return Ok(temp),
+                _ => {
+                    let _ = temp.close();
+                }
+            }
+        }
+        let err = std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!(
+                "no writable location on another filesystem, set {} to one",
+                OTHER_FS_ENV
+            ),
+        );
+        Err(FixtureError::new(FixtureKind::CreateDir)
+            .chain(err)
+            .with_path(other))
+    }
+}
+
+/// Where to try creating a directory on another filesystem, in order.
+fn candidates() -> Vec<path::PathBuf> {
+    let mut candidates: Vec<path::PathBuf> = Vec::new();
+    candidates.extend(std::env::var_os(OTHER_FS_ENV).map(Into::into));
+    candidates.push(std::env::temp_dir());
+    if cfg!(unix) {
+        candidates.extend(std::env::var_os("XDG_RUNTIME_DIR").map(Into::into));
+        candidates.extend(
+            ["/dev/shm", "/var/tmp", "/tmp"]
+                .iter()
+                .map(path::PathBuf::from),
+        );
+    }
+    candidates.extend(super::dir::target_dir());
+    candidates.extend(std::env::current_dir().ok());
+    if cfg!(windows) {
+        candidates.extend((b'C'..=b'Z').map(|drive| format!("{}:\\", char::from(drive)).into()));
+    }
+    candidates
+}
+
+/// An identifier of the filesystem `path` is on.
+#[cfg(unix)]
+fn filesystem(path: &path::Path) -> std::io::Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    path.metadata().map(|metadata| metadata.dev())
+}
+
+/// An identifier of the filesystem `path` is on.
+///
+/// Volumes mounted into folders share their drive's identifier.
+#[cfg(not(unix))]
+fn filesystem(path: &path::Path) -> std::io::Result<std::ffi::OsString> {
+    let path = path.canonicalize()?;
+    match path.components().next() {
+        Some(path::Component::Prefix(prefix)) => Ok(prefix.as_os_str().to_ascii_uppercase()),
+        _ => Ok(std::ffi::OsString::new()),
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path;
+
+use sha2::Digest;
+
+use super::errors::*;
+use super::ChildPath;
+
+/// Populate many files, hard-linking files with identical content.
+///
+/// Generated stress fixtures often contain thousands of files sharing the same handful of
+/// contents.  Rather than writing each one, `DedupWriter` writes the first occurrence of a
+/// content and hard-links later occurrences to it (falling back to a copy when the filesystem
+/// can't link).
+///
+/// Hard-linked files share their data, so writing through one of the links is visible through
+/// all of them.  By default, `DedupWriter` breaks the link before overwriting an existing path;
+/// see [`DedupWriter::break_links`].
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::fixture::DedupWriter;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// let mut writer = DedupWriter::new();
+/// for i in 0..100 {
+///     writer
+///         .write_str(&temp.child(format!("data/{}.txt", i)), "same content")
+///         .unwrap();
+/// }
+/// temp.child("data/42.txt").assert("same content");
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct DedupWriter {
+    seen: HashMap<Vec<u8>, path::PathBuf>,
+    break_links: bool,
+}
+
+impl DedupWriter {
+    /// Create a writer with no known contents.
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+            break_links: true,
+        }
+    }
+
+    /// Control whether overwriting an existing path first breaks its hard links.
+    ///
+    /// When `false`, new content is written in-place and is visible through every path linked
+    /// to it.
+    pub fn break_links(mut self, yes: bool) -> Self {
+        self.break_links = yes;
+        self
+    }
+
+    /// Write a binary file at `path`, linking to an earlier file with the same content.
+    pub fn write_binary(&mut self, path: &ChildPath, data: &[u8]) -> Result<(), FixtureError> {
+        let path = path.path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        }
+        if self.break_links && path.symlink_metadata().is_ok() {
+            fs::remove_file(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        }
+
+        let digest = sha2::Sha256::digest(data).to_vec();
+        // Recorded paths may have been rewritten since, so check they still hold `data`
+        let original = self
+            .seen
+            .get(&digest)
+            .filter(|original| *original != path && has_content(original, data))
+            .cloned();
+        match original {
+            Some(original) => {
+                if path.symlink_metadata().is_ok() {
+                    fs::remove_file(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+                }
+                if fs::hard_link(&original, path).is_err() {
+                    fs::copy(&original, path).chain(FixtureError::new(FixtureKind::CopyFile))?;
+                }
+            }
+            None => {
+                fs::write(path, data).chain(FixtureError::new(FixtureKind::WriteFile))?;
+                self.seen.insert(digest, path.to_owned());
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a text file at `path`, linking to an earlier file with the same content.
+    pub fn write_str(&mut self, path: &ChildPath, data: &str) -> Result<(), FixtureError> {
+        self.write_binary(path, data.as_bytes())
+    }
+}
+
+impl Default for DedupWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn has_content(path: &path::Path, data: &[u8]) -> bool {
+    fs::read(path).map(|actual| actual == data).unwrap_or(false)
+}
@@ -27,7 +27,8 @@ use super::errors::*;
 /// handles (like [`File`] and [`ReadDir`]) to files inside the
 /// directory are dropped before the `TempDir` goes out of scope. The
 /// `TempDir` destructor will silently ignore any errors in deleting
-/// the directory; to instead handle errors call [`TempDir::close()`].
+/// the directory; to instead handle errors call [`TempDir::close()`],
+/// or set a stricter [`CleanupPolicy`].
 ///
 /// Note that if the program exits before the `TempDir` destructor is
 /// run, such as via [`std::process::exit()`], by segfaulting, or by
@@ -52,13 +53,98 @@ use super::errors::*;
 /// [`ReadDir`]: std::fs::ReadDir
 pub struct TempDir {
     temp: Inner,
+    context: std::sync::Arc<super::context::Context>,
+    persist_on_failure: bool,
+    cleanup: CleanupPolicy,
+}
+
+/// What a [`TempDir`] does about entries it can't remove, set with
+/// [`TempDir::cleanup_policy`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CleanupPolicy {
+    /// Leave them behind, silently when dropped.
+    BestEffort,
+    /// Panic when dropped, listing them, so leftovers from the code under test fail the test.
+    ///
+    /// When already panicking, they are printed instead.
+    Strict,
+    /// Make every entry writable first, so read-only files and directories don't stop removal,
+    /// then remove them as for [`CleanupPolicy::BestEffort`].
+    ForceChmodThenDelete,
+}
+
+impl Default for CleanupPolicy {
+    fn default() -> Self {
+        CleanupPolicy::BestEffort
+    }
 }
 
 enum Inner {
-    Temp(tempfile::TempDir),
+    Temp(std::sync::Arc<Shared>),
     Persisted(path::PathBuf),
 }
 
+/// The directory, removed once the [`TempDir`] and every [`PathHandle`] are dropped.
+#[derive(Debug)]
+struct Shared {
+    path: path::PathBuf,
+    temp: std::sync::Mutex<Option<tempfile::TempDir>>,
+}
+
+impl Inner {
+    fn temp(temp: tempfile::TempDir) -> Self {
+        Inner::Temp(std::sync::Arc::new(Shared {
+            path: temp.path().to_owned(),
+            temp: std::sync::Mutex::new(Some(temp)),
+        }))
+    }
+
+    /// Like [`Inner::temp`], but accessed through its canonical path.
+    fn canonical(temp: tempfile::TempDir) -> std::io::Result<Self> {
+        Ok(Inner::Temp(std::sync::Arc::new(Shared {
+            path: temp.path().canonicalize()?,
+            temp: std::sync::Mutex::new(Some(temp)),
+        })))
+    }
+
+    fn path(&self) -> &path::Path {
+        match self {
+            Inner::Temp(shared) => shared.path.as_path(),
+            Inner::Persisted(path) => path.as_path(),
+        }
+    }
+
+    /// Stop the directory from being removed, even by outstanding [`PathHandle`]s.
+    fn keep(self) -> path::PathBuf {
+        match self {
+            Inner::Temp(shared) => {
+                if let Some(temp) = shared.lock().take() {
+                    let _ = temp.into_path();
+                }
+                shared.path.clone()
+            }
+            Inner::Persisted(path) => path,
+        }
+    }
+
+    /// The directory to remove now, unless it's persisted or a [`PathHandle`] still holds it.
+    fn into_removable(self) -> Option<tempfile::TempDir> {
+        match self {
+            Inner::Temp(shared) => std::sync::Arc::try_unwrap(shared)
+                .ok()
+                .and_then(|shared| shared.temp.into_inner().unwrap_or_else(|e| e.into_inner())),
+            Inner::Persisted(_) => None,
+        }
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<tempfile::TempDir>> {
+        self.temp.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
 impl TempDir {
     /// Attempts to make a temporary directory inside of `env::temp_dir()`.
     ///
@@ -81,8 +167,298 @@ impl TempDir {
     /// ```
     pub fn new() -> Result<Self, FixtureError> {
         let temp = tempfile::TempDir::new().chain(FixtureError::new(FixtureKind::CreateDir))?;
-        let temp = Inner::Temp(temp);
-        Ok(Self { temp })
+        Ok(Self::from_inner(Inner::temp(temp)))
+    }
+
+    /// Attempts to make a temporary directory inside of `env::temp_dir()`, with its path
+    /// canonicalized.
+    ///
+    /// The temp directory may be reached through a symlink, like macOS's `/tmp` to
+    /// `/private/tmp`, so paths reported by the code under test don't match those of the
+    /// fixture.  With symlinks resolved in the root, every [`ChildPath`][super::ChildPath] of
+    /// the fixture is already canonical, and can be compared directly.  On Windows, the path
+    /// starts with `\\?\`.
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created, or its path resolved, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new_canonical().unwrap();
+    /// assert_eq!(tmp_dir.path(), tmp_dir.path().canonicalize().unwrap());
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn new_canonical() -> Result<Self, FixtureError> {
+        let temp = tempfile::TempDir::new().chain(FixtureError::new(FixtureKind::CreateDir))?;
+        let inner = Inner::canonical(temp).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(Self::from_inner(inner))
+    }
+
+    /// Attempts to make a temporary directory inside of `parent`.
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new_in(std::env::temp_dir()).unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn new_in<P>(parent: P) -> Result<Self, FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        let temp =
+            tempfile::TempDir::new_in(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(Self::from_inner(Inner::temp(temp)))
+    }
+
+    /// Attempts to make a temporary directory inside the first of `candidates` that works.
+    ///
+    /// This is useful when the default temp location may be slow or too small, like on some CI
+    /// runners.  The parent that was used is `tmp_dir.path().parent()`.
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created in any of the `candidates`, the error for the last
+    /// one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let candidates = std::env::var_os("RUNNER_TEMP")
+    ///     .map(std::path::PathBuf::from)
+    ///     .into_iter()
+    ///     .chain(Some("/dev/shm".into()))
+    ///     .chain(Some(std::env::temp_dir()));
+    /// let tmp_dir = TempDir::new_in_any(candidates).unwrap();
+    /// println!("Using {}", tmp_dir.path().parent().unwrap().display());
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn new_in_any<I, P>(candidates: I) -> Result<Self, FixtureError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<path::Path>,
+    {
+        let mut error = None;
+        for candidate in candidates {
+            match Self::new_in(candidate) {
+                Ok(temp) => return Ok(temp),
+                Err(err) => error = Some(err),
+            }
+        }
+        Err(error.unwrap_or_else(|| FixtureError::new(FixtureKind::CreateDir)))
+    }
+
+    /// Attempts to make a temporary directory, whose name starts with `prefix`, inside of
+    /// `env::temp_dir()`.
+    ///
+    /// Named directories are easier to recognize when inspecting persisted fixtures.
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::with_prefix("my_test.").unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn with_prefix<S>(prefix: S) -> Result<Self, FixtureError>
+    where
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let temp = tempfile::Builder::new()
+            .prefix(prefix.as_ref())
+            .tempdir()
+            .chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(Self::from_inner(Inner::temp(temp)))
+    }
+
+    /// Configure where the temporary directory is created and how it is named.
+    ///
+    /// See [`TempDirBuilder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::builder()
+    ///     .prefix("my_test.")
+    ///     .suffix(".d")
+    ///     .parent(std::env::temp_dir())
+    ///     .create()
+    ///     .unwrap();
+    /// let name = tmp_dir.path().file_name().unwrap().to_str().unwrap();
+    /// assert!(name.starts_with("my_test.") && name.ends_with(".d"));
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn builder() -> TempDirBuilder {
+        TempDirBuilder::default()
+    }
+
+    /// Attempts to make a temporary directory named after the currently running test.
+    ///
+    /// The test name is taken from the current thread's name, as set by the Rust test harness
+    /// (e.g. `tests::my_test` becomes `tests-my_test.<random>`).  When the name is unavailable,
+    /// this behaves like [`TempDir::new()`].
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::for_test().unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn for_test() -> Result<Self, FixtureError> {
+        match current_test_name() {
+            Some(name) => Self::with_prefix(format!("{}.", name)),
+            None => Self::new(),
+        }
+    }
+
+    /// Attempts to make a temporary directory, populated with the tree declared by `f`.
+    ///
+    /// See [`TreeBuilder`][super::TreeBuilder].
+    ///
+    /// # Errors
+    ///
+    /// If the directory or any entry can not be created, `Err` is returned, naming every
+    /// failing path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::build(|b| {
+    ///     b.dir("src", |d| d.file("main.rs", "fn main() {}"))
+    ///         .dir("target", |d| d)
+    ///         .file("Cargo.toml", "[package]\nname = \"demo\"\n")
+    /// })
+    /// .unwrap();
+    /// tmp_dir.child("src/main.rs").assert("fn main() {}");
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn build<F>(f: F) -> Result<Self, FixtureError>
+    where
+        F: FnOnce(&mut super::TreeBuilder) -> &mut super::TreeBuilder,
+    {
+        Self::with_layout(|b| {
+            f(b);
+        })
+    }
+
+    /// Attempts to make a temporary directory, populated by the statements in `f`.
+    ///
+    /// Like [`TempDir::build`], for when separate statements read better than one chain.
+    ///
+    /// # Errors
+    ///
+    /// If the directory or any entry can not be created, `Err` is returned, naming every
+    /// failing path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::with_layout(|t| {
+    ///     t.file("a.txt", "x");
+    ///     t.empty_dir("b");
+    /// })
+    /// .unwrap();
+    /// tmp_dir.child("a.txt").assert("x");
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn with_layout<F>(f: F) -> Result<Self, FixtureError>
+    where
+        F: FnOnce(&mut super::TreeBuilder),
+    {
+        let temp = Self::new()?;
+        let mut builder = super::TreeBuilder::new(temp.path());
+        f(&mut builder);
+        builder.finish()?;
+        Ok(temp)
+    }
+
+    /// Attempts to make a persistent scratch directory for the currently running test.
+    ///
+    /// The directory is `target/assert_fs/<test-name>/` (or under `$ASSERT_FS_SCRATCH_DIR`, if
+    /// set) and is kept after the test so its outputs can be inspected or diffed across runs.
+    /// Any leftovers from a previous run of the same test are removed first.
+    ///
+    /// When running under CI (`$CI` is set) or the test name or `target` directory can't be
+    /// determined, this behaves like [`TempDir::new()`] to keep runs isolated.
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::scratch().unwrap();
+    /// println!("{}", tmp_dir.path().display());
+    /// ```
+    pub fn scratch() -> Result<Self, FixtureError> {
+        if std::env::var_os("CI").is_some() {
+            return Self::new();
+        }
+        let root = match scratch_root() {
+            Some(root) => root,
+            None => return Self::new(),
+        };
+        let name = match current_test_name() {
+            Some(name) => name,
+            None => return Self::new(),
+        };
+
+        let path = root.join(name);
+        if path.exists() {
+            std::fs::remove_dir_all(&path).chain(FixtureError::new(FixtureKind::Cleanup))?;
+        }
+        std::fs::create_dir_all(&path).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(Self::from_inner(Inner::Persisted(path)))
     }
 
     /// Conditionally persist the temporary directory for debug purposes.
@@ -125,78 +501,1093 @@ impl TempDir {
     /// // Ensure deletion happens.
     /// tmp_dir.close().unwrap();
     /// ```
-    pub fn into_persistent(self) -> Self {
-        let path = match self.temp {
-            Inner::Temp(temp) => temp.into_path(),
-            Inner::Persisted(path) => path,
-        };
-        let temp = Inner::Persisted(path);
-        Self { temp }
+    pub fn into_persistent(mut self) -> Self {
+        let path = self.take().keep();
+        self.temp = Inner::Persisted(path);
+        self
     }
 
-    /// Accesses the [`Path`] to the temporary directory.
+    /// Keep the temporary directory if the test fails, printing its path.
     ///
-    /// [`Path`]: std::path::Path
+    /// Failure is detected by the `TempDir` being dropped while panicking, so the directory is
+    /// kept for any failing assertion, not just `assert_fs`'s own.
+    ///
+    /// Alternatively, set the `ASSERT_FS_PERSIST` environment variable to keep every
+    /// `TempDir`, without changing the test.
     ///
     /// # Examples
     ///
     /// ```
+    /// use assert_fs::prelude::*;
     /// use assert_fs::fixture::TempDir;
     ///
-    /// let tmp_dir = TempDir::new().unwrap();
+    /// let tmp_dir = TempDir::new().unwrap().persist_on_failure();
+    /// tmp_dir.child("foo.txt").write_str("Hello").unwrap();
     ///
-    /// println!("{}", tmp_dir.path().display());
+    /// // ... a failing `assert_eq!` here will keep the fixture ...
     ///
     /// // Ensure deletion happens.
     /// tmp_dir.close().unwrap();
     /// ```
-    pub fn path(&self) -> &path::Path {
-        match self.temp {
-            Inner::Temp(ref temp) => temp.path(),
-            Inner::Persisted(ref path) => path.as_path(),
+    pub fn persist_on_failure(mut self) -> Self {
+        self.persist_on_failure = true;
+        self
+    }
+
+    /// Change what happens to entries that can't be removed.
+    ///
+    /// See [`CleanupPolicy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::{CleanupPolicy, TempDir};
+    ///
+    /// let tmp_dir = TempDir::new()
+    ///     .unwrap()
+    ///     .cleanup_policy(CleanupPolicy::ForceChmodThenDelete);
+    /// let config = tmp_dir.child("config/settings.toml");
+    /// config.touch().unwrap();
+    /// config.set_readonly(true).unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn cleanup_policy(mut self, policy: CleanupPolicy) -> Self {
+        self.cleanup = policy;
+        self
+    }
+
+    /// Keep the directory, and its fixture state, for the rest of the process.
+    ///
+    /// For one-time setup shared by many tests through a `static`, like a built toolchain or a
+    /// large corpus.  The directory is never removed; it is listed by [`TempDir::leaked`] so
+    /// checks for leftover fixtures can tell it was leaked on purpose.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use once_cell::sync::OnceCell;
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// fn corpus() -> &'static Path {
+    ///     static CORPUS: OnceCell<&'static Path> = OnceCell::new();
+    ///     CORPUS.get_or_init(|| {
+    ///         let tmp_dir = TempDir::new().unwrap();
+    ///         tmp_dir.child("seed.txt").write_str("Hello").unwrap();
+    ///         tmp_dir.leak_for_process_lifetime()
+    ///     })
+    /// }
+    ///
+    /// assert!(corpus().join("seed.txt").exists());
+    /// assert!(TempDir::leaked().contains(&corpus()));
+    /// ```
+    pub fn leak_for_process_lifetime(self) -> &'static path::Path {
+        let path = Box::leak(Box::new(self)).path();
+        LEAKED.lock().unwrap_or_else(|e| e.into_inner()).push(path);
+        path
+    }
+
+    /// Directories kept with [`TempDir::leak_for_process_lifetime`].
+    pub fn leaked() -> Vec<&'static path::Path> {
+        LEAKED.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn take(&mut self) -> Inner {
+        std::mem::replace(&mut self.temp, Inner::Persisted(path::PathBuf::new()))
+    }
+
+    /// Keep the directory, reporting where, if requested through `ASSERT_FS_PERSIST`.
+    fn retain_if_requested(&mut self) -> bool {
+        let requested = std::env::var_os("ASSERT_FS_PERSIST")
+            .map(|v| !v.is_empty() && v != "0")
+            .unwrap_or(false);
+        let failing = self.persist_on_failure && std::thread::panicking();
+        if !(requested || failing) {
+            return false;
         }
+        if let Inner::Temp(_) = self.temp {
+            let path = self.take().keep();
+            eprintln!("Fixture retained at {}", path.display());
+            self.temp = Inner::Persisted(path);
+        }
+        true
     }
 
-    /// Closes and removes the temporary directory, returning a `Result`.
+    fn from_inner(temp: Inner) -> Self {
+        let path = temp.path();
+        let context = super::context::register(path);
+        Self {
+            temp,
+            context,
+            persist_on_failure: false,
+            cleanup: CleanupPolicy::default(),
+        }
+    }
+
+    /// Change where the fixture gets the current time from.
     ///
-    /// Although `TempDir` removes the directory on drop, in the destructor
-    /// any errors are ignored. To detect errors cleaning up the temporary
-    /// directory, call `close` instead.
+    /// See [`Clock`][super::Clock].
     ///
-    /// # Errors
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::fixture::{Clock, TempDir};
     ///
-    /// This function may return a variety of [`std::io::Error`]s that result from deleting
-    /// the files and directories contained with the temporary directory,
-    /// as well as from deleting the temporary directory itself. These errors
-    /// may be platform specific.
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// let base = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    /// tmp_dir.set_clock(Clock::fixed(base));
+    /// assert_eq!(tmp_dir.now(), base);
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn set_clock(&self, clock: super::Clock) {
+        *self
+            .context
+            .clock
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = clock;
+    }
+
+    /// The current time, according to the fixture's [`Clock`][super::Clock].
+    pub fn now(&self) -> std::time::SystemTime {
+        self.context
+            .clock
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .now()
+    }
+
+    /// Call `f` after every file written by long-running operations within the fixture.
     ///
+    /// This covers copying (`copy_from`, `sync_from`), archive extraction, and corpus
+    /// generation.  See [`Progress`][super::Progress].
     ///
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(feature = "glob")] {
+    /// use assert_fs::prelude::*;
     /// use assert_fs::fixture::TempDir;
     ///
     /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.on_progress(|progress| eprintln!("{}", progress));
+    /// tmp_dir.copy_from("tests/fixture", &["*.txt"]).unwrap();
     ///
     /// // Ensure deletion happens.
     /// tmp_dir.close().unwrap();
+    /// # }
     /// ```
-    pub fn close(self) -> Result<(), FixtureError> {
-        match self.temp {
-            Inner::Temp(temp) => temp
-                .close()
-                .chain(FixtureError::new(FixtureKind::Cleanup))?,
-            Inner::Persisted(_) => (),
-        }
-        Ok(())
+    pub fn on_progress<F>(&self, f: F)
+    where
+        F: Fn(&super::Progress) + Send + Sync + 'static,
+    {
+        *self
+            .context
+            .progress
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = Some(std::sync::Arc::new(f));
     }
-}
 
-impl AsRef<path::Path> for TempDir {
-    fn as_ref(&self) -> &path::Path {
-        self.path()
+    /// Limit writes through fixture APIs within the fixture to `bytes_per_second`, or `None` for
+    /// no limit.
+    ///
+    /// Each write sleeps as long as it would take on a disk of that speed, to exercise timeout
+    /// and backpressure handling in the code under test.  Writes from other threads queue up
+    /// behind each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.set_write_rate(Some(1024 * 1024));
+    /// tmp_dir.child("blob.bin").write_binary(&[0; 1024]).unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn set_write_rate(&self, bytes_per_second: Option<u64>) {
+        self.context
+            .write_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .set(bytes_per_second);
     }
-}
+
+    /// Emit a [`tracing`](https://docs.rs/tracing) event for each operation within the fixture
+    /// (requires the `tracing` feature).
+    ///
+    /// Events use the `assert_fs` target, at `DEBUG` level, or `WARN` for failed operations, with
+    /// the operation, resolved path and byte count as fields.  Install a subscriber, like
+    /// `tracing-subscriber`'s test writer, to see what a test did to its fixture as it happens,
+    /// rather than after the fact with [`TempDir::operation_log`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.trace(true);
+    /// tmp_dir.child("foo.txt").write_str("Hello").unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn trace(&self, enabled: bool) {
+        self.context
+            .trace
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Change how [`PathChild::unique_child`][super::PathChild::unique_child] names entries
+    /// within the fixture.
+    ///
+    /// See [`NamePolicy`][super::NamePolicy].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::{NamePolicy, TempDir};
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.set_name_policy(NamePolicy::seeded(42));
+    /// tmp_dir.child("out").unique_child(".log").touch().unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn set_name_policy(&self, policy: super::NamePolicy) {
+        *self.context.names.lock().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// Everything done to the fixture through the `assert_fs` API, in order.
+    ///
+    /// This helps reconstruct the exact setup of a fixture when debugging flaky tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("foo.txt").write_str("Hello").unwrap();
+    ///
+    /// for operation in tmp_dir.operation_log() {
+    ///     println!("{}", operation);
+    /// }
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn operation_log(&self) -> Vec<super::Operation> {
+        self.context
+            .log
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Check every entry in the fixture was created through the `assert_fs` API.
+    ///
+    /// Call this once the fixture is set up, before running the code under test, to keep
+    /// fixture construction auditable and reproducible: entries written directly with
+    /// [`std::fs`] are missing from the [`TempDir::operation_log`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FixtureKind::Untracked`], listing the entries
+    /// that weren't created through the API.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("config/app.toml").write_str("debug = true").unwrap();
+    /// tmp_dir.verify_setup().unwrap();
+    ///
+    /// std::fs::write(tmp_dir.path().join("sneaky.txt"), "").unwrap();
+    /// assert!(tmp_dir.verify_setup().is_err());
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn verify_setup(&self) -> Result<(), FixtureError> {
+        let untracked = super::oplog::untracked(self.path(), &self.operation_log())?;
+        if untracked.is_empty() {
+            return Ok(());
+        }
+        let listing = untracked
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let err = std::io::Error::new(std::io::ErrorKind::Other, listing);
+        Err(FixtureError::new(FixtureKind::Untracked).chain(err))
+    }
+
+    /// Mark the current state of the fixture, to later [restore][TempDir::restore] it.
+    ///
+    /// Every file is hashed and copied aside, so one expensive fixture can be reused across
+    /// several cases within a test, rewinding between them rather than setting up again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("config.toml").write_str("debug = false").unwrap();
+    /// let checkpoint = tmp_dir.checkpoint().unwrap();
+    ///
+    /// for case in &["debug = true", "invalid"] {
+    ///     tmp_dir.child("config.toml").write_str(case).unwrap();
+    ///     tmp_dir.child("out.log").touch().unwrap();
+    ///     // ... run the code under test ...
+    ///     tmp_dir.restore(&checkpoint).unwrap();
+    /// }
+    /// tmp_dir.child("config.toml").assert("debug = false");
+    /// assert_eq!(tmp_dir.operation_log().len(), 1);
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn checkpoint(&self) -> Result<super::Checkpoint, FixtureError> {
+        let operations = self
+            .context
+            .log
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len();
+        let snapshot = super::PathSnapshot::snapshot(self)?;
+        Ok(super::Checkpoint {
+            snapshot,
+            operations,
+            context: std::sync::Arc::downgrade(&self.context),
+        })
+    }
+
+    /// Rewind the fixture to `checkpoint`.
+    ///
+    /// Entries created since are removed, and changed or removed ones restored, like
+    /// [`PathSnapshot::reset_to`][super::PathSnapshot::reset_to].  Operations logged since are
+    /// dropped from the [`TempDir::operation_log`], so it describes the rewound tree.
+    ///
+    /// A checkpoint can be restored any number of times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `checkpoint` was taken of a different fixture.
+    pub fn restore(&self, checkpoint: &super::Checkpoint) -> Result<(), FixtureError> {
+        assert!(
+            std::ptr::eq(
+                checkpoint.context.as_ptr(),
+                std::sync::Arc::as_ptr(&self.context)
+            ),
+            "checkpoint taken of a different fixture"
+        );
+        super::PathSnapshot::reset_to(self, &checkpoint.snapshot)?;
+        self.context
+            .log
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .truncate(checkpoint.operations);
+        Ok(())
+    }
+
+    /// Accesses the [`Path`] to the temporary directory.
+    ///
+    /// [`Path`]: std::path::Path
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    ///
+    /// println!("{}", tmp_dir.path().display());
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn path(&self) -> &path::Path {
+        self.temp.path()
+    }
+
+    /// An owned, cheaply cloned handle to the directory, keeping it from being removed while
+    /// any handle exists.
+    ///
+    /// For handing the fixture to worker threads or `'static` tasks.  Once the `TempDir` is
+    /// dropped or [closed][TempDir::close], the directory is removed when the last handle is
+    /// dropped instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// let handle = tmp_dir.path_handle();
+    /// let worker = std::thread::spawn(move || {
+    ///     std::fs::write(handle.join("out.txt"), "done").unwrap();
+    ///     handle
+    /// });
+    /// let handle = worker.join().unwrap();
+    ///
+    /// drop(tmp_dir);
+    /// assert!(handle.join("out.txt").exists());
+    /// drop(handle);
+    /// ```
+    pub fn path_handle(&self) -> PathHandle {
+        let shared = match self.temp {
+            Inner::Temp(ref shared) => shared.clone(),
+            Inner::Persisted(ref path) => std::sync::Arc::new(Shared {
+                path: path.clone(),
+                temp: std::sync::Mutex::new(None),
+            }),
+        };
+        PathHandle { shared }
+    }
+
+    /// Print the fixture's path and contents if the test panics.
+    ///
+    /// See [`DumpOnPanic`][super::DumpOnPanic].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// let _guard = tmp_dir.dump_on_panic();
+    ///
+    /// // ... a failing `assert_eq!` here will show the fixture ...
+    ///
+    /// drop(_guard);
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn dump_on_panic(&self) -> super::DumpOnPanic {
+        super::DumpOnPanic::new(self.path())
+    }
+
+    /// Reject all writes to the fixture until the returned guard is dropped.
+    ///
+    /// Where privileged, the directory is remounted read-only so writes fail like on real
+    /// read-only media; elsewhere write permissions are stripped.  See
+    /// [`ReadOnly`][super::ReadOnly].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("foo.txt").write_str("Hello").unwrap();
+    /// let guard = tmp_dir.read_only().unwrap();
+    ///
+    /// // ... run the tool against the read-only fixture ...
+    ///
+    /// guard.restore().unwrap();
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn read_only(&self) -> Result<super::ReadOnly, FixtureError> {
+        super::ReadOnly::new(self.path())
+    }
+
+    /// Run `f` with the current directory, temp directory and environment pointed into the
+    /// fixture, restoring them afterwards.
+    ///
+    /// See [`Isolation`][super::Isolation] for setting other environment variables or
+    /// spawning processes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// let scratch = tmp_dir
+    ///     .run_isolated(|| {
+    ///         std::fs::write("foo.txt", "Hello").unwrap();
+    ///         std::env::temp_dir()
+    ///     })
+    ///     .unwrap();
+    /// assert!(scratch.starts_with(tmp_dir.path()));
+    /// tmp_dir.child("foo.txt").assert("Hello");
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn run_isolated<F, R>(&self, f: F) -> Result<R, FixtureError>
+    where
+        F: FnOnce() -> R,
+    {
+        self.isolation().run(f)
+    }
+
+    /// Configure running code as if the fixture were the whole machine.
+    ///
+    /// See [`Isolation`][super::Isolation].
+    pub fn isolation(&self) -> super::Isolation<'_> {
+        super::Isolation::new(self.path())
+    }
+
+    /// A [`Command`][std::process::Command] for `program`, running in the fixture.
+    ///
+    /// Only the working directory is set; for `TMPDIR` and `HOME` within the fixture too, see
+    /// [`Isolation::command`][super::Isolation::command].  The result works with crates
+    /// extending `Command`, like `assert_cmd`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("input.txt").write_str("Hello").unwrap();
+    ///
+    /// let status = tmp_dir.command("cat").arg("input.txt").status().unwrap();
+    /// assert!(status.success());
+    ///
+    /// let isolated = tmp_dir.isolation().home().command("git").unwrap();
+    /// # drop(isolated);
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn command<S>(&self, program: S) -> std::process::Command
+    where
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut command = std::process::Command::new(program);
+        command.current_dir(self.path());
+        command
+    }
+
+    /// Point the environment variable `key` at the fixture, until the guard is dropped.
+    ///
+    /// For code locating its files through variables like `HOME`, `XDG_CONFIG_HOME`, or
+    /// `TMPDIR`.  See [`EnvGuard`][super::EnvGuard].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// {
+    ///     let _home = tmp_dir.env_guard("HOME");
+    ///     let _config = tmp_dir.env_guard("XDG_CONFIG_HOME");
+    ///     assert_eq!(std::env::var_os("HOME").unwrap(), tmp_dir.path());
+    /// }
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn env_guard<K>(&self, key: K) -> super::EnvGuard
+    where
+        K: AsRef<std::ffi::OsStr>,
+    {
+        super::EnvGuard::set(key.as_ref(), self.path().as_os_str())
+    }
+
+    /// Populate the fixture with everything in `source`, cheaply where possible.
+    ///
+    /// For large, read-only fixtures, reflinks or hard links avoid the cost of copying for
+    /// each test.  Symlinks are recreated as-is.  See [`CloneStrategy`][super::CloneStrategy]
+    /// for the trade-offs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::{CloneStrategy, TempDir};
+    ///
+    /// let pristine = TempDir::new().unwrap();
+    /// pristine.child("data/large.bin").write_binary(&[0; 4096]).unwrap();
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.clone_from(pristine.path(), CloneStrategy::Auto).unwrap();
+    /// tmp_dir.child("data/large.bin").assert_size(4096);
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// pristine.close().unwrap();
+    /// ```
+    pub fn clone_from<P>(
+        &self,
+        source: P,
+        strategy: super::CloneStrategy,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        super::tools::clone_tree(self.path(), source.as_ref(), strategy)
+    }
+
+    /// The total length of the files within the fixture, recursively.
+    ///
+    /// Symlinks aren't followed, so their targets aren't counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("a.txt").write_str("Hello").unwrap();
+    /// tmp_dir.child("nested/b.txt").write_str("World!").unwrap();
+    /// assert_eq!(tmp_dir.total_size().unwrap(), 11);
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn total_size(&self) -> Result<u64, FixtureError> {
+        total_size(self.path())
+    }
+
+    /// Whether the filesystem the fixture is on tells apart names differing only in case.
+    ///
+    /// Probes by creating a lowercase file and looking it up in uppercase, so tests can cover
+    /// case-insensitive filesystems, like the defaults on macOS and Windows, deliberately.  See
+    /// [`PathAssertExactCase`][crate::assert::PathAssertExactCase] to check the case of names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("readme.md").touch().unwrap();
+    /// if !tmp_dir.is_case_sensitive().unwrap() {
+    ///     assert!(tmp_dir.child("README.md").exists());
+    /// }
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn is_case_sensitive(&self) -> Result<bool, FixtureError> {
+        let probe = tempfile::Builder::new()
+            .prefix(".case-probe-")
+            .tempfile_in(self.path())
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        let name = probe
+            .path()
+            .file_name()
+            .expect("temp files are named")
+            .to_string_lossy()
+            .to_ascii_uppercase();
+        match self.path().join(name).symlink_metadata() {
+            Ok(_) => Ok(false),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(true),
+            Err(err) => Err(err).chain(FixtureError::new(FixtureKind::ReadFile)),
+        }
+    }
+
+    /// Closes and removes the temporary directory, returning a `Result`.
+    ///
+    /// Although `TempDir` removes the directory on drop, in the destructor
+    /// any errors are ignored. To detect errors cleaning up the temporary
+    /// directory, call `close` instead.
+    ///
+    /// # Errors
+    ///
+    /// Removal continues past failures, and the error lists every path that could not be
+    /// removed, with the [`std::io::Error`] for each.  These errors may be platform specific.
+    /// See [`TempDir::close_with_report`] to inspect them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn close(mut self) -> Result<(), FixtureError> {
+        if self.retain_if_requested() {
+            return Ok(());
+        }
+        let path = match self.take().into_removable() {
+            Some(temp) => temp.into_path(),
+            None => return Ok(()),
+        };
+        let report = remove_all(&path, self.cleanup);
+        match report.failures.first() {
+            None => Ok(()),
+            Some(failure) => {
+                let failed = failure.path.clone();
+                Err(FixtureError::new(FixtureKind::Cleanup)
+                    .chain(report)
+                    .with_path(failed))
+            }
+        }
+    }
+
+    /// Like [`TempDir::close`], retrying removal according to `retry`.
+    ///
+    /// On Windows, virus scanners and indexers briefly hold files open after a test, failing
+    /// removal.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from the last attempt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::Retry;
+    /// use assert_fs::fixture::TempDir;
+    /// use std::time::Duration;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    ///
+    /// tmp_dir
+    ///     .close_with_retry(&Retry::backoff(Duration::from_millis(50)).max_attempts(5))
+    ///     .unwrap();
+    /// ```
+    pub fn close_with_retry(mut self, retry: &super::Retry) -> Result<(), FixtureError> {
+        if self.retain_if_requested() {
+            return Ok(());
+        }
+        let path = match self.take().into_removable() {
+            Some(temp) => temp.into_path(),
+            None => return Ok(()),
+        };
+        if self.cleanup == CleanupPolicy::ForceChmodThenDelete {
+            make_writable(&path);
+        }
+        retry
+            .run(|| match std::fs::remove_dir_all(&path) {
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                result => result,
+            })
+            .chain(FixtureError::new(FixtureKind::Cleanup))
+    }
+
+    /// Closes and removes the temporary directory, reporting every path that could not be
+    /// removed and why.
+    ///
+    /// Unlike [`TempDir::close`], removal continues past failures, so tests can verify the tool
+    /// under test released everything (e.g. no open handles on Windows).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("foo.txt").touch().unwrap();
+    ///
+    /// let report = tmp_dir.close_with_report();
+    /// assert!(report.is_clean(), "{}", report);
+    /// ```
+    pub fn close_with_report(mut self) -> CleanupReport {
+        if self.retain_if_requested() {
+            return CleanupReport::default();
+        }
+        match self.take().into_removable() {
+            Some(temp) => remove_all(&temp.into_path(), self.cleanup),
+            None => CleanupReport::default(),
+        }
+    }
+}
+
+/// Remove `path` and everything within it, continuing past failures.
+fn remove_all(path: &path::Path, policy: CleanupPolicy) -> CleanupReport {
+    if policy == CleanupPolicy::ForceChmodThenDelete {
+        make_writable(path);
+    }
+    let mut report = CleanupReport::default();
+    for entry in crate::order::walk(path).contents_first(true) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                let failed = err.path().unwrap_or(path).to_owned();
+                report.failures.push(CleanupFailure {
+                    path: failed,
+                    error: err.into(),
+                });
+                continue;
+            }
+        };
+        let removed = if entry.file_type().is_dir() {
+            std::fs::remove_dir(entry.path())
+        } else {
+            std::fs::remove_file(entry.path())
+        };
+        if let Err(error) = removed {
+            report.failures.push(CleanupFailure {
+                path: entry.path().to_owned(),
+                error,
+            });
+        }
+    }
+    report
+}
+
+/// Give the owner write access to `path` and everything within it, ignoring failures.
+///
+/// Directories are made writable before listing them, so unreadable ones can be walked too.
+fn make_writable(path: &path::Path) {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(_) => return,
+    };
+    if metadata.file_type().is_symlink() {
+        return;
+    }
+    let mut permissions = metadata.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let owner = if metadata.is_dir() { 0o700 } else { 0o200 };
+        permissions.set_mode(permissions.mode() | owner);
+    }
+    #[cfg(not(unix))]
+    {
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+    }
+    let _ = std::fs::set_permissions(path, permissions);
+    if metadata.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                make_writable(&entry.path());
+            }
+        }
+    }
+}
+
+/// An owned handle to a [`TempDir`]'s directory, keeping it from being removed.
+///
+/// Created with [`TempDir::path_handle`].
+#[derive(Clone, Debug)]
+pub struct PathHandle {
+    shared: std::sync::Arc<Shared>,
+}
+
+impl PathHandle {
+    /// Accesses the [`Path`][std::path::Path] to the directory.
+    pub fn path(&self) -> &path::Path {
+        &self.shared.path
+    }
+
+    /// Access a path within the directory.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `path` is absolute or uses `..` to escape the directory, like
+    /// [`PathChild::child`][super::PathChild::child].
+    #[track_caller]
+    pub fn child<P>(&self, path: P) -> super::ChildPath
+    where
+        P: AsRef<path::Path>,
+    {
+        let path = path.as_ref();
+        if let Err(reason) = super::child::validate_child(path) {
+            panic!("Invalid child path `{}`: {}", path.display(), reason);
+        }
+        super::ChildPath::new(self.path().join(path))
+    }
+}
+
+impl AsRef<path::Path> for PathHandle {
+    fn as_ref(&self) -> &path::Path {
+        self.path()
+    }
+}
+
+impl std::ops::Deref for PathHandle {
+    type Target = path::Path;
+    #[inline]
+    fn deref(&self) -> &path::Path {
+        self.path()
+    }
+}
+
+/// Create a [`TempDir`] in a specific location, with a recognizable name.
+///
+/// For example, to test against a tmpfs or a case-insensitive volume, or to find the fixture
+/// when inspecting `/tmp`.  The name is `<prefix><random><suffix>`.
+///
+/// Created with [`TempDir::builder`].
+#[derive(Clone, Debug, Default)]
+pub struct TempDirBuilder {
+    prefix: Option<std::ffi::OsString>,
+    suffix: Option<std::ffi::OsString>,
+    parent: Option<path::PathBuf>,
+}
+
+impl TempDirBuilder {
+    /// Start the directory's name with `prefix`.
+    pub fn prefix<S>(mut self, prefix: S) -> Self
+    where
+        S: Into<std::ffi::OsString>,
+    {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// End the directory's name with `suffix`.
+    pub fn suffix<S>(mut self, suffix: S) -> Self
+    where
+        S: Into<std::ffi::OsString>,
+    {
+        self.suffix = Some(suffix.into());
+        self
+    }
+
+    /// Create the directory inside of `parent`, rather than `env::temp_dir()`.
+    pub fn parent<P>(mut self, parent: P) -> Self
+    where
+        P: Into<path::PathBuf>,
+    {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    /// Attempts to make the temporary directory.
+    ///
+    /// # Errors
+    ///
+    /// If the directory can not be created, `Err` is returned.
+    pub fn create(&self) -> Result<TempDir, FixtureError> {
+        let mut builder = tempfile::Builder::new();
+        if let Some(prefix) = &self.prefix {
+            builder.prefix(prefix);
+        }
+        if let Some(suffix) = &self.suffix {
+            builder.suffix(suffix);
+        }
+        let temp = match &self.parent {
+            Some(parent) => builder.tempdir_in(parent),
+            None => builder.tempdir(),
+        }
+        .chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(TempDir::from_inner(Inner::temp(temp)))
+    }
+}
+
+/// The total length of the file at `path`, or the files within it.
+pub(crate) fn total_size(path: &path::Path) -> Result<u64, FixtureError> {
+    let mut total = 0;
+    for entry in walkdir::WalkDir::new(path) {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        if entry.file_type().is_file() {
+            let metadata = entry
+                .metadata()
+                .chain(FixtureError::new(FixtureKind::Walk))?;
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
+/// The outcome of [`TempDir::close_with_report`], and the cause of [`TempDir::close`] failing.
+#[derive(Debug, Default)]
+pub struct CleanupReport {
+    failures: Vec<CleanupFailure>,
+}
+
+impl CleanupReport {
+    /// Whether everything was removed.
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The paths that could not be removed.
+    pub fn failures(&self) -> &[CleanupFailure] {
+        &self.failures
+    }
+}
+
+impl std::fmt::Display for CleanupReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_clean() {
+            return write!(f, "Cleaned up fixture.");
+        }
+        write!(f, "Failed to cleanup fixture:")?;
+        for failure in &self.failures {
+            write!(f, "\n  {}: {}", failure.path.display(), failure.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CleanupReport {}
+
+/// A path [`TempDir::close_with_report`] could not remove.
+#[derive(Debug)]
+pub struct CleanupFailure {
+    path: path::PathBuf,
+    error: std::io::Error,
+}
+
+impl CleanupFailure {
+    /// The path left behind.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    /// Why it could not be removed.
+    pub fn error(&self) -> &std::io::Error {
+        &self.error
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // Otherwise `tempfile` removes it, ignoring failures
+        if self.retain_if_requested() || self.cleanup == CleanupPolicy::BestEffort {
+            return;
+        }
+        let report = match self.take().into_removable() {
+            Some(temp) => remove_all(&temp.into_path(), self.cleanup),
+            None => return,
+        };
+        if self.cleanup == CleanupPolicy::Strict && !report.is_clean() {
+            if std::thread::panicking() {
+                eprintln!("{}", report);
+            } else {
+                panic!("{}", report);
+            }
+        }
+    }
+}
+
+impl AsRef<path::Path> for TempDir {
+    fn as_ref(&self) -> &path::Path {
+        self.path()
+    }
+}
 
 impl std::ops::Deref for TempDir {
     type Target = path::Path;
@@ -205,3 +1596,47 @@ impl std::ops::Deref for TempDir {
         self.path()
     }
 }
+
+static LEAKED: once_cell::sync::Lazy<std::sync::Mutex<Vec<&'static path::Path>>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+pub(crate) fn current_test_name() -> Option<String> {
+    let thread = std::thread::current();
+    let name = thread.name()?;
+    if name == "main" {
+        return None;
+    }
+    let name = name
+        .replace("::", "-")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Some(name)
+}
+
+fn scratch_root() -> Option<path::PathBuf> {
+    if let Some(root) = std::env::var_os("ASSERT_FS_SCRATCH_DIR") {
+        return Some(root.into());
+    }
+    Some(target_dir()?.join("assert_fs"))
+}
+
+/// Cargo's target directory, when running tests through Cargo.
+pub(crate) fn target_dir() -> Option<path::PathBuf> {
+    if let Some(target) = std::env::var_os("CARGO_TARGET_DIR") {
+        return Some(target.into());
+    }
+    // Test binaries live in `target/<profile>/deps/`
+    let exe = std::env::current_exe().ok()?;
+    let deps = exe.parent()?;
+    if deps.file_name()? != "deps" {
+        return None;
+    }
+    Some(deps.parent()?.parent()?.to_owned())
+}
@@ -2,6 +2,7 @@
 
 use std::error::Error;
 use std::fmt;
+use std::path;
 
 pub(crate) trait ChainError {
     fn chain<F>(self, cause: F) -> Self
@@ -48,14 +49,46 @@ pub enum FixtureKind {
     Walk,
     /// Failed when copying a file.
     CopyFile,
+    /// Failed when reading a file.
+    ReadFile,
     /// Failed when writing to a file.
     WriteFile,
     /// Failed when creating a directory.
     CreateDir,
+    /// Failed when removing a file or directory.
+    Remove,
     /// Failed to cleanup fixture.
     Cleanup,
     /// Failed to create symlink
     Symlink,
+    /// Failed when creating a hard link.
+    HardLink,
+    /// Failed when changing permissions.
+    Permissions,
+    /// Timed out waiting for a condition.
+    Timeout,
+    /// Failed when extracting an archive.
+    Extract,
+    /// Failed when mounting or unmounting a filesystem.
+    Mount,
+    /// Failed when setting a timestamp.
+    Timestamp,
+    /// Failed when isolating the process within a fixture.
+    Isolate,
+    /// Failed when parsing a fixture description.
+    Spec,
+    /// Found entries not created through the fixture API.
+    Untracked,
+    /// A glob pattern matched nothing.
+    NoMatch,
+    /// Failed when watching for filesystem events.
+    Watch,
+    /// Failed when rendering a template.
+    Template,
+    /// Failed when running `git`.
+    Git,
+    /// Failed when locking a file.
+    Lock,
 }
 
 impl fmt::Display for FixtureKind {
@@ -63,31 +96,86 @@ impl fmt::Display for FixtureKind {
         match *self {
             FixtureKind::Walk => write!(f, "Failed when walking the source tree,"),
             FixtureKind::CopyFile => write!(f, "Failed when copying a file."),
+            FixtureKind::ReadFile => write!(f, "Failed when reading a file."),
             FixtureKind::WriteFile => write!(f, "Failed when writing to a file."),
             FixtureKind::CreateDir => write!(f, "Failed when creating a directory."),
+            FixtureKind::Remove => write!(f, "Failed when removing a path."),
             FixtureKind::Cleanup => write!(f, "Failed to cleanup fixture."),
             FixtureKind::Symlink => write!(f, "Failed when symlinking to the target."),
+            FixtureKind::HardLink => write!(f, "Failed when hard linking to the target."),
+            FixtureKind::Permissions => write!(f, "Failed when changing permissions."),
+            FixtureKind::Timeout => write!(f, "Timed out waiting for the fixture."),
+            FixtureKind::Extract => write!(f, "Failed when extracting an archive."),
+            FixtureKind::Mount => write!(f, "Failed when mounting a filesystem."),
+            FixtureKind::Timestamp => write!(f, "Failed when setting a timestamp."),
+            FixtureKind::Isolate => write!(f, "Failed when isolating the process."),
+            FixtureKind::Spec => write!(f, "Failed when parsing the fixture description."),
+            FixtureKind::Untracked => write!(f, "Found entries not created by the fixture."),
+            FixtureKind::NoMatch => write!(f, "A pattern matched nothing."),
+            FixtureKind::Watch => write!(f, "Failed when watching for changes."),
+            FixtureKind::Template => write!(f, "Failed when rendering a template."),
+            FixtureKind::Git => write!(f, "Failed when running git."),
+            FixtureKind::Lock => write!(f, "Failed when locking a file."),
         }
     }
 }
 
 /// Failure when initializing the fixture.
+///
+/// Errors from fixture operations name the operation and the path it failed on, so a failing
+/// setup can be diagnosed from the message alone.
 #[derive(Debug)]
 pub struct FixtureError {
     kind: FixtureKind,
+    operation: Option<super::OperationKind>,
+    path: Option<path::PathBuf>,
     cause: Option<Box<dyn Error + Send + Sync + 'static>>,
 }
 
 impl FixtureError {
     /// Create a `FixtureError`.
     pub fn new(kind: FixtureKind) -> Self {
-        Self { kind, cause: None }
+        Self {
+            kind,
+            operation: None,
+            path: None,
+            cause: None,
+        }
     }
 
     /// Fixture initialization cause.
     pub fn kind(&self) -> FixtureKind {
         self.kind
     }
+
+    /// The operation that failed, like [`OperationKind::WriteStr`][super::OperationKind].
+    pub fn operation(&self) -> Option<super::OperationKind> {
+        self.operation
+    }
+
+    /// The path the operation failed on.
+    pub fn path(&self) -> Option<&path::Path> {
+        self.path.as_deref()
+    }
+
+    /// Name the path the error is about, unless already named.
+    pub fn with_path<P>(mut self, path: P) -> Self
+    where
+        P: Into<path::PathBuf>,
+    {
+        if self.path.is_none() {
+            self.path = Some(path.into());
+        }
+        self
+    }
+
+    /// Name the operation that failed and its path, unless already named.
+    pub(crate) fn at(mut self, operation: super::OperationKind, path: &path::Path) -> Self {
+        if self.operation.is_none() {
+            self.operation = Some(operation);
+        }
+        self.with_path(path)
+    }
 }
 
 impl Error for FixtureError {
@@ -105,14 +193,17 @@ impl Error for FixtureError {
 
 impl fmt::Display for FixtureError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.cause {
-            Some(ref cause) => write!(
-                f,
-                "Failed to initialize fixture: {}\nCause: {}",
-                self.kind, cause
-            ),
-            None => write!(f, "Failed to initialize fixture: {}", self.kind),
+        write!(f, "Failed to initialize fixture: {}", self.kind)?;
+        if let Some(operation) = self.operation {
+            write!(f, "\nOperation: {}", operation)?;
+        }
+        if let Some(ref path) = self.path {
+            write!(f, "\nPath: {}", path.display())?;
+        }
+        if let Some(ref cause) = self.cause {
+            write!(f, "\nCause: {}", cause)?;
         }
+        Ok(())
     }
 }
 
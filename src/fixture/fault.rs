@@ -0,0 +1,171 @@
+//! Stage broken filesystem state, to test error handling.
+//!
+//! Permission changes are undone when the returned [`FaultGuard`] is dropped, so the
+//! [`TempDir`][super::TempDir] can still be cleaned up.  Declare the guard after the `TempDir`
+//! so it's dropped first.
+//!
+//! Root, and Windows administrators, bypass permissions, so reads and writes may still
+//! succeed when running as them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use assert_fs::fixture::fault;
+//! use assert_fs::prelude::*;
+//!
+//! let temp = assert_fs::TempDir::new().unwrap();
+//! let config = temp.child("config.toml");
+//! config.write_str("port = 8080\n").unwrap();
+//! let _unreadable = fault::make_unreadable(&config).unwrap();
+//! fault::dangling_symlink(temp.child("current"), temp.child("releases/v2")).unwrap();
+//!
+//! // ... run the tool, expecting it to report both problems ...
+//! ```
+
+use std::fs;
+use std::path;
+
+use super::errors::*;
+
+/// Undoes the permission changes of a [fault][self] when dropped.
+///
+/// Dropping ignores errors; use [`FaultGuard::restore`] to check them.
+#[derive(Debug)]
+#[must_use = "permissions are restored when the guard is dropped"]
+pub struct FaultGuard {
+    restore: Vec<(path::PathBuf, fs::Permissions)>,
+}
+
+impl FaultGuard {
+    /// Restore the original permissions, returning a `Result`.
+    pub fn restore(mut self) -> Result<(), FixtureError> {
+        self.restore_permissions()
+    }
+
+    fn restore_permissions(&mut self) -> Result<(), FixtureError> {
+        let mut result = Ok(());
+        for (path, permissions) in self.restore.drain(..).rev() {
+            let restored = fs::set_permissions(&path, permissions)
+                .chain(FixtureError::new(FixtureKind::Permissions))
+                .map_err(|err| err.with_path(&path));
+            if result.is_ok() {
+                result = restored;
+            }
+        }
+        result
+    }
+}
+
+impl Drop for FaultGuard {
+    fn drop(&mut self) {
+        let _ = self.restore_permissions();
+    }
+}
+
+/// Remove read permission from the file or directory at `path`, until the guard is dropped.
+///
+/// A directory without read permission can't be listed, though its entries can still be
+/// opened by name.
+///
+/// # Errors
+///
+/// Unsupported outside of Unix.
+pub fn make_unreadable<P>(path: P) -> Result<FaultGuard, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    strip(path.as_ref(), 0o444)
+}
+
+/// Remove write permission from the directory at `path`, until the guard is dropped.
+///
+/// Entries can't be created, removed, or renamed within it, though existing files can still be
+/// modified.
+///
+/// # Errors
+///
+/// Fails if `path` is not a directory.
+pub fn make_dir_readonly<P>(path: P) -> Result<FaultGuard, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    let path = path.as_ref();
+    if !path.is_dir() {
+        let err = std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a directory");
+        return Err(FixtureError::new(FixtureKind::Permissions)
+            .chain(err)
+            .with_path(path));
+    }
+    strip(path, 0o222)
+}
+
+/// Create a symlink at `link` to `target`, which must not exist.
+///
+/// # Errors
+///
+/// Fails if `target` exists, as the link would not dangle.
+pub fn dangling_symlink<L, T>(link: L, target: T) -> Result<(), FixtureError>
+where
+    L: AsRef<path::Path>,
+    T: AsRef<path::Path>,
+{
+    let (link, target) = (link.as_ref(), target.as_ref());
+    let resolved = match link.parent() {
+        Some(parent) => parent.join(target),
+        None => target.to_owned(),
+    };
+    if resolved.symlink_metadata().is_ok() {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("target `{}` exists", resolved.display()),
+        );
+        return Err(FixtureError::new(FixtureKind::Symlink)
+            .chain(err)
+            .with_path(link));
+    }
+    super::tools::symlink_to_file(link, target)
+}
+
+#[cfg(unix)]
+fn strip(path: &path::Path, bits: u32) -> Result<FaultGuard, FixtureError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let original = path
+        .metadata()
+        .chain(FixtureError::new(FixtureKind::Permissions))
+        .map_err(|err| err.with_path(path))?
+        .permissions();
+    let stripped = fs::Permissions::from_mode(original.mode() & !bits);
+    fs::set_permissions(path, stripped)
+        .chain(FixtureError::new(FixtureKind::Permissions))
+        .map_err(|err| err.with_path(path))?;
+    Ok(FaultGuard {
+        restore: vec![(path.to_owned(), original)],
+    })
+}
+
+#[cfg(not(unix))]
+fn strip(path: &path::Path, bits: u32) -> Result<FaultGuard, FixtureError> {
+    if bits & 0o444 != 0 {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "unreadable files are unsupported on this platform",
+        );
+        return Err(FixtureError::new(FixtureKind::Permissions)
+            .chain(err)
+            .with_path(path));
+    }
+    let original = path
+        .metadata()
+        .chain(FixtureError::new(FixtureKind::Permissions))
+        .map_err(|err| err.with_path(path))?
+        .permissions();
+    let mut readonly = original.clone();
+    readonly.set_readonly(true);
+    fs::set_permissions(path, readonly)
+        .chain(FixtureError::new(FixtureKind::Permissions))
+        .map_err(|err| err.with_path(path))?;
+    Ok(FaultGuard {
+        restore: vec![(path.to_owned(), original)],
+    })
+}
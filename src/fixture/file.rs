@@ -20,6 +20,11 @@ use super::errors::*;
 /// responsibility to ensure that no further file system operations are attempted inside the
 /// temporary directory once it has been deleted.
 ///
+/// For a single fixture file, the file-level extension traits of [`ChildPath`] work on
+/// `NamedTempFile` too, like [`FileTouch`], [`FileWriteStr`], [`FileWriteBin`], [`FileRead`],
+/// and [`PathRemove`], as do the file assertions, like
+/// [`PathAssert`][crate::assert::PathAssert].
+///
 /// # Resource Leaking
 ///
 /// Various platform-specific conditions may cause `NamedTempFile` to fail
@@ -47,6 +52,27 @@ use super::errors::*;
 /// tmp_file.close().unwrap();
 /// ```
 ///
+/// Create and check a temporary file through the extension traits.
+///
+/// ```
+/// use assert_fs::fixture::NamedTempFile;
+/// use assert_fs::prelude::*;
+///
+/// let tmp_file = NamedTempFile::new("config.toml").unwrap();
+/// tmp_file.write_str("port = 8080\n").unwrap();
+/// tmp_file.assert("port = 8080\n");
+///
+/// tmp_file.remove_file().unwrap();
+/// tmp_file.assert(predicates::path::missing());
+/// tmp_file.close().unwrap();
+/// ```
+///
+/// [`ChildPath`]: super::ChildPath
+/// [`FileTouch`]: super::FileTouch
+/// [`FileWriteStr`]: super::FileWriteStr
+/// [`FileWriteBin`]: super::FileWriteBin
+/// [`FileRead`]: super::FileRead
+/// [`PathRemove`]: super::PathRemove
 /// [`File`]: std::fs::File
 /// [`Path`]: std::path::Path
 /// [`ReadDir`]: std::fs::ReadDir
@@ -90,6 +116,38 @@ impl NamedTempFile {
         Ok(Self { temp, path })
     }
 
+    /// Attempts to make a temporary file inside of `dir`.
+    ///
+    /// For when the file must be on a particular filesystem, e.g. next to the file it will
+    /// replace.  The file and parent directory will be automatically deleted once the returned
+    /// `NamedTempFile` is destroyed.
+    ///
+    /// # Errors
+    ///
+    /// If the parent directory can not be created, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::fixture::NamedTempFile;
+    ///
+    /// let tmp_file = NamedTempFile::new_in(std::env::temp_dir(), "foo.rs").unwrap();
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_file.close().unwrap();
+    /// ```
+    pub fn new_in<P, S>(dir: P, name: S) -> Result<Self, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<ffi::OsStr>,
+    {
+        let temp = tempfile::TempDir::new_in(dir.as_ref())
+            .chain(FixtureError::new(FixtureKind::CreateDir))?;
+        let path = temp.path().join(name.as_ref());
+        let temp = Inner::Temp(temp);
+        Ok(Self { temp, path })
+    }
+
     /// Conditionally persist the temporary file for debug purposes.
     ///
     /// Note: this operation is not reversible, i.e. `into_persistent_if(false)` is a no-op.
@@ -134,7 +192,7 @@ impl NamedTempFile {
         let mut temp = Inner::Persisted;
         ::std::mem::swap(&mut self.temp, &mut temp);
         if let Inner::Temp(temp) = temp {
-            temp.into_path();
+            let _ = temp.into_path();
         }
 
         self
@@ -0,0 +1,238 @@
+use std::fs;
+use std::io::Write;
+use std::path;
+
+use super::errors::*;
+use super::naming::splitmix64;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::NamedTempFile;
+
+/// Bytes written per call.
+const CHUNK: usize = 64 * 1024;
+
+/// The words [`FileGenerate::write_random_text`] draws from.
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+];
+
+/// The most words on a line of [`FileGenerate::write_random_text`].
+const MAX_LINE_WORDS: u64 = 12;
+
+/// Fill a file at [`ChildPath`] with generated content.
+///
+/// Content is the same for the same arguments, across runs and platforms, so large or realistic
+/// inputs can be produced on the fly rather than checked in.
+pub trait FileGenerate {
+    /// Write `len` pseudo-random bytes, determined by `seed`, creating the file and any missing
+    /// parent directories.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let first = temp.child("first.bin");
+    /// first.write_random_bytes(1024, 42).unwrap();
+    /// let second = temp.child("second.bin");
+    /// second.write_random_bytes(1024, 42).unwrap();
+    /// assert_eq!(std::fs::read(&first).unwrap(), std::fs::read(&second).unwrap());
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_random_bytes(&self, len: u64, seed: u64) -> Result<(), FixtureError>;
+
+    /// Write `lines` lines of pseudo-random lowercase words, determined by `seed`, creating the
+    /// file and any missing parent directories.
+    ///
+    /// Each line has between 1 and 12 words separated by single spaces, and ends with `\n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("app.log");
+    /// log.write_random_text(100, 7).unwrap();
+    /// let text = std::fs::read_to_string(&log).unwrap();
+    /// assert_eq!(text.lines().count(), 100);
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_random_text(&self, lines: usize, seed: u64) -> Result<(), FixtureError>;
+
+    /// Write `len` zero bytes, creating the file and any missing parent directories.
+    ///
+    /// Unlike [`FileWriteStream::write_file_of_size`][super::FileWriteStream::write_file_of_size],
+    /// the zeros are written out, so the file is never sparse and takes up `len` bytes on disk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let image = temp.child("disk.img");
+    /// image.write_zeros(4096).unwrap();
+    /// assert_eq!(image.metadata().unwrap().len(), 4096);
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_zeros(&self, len: u64) -> Result<(), FixtureError>;
+}
+
+impl FileGenerate for ChildPath {
+    fn write_random_bytes(&self, len: u64, seed: u64) -> Result<(), FixtureError> {
+        write_random_bytes(self.path(), len, seed)
+    }
+
+    fn write_random_text(&self, lines: usize, seed: u64) -> Result<(), FixtureError> {
+        write_random_text(self.path(), lines, seed)
+    }
+
+    fn write_zeros(&self, len: u64) -> Result<(), FixtureError> {
+        write_zeros(self.path(), len)
+    }
+}
+
+impl FileGenerate for NamedTempFile {
+    fn write_random_bytes(&self, len: u64, seed: u64) -> Result<(), FixtureError> {
+        write_random_bytes(self.path(), len, seed)
+    }
+
+    fn write_random_text(&self, lines: usize, seed: u64) -> Result<(), FixtureError> {
+        write_random_text(self.path(), lines, seed)
+    }
+
+    fn write_zeros(&self, len: u64) -> Result<(), FixtureError> {
+        write_zeros(self.path(), len)
+    }
+}
+
+fn write_random_bytes(path: &path::Path, len: u64, seed: u64) -> Result<(), FixtureError> {
+    let mut state = seed;
+    write_chunks(OperationKind::WriteRandomBytes, path, len, |chunk| {
+        for word in chunk.chunks_mut(8) {
+            let bytes = splitmix64(&mut state).to_le_bytes();
+            word.copy_from_slice(&bytes[..word.len()]);
+        }
+    })
+}
+
+fn write_zeros(path: &path::Path, len: u64) -> Result<(), FixtureError> {
+    write_chunks(OperationKind::WriteZeros, path, len, |_| {})
+}
+
+/// Write `len` bytes to `path`, a chunk at a time, as `fill` sets them.
+///
+/// Chunks start zeroed, and are at most [`CHUNK`] bytes, a multiple of 8 except for the last.
+fn write_chunks<F>(
+    kind: OperationKind,
+    path: &path::Path,
+    len: u64,
+    mut fill: F,
+) -> Result<(), FixtureError>
+where
+    F: FnMut(&mut [u8]),
+{
+    record(kind, path, Some(len), || {
+        super::tools::ensure_parent_dir(path)?;
+        let mut file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        let mut chunk = vec![0; CHUNK];
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(CHUNK as u64) as usize;
+            let chunk = &mut chunk[..n];
+            fill(chunk);
+            file.write_all(chunk)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            remaining -= n as u64;
+        }
+        Ok(())
+    })
+}
+
+fn write_random_text(path: &path::Path, lines: usize, seed: u64) -> Result<(), FixtureError> {
+    record(OperationKind::WriteRandomText, path, None, || {
+        super::tools::ensure_parent_dir(path)?;
+        let file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        let mut file = std::io::BufWriter::new(file);
+        let mut state = seed;
+        let mut line = String::new();
+        for _ in 0..lines {
+            random_line(&mut state, &mut line);
+            file.write_all(line.as_bytes())
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        }
+        file.flush()
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        Ok(())
+    })
+}
+
+/// Replace `line` with the next line of words from `state`, including its `\n`.
+fn random_line(state: &mut u64, line: &mut String) {
+    line.clear();
+    let words = 1 + splitmix64(state) % MAX_LINE_WORDS;
+    for i in 0..words {
+        if i > 0 {
+            line.push(' ');
+        }
+        let word = splitmix64(state) % WORDS.len() as u64;
+        line.push_str(WORDS[word as usize]);
+    }
+    line.push('\n');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn random_line_is_seeded() {
+        let (mut a, mut b) = (3, 3);
+        let (mut first, mut second) = (String::new(), String::new());
+        for _ in 0..10 {
+            random_line(&mut a, &mut first);
+            random_line(&mut b, &mut second);
+            assert_eq!(first, second);
+            assert!(first.ends_with('\n'));
+            let words = first.split_whitespace().count() as u64;
+            assert!((1..=MAX_LINE_WORDS).contains(&words));
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::ffi;
+use std::path;
+use std::process;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::TempDir;
+
+/// Author, committer, and date of every commit, so commit ids are the same on every run.
+const IDENTITY: &[(&str, &str)] = &[
+    ("GIT_AUTHOR_NAME", "assert_fs"),
+    ("GIT_AUTHOR_EMAIL", "assert_fs@example.com"),
+    ("GIT_AUTHOR_DATE", "2000-01-01T00:00:00Z"),
+    ("GIT_COMMITTER_NAME", "assert_fs"),
+    ("GIT_COMMITTER_EMAIL", "assert_fs@example.com"),
+    ("GIT_COMMITTER_DATE", "2000-01-01T00:00:00Z"),
+];
+
+/// Variables that would point `git` at another repository, e.g. when tests run from a hook.
+const REPO_VARS: &[&str] = &[
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_INDEX_FILE",
+    "GIT_OBJECT_DIRECTORY",
+    "GIT_CEILING_DIRECTORIES",
+];
+
+/// Create a git repository in [`TempDir`] (requires the `git` feature).
+///
+/// Shells out to `git`, which must be on the `PATH`.
+pub trait PathGitRepo {
+    /// Initialize an empty repository, with `main` as its branch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let repo = temp.init_git_repo().unwrap();
+    /// repo.child("tracked.txt").write_str("committed").unwrap();
+    /// repo.commit_all("Initial commit").unwrap();
+    ///
+    /// // Dirty and untracked files
+    /// repo.child("tracked.txt").write_str("changed").unwrap();
+    /// repo.child("untracked.txt").touch().unwrap();
+    /// assert_eq!(
+    ///     repo.git(["status", "--porcelain"]).unwrap(),
+    ///     " M tracked.txt\n?? untracked.txt\n"
+    /// );
+    /// temp.close().unwrap();
+    /// ```
+    fn init_git_repo(&self) -> Result<GitRepo, FixtureError>;
+}
+
+impl PathGitRepo for TempDir {
+    fn init_git_repo(&self) -> Result<GitRepo, FixtureError> {
+        init_git_repo(self.path())
+    }
+}
+
+impl PathGitRepo for ChildPath {
+    fn init_git_repo(&self) -> Result<GitRepo, FixtureError> {
+        init_git_repo(self.path())
+    }
+}
+
+/// A git repository, created by [`PathGitRepo::init_git_repo`].
+///
+/// Commits are made by a fixed author at a fixed date, and user and system git configuration
+/// is ignored, so the same steps give the same commit ids everywhere.
+#[derive(Clone, Debug)]
+pub struct GitRepo {
+    path: path::PathBuf,
+}
+
+impl GitRepo {
+    /// The repository's working tree.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    /// Access a path within the working tree.
+    pub fn child<P>(&self, path: P) -> ChildPath
+    where
+        P: AsRef<path::Path>,
+    {
+        ChildPath::new(self.path.join(path))
+    }
+
+    /// Stage `path`, relative to the working tree, for the next commit.
+    pub fn add<P>(&self, path: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        self.git([
+            ffi::OsStr::new("add"),
+            ffi::OsStr::new("--"),
+            path.as_ref().as_os_str(),
+        ])
+        .map(|_| ())
+    }
+
+    /// Stage everything in the working tree, including deletions, and commit it, returning the
+    /// commit id.
+    ///
+    /// Commits even if nothing changed.
+    pub fn commit_all(&self, message: &str) -> Result<String, FixtureError> {
+        record(OperationKind::CommitAll, &self.path, None, || {
+            self.git(["add", "--all"])?;
+            self.git(["commit", "--quiet", "--allow-empty", "--message", message])?;
+            self.git(["rev-parse", "HEAD"])
+                .map(|id| id.trim_end().to_owned())
+        })
+    }
+
+    /// Run `git` with `args` in the working tree, returning what it printed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `git` can't be run or exits unsuccessfully, including its error output.
+    pub fn git<I, S>(&self, args: I) -> Result<String, FixtureError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<ffi::OsStr>,
+    {
+        git(&self.path, args)
+    }
+}
+
+fn init_git_repo(path: &path::Path) -> Result<GitRepo, FixtureError> {
+    record(OperationKind::InitGitRepo, &path.join(".git"), None, || {
+        super::tools::create_dir_all(path)?;
+        git(path, ["init", "--quiet"])?;
+        // `init --initial-branch` needs git 2.28
+        git(path, ["symbolic-ref", "HEAD", "refs/heads/main"])?;
+        Ok(GitRepo {
+            path: path.to_owned(),
+        })
+    })
+}
+
+fn git<I, S>(dir: &path::Path, args: I) -> Result<String, FixtureError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<ffi::OsStr>,
+{
+    let mut command = process::Command::new("git");
+    command
+        .args(["-c", "commit.gpgsign=false", "-c", "core.autocrlf=false"])
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("GIT_CONFIG_GLOBAL", null_device())
+        .envs(IDENTITY.iter().copied());
+    for var in REPO_VARS {
+        command.env_remove(var);
+    }
+    let output = command
+        .output()
+        .chain(FixtureError::new(FixtureKind::Git))
+        .map_err(|err| err.with_path(dir))?;
+    if !output.status.success() {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "`{:?}` failed with {}: {}",
+                command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        );
+        return Err(FixtureError::new(FixtureKind::Git)
+            .chain(err)
+            .with_path(dir));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A config file that's always empty, to ignore the user's `~/.gitconfig`.
+fn null_device() -> &'static str {
+    if cfg!(windows) {
+        "NUL"
+    } else {
+        "/dev/null"
+    }
+}
@@ -0,0 +1,291 @@
+use std::env;
+use std::ffi;
+use std::fs;
+use std::path;
+use std::process;
+use std::sync;
+use std::thread;
+
+use super::errors::*;
+
+/// Environment variables pointed at the sandbox's scratch directory.
+const TEMP_VARS: &[&str] = &["TMPDIR", "TEMP", "TMP"];
+
+/// Name of the scratch directory, within the fixture, used for temporary files.
+const TEMP_DIR: &str = ".tmp";
+
+/// Environment variables pointed at the sandbox's home directory, with [`Isolation::home`].
+const HOME_VARS: &[&str] = &["HOME", "USERPROFILE"];
+
+/// Name of the home directory, within the fixture.
+const HOME_DIR: &str = ".home";
+
+/// Only one thread at a time may own the process' working directory and environment.
+static LOCK: once_cell::sync::Lazy<EnvLock> = once_cell::sync::Lazy::new(|| EnvLock {
+    owner: sync::Mutex::new(None),
+    released: sync::Condvar::new(),
+});
+
+/// A lock the owning thread may take several times, so guards can be combined.
+struct EnvLock {
+    owner: sync::Mutex<Option<(thread::ThreadId, usize)>>,
+    released: sync::Condvar,
+}
+
+impl EnvLock {
+    fn lock(&'static self) -> EnvLockGuard {
+        let me = thread::current().id();
+        let mut owner = self.owner.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            match owner.as_mut() {
+                None => {
+                    *owner = Some((me, 1));
+                    break;
+                }
+                Some((id, count)) if *id == me => {
+                    *count += 1;
+                    break;
+                }
+                Some(_) => {
+                    owner = self.released.wait(owner).unwrap_or_else(|e| e.into_inner());
+                }
+            }
+        }
+        EnvLockGuard { lock: self }
+    }
+}
+
+struct EnvLockGuard {
+    lock: &'static EnvLock,
+}
+
+impl Drop for EnvLockGuard {
+    fn drop(&mut self) {
+        let mut owner = self.lock.owner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, count)) = owner.as_mut() {
+            *count -= 1;
+            if *count == 0 {
+                *owner = None;
+                self.lock.released.notify_all();
+            }
+        }
+    }
+}
+
+/// Run code as if the fixture were the whole machine.
+///
+/// Created with [`TempDir::isolation`][super::TempDir::isolation].  While running, the current
+/// directory is the fixture, `TMPDIR`, `TEMP` and `TMP` point to a `.tmp` directory within it,
+/// and any variables set with [`Isolation::env`] are applied.  Everything is restored
+/// afterwards, even if the closure panics.
+///
+/// The working directory and environment are process-wide, so isolated runs are serialized
+/// against each other, and must not be nested.  Code not going through `Isolation` can still
+/// observe the changes.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.isolation()
+///     .env("HOME", temp.path())
+///     .env_remove("XDG_CONFIG_HOME")
+///     .run(|| std::fs::write("out.txt", "data").unwrap())
+///     .unwrap();
+/// temp.child("out.txt").assert("data");
+/// temp.close().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Isolation<'a> {
+    root: &'a path::Path,
+    vars: Vec<(ffi::OsString, Option<ffi::OsString>)>,
+    home: bool,
+}
+
+impl<'a> Isolation<'a> {
+    pub(crate) fn new(root: &'a path::Path) -> Self {
+        Self {
+            root,
+            vars: Vec::new(),
+            home: false,
+        }
+    }
+
+    /// Point `HOME`, and `USERPROFILE` on Windows, at a `.home` directory within the fixture
+    /// while isolated.
+    ///
+    /// Keeps tools from reading, or writing, the real user's configuration.
+    pub fn home(mut self) -> Self {
+        self.home = true;
+        self
+    }
+
+    /// Set `key` to `value` while isolated.
+    pub fn env<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<ffi::OsStr>,
+        V: AsRef<ffi::OsStr>,
+    {
+        self.vars
+            .push((key.as_ref().to_owned(), Some(value.as_ref().to_owned())));
+        self
+    }
+
+    /// Unset `key` while isolated.
+    pub fn env_remove<K>(mut self, key: K) -> Self
+    where
+        K: AsRef<ffi::OsStr>,
+    {
+        self.vars.push((key.as_ref().to_owned(), None));
+        self
+    }
+
+    /// Run `f` within the sandbox, returning its result.
+    pub fn run<F, R>(&self, f: F) -> Result<R, FixtureError>
+    where
+        F: FnOnce() -> R,
+    {
+        let vars = self.vars()?;
+        let lock = LOCK.lock();
+        let cwd = env::current_dir().chain(FixtureError::new(FixtureKind::Isolate))?;
+        let mut restore = Restore {
+            cwd,
+            vars: Vec::new(),
+            _lock: lock,
+        };
+        env::set_current_dir(self.root).chain(FixtureError::new(FixtureKind::Isolate))?;
+        for (key, value) in vars {
+            restore.vars.push((key.clone(), env::var_os(&key)));
+            match value {
+                Some(value) => env::set_var(&key, value),
+                None => env::remove_var(&key),
+            }
+        }
+        Ok(f())
+    }
+
+    /// A [`Command`][std::process::Command] for `program` that will run within the sandbox.
+    ///
+    /// Unlike [`Isolation::run`], this leaves the current process alone.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let status = temp.isolation().command("make").unwrap().status().unwrap();
+    /// assert!(status.success());
+    /// temp.close().unwrap();
+    /// ```
+    pub fn command<S>(&self, program: S) -> Result<process::Command, FixtureError>
+    where
+        S: AsRef<ffi::OsStr>,
+    {
+        let mut command = process::Command::new(program);
+        command.current_dir(self.root);
+        for (key, value) in self.vars()? {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        Ok(command)
+    }
+
+    fn vars(&self) -> Result<Vec<(ffi::OsString, Option<ffi::OsString>)>, FixtureError> {
+        let temp = self.root.join(TEMP_DIR);
+        fs::create_dir_all(&temp).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        let mut vars: Vec<_> = TEMP_VARS
+            .iter()
+            .map(|key| {
+                (
+                    ffi::OsString::from(key),
+                    Some(temp.clone().into_os_string()),
+                )
+            })
+            .collect();
+        if self.home {
+            let home = self.root.join(HOME_DIR);
+            fs::create_dir_all(&home).chain(FixtureError::new(FixtureKind::CreateDir))?;
+            vars.extend(HOME_VARS.iter().map(|key| {
+                (
+                    ffi::OsString::from(key),
+                    Some(home.clone().into_os_string()),
+                )
+            }));
+        }
+        vars.extend(self.vars.iter().cloned());
+        Ok(vars)
+    }
+}
+
+struct Restore {
+    cwd: path::PathBuf,
+    vars: Vec<(ffi::OsString, Option<ffi::OsString>)>,
+    _lock: EnvLockGuard,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        // In reverse, so a variable set twice ends up with its original value
+        for (key, value) in self.vars.drain(..).rev() {
+            match value {
+                Some(value) => env::set_var(&key, value),
+                None => env::remove_var(&key),
+            }
+        }
+        let _ = env::set_current_dir(&self.cwd);
+    }
+}
+
+/// An environment variable set for as long as the guard lives.
+///
+/// Created with [`TempDir::env_guard`][super::TempDir::env_guard].  The previous value is
+/// restored on drop, even if the test panics.
+///
+/// The environment is process-wide, so while any guard is alive, other threads creating guards
+/// or running [`Isolation::run`] wait for it to be dropped.  One thread may hold several guards
+/// at once.  Code not going through `assert_fs` can still observe the changes.
+#[must_use = "the variable is restored when the guard is dropped"]
+pub struct EnvGuard {
+    key: ffi::OsString,
+    previous: Option<ffi::OsString>,
+    _lock: EnvLockGuard,
+}
+
+impl EnvGuard {
+    pub(crate) fn set(key: &ffi::OsStr, value: &ffi::OsStr) -> Self {
+        let lock = LOCK.lock();
+        let previous = env::var_os(key);
+        env::set_var(key, value);
+        Self {
+            key: key.to_owned(),
+            previous,
+            _lock: lock,
+        }
+    }
+
+    /// The variable's value before the guard was created.
+    pub fn previous(&self) -> Option<&ffi::OsStr> {
+        self.previous.as_deref()
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        match self.previous.take() {
+            Some(value) => env::set_var(&self.key, value),
+            None => env::remove_var(&self.key),
+        }
+    }
+}
+
+impl std::fmt::Debug for EnvGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnvGuard")
+            .field("key", &self.key)
+            .field("previous", &self.previous)
+            .finish()
+    }
+}
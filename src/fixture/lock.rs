@@ -0,0 +1,197 @@
+use std::fs;
+use std::io;
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::NamedTempFile;
+
+/// Hold a lock on a file at [`ChildPath`] or [`NamedTempFile`] (requires the `lock` feature).
+///
+/// Locks are advisory `flock` locks on Unix and `LockFileEx` locks on Windows, held on a handle
+/// of their own, so the code under test sees them whether it runs in this process or another.
+/// Only code using the same kind of lock sees them: on Unix, `fcntl` locks are separate.
+///
+/// Locking never waits: it fails if the file is already locked in a conflicting way, rather
+/// than hang the test.
+///
+/// See [`PathAssertLock`][crate::assert::PathAssertLock] to check whether a file is locked.
+pub trait FileLock {
+    /// Lock the file for exclusive use, creating it and any missing parent directories.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let lock_file = temp.child("app.lock");
+    /// let guard = lock_file.lock_exclusive().unwrap();
+    /// // ... run the code under test, which should report the lock as taken ...
+    /// assert!(lock_file.lock_shared().is_err());
+    /// drop(guard);
+    /// lock_file.assert_unlocked();
+    /// temp.close().unwrap();
+    /// ```
+    fn lock_exclusive(&self) -> Result<LockGuard, FixtureError>;
+
+    /// Lock the file for shared use, creating it and any missing parent directories.
+    ///
+    /// Other shared locks can be held alongside it.
+    fn lock_shared(&self) -> Result<LockGuard, FixtureError>;
+}
+
+impl FileLock for ChildPath {
+    fn lock_exclusive(&self) -> Result<LockGuard, FixtureError> {
+        lock(self.path(), true)
+    }
+
+    fn lock_shared(&self) -> Result<LockGuard, FixtureError> {
+        lock(self.path(), false)
+    }
+}
+
+impl FileLock for NamedTempFile {
+    fn lock_exclusive(&self) -> Result<LockGuard, FixtureError> {
+        lock(self.path(), true)
+    }
+
+    fn lock_shared(&self) -> Result<LockGuard, FixtureError> {
+        lock(self.path(), false)
+    }
+}
+
+/// A lock taken by [`FileLock`], released when dropped.
+#[derive(Debug)]
+pub struct LockGuard {
+    path: path::PathBuf,
+    exclusive: bool,
+    // Closing the handle releases the lock
+    _file: fs::File,
+}
+
+impl LockGuard {
+    /// The locked file.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    /// Whether the lock is exclusive, rather than shared.
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+}
+
+fn lock(path: &path::Path, exclusive: bool) -> Result<LockGuard, FixtureError> {
+    let kind = if exclusive {
+        OperationKind::LockExclusive
+    } else {
+        OperationKind::LockShared
+    };
+    record(kind, path, None, || {
+        super::tools::ensure_parent_dir(path)?;
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .chain(FixtureError::new(FixtureKind::Lock))?;
+        if !try_lock(&file, exclusive).chain(FixtureError::new(FixtureKind::Lock))? {
+            let err = io::Error::new(io::ErrorKind::WouldBlock, "the file is already locked");
+            return Err(FixtureError::new(FixtureKind::Lock).chain(err));
+        }
+        Ok(LockGuard {
+            path: path.to_owned(),
+            exclusive,
+            _file: file,
+        })
+    })
+}
+
+/// Whether anything holds a lock on `path`, which isn't locked if missing.
+pub(crate) fn is_locked(path: &path::Path) -> Result<bool, FixtureError> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => {
+            return Err(FixtureError::new(FixtureKind::Lock)
+                .chain(err)
+                .with_path(path))
+        }
+    };
+    // Released again when `file` is closed
+    try_lock(&file, true)
+        .map(|locked| !locked)
+        .chain(FixtureError::new(FixtureKind::Lock))
+        .map_err(|err| err.with_path(path))
+}
+
+/// Lock `file` without waiting, returning whether it was locked.
+#[cfg(unix)]
+fn try_lock(file: &fs::File, exclusive: bool) -> io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    let operation = if exclusive {
+        libc::LOCK_EX
+    } else {
+        libc::LOCK_SH
+    };
+    // SAFETY: the descriptor is open for the duration of the call.
+    let ret = unsafe { libc::flock(file.as_raw_fd(), operation | libc::LOCK_NB) };
+    if ret == 0 {
+        return Ok(true);
+    }
+    let err = io::Error::last_os_error();
+    match err.kind() {
+        io::ErrorKind::WouldBlock => Ok(false),
+        _ => Err(err),
+    }
+}
+
+/// Lock `file` without waiting, returning whether it was locked.
+#[cfg(windows)]
+fn try_lock(file: &fs::File, exclusive: bool) -> io::Result<bool> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Foundation::ERROR_LOCK_VIOLATION;
+    use windows_sys::Win32::Storage::FileSystem::{
+        LockFileEx, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY,
+    };
+    use windows_sys::Win32::System::IO::OVERLAPPED;
+
+    let mut flags = LOCKFILE_FAIL_IMMEDIATELY;
+    if exclusive {
+        flags |= LOCKFILE_EXCLUSIVE_LOCK;
+    }
+    // SAFETY: an all-zero `OVERLAPPED` locks from offset 0.
+    let mut overlapped: OVERLAPPED = unsafe { std::mem::zeroed() };
+    // SAFETY: the handle is open for the duration of the call, and the whole file is locked.
+    let ret = unsafe {
+        LockFileEx(
+            file.as_raw_handle() as _,
+            flags,
+            0,
+            u32::MAX,
+            u32::MAX,
+            &mut overlapped,
+        )
+    };
+    if ret != 0 {
+        return Ok(true);
+    }
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(code) if code == ERROR_LOCK_VIOLATION as i32 => Ok(false),
+        _ => Err(err),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn try_lock(_file: &fs::File, _exclusive: bool) -> io::Result<bool> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "file locks are unsupported on this platform",
+    ))
+}
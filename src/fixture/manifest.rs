@@ -0,0 +1,438 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path;
+
+use sha2::Digest;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::TempDir;
+
+/// The first line of every manifest.
+const HEADER: &str = "# assert_fs manifest v1";
+
+/// Record a directory tree as a manifest: one line per entry, with its kind, mode, size, and
+/// content hash.
+///
+/// A manifest is a compact stand-in for a golden tree, written while authoring a fixture and
+/// checked in, then verified with
+/// [`PathAssertTree::assert_manifest`][crate::assert::PathAssertTree::assert_manifest], without
+/// committing every file of the tree.
+///
+/// Each line is tab-separated: `file`, `dir`, or `link`, the permission bits in octal on Unix,
+/// the size, the SHA-256 of the content, and the path relative to the root with `/`
+/// separators, plus the target for symlinks.  Fields that don't apply are `-`.  Lines are
+/// sorted by path, so manifests diff well.
+pub trait PathManifest {
+    /// The manifest of the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("src/lib.rs").write_str("pub fn answer() {}\n").unwrap();
+    /// let manifest = temp.manifest().unwrap();
+    /// assert!(manifest.lines().any(|line| line.ends_with("\tsrc/lib.rs")));
+    /// temp.close().unwrap();
+    /// ```
+    fn manifest(&self) -> Result<String, FixtureError>;
+
+    /// Write the manifest of the tree to `path`, creating any missing parent directories.
+    ///
+    /// When `path` is within the tree, the manifest leaves itself out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let out = temp.child("out");
+    /// out.child("report.txt").write_str("ok\n").unwrap();
+    /// let manifest = temp.child("expected.manifest");
+    /// out.write_manifest(&manifest).unwrap();
+    ///
+    /// out.assert_manifest(&manifest);
+    /// temp.close().unwrap();
+    /// ```
+    fn write_manifest<P>(&self, path: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>;
+}
+
+impl PathManifest for TempDir {
+    fn manifest(&self) -> Result<String, FixtureError> {
+        tree_manifest(self.path(), None).map(|entries| render(&entries))
+    }
+
+    fn write_manifest<P>(&self, path: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        write_manifest(self.path(), path.as_ref())
+    }
+}
+
+impl PathManifest for ChildPath {
+    fn manifest(&self) -> Result<String, FixtureError> {
+        tree_manifest(self.path(), None).map(|entries| render(&entries))
+    }
+
+    fn write_manifest<P>(&self, path: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        write_manifest(self.path(), path.as_ref())
+    }
+}
+
+/// An entry of a manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ManifestEntry {
+    kind: &'static str,
+    mode: Option<u32>,
+    size: Option<u64>,
+    sha256: Option<String>,
+    target: Option<String>,
+}
+
+impl ManifestEntry {
+    /// How `self` differs from `expected`, one description per field.
+    ///
+    /// Modes are only compared when both sides have one.
+    pub(crate) fn differences(&self, expected: &Self) -> Vec<String> {
+        if self.kind != expected.kind {
+            return vec![format!("{}, expected {}", self.kind, expected.kind)];
+        }
+        let mut differences = Vec::new();
+        if let (Some(actual), Some(expected)) = (self.mode, expected.mode) {
+            if actual != expected {
+                differences.push(format!("mode {:o}, expected {:o}", actual, expected));
+            }
+        }
+        if self.size != expected.size {
+            differences.push(format!(
+                "size {}, expected {}",
+                field(&self.size),
+                field(&expected.size)
+            ));
+        } else if self.sha256 != expected.sha256 {
+            differences.push("content".to_owned());
+        }
+        if self.target != expected.target {
+            differences.push(format!(
+                "links to `{}`, expected `{}`",
+                field(&self.target),
+                field(&expected.target)
+            ));
+        }
+        differences
+    }
+}
+
+pub(crate) fn write_manifest(root: &path::Path, path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::WriteManifest, path, None, || {
+        let manifest = render(&tree_manifest(root, Some(path))?);
+        super::tools::ensure_parent_dir(path)?;
+        fs::write(path, manifest).chain(FixtureError::new(FixtureKind::WriteFile))
+    })
+}
+
+/// The entries of the tree at `root`, by path relative to it, leaving out `exclude`.
+pub(crate) fn tree_manifest(
+    root: &path::Path,
+    exclude: Option<&path::Path>,
+) -> Result<BTreeMap<String, ManifestEntry>, FixtureError> {
+    let exclude = match exclude {
+        Some(exclude) if exclude.is_relative() => {
+            std::env::current_dir().ok().map(|cwd| cwd.join(exclude))
+        }
+        exclude => exclude.map(path::Path::to_owned),
+    };
+    let mut entries = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root).min_depth(1) {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        let path = entry.path();
+        if exclude.as_deref() == Some(path) {
+            continue;
+        }
+        let rel = path
+            .strip_prefix(root)
+            .expect("walked entries are within the root");
+        let name = rel
+            .components()
+            .map(|c| {
+                c.as_os_str().to_str().ok_or_else(|| {
+                    let err = io::Error::new(io::ErrorKind::InvalidData, "the name isn't UTF-8");
+                    FixtureError::new(FixtureKind::ReadFile)
+                        .chain(err)
+                        .with_path(path)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join("/");
+        let manifest_entry = describe(path, &entry)
+            .chain(FixtureError::new(FixtureKind::ReadFile))
+            .map_err(|err| err.with_path(path))?;
+        entries.insert(name, manifest_entry);
+    }
+    Ok(entries)
+}
+
+fn describe(path: &path::Path, entry: &walkdir::DirEntry) -> io::Result<ManifestEntry> {
+    let metadata = entry.metadata()?;
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        let target = fs::read_link(path)?;
+        return Ok(ManifestEntry {
+            kind: "link",
+            mode: None,
+            size: None,
+            sha256: None,
+            target: Some(target.to_string_lossy().replace('\\', "/")),
+        });
+    }
+    let mode = mode(&metadata);
+    if file_type.is_dir() {
+        return Ok(ManifestEntry {
+            kind: "dir",
+            mode,
+            size: None,
+            sha256: None,
+            target: None,
+        });
+    }
+    let mut hasher = sha2::Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    let sha256 = crate::hex::encode(&hasher.finalize());
+    Ok(ManifestEntry {
+        kind: "file",
+        mode,
+        size: Some(metadata.len()),
+        sha256: Some(sha256),
+        target: None,
+    })
+}
+
+#[cfg(unix)]
+fn mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+fn render(entries: &BTreeMap<String, ManifestEntry>) -> String {
+    let mut manifest = format!("{}\n", HEADER);
+    for (name, entry) in entries {
+        let mode = entry.mode.map(|mode| format!("{:o}", mode));
+        let _ = write!(
+            manifest,
+            "{}\t{}\t{}\t{}\t{}",
+            entry.kind,
+            field(&mode),
+            field(&entry.size),
+            field(&entry.sha256),
+            escape(name)
+        );
+        if let Some(target) = &entry.target {
+            let _ = write!(manifest, "\t{}", escape(target));
+        }
+        manifest.push('\n');
+    }
+    manifest
+}
+
+/// Parse the manifest at `path`.
+pub(crate) fn read_manifest(
+    path: &path::Path,
+) -> Result<BTreeMap<String, ManifestEntry>, FixtureError> {
+    let manifest = fs::read_to_string(path)
+        .chain(FixtureError::new(FixtureKind::ReadFile))
+        .map_err(|err| err.with_path(path))?;
+    parse(&manifest)
+        .chain(FixtureError::new(FixtureKind::Spec))
+        .map_err(|err| err.with_path(path))
+}
+
+fn parse(manifest: &str) -> io::Result<BTreeMap<String, ManifestEntry>> {
+    let mut lines = manifest.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim_end() == HEADER => {}
+        _ => return Err(invalid(0, "missing manifest header")),
+    }
+    let mut entries = BTreeMap::new();
+    for (i, line) in lines {
+        // Tolerate manifests checked out with `\r\n`
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<_> = line.split('\t').collect();
+        let kind = match fields[0] {
+            "file" => "file",
+            "dir" => "dir",
+            "link" => "link",
+            _ => return Err(invalid(i, "unknown entry kind")),
+        };
+        let expected_fields = if kind == "link" { 6 } else { 5 };
+        if fields.len() != expected_fields {
+            return Err(invalid(i, "wrong number of fields"));
+        }
+        let mode = optional(fields[1])
+            .map(|mode| u32::from_str_radix(mode, 8))
+            .transpose()
+            .map_err(|_| invalid(i, "invalid mode"))?;
+        let size = optional(fields[2])
+            .map(str::parse)
+            .transpose()
+            .map_err(|_| invalid(i, "invalid size"))?;
+        let entry = ManifestEntry {
+            kind,
+            mode,
+            size,
+            sha256: optional(fields[3]).map(str::to_owned),
+            target: fields.get(5).map(|target| unescape(target)),
+        };
+        entries.insert(unescape(fields[4]), entry);
+    }
+    Ok(entries)
+}
+
+fn invalid(line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line + 1, message),
+    )
+}
+
+fn optional(field: &str) -> Option<&str> {
+    if field == "-" {
+        None
+    } else {
+        Some(field)
+    }
+}
+
+fn field<T>(value: &Option<T>) -> String
+where
+    T: std::fmt::Display,
+{
+    match value {
+        Some(value) => value.to_string(),
+        None => "-".to_owned(),
+    }
+}
+
+/// `name` with the characters separating fields and lines escaped.
+fn escape(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(escaped: &str) -> String {
+    let mut name = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            name.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => name.push('\t'),
+            Some('n') => name.push('\n'),
+            Some('r') => name.push('\r'),
+            Some(c) => name.push(c),
+            None => name.push('\\'),
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_parse_round_trip() {
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            "a\tb.txt".to_owned(),
+            ManifestEntry {
+                kind: "file",
+                mode: Some(0o644),
+                size: Some(3),
+                sha256: Some("abc".to_owned()),
+                target: None,
+            },
+        );
+        entries.insert(
+            "dir".to_owned(),
+            ManifestEntry {
+                kind: "dir",
+                mode: None,
+                size: None,
+                sha256: None,
+                target: None,
+            },
+        );
+        entries.insert(
+            "dir/link".to_owned(),
+            ManifestEntry {
+                kind: "link",
+                mode: None,
+                size: None,
+                sha256: None,
+                target: Some("../a\\b".to_owned()),
+            },
+        );
+        let manifest = render(&entries);
+        assert!(
+            manifest.contains("file\t644\t3\tabc\ta\\tb.txt\n"),
+            "{}",
+            manifest
+        );
+        assert_eq!(parse(&manifest).unwrap(), entries);
+        assert!(parse("file\t-\t-\t-\ta\n").is_err());
+    }
+
+    #[test]
+    fn differences_describe_fields() {
+        let file = ManifestEntry {
+            kind: "file",
+            mode: Some(0o644),
+            size: Some(3),
+            sha256: Some("abc".to_owned()),
+            target: None,
+        };
+        let mut changed = file.clone();
+        changed.mode = Some(0o755);
+        changed.sha256 = Some("def".to_owned());
+        assert_eq!(
+            changed.differences(&file),
+            vec!["mode 755, expected 644", "content"]
+        );
+        changed.mode = None;
+        changed.size = Some(4);
+        assert_eq!(changed.differences(&file), vec!["size 4, expected 3"]);
+    }
+}
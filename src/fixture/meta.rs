@@ -0,0 +1,221 @@
+use std::fs;
+use std::io;
+use std::path;
+use std::time;
+
+use sha2::Digest;
+
+use super::errors::*;
+use super::ChildPath;
+use super::NamedTempFile;
+use super::TempDir;
+
+/// Capture the metadata of a path as a plain value.
+///
+/// For assertions spanning several steps, like "the file was rewritten, but kept its mode",
+/// without re-querying [`std::fs`] and handling each platform's quirks at every step.
+///
+pub trait PathMetadataSnapshot {
+    /// The metadata of the path, without following a final symlink.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("config.toml");
+    /// config.write_str("a = 1\n").unwrap();
+    /// let before = config.metadata_snapshot().unwrap().hashed().unwrap();
+    ///
+    /// // ... run the code under test ...
+    /// config.write_str("a = 2\n").unwrap();
+    ///
+    /// let after = config.metadata_snapshot().unwrap().hashed().unwrap();
+    /// assert_eq!(before.mode(), after.mode());
+    /// assert!(before.differences(&after).contains(&"sha256"));
+    /// temp.close().unwrap();
+    /// ```
+    fn metadata_snapshot(&self) -> Result<FileMeta, FixtureError>;
+}
+
+impl PathMetadataSnapshot for TempDir {
+    fn metadata_snapshot(&self) -> Result<FileMeta, FixtureError> {
+        FileMeta::capture(self.path())
+    }
+}
+
+impl PathMetadataSnapshot for ChildPath {
+    fn metadata_snapshot(&self) -> Result<FileMeta, FixtureError> {
+        FileMeta::capture(self.path())
+    }
+}
+
+impl PathMetadataSnapshot for NamedTempFile {
+    fn metadata_snapshot(&self) -> Result<FileMeta, FixtureError> {
+        FileMeta::capture(self.path())
+    }
+}
+
+/// The kind of entry a [`FileMeta`] describes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum EntryKind {
+    /// A regular file.
+    File,
+    /// A directory.
+    Dir,
+    /// A symbolic link.
+    Symlink,
+    /// Anything else, like a socket or a device.
+    Other,
+}
+
+/// The metadata of a path, captured at one point in time.
+///
+/// Created with [`PathMetadataSnapshot::metadata_snapshot`].  Values can be compared, and,
+/// with the `serde` feature, serialized, e.g. to check metadata against a previous run.  The
+/// path itself isn't part of the value, so the metadata of different paths can be compared.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileMeta {
+    kind: EntryKind,
+    len: u64,
+    mode: Option<u32>,
+    modified: Option<time::SystemTime>,
+    link_target: Option<path::PathBuf>,
+    sha256: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    path: path::PathBuf,
+}
+
+impl FileMeta {
+    /// Capture the metadata of `path`, without following a final symlink.
+    pub fn capture(path: &path::Path) -> Result<Self, FixtureError> {
+        let metadata =
+            fs::symlink_metadata(path).chain(FixtureError::new(FixtureKind::ReadFile))?;
+        let file_type = metadata.file_type();
+        let kind = if file_type.is_symlink() {
+            EntryKind::Symlink
+        } else if file_type.is_dir() {
+            EntryKind::Dir
+        } else if file_type.is_file() {
+            EntryKind::File
+        } else {
+            EntryKind::Other
+        };
+        let link_target = if kind == EntryKind::Symlink {
+            Some(fs::read_link(path).chain(FixtureError::new(FixtureKind::ReadFile))?)
+        } else {
+            None
+        };
+        Ok(Self {
+            kind,
+            len: metadata.len(),
+            mode: mode(&metadata),
+            modified: metadata.modified().ok(),
+            link_target,
+            sha256: None,
+            path: path.to_owned(),
+        })
+    }
+
+    /// Also capture the SHA-256 of the file's content.
+    ///
+    /// Content is only hashed on request, as reading large files is slow.  Entries other than
+    /// files are left unchanged.
+    pub fn hashed(mut self) -> Result<Self, FixtureError> {
+        if self.kind == EntryKind::File {
+            let mut file =
+                fs::File::open(&self.path).chain(FixtureError::new(FixtureKind::ReadFile))?;
+            let mut hasher = sha2::Sha256::new();
+            io::copy(&mut file, &mut hasher).chain(FixtureError::new(FixtureKind::ReadFile))?;
+            self.sha256 = Some(crate::hex::encode(&hasher.finalize()));
+        }
+        Ok(self)
+    }
+
+    /// What kind of entry this is.
+    pub fn kind(&self) -> EntryKind {
+        self.kind
+    }
+
+    /// The length in bytes, as reported by the filesystem.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the length is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The permission bits, on Unix.
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    /// When the entry was last modified, if the platform reports it.
+    pub fn modified(&self) -> Option<time::SystemTime> {
+        self.modified
+    }
+
+    /// Where a symlink points.
+    pub fn link_target(&self) -> Option<&path::Path> {
+        self.link_target.as_deref()
+    }
+
+    /// The SHA-256 of a file's content, if captured with [`FileMeta::hashed`].
+    pub fn sha256(&self) -> Option<&str> {
+        self.sha256.as_deref()
+    }
+
+    /// The names of the fields that differ from `other`, for failure messages.
+    ///
+    /// Hashes are only compared when both sides have one.
+    pub fn differences(&self, other: &FileMeta) -> Vec<&'static str> {
+        let mut differences = Vec::new();
+        if self.kind != other.kind {
+            differences.push("kind");
+        }
+        if self.len != other.len {
+            differences.push("len");
+        }
+        if self.mode != other.mode {
+            differences.push("mode");
+        }
+        if self.modified != other.modified {
+            differences.push("modified");
+        }
+        if self.link_target != other.link_target {
+            differences.push("link_target");
+        }
+        if let (Some(hash), Some(other)) = (&self.sha256, &other.sha256) {
+            if hash != other {
+                differences.push("sha256");
+            }
+        }
+        differences
+    }
+}
+
+impl PartialEq for FileMeta {
+    fn eq(&self, other: &Self) -> bool {
+        self.differences(other).is_empty() && self.sha256.is_some() == other.sha256.is_some()
+    }
+}
+
+impl Eq for FileMeta {}
+
+#[cfg(unix)]
+fn mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
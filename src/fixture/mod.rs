@@ -1,13 +1,123 @@
 //! Initialize the filesystem to use as test fixtures.
 
+#[cfg(any(feature = "archive-tar", feature = "archive-zip"))]
+mod archive;
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "xattr")]
+mod attrs;
+mod build;
+mod cache;
 mod child;
+mod clock;
+mod contend;
+mod context;
+mod corpus;
+mod cross_fs;
+mod dedup;
 mod dir;
 mod errors;
+pub mod fault;
 mod file;
+mod generate;
+#[cfg(feature = "git")]
+mod git;
+mod isolate;
+#[cfg(feature = "lock")]
+mod lock;
+mod manifest;
+mod meta;
+pub mod names;
+mod naming;
+mod oplog;
+pub mod ops;
+mod panic;
+mod progress;
+#[cfg(feature = "proptest")]
+mod random_tree;
+mod readonly;
+#[cfg(feature = "glob")]
+mod remap;
+mod retry;
+mod session;
+mod set;
+#[cfg(feature = "small-fs")]
+mod sized;
+mod slow;
+mod snapshot;
+mod sparse;
+#[cfg(feature = "spec-json")]
+mod spec;
+#[cfg(unix)]
+mod special;
+mod store;
+mod stress;
+mod template;
+mod throttle;
 mod tools;
+#[cfg(feature = "tracing")]
+mod trace;
+mod tree;
+mod variant;
+#[cfg(feature = "glob")]
+mod wait;
+#[cfg(feature = "watch")]
+mod watch;
+mod workspace;
 
+#[cfg(any(feature = "archive-tar", feature = "archive-zip"))]
+pub use self::archive::*;
+#[cfg(feature = "async")]
+pub use self::asynchronous::*;
+#[cfg(feature = "xattr")]
+pub use self::attrs::*;
+pub use self::build::*;
+pub use self::cache::*;
 pub use self::child::*;
+pub use self::clock::*;
+pub use self::contend::*;
+pub use self::corpus::*;
+pub use self::cross_fs::*;
+pub use self::dedup::*;
 pub use self::dir::*;
 pub use self::errors::*;
 pub use self::file::*;
+pub use self::generate::*;
+#[cfg(feature = "git")]
+pub use self::git::*;
+pub use self::isolate::*;
+#[cfg(feature = "lock")]
+pub use self::lock::*;
+pub use self::manifest::*;
+pub use self::meta::*;
+pub use self::naming::*;
+pub use self::oplog::*;
+pub use self::panic::*;
+pub use self::progress::*;
+#[cfg(feature = "proptest")]
+pub use self::random_tree::*;
+pub use self::readonly::*;
+#[cfg(feature = "glob")]
+pub use self::remap::*;
+pub use self::retry::*;
+pub use self::set::*;
+#[cfg(feature = "small-fs")]
+pub use self::sized::*;
+pub use self::slow::*;
+pub use self::snapshot::*;
+pub use self::sparse::*;
+#[cfg(feature = "spec-json")]
+pub use self::spec::*;
+#[cfg(unix)]
+pub use self::special::*;
+pub use self::store::*;
+pub use self::stress::*;
+pub use self::template::*;
 pub use self::tools::*;
+pub use self::tree::*;
+pub use self::variant::*;
+#[cfg(feature = "glob")]
+pub use self::wait::*;
+#[cfg(feature = "watch")]
+pub use self::watch::*;
+pub use self::workspace::*;
@@ -0,0 +1,185 @@
+//! Create children with names that tend to break path handling.
+//!
+//! Each function creates an empty file within `parent`, creating `parent` if needed, and returns
+//! its path.  When the platform or filesystem can't store the name as given, it fails with an
+//! [`Unsupported`][std::io::ErrorKind::Unsupported] cause rather than creating something else.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use assert_fs::fixture::names;
+//! use assert_fs::prelude::*;
+//!
+//! let temp = assert_fs::TempDir::new().unwrap();
+//! let emoji = names::emoji(&temp).unwrap();
+//! match names::non_utf8(&temp) {
+//!     Ok(file) => assert!(file.path().to_str().is_none()),
+//!     // e.g. Windows or APFS
+//!     Err(err) => println!("skipping: {}", err),
+//! }
+//!
+//! // ... run the tool over `temp`, expecting it to list both ...
+//! # let _ = emoji;
+//! ```
+
+use std::ffi;
+use std::fs;
+use std::path;
+
+use super::errors::*;
+use super::ChildPath;
+
+/// The length, in bytes, of paths created by [`long_path`], beyond Windows' `MAX_PATH` of 260.
+pub const LONG_PATH_LEN: usize = 300;
+
+/// Create `{stem} `, with a trailing space, which Windows normally strips.
+///
+/// On Windows, the file is created through a `\\?\` path, which keeps the space.
+pub fn trailing_space<P>(parent: P, stem: &str) -> Result<ChildPath, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    create(parent.as_ref(), format!("{} ", stem).as_ref())
+}
+
+/// Create `{stem}.`, with a trailing dot, which Windows normally strips.
+///
+/// On Windows, the file is created through a `\\?\` path, which keeps the dot.
+pub fn trailing_dot<P>(parent: P, stem: &str) -> Result<ChildPath, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    create(parent.as_ref(), format!("{}.", stem).as_ref())
+}
+
+/// Create a file whose name contains bytes that aren't valid UTF-8.
+///
+/// # Errors
+///
+/// Unsupported outside of Unix, see
+/// [`PathStress::make_non_unicode_file`][super::PathStress::make_non_unicode_file] for an
+/// unpaired surrogate on Windows.  Filesystems that only store Unicode names, like APFS, reject
+/// the name.
+pub fn non_utf8<P>(parent: P) -> Result<ChildPath, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    let parent = parent.as_ref();
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        create(parent, ffi::OsStr::from_bytes(b"latin1-caf\xe9.txt"))
+    }
+    #[cfg(not(unix))]
+    {
+        Err(unsupported(
+            parent,
+            "non-UTF-8 names are only supported on Unix",
+        ))
+    }
+}
+
+/// Create a file whose path is [`LONG_PATH_LEN`] bytes, nesting directories as needed.
+///
+/// On Windows, the file is created through a `\\?\` path, which isn't limited to `MAX_PATH`.
+///
+/// # Errors
+///
+/// Fails if `parent` is already longer than [`LONG_PATH_LEN`].
+pub fn long_path<P>(parent: P) -> Result<ChildPath, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    let parent = parent.as_ref();
+    fs::create_dir_all(parent)
+        .chain(FixtureError::new(FixtureKind::CreateDir))
+        .map_err(|err| err.with_path(parent))?;
+    let parent = verbatim(parent)?;
+    super::stress::make_path_of_len(&parent, LONG_PATH_LEN)
+}
+
+/// Create a file named with emoji, including a multi-codepoint ZWJ sequence.
+pub fn emoji<P>(parent: P) -> Result<ChildPath, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    create(
+        parent.as_ref(),
+        "emoji-\u{1f600}-\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f467}.txt".as_ref(),
+    )
+}
+
+/// Create `café.txt` twice, NFC then NFD normalized, returning both.
+///
+/// # Errors
+///
+/// Unsupported on filesystems that normalize names, like HFS+, or treat both forms as the same
+/// name, like APFS.
+pub fn normalization_pair<P>(parent: P) -> Result<(ChildPath, ChildPath), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    let parent = parent.as_ref();
+    let nfc = create(parent, "caf\u{e9}.txt".as_ref())?;
+    let nfd_name = "cafe\u{301}.txt";
+    let result = if nfc.path().with_file_name(nfd_name).exists() {
+        Err(unsupported(
+            nfc.path(),
+            "the filesystem doesn't distinguish NFC and NFD names",
+        ))
+    } else {
+        create(parent, nfd_name.as_ref())
+    };
+    match result {
+        Ok(nfd) => Ok((nfc, nfd)),
+        Err(err) => {
+            let _ = fs::remove_file(nfc.path());
+            Err(err)
+        }
+    }
+}
+
+/// Create `name` within `parent`, checking it's stored as given.
+fn create(parent: &path::Path, name: &ffi::OsStr) -> Result<ChildPath, FixtureError> {
+    fs::create_dir_all(parent)
+        .chain(FixtureError::new(FixtureKind::CreateDir))
+        .map_err(|err| err.with_path(parent))?;
+    let parent = verbatim(parent)?;
+    let path = parent.join(name);
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .chain(FixtureError::new(FixtureKind::WriteFile))
+        .map_err(|err| err.with_path(&path))?;
+    // Windows may trim the name, and HFS+ normalizes it, so check what actually landed
+    let listed = fs::read_dir(&parent)
+        .map(|entries| entries.flatten().any(|entry| entry.file_name() == name))
+        .unwrap_or(false);
+    if !listed {
+        let _ = fs::remove_file(&path);
+        return Err(unsupported(&path, "the filesystem altered the name"));
+    }
+    Ok(ChildPath::new(path))
+}
+
+/// `path` in a form that bypasses Windows' name and length normalization.
+#[cfg(windows)]
+fn verbatim(path: &path::Path) -> Result<path::PathBuf, FixtureError> {
+    path.canonicalize()
+        .chain(FixtureError::new(FixtureKind::CreateDir))
+        .map_err(|err| err.with_path(path))
+}
+
+#[cfg(not(windows))]
+fn verbatim(path: &path::Path) -> Result<path::PathBuf, FixtureError> {
+    Ok(path.to_owned())
+}
+
+fn unsupported(path: &path::Path, message: &str) -> FixtureError {
+    let err = std::io::Error::new(std::io::ErrorKind::Unsupported, message);
+    FixtureError::new(FixtureKind::WriteFile)
+        .chain(err)
+        .with_path(path)
+}
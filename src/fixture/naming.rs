@@ -0,0 +1,125 @@
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::path;
+use std::sync;
+
+/// How [`PathChild::unique_child`][super::PathChild::unique_child] generates names.
+///
+/// Set for a fixture with [`TempDir::set_name_policy`][super::TempDir::set_name_policy].  The
+/// default, [`NamePolicy::random`], avoids collisions between runs; [`NamePolicy::counter`] and
+/// [`NamePolicy::seeded`] make generated names deterministic, so output trees containing them
+/// can be snapshotted.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::fixture::NamePolicy;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.set_name_policy(NamePolicy::counter().prefix("upload-"));
+/// let first = temp.unique_child(".bin");
+/// assert_eq!(first.file_name().unwrap(), "upload-0.bin");
+/// temp.close().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct NamePolicy {
+    prefix: String,
+    kind: Kind,
+}
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Random,
+    Counter(u64),
+    Seeded(u64),
+}
+
+impl NamePolicy {
+    /// Random names, differing between runs.
+    pub fn random() -> Self {
+        Self {
+            prefix: String::new(),
+            kind: Kind::Random,
+        }
+    }
+
+    /// Sequential names: `0`, `1`, `2`, ...
+    pub fn counter() -> Self {
+        Self {
+            prefix: String::new(),
+            kind: Kind::Counter(0),
+        }
+    }
+
+    /// Random-looking names, always the same sequence for the same `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            prefix: String::new(),
+            kind: Kind::Seeded(seed),
+        }
+    }
+
+    /// Start every name with `prefix`.
+    pub fn prefix<S>(mut self, prefix: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn next(&mut self) -> String {
+        let id = match self.kind {
+            Kind::Random => {
+                let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+                hasher.write_u64(0);
+                format!("{:016x}", hasher.finish())
+            }
+            Kind::Counter(ref mut next) => {
+                let id = next.to_string();
+                *next += 1;
+                id
+            }
+            Kind::Seeded(ref mut state) => format!("{:016x}", splitmix64(state)),
+        };
+        format!("{}{}", self.prefix, id)
+    }
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
+pub(super) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+pub(crate) type Policy = sync::Mutex<NamePolicy>;
+
+/// Generate a name for a new entry in `dir`, using the policy of the innermost fixture
+/// containing it.
+pub(crate) fn unique_child(dir: &path::Path, suffix: &str) -> path::PathBuf {
+    let context = super::context::innermost(dir);
+    let mut fallback = NamePolicy::default();
+    loop {
+        let name = match context {
+            Some(ref context) => context
+                .names
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .next(),
+            None => fallback.next(),
+        };
+        let path = dir.join(format!("{}{}", name, suffix));
+        if path.symlink_metadata().is_err() {
+            return path;
+        }
+    }
+}
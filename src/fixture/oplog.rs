@@ -0,0 +1,305 @@
+use std::fmt;
+use std::path;
+use std::sync;
+use std::time;
+
+use super::errors::*;
+
+/// A fixture operation, as recorded in [`TempDir::operation_log`][super::TempDir::operation_log].
+#[derive(Clone, Debug)]
+pub struct Operation {
+    kind: OperationKind,
+    path: path::PathBuf,
+    bytes: Option<u64>,
+    timestamp: time::SystemTime,
+    error: Option<String>,
+}
+
+impl Operation {
+    /// What was done.
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+    }
+
+    /// The path operated on.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    /// How many bytes were written, when relevant.
+    pub fn bytes(&self) -> Option<u64> {
+        self.bytes
+    }
+
+    /// When the operation completed.
+    pub fn timestamp(&self) -> time::SystemTime {
+        self.timestamp
+    }
+
+    /// Why the operation failed, if it did.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.path.display())?;
+        if let Some(bytes) = self.bytes {
+            write!(f, " ({} bytes)", bytes)?;
+        }
+        match self.error {
+            Some(ref error) => write!(f, ": {}", error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The kind of a recorded [`Operation`], named after the fixture API that was called.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OperationKind {
+    /// [`PathCreateDir::create_dir_all`][super::PathCreateDir::create_dir_all]
+    CreateDirAll,
+    /// [`FileTouch::touch`][super::FileTouch::touch]
+    Touch,
+    /// [`FileTimestamp::touch_at`][super::FileTimestamp::touch_at]
+    TouchAt,
+    /// [`FileTimestamp::set_mtime`][super::FileTimestamp::set_mtime] or
+    /// [`FileTimestamp::set_times`][super::FileTimestamp::set_times]
+    SetTimes,
+    /// [`FileWriteBin::write_binary`][super::FileWriteBin::write_binary]
+    WriteBinary,
+    /// [`FileWriteStr::write_str`][super::FileWriteStr::write_str]
+    WriteStr,
+    /// [`FileWriteFmt::write_fmt`][super::FileWriteFmt::write_fmt]
+    WriteFmt,
+    /// [`FileWriteAtomic::write_binary_atomic`][super::FileWriteAtomic::write_binary_atomic] or
+    /// [`FileWriteAtomic::write_str_atomic`][super::FileWriteAtomic::write_str_atomic]
+    WriteAtomic,
+    /// [`FileAppend::append_binary`][super::FileAppend::append_binary]
+    AppendBinary,
+    /// [`FileAppend::append_str`][super::FileAppend::append_str]
+    AppendStr,
+    /// [`FileWriteFile::write_file`][super::FileWriteFile::write_file]
+    WriteFile,
+    /// [`FileWriteStream::write_from`][super::FileWriteStream::write_from]
+    WriteFrom,
+    /// [`FileWriteStream::write_file_of_size`][super::FileWriteStream::write_file_of_size]
+    WriteOfSize,
+    /// [`FileGenerate::write_random_bytes`][super::FileGenerate::write_random_bytes]
+    WriteRandomBytes,
+    /// [`FileGenerate::write_random_text`][super::FileGenerate::write_random_text]
+    WriteRandomText,
+    /// [`FileGenerate::write_zeros`][super::FileGenerate::write_zeros]
+    WriteZeros,
+    /// [`FileWriteSparse::write_sparse`][super::FileWriteSparse::write_sparse]
+    WriteSparse,
+    /// `PathGitRepo::init_git_repo`
+    InitGitRepo,
+    /// `GitRepo::commit_all`
+    CommitAll,
+    /// [`FileCapture::capture_from`][super::FileCapture::capture_from]
+    Capture,
+    /// [`FileWriteScript::write_script`][super::FileWriteScript::write_script]
+    WriteScript,
+    /// `PathPermissions::set_mode`
+    SetMode,
+    /// [`PathPermissions::set_readonly`][super::PathPermissions::set_readonly]
+    SetReadonly,
+    /// [`FilePatch::truncate`][super::FilePatch::truncate]
+    Truncate,
+    /// [`FilePatch::patch_bytes`][super::FilePatch::patch_bytes]
+    PatchBytes,
+    /// `PathCopy::copy_from`
+    CopyFrom,
+    /// `PathCopy::sync_from`
+    SyncFrom,
+    /// `PathCopy::link_farm_from`
+    LinkFarmFrom,
+    /// [`TempDir::clone_from`][super::TempDir::clone_from]
+    CloneFrom,
+    /// [`PathCopy::copy_to`][super::PathCopy::copy_to]
+    CopyTo,
+    /// `PathCopy::export_to`
+    ExportTo,
+    /// `PathExtract::extract_from`
+    Extract,
+    /// [`PathCorpus::write_corpus`][super::PathCorpus::write_corpus]
+    WriteCorpus,
+    /// [`PathManifest::write_manifest`][super::PathManifest::write_manifest]
+    WriteManifest,
+    /// [`SymlinkToFile::symlink_to_file`][super::SymlinkToFile::symlink_to_file]
+    SymlinkToFile,
+    /// [`SymlinkToDir::symlink_to_dir`][super::SymlinkToDir::symlink_to_dir]
+    SymlinkToDir,
+    /// [`HardLinkTo::hard_link_to`][super::HardLinkTo::hard_link_to]
+    HardLinkTo,
+    /// `PathSpecialFile::mkfifo`
+    Mkfifo,
+    /// `PathXattr::set_xattr`
+    SetXattr,
+    /// `FileLock::lock_exclusive`
+    LockExclusive,
+    /// `FileLock::lock_shared`
+    LockShared,
+    /// [`PathRemove::remove_file`][super::PathRemove::remove_file]
+    RemoveFile,
+    /// [`PathRemove::remove_dir`][super::PathRemove::remove_dir]
+    RemoveDir,
+    /// [`PathRemove::remove_all`][super::PathRemove::remove_all]
+    RemoveAll,
+    /// [`PathClear::clear`][super::PathClear::clear]
+    Clear,
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OperationKind::CreateDirAll => "create_dir_all",
+            OperationKind::Touch => "touch",
+            OperationKind::TouchAt => "touch_at",
+            OperationKind::SetTimes => "set_times",
+            OperationKind::WriteBinary => "write_binary",
+            OperationKind::WriteStr => "write_str",
+            OperationKind::WriteFmt => "write_fmt",
+            OperationKind::WriteAtomic => "write_atomic",
+            OperationKind::AppendBinary => "append_binary",
+            OperationKind::AppendStr => "append_str",
+            OperationKind::WriteFrom => "write_from",
+            OperationKind::WriteOfSize => "write_file_of_size",
+            OperationKind::WriteRandomBytes => "write_random_bytes",
+            OperationKind::WriteRandomText => "write_random_text",
+            OperationKind::WriteZeros => "write_zeros",
+            OperationKind::WriteSparse => "write_sparse",
+            OperationKind::InitGitRepo => "init_git_repo",
+            OperationKind::CommitAll => "commit_all",
+            OperationKind::Capture => "capture_from",
+            OperationKind::WriteFile => "write_file",
+            OperationKind::WriteScript => "write_script",
+            OperationKind::SetMode => "set_mode",
+            OperationKind::SetReadonly => "set_readonly",
+            OperationKind::Truncate => "truncate",
+            OperationKind::PatchBytes => "patch_bytes",
+            OperationKind::CopyFrom => "copy_from",
+            OperationKind::SyncFrom => "sync_from",
+            OperationKind::LinkFarmFrom => "link_farm_from",
+            OperationKind::CloneFrom => "clone_from",
+            OperationKind::CopyTo => "copy_to",
+            OperationKind::ExportTo => "export_to",
+            OperationKind::Extract => "extract_from",
+            OperationKind::WriteCorpus => "write_corpus",
+            OperationKind::WriteManifest => "write_manifest",
+            OperationKind::SymlinkToFile => "symlink_to_file",
+            OperationKind::SymlinkToDir => "symlink_to_dir",
+            OperationKind::HardLinkTo => "hard_link_to",
+            OperationKind::Mkfifo => "mkfifo",
+            OperationKind::SetXattr => "set_xattr",
+            OperationKind::LockExclusive => "lock_exclusive",
+            OperationKind::LockShared => "lock_shared",
+            OperationKind::RemoveFile => "remove_file",
+            OperationKind::RemoveDir => "remove_dir",
+            OperationKind::RemoveAll => "remove_all",
+            OperationKind::Clear => "clear",
+        };
+        f.write_str(name)
+    }
+}
+
+impl OperationKind {
+    /// Whether the operation creates its path, and any missing parent directories.
+    pub(crate) fn creates_path(self) -> bool {
+        !matches!(
+            self,
+            OperationKind::CopyTo
+                | OperationKind::ExportTo
+                | OperationKind::RemoveFile
+                | OperationKind::RemoveDir
+                | OperationKind::RemoveAll
+                | OperationKind::Clear
+        )
+    }
+
+    /// Whether the operation creates entries within its path.
+    pub(crate) fn creates_tree(self) -> bool {
+        matches!(
+            self,
+            OperationKind::CopyFrom
+                | OperationKind::SyncFrom
+                | OperationKind::LinkFarmFrom
+                | OperationKind::CloneFrom
+                | OperationKind::Extract
+                | OperationKind::WriteCorpus
+                | OperationKind::InitGitRepo
+        )
+    }
+}
+
+/// Entries under `root` that no successful operation in `operations` created.
+pub(crate) fn untracked(
+    root: &path::Path,
+    operations: &[Operation],
+) -> Result<Vec<path::PathBuf>, FixtureError> {
+    let created: Vec<_> = operations
+        .iter()
+        .filter(|op| op.error.is_none() && op.kind.creates_path())
+        .collect();
+    let mut untracked = Vec::new();
+    for entry in walkdir::WalkDir::new(root).min_depth(1).sort_by_file_name() {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        let path = entry.path();
+        let tracked = created.iter().any(|op| {
+            op.path.starts_with(path) || (op.kind.creates_tree() && path.starts_with(&op.path))
+        });
+        if !tracked {
+            untracked.push(path.to_owned());
+        }
+    }
+    Ok(untracked)
+}
+
+pub(crate) type Log = sync::Mutex<Vec<Operation>>;
+
+/// The innermost live fixture containing `path`.
+pub(crate) fn fixture_root(path: &path::Path) -> Option<path::PathBuf> {
+    super::context::innermost(path).map(|context| context.root.clone())
+}
+
+/// Run `op`, recording it in the logs of all fixtures containing `path`.
+pub(crate) fn record<T, F>(
+    kind: OperationKind,
+    path: &path::Path,
+    bytes: Option<u64>,
+    op: F,
+) -> Result<T, FixtureError>
+where
+    F: FnOnce() -> Result<T, FixtureError>,
+{
+    let result = op().map_err(|err| err.at(kind, path));
+    let contexts = super::context::containing(path);
+    let innermost = contexts.last().map(|context| &**context);
+    if let (Ok(_), Some(bytes), Some(context)) = (&result, bytes, innermost) {
+        super::throttle::wait(&context.write_limit, bytes);
+    }
+    #[cfg(feature = "tracing")]
+    super::trace::emit(innermost, kind, path, bytes, &result);
+
+    if !contexts.is_empty() {
+        let operation = Operation {
+            kind,
+            path: path.to_owned(),
+            bytes,
+            timestamp: time::SystemTime::now(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        for context in contexts {
+            context
+                .log
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(operation.clone());
+        }
+    }
+
+    result
+}
@@ -0,0 +1,158 @@
+//! The operations behind the fixture extension traits, on arbitrary paths.
+//!
+//! For harness authors building their own fixture types, these behave exactly like the trait
+//! methods on [`ChildPath`][super::ChildPath]: parent directories are created as needed, errors
+//! are [`FixtureError`]s, and operations within a [`TempDir`][super::TempDir] show up in its
+//! [operation log][super::TempDir::operation_log].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use assert_fs::fixture::ops;
+//!
+//! let temp = assert_fs::TempDir::new().unwrap();
+//! let config = temp.path().join("etc/app.toml");
+//! ops::write_str(&config, "port = 8080\n").unwrap();
+//! assert_eq!(ops::read_str(&config).unwrap(), "port = 8080\n");
+//! temp.close().unwrap();
+//! ```
+
+use std::path;
+
+#[cfg(feature = "glob")]
+use super::CopyOptions;
+#[cfg(feature = "glob")]
+use super::CopyReport;
+use super::FixtureError;
+
+/// Create an empty file at `path`, like [`FileTouch::touch`][super::FileTouch::touch].
+pub fn touch<P>(path: P) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::touch(path.as_ref())
+}
+
+/// Write `data` to `path`, like [`FileWriteBin::write_binary`][super::FileWriteBin::write_binary].
+pub fn write_binary<P>(path: P, data: &[u8]) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::write_binary(path.as_ref(), data)
+}
+
+/// Write `data` to `path`, like [`FileWriteStr::write_str`][super::FileWriteStr::write_str].
+pub fn write_str<P>(path: P, data: &str) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::write_str(path.as_ref(), data)
+}
+
+/// Copy the file `data` to `path`, like
+/// [`FileWriteFile::write_file`][super::FileWriteFile::write_file].
+pub fn write_file<P, D>(path: P, data: D) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+    D: AsRef<path::Path>,
+{
+    super::tools::write_file(path.as_ref(), data.as_ref())
+}
+
+/// Read the content of `path`, like [`FileRead::read_binary`][super::FileRead::read_binary].
+pub fn read_binary<P>(path: P) -> Result<Vec<u8>, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::read_binary(path.as_ref())
+}
+
+/// Read the content of `path` as UTF-8, like [`FileRead::read_str`][super::FileRead::read_str].
+pub fn read_str<P>(path: P) -> Result<String, FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::read_str(path.as_ref())
+}
+
+/// Create `path` and its missing parents, like
+/// [`PathCreateDir::create_dir_all`][super::PathCreateDir::create_dir_all].
+pub fn create_dir_all<P>(path: P) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::create_dir_all(path.as_ref())
+}
+
+/// Remove the file at `path`, like [`PathRemove::remove_file`][super::PathRemove::remove_file].
+pub fn remove_file<P>(path: P) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::remove_file(path.as_ref())
+}
+
+/// Remove the empty directory at `path`, like
+/// [`PathRemove::remove_dir`][super::PathRemove::remove_dir].
+pub fn remove_dir<P>(path: P) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::remove_dir(path.as_ref())
+}
+
+/// Remove `path` and everything within it, like
+/// [`PathRemove::remove_all`][super::PathRemove::remove_all].
+pub fn remove_all<P>(path: P) -> Result<(), FixtureError>
+where
+    P: AsRef<path::Path>,
+{
+    super::tools::remove_all(path.as_ref())
+}
+
+/// Copy the entries of `source` matching the glob `patterns` into `target`, like
+/// [`PathCopy::copy_from_with_report`][super::PathCopy::copy_from_with_report].
+#[cfg(feature = "glob")]
+pub fn copy_from<T, P, S>(
+    target: T,
+    source: P,
+    patterns: &[S],
+    options: CopyOptions,
+) -> Result<CopyReport, FixtureError>
+where
+    T: AsRef<path::Path>,
+    P: AsRef<path::Path>,
+    S: AsRef<str>,
+{
+    super::tools::copy_files(target.as_ref(), source.as_ref(), patterns, options)
+}
+
+/// Create a symlink at `link` to the file `target`, like
+/// [`SymlinkToFile::symlink_to_file`][super::SymlinkToFile::symlink_to_file].
+pub fn symlink_to_file<L, T>(link: L, target: T) -> Result<(), FixtureError>
+where
+    L: AsRef<path::Path>,
+    T: AsRef<path::Path>,
+{
+    super::tools::symlink_to_file(link.as_ref(), target.as_ref())
+}
+
+/// Create a symlink at `link` to the directory `target`, like
+/// [`SymlinkToDir::symlink_to_dir`][super::SymlinkToDir::symlink_to_dir].
+pub fn symlink_to_dir<L, T>(link: L, target: T) -> Result<(), FixtureError>
+where
+    L: AsRef<path::Path>,
+    T: AsRef<path::Path>,
+{
+    super::tools::symlink_to_dir(link.as_ref(), target.as_ref())
+}
+
+/// Create a hard link at `link` to `target`, like
+/// [`HardLinkTo::hard_link_to`][super::HardLinkTo::hard_link_to].
+pub fn hard_link_to<L, T>(link: L, target: T) -> Result<(), FixtureError>
+where
+    L: AsRef<path::Path>,
+    T: AsRef<path::Path>,
+{
+    super::tools::hard_link_to(link.as_ref(), target.as_ref())
+}
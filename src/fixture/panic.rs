@@ -0,0 +1,77 @@
+use std::path;
+
+/// Print the state of a fixture when the test panics.
+///
+/// Created with [`TempDir::dump_on_panic`][super::TempDir::dump_on_panic].  When the guard is
+/// dropped during a panic, for any reason and not just `assert_fs`'s own assertions, it prints
+/// the fixture's path and a bounded listing of its contents to `stderr`, which the test harness
+/// shows alongside the failure.
+///
+/// Declare the guard after the [`TempDir`][super::TempDir] so it's dropped, and prints, before
+/// the directory is cleaned up.
+#[derive(Debug)]
+pub struct DumpOnPanic {
+    path: path::PathBuf,
+    max_entries: usize,
+}
+
+impl DumpOnPanic {
+    /// Guard an arbitrary directory.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<path::PathBuf>,
+    {
+        Self {
+            path: path.into(),
+            max_entries: 100,
+        }
+    }
+
+    /// Limit how many entries are printed.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    fn dump(&self) -> String {
+        format!(
+            "Fixture state at failure:\n{}",
+            super::tree::render_listing(&self.path, self.max_entries)
+        )
+    }
+}
+
+impl Drop for DumpOnPanic {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            eprintln!("{}", self.dump());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn dump_lists_fixture() {
+        let temp = crate::TempDir::new().unwrap();
+        temp.child("foo/bar.txt").write_str("hello").unwrap();
+        temp.child("foo/baz.txt").touch().unwrap();
+
+        let dump = temp.dump_on_panic().dump();
+        assert!(dump.starts_with("Fixture state at failure:\n"), "{}", dump);
+        assert!(
+            dump.contains(&temp.path().display().to_string()),
+            "{}",
+            dump
+        );
+        assert!(dump.contains("foo/"), "{}", dump);
+        assert!(dump.contains("bar.txt"), "{}", dump);
+
+        let dump = temp.dump_on_panic().max_entries(1).dump();
+        assert!(dump.contains("... 2 more entries"), "{}", dump);
+        assert!(!dump.contains("bar.txt"), "{}", dump);
+        temp.close().unwrap();
+    }
+}
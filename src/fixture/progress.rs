@@ -0,0 +1,89 @@
+use std::fmt;
+use std::path;
+use std::sync;
+
+use super::oplog::OperationKind;
+
+/// How far along a long-running fixture operation is.
+///
+/// Reported to the callback set with [`TempDir::on_progress`][super::TempDir::on_progress]
+/// after every file, so big setup steps can emit heartbeat output, e.g. to avoid CI timeouts
+/// for steps without output.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    kind: OperationKind,
+    files: u64,
+    bytes: u64,
+}
+
+impl Progress {
+    /// The operation in progress.
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+    }
+
+    /// How many files have been written so far.
+    pub fn files(&self) -> u64 {
+        self.files
+    }
+
+    /// How many bytes have been written so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} files, {} bytes",
+            self.kind, self.files, self.bytes
+        )
+    }
+}
+
+pub(crate) type Callback = dyn Fn(&Progress) + Send + Sync;
+
+pub(crate) type Shared = sync::RwLock<Option<sync::Arc<Callback>>>;
+
+/// Counts the files written by one operation, reporting to the fixture containing the target.
+///
+/// Also applies the fixture's write rate limit, see
+/// [`TempDir::set_write_rate`][super::TempDir::set_write_rate].
+pub(crate) struct Tracker {
+    target: path::PathBuf,
+    callback: Option<sync::Arc<Callback>>,
+    progress: Progress,
+}
+
+impl Tracker {
+    pub(crate) fn new(kind: OperationKind, target: &path::Path) -> Self {
+        let callback = super::context::innermost(target).and_then(|context| {
+            context
+                .progress
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone()
+        });
+        Self {
+            target: target.to_owned(),
+            callback,
+            progress: Progress {
+                kind,
+                files: 0,
+                bytes: 0,
+            },
+        }
+    }
+
+    /// Record another file of `bytes` being written.
+    pub(crate) fn file(&mut self, bytes: u64) {
+        super::throttle::throttle(&self.target, bytes);
+        self.progress.files += 1;
+        self.progress.bytes += bytes;
+        if let Some(ref callback) = self.callback {
+            callback(&self.progress);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeSet;
+use std::path;
+
+use proptest::prelude::*;
+use unicode_normalization::UnicodeNormalization;
+
+use super::errors::*;
+
+/// The characters [`tree`] draws entry names from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NameAlphabet {
+    /// Lowercase ASCII letters, digits, `-` and `_`.
+    Ascii,
+    /// Any printable Unicode, except what's invalid in names on Windows.
+    Unicode,
+    /// Unicode, mixed with names that tend to trip up tools: leading dots and dashes, spaces,
+    /// shell metacharacters, and names resembling ones reserved on Windows.
+    Tricky,
+}
+
+/// Names that are valid everywhere, but that tools commonly mishandle.
+const TRICKY_NAMES: &[&str] = &[
+    ".hidden",
+    "-rf",
+    "--",
+    "a b",
+    " leading",
+    "#hash",
+    "semi;colon",
+    "$HOME",
+    "'quoted'",
+    "con_",
+    "CON1.txt.bak",
+    "aux_1",
+    "nul-",
+    "caf\u{e9}",
+    "cafe\u{301}",
+    "\u{1f600}",
+    "\u{202e}txt.exe",
+];
+
+/// The shape of the trees generated by [`tree`].
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::{NameAlphabet, TreeParams};
+///
+/// let params = TreeParams::new()
+///     .max_depth(4)
+///     .max_entries(32)
+///     .max_file_size(64 * 1024)
+///     .names(NameAlphabet::Tricky);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TreeParams {
+    max_depth: usize,
+    max_entries: usize,
+    max_file_size: usize,
+    names: NameAlphabet,
+}
+
+impl TreeParams {
+    /// Up to 16 entries, 3 levels deep, of up to 1 KiB, with ASCII names.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most path components of an entry, at least 1.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth.max(1);
+        self
+    }
+
+    /// The most files and empty directories generated.
+    ///
+    /// Entries clashing with earlier ones are dropped, so trees may have fewer.
+    pub fn max_entries(mut self, entries: usize) -> Self {
+        self.max_entries = entries;
+        self
+    }
+
+    /// The largest file generated, in bytes.
+    pub fn max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Where entry names are drawn from.
+    pub fn names(mut self, names: NameAlphabet) -> Self {
+        self.names = names;
+        self
+    }
+}
+
+impl Default for TreeParams {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_entries: 16,
+            max_file_size: 1024,
+            names: NameAlphabet::Ascii,
+        }
+    }
+}
+
+/// An entry of a [`GeneratedTree`], relative to its root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GeneratedEntry {
+    /// An empty directory.
+    Dir(path::PathBuf),
+    /// A file and its content.
+    File(path::PathBuf, Vec<u8>),
+}
+
+/// A directory tree generated by [`tree`].
+///
+/// No two entries' names differ only by case or Unicode normalization, so the tree can be
+/// created on any filesystem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedTree {
+    entries: Vec<GeneratedEntry>,
+}
+
+impl GeneratedTree {
+    /// The files and empty directories, in the order they were generated.
+    ///
+    /// Parent directories are implied by their entries.
+    pub fn entries(&self) -> &[GeneratedEntry] {
+        &self.entries
+    }
+
+    /// Create the tree within `root`.
+    pub fn materialize<P>(&self, root: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        let root = root.as_ref();
+        for entry in &self.entries {
+            match entry {
+                GeneratedEntry::Dir(dir) => super::tools::create_dir_all(&root.join(dir))?,
+                GeneratedEntry::File(file, content) => {
+                    super::tools::write_binary(&root.join(file), content)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn from_raw(raw: Vec<(Vec<String>, Option<Vec<u8>>)>) -> Self {
+        // Compared case-insensitively and normalized, as some filesystems do
+        let mut files = BTreeSet::new();
+        let mut dirs = BTreeSet::new();
+        let mut entries = Vec::new();
+        for (components, content) in raw {
+            let keys: Vec<String> = components
+                .iter()
+                .map(|name| name.nfc().collect::<String>().to_lowercase())
+                .collect();
+            let under_file = (1..keys.len()).any(|i| files.contains(&keys[..i]));
+            if under_file || files.contains(&keys) || dirs.contains(&keys) {
+                continue;
+            }
+            for i in 1..keys.len() {
+                dirs.insert(keys[..i].to_vec());
+            }
+            let path: path::PathBuf = components.iter().collect();
+            match content {
+                Some(content) => {
+                    files.insert(keys);
+                    entries.push(GeneratedEntry::File(path, content));
+                }
+                None => {
+                    dirs.insert(keys);
+                    entries.push(GeneratedEntry::Dir(path));
+                }
+            }
+        }
+        Self { entries }
+    }
+}
+
+/// A [`Strategy`] generating directory trees shaped by `params` (requires the `proptest`
+/// feature).
+///
+/// Shrinking removes entries, shortens paths, and empties files, to find the smallest tree a
+/// failing property still fails for.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::{tree, GeneratedEntry, TreeParams};
+/// use assert_fs::prelude::*;
+/// use proptest::prelude::*;
+///
+/// proptest!(|(generated in tree(TreeParams::new()))| {
+///     let temp = assert_fs::TempDir::new().unwrap();
+///     generated.materialize(temp.path()).unwrap();
+///     for entry in generated.entries() {
+///         if let GeneratedEntry::File(path, content) = entry {
+///             prop_assert_eq!(&std::fs::read(temp.child(path)).unwrap(), content);
+///         }
+///     }
+/// });
+/// ```
+pub fn tree(params: TreeParams) -> impl Strategy<Value = GeneratedTree> {
+    let path = proptest::collection::vec(name(params.names), 1..=params.max_depth);
+    let content = proptest::collection::vec(any::<u8>(), 0..=params.max_file_size);
+    let entry = (path, proptest::option::weighted(0.8, content));
+    proptest::collection::vec(entry, 0..=params.max_entries).prop_map(GeneratedTree::from_raw)
+}
+
+fn name(names: NameAlphabet) -> BoxedStrategy<String> {
+    let unicode = "[^/\\\\<>:\"|?*\\x00-\\x1f\\x7f]{1,8}".prop_filter("valid on Windows", |name| {
+        !name.ends_with(' ') && !name.ends_with('.')
+    });
+    match names {
+        NameAlphabet::Ascii => "[a-z0-9_-]{1,12}".boxed(),
+        NameAlphabet::Unicode => unicode.boxed(),
+        NameAlphabet::Tricky => prop_oneof![
+            unicode,
+            proptest::sample::select(TRICKY_NAMES).prop_map(str::to_owned),
+        ]
+        .boxed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn raw(path: &str, content: Option<&[u8]>) -> (Vec<String>, Option<Vec<u8>>) {
+        (
+            path.split('/').map(str::to_owned).collect(),
+            content.map(<[u8]>::to_vec),
+        )
+    }
+
+    #[test]
+    fn from_raw_drops_clashes() {
+        let tree = GeneratedTree::from_raw(vec![
+            raw("a/b", Some(b"1")),
+            raw("a/b/c", Some(b"2")),
+            raw("A/B", None),
+            raw("a", Some(b"3")),
+            raw("caf\u{e9}", None),
+            raw("cafe\u{301}", Some(b"4")),
+            raw("a/c", None),
+        ]);
+        assert_eq!(
+            tree.entries(),
+            &[
+                GeneratedEntry::File("a/b".into(), b"1".to_vec()),
+                GeneratedEntry::Dir("caf\u{e9}".into()),
+                GeneratedEntry::Dir("a/c".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs;
+use std::path;
+
+use super::errors::*;
+
+/// How a [`ReadOnly`] guard is rejecting writes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReadOnlyMode {
+    /// The directory was bind-mounted read-only, so writes fail with `EROFS`, like on real
+    /// read-only media.  Requires the privileges to mount filesystems (Linux only).
+    Remount,
+    /// Write permission was removed from every entry, so writes fail with a permission error.
+    Permissions,
+}
+
+/// Make a fixture reject all writes until dropped.
+///
+/// Created with [`TempDir::read_only`][super::TempDir::read_only].  Where privileged, the
+/// directory is remounted read-only; otherwise write permissions are stripped.  Dropping the
+/// guard restores the fixture.
+///
+/// Declare the guard after the [`TempDir`][super::TempDir] so it's dropped, and restores
+/// write access, before the directory is cleaned up.
+#[derive(Debug)]
+pub struct ReadOnly {
+    path: path::PathBuf,
+    mode: ReadOnlyMode,
+    restore: Vec<(path::PathBuf, fs::Permissions)>,
+    active: bool,
+}
+
+impl ReadOnly {
+    pub(crate) fn new(path: &path::Path) -> Result<Self, FixtureError> {
+        let mut guard = Self {
+            path: path.to_owned(),
+            mode: ReadOnlyMode::Remount,
+            restore: Vec::new(),
+            active: false,
+        };
+        if remount(path, true).is_err() {
+            guard.mode = ReadOnlyMode::Permissions;
+            if let Err(err) = guard.strip_permissions() {
+                let _ = guard.restore_permissions();
+                return Err(err);
+            }
+        }
+        guard.active = true;
+        Ok(guard)
+    }
+
+    /// How writes are being rejected.
+    pub fn mode(&self) -> ReadOnlyMode {
+        self.mode
+    }
+
+    /// Restore write access, returning a `Result`.
+    pub fn restore(mut self) -> Result<(), FixtureError> {
+        self.deactivate()
+    }
+
+    fn deactivate(&mut self) -> Result<(), FixtureError> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+        match self.mode {
+            ReadOnlyMode::Remount => remount(&self.path, false),
+            ReadOnlyMode::Permissions => self.restore_permissions(),
+        }
+    }
+
+    fn strip_permissions(&mut self) -> Result<(), FixtureError> {
+        // Children first, so directories stay traversable while walking
+        for entry in walkdir::WalkDir::new(&self.path).contents_first(true) {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            if entry.file_type().is_symlink() {
+                continue;
+            }
+            let permissions = entry
+                .metadata()
+                .chain(FixtureError::new(FixtureKind::Permissions))?
+                .permissions();
+            if permissions.readonly() {
+                continue;
+            }
+            let mut readonly = permissions.clone();
+            readonly.set_readonly(true);
+            fs::set_permissions(entry.path(), readonly)
+                .chain(FixtureError::new(FixtureKind::Permissions))?;
+            self.restore.push((entry.path().to_owned(), permissions));
+        }
+        Ok(())
+    }
+
+    fn restore_permissions(&mut self) -> Result<(), FixtureError> {
+        // Parents first, so children can be modified again
+        let mut result = Ok(());
+        for (path, permissions) in self.restore.drain(..).rev() {
+            let restored = fs::set_permissions(&path, permissions)
+                .chain(FixtureError::new(FixtureKind::Permissions));
+            if result.is_ok() {
+                result = restored;
+            }
+        }
+        result
+    }
+}
+
+impl Drop for ReadOnly {
+    fn drop(&mut self) {
+        let _ = self.deactivate();
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn remount(path: &path::Path, read_only: bool) -> Result<(), FixtureError> {
+    if !read_only {
+        return run(std::process::Command::new("umount").arg(path));
+    }
+    run(std::process::Command::new("mount")
+        .arg("--bind")
+        .arg(path)
+        .arg(path))?;
+    let remounted = run(std::process::Command::new("mount")
+        .args(["-o", "remount,bind,ro"])
+        .arg(path));
+    if remounted.is_err() {
+        let _ = run(std::process::Command::new("umount").arg(path));
+    }
+    remounted
+}
+
+#[cfg(not(target_os = "linux"))]
+fn remount(_path: &path::Path, _read_only: bool) -> Result<(), FixtureError> {
+    let err = std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "read-only remounts are unsupported on this platform",
+    );
+    Err(FixtureError::new(FixtureKind::Mount).chain(err))
+}
+
+#[cfg(target_os = "linux")]
+fn run(command: &mut std::process::Command) -> Result<(), FixtureError> {
+    let status = command
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .chain(FixtureError::new(FixtureKind::Mount))?;
+    if !status.success() {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("`{:?}` failed with {}", command, status),
+        );
+        return Err(FixtureError::new(FixtureKind::Mount).chain(err));
+    }
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::TempDir;
+
+/// What [`PathCopyMapped::copy_from_mapped`] does with an entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CopyAction {
+    /// Copy the entry as-is.
+    Copy,
+    /// Leave the entry out, and for a directory, everything within it.
+    Skip,
+    /// Copy the entry to this path, relative to the destination, instead.
+    ///
+    /// Entries within a renamed directory follow it, unless renamed themselves.
+    Rename(path::PathBuf),
+    /// Write this content instead of the file's.
+    Rewrite(Vec<u8>),
+    /// Write this content to this path, relative to the destination, instead.
+    RenameRewrite(path::PathBuf, Vec<u8>),
+}
+
+/// An entry being copied by [`PathCopyMapped::copy_from_mapped`].
+#[derive(Copy, Clone, Debug)]
+pub struct CopyEntry<'a> {
+    path: &'a path::Path,
+    source_path: &'a path::Path,
+    is_dir: bool,
+}
+
+impl CopyEntry<'_> {
+    /// The path of the entry, relative to `source`.
+    pub fn path(&self) -> &path::Path {
+        self.path
+    }
+
+    /// The full path of the entry within `source`, to read its content.
+    pub fn source_path(&self) -> &path::Path {
+        self.source_path
+    }
+
+    /// Whether the entry is a directory, rather than a file.
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+}
+
+/// Copy files into [`TempDir`], deciding what happens to each entry.
+///
+/// For anonymizing fixtures, or relocating files into a different layout, while copying.
+/// Symlinks are followed, copying what they point to.
+pub trait PathCopyMapped {
+    /// Copy the entries of `source` matching the glob `patterns`, like
+    /// [`PathCopy::copy_from`][super::PathCopy::copy_from], as `map` decides for each.
+    ///
+    /// Entries are visited parents first, in sorted order.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `map` renames an entry to an absolute path or out of the destination, or
+    /// rewrites a directory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::CopyAction;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let source = assert_fs::TempDir::new().unwrap();
+    /// source.child("users/alice.json").write_str("{\"email\": \"alice@example.com\"}").unwrap();
+    /// source.child("users/cache/tmp.bin").touch().unwrap();
+    /// source.child("README.md").touch().unwrap();
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.copy_from_mapped(source.path(), &["**"], |entry| {
+    ///     if entry.path().ends_with("cache") {
+    ///         CopyAction::Skip
+    ///     } else if entry.path() == std::path::Path::new("users") {
+    ///         CopyAction::Rename("data/users".into())
+    ///     } else if entry.path().extension() == Some("json".as_ref()) {
+    ///         let content = std::fs::read_to_string(entry.source_path()).unwrap();
+    ///         CopyAction::Rewrite(content.replace("alice@example.com", "user@test").into_bytes())
+    ///     } else {
+    ///         CopyAction::Copy
+    ///     }
+    /// })
+    /// .unwrap();
+    ///
+    /// temp.child("data/users/alice.json").assert("{\"email\": \"user@test\"}");
+    /// temp.child("data/users/cache").assert(predicates::path::missing());
+    /// temp.child("README.md").assert("");
+    /// temp.close().unwrap();
+    /// source.close().unwrap();
+    /// ```
+    fn copy_from_mapped<P, S, F>(
+        &self,
+        source: P,
+        patterns: &[S],
+        map: F,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+        F: FnMut(&CopyEntry<'_>) -> CopyAction;
+}
+
+impl PathCopyMapped for TempDir {
+    fn copy_from_mapped<P, S, F>(
+        &self,
+        source: P,
+        patterns: &[S],
+        map: F,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+        F: FnMut(&CopyEntry<'_>) -> CopyAction,
+    {
+        copy_from_mapped(self.path(), source.as_ref(), patterns, map)
+    }
+}
+
+impl PathCopyMapped for ChildPath {
+    fn copy_from_mapped<P, S, F>(
+        &self,
+        source: P,
+        patterns: &[S],
+        map: F,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+        F: FnMut(&CopyEntry<'_>) -> CopyAction,
+    {
+        copy_from_mapped(self.path(), source.as_ref(), patterns, map)
+    }
+}
+
+fn copy_from_mapped<S, F>(
+    target: &path::Path,
+    source: &path::Path,
+    patterns: &[S],
+    mut map: F,
+) -> Result<(), FixtureError>
+where
+    S: AsRef<str>,
+    F: FnMut(&CopyEntry<'_>) -> CopyAction,
+{
+    record(OperationKind::CopyFrom, target, None, || {
+        let source = source
+            .canonicalize()
+            .chain(FixtureError::new(FixtureKind::Walk))?;
+        let mut skipped: Vec<path::PathBuf> = Vec::new();
+        // Directories copied somewhere other than their relative path, and where to
+        let mut renamed: Vec<(path::PathBuf, path::PathBuf)> = Vec::new();
+        for entry in crate::order::glob(&source, patterns)
+            .follow_links(true)
+            .build()
+            .chain(FixtureError::new(FixtureKind::Walk))?
+        {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(&source)
+                .expect("entries to be under `source`");
+            // The root itself, or within a skipped directory
+            if rel.as_os_str().is_empty() || skipped.iter().any(|dir| rel.starts_with(dir)) {
+                continue;
+            }
+            let file_type = entry.file_type();
+            if !file_type.is_dir() && !file_type.is_file() {
+                continue;
+            }
+            let is_dir = file_type.is_dir();
+            let action = map(&CopyEntry {
+                path: rel,
+                source_path: entry.path(),
+                is_dir,
+            });
+            let (dest, content) = match action {
+                CopyAction::Copy => (follow_renames(rel, &renamed), None),
+                CopyAction::Skip => {
+                    if is_dir {
+                        skipped.push(rel.to_owned());
+                    }
+                    continue;
+                }
+                CopyAction::Rename(dest) => (dest, None),
+                CopyAction::Rewrite(content) => (follow_renames(rel, &renamed), Some(content)),
+                CopyAction::RenameRewrite(dest, content) => (dest, Some(content)),
+            };
+            check_dest(&dest).map_err(|err| err.with_path(entry.path()))?;
+            let target_path = target.join(&dest);
+            if is_dir {
+                if content.is_some() {
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "directories can't be rewritten",
+                    );
+                    return Err(FixtureError::new(FixtureKind::CopyFile)
+                        .chain(err)
+                        .with_path(entry.path()));
+                }
+                fs::create_dir_all(&target_path)
+                    .chain(FixtureError::new(FixtureKind::CreateDir))
+                    .map_err(|err| err.with_path(&target_path))?;
+                if dest != rel {
+                    renamed.push((rel.to_owned(), dest));
+                }
+                continue;
+            }
+            super::tools::ensure_parent_dir(&target_path)
+                .map_err(|err| err.with_path(&target_path))?;
+            match content {
+                Some(content) => fs::write(&target_path, content)
+                    .chain(FixtureError::new(FixtureKind::WriteFile))
+                    .map_err(|err| err.with_path(&target_path))?,
+                None => {
+                    fs::copy(entry.path(), &target_path)
+                        .chain(FixtureError::new(FixtureKind::CopyFile))
+                        .map_err(|err| err.with_path(entry.path()))?;
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Where `rel` goes by default, following the deepest renamed directory containing it.
+fn follow_renames(rel: &path::Path, renamed: &[(path::PathBuf, path::PathBuf)]) -> path::PathBuf {
+    renamed
+        .iter()
+        .filter(|(from, _)| rel.starts_with(from))
+        .max_by_key(|(from, _)| from.components().count())
+        .map(|(from, to)| to.join(rel.strip_prefix(from).expect("`rel` to be under `from`")))
+        .unwrap_or_else(|| rel.to_owned())
+}
+
+fn check_dest(dest: &path::Path) -> Result<(), FixtureError> {
+    let escapes = dest
+        .components()
+        .any(|c| !matches!(c, path::Component::Normal(_) | path::Component::CurDir));
+    if escapes || dest.as_os_str().is_empty() {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "`{}` is not a relative path within the destination",
+                dest.display()
+            ),
+        );
+        return Err(FixtureError::new(FixtureKind::CopyFile).chain(err));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn follow_renames_uses_deepest() {
+        let renamed = vec![
+            (path::PathBuf::from("a"), path::PathBuf::from("x")),
+            (path::PathBuf::from("a/b"), path::PathBuf::from("y")),
+        ];
+        assert_eq!(
+            follow_renames(path::Path::new("a/c.txt"), &renamed),
+            path::Path::new("x/c.txt")
+        );
+        assert_eq!(
+            follow_renames(path::Path::new("a/b/c.txt"), &renamed),
+            path::Path::new("y/c.txt")
+        );
+        assert_eq!(
+            follow_renames(path::Path::new("d.txt"), &renamed),
+            path::Path::new("d.txt")
+        );
+    }
+
+    #[test]
+    fn check_dest_rejects_escapes() {
+        assert!(check_dest(path::Path::new("data/users")).is_ok());
+        assert!(check_dest(path::Path::new("../outside")).is_err());
+        assert!(check_dest(path::Path::new("")).is_err());
+        #[cfg(unix)]
+        assert!(check_dest(path::Path::new("/etc/passwd")).is_err());
+    }
+}
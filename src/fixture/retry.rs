@@ -0,0 +1,205 @@
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::thread;
+use std::time;
+
+/// How to retry an operation that may take a while to succeed.
+///
+/// Used by [`PathAssert::assert_eventually`][crate::assert::PathAssert::assert_eventually],
+/// [`PathWait::wait_until_with`][super::PathWait::wait_until_with], and
+/// [`TempDir::close_with_retry`][super::TempDir::close_with_retry], so a suite running in a
+/// slow or flaky environment can tune them all in one place.
+///
+/// Attempts stop when either [`Retry::max_attempts`] or [`Retry::timeout`] is reached.  The
+/// default polls every 10ms, backing off exponentially up to 1s, for up to 10s.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::Retry;
+/// use std::time::Duration;
+///
+/// let retry = Retry::backoff(Duration::from_millis(5))
+///     .max_delay(Duration::from_millis(500))
+///     .timeout(Duration::from_secs(30))
+///     .jitter(true);
+///
+/// let mut calls = 0;
+/// let answer = retry.run(|| {
+///     calls += 1;
+///     if calls < 3 { Err("not yet") } else { Ok(42) }
+/// });
+/// assert_eq!(answer, Ok(42));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Retry {
+    initial: time::Duration,
+    factor: u32,
+    max_delay: time::Duration,
+    max_attempts: Option<u32>,
+    timeout: Option<time::Duration>,
+    jitter: bool,
+}
+
+impl Retry {
+    /// Wait `delay` between each attempt.
+    pub fn fixed(delay: time::Duration) -> Self {
+        Self {
+            initial: delay,
+            factor: 1,
+            max_delay: delay,
+            ..Self::default()
+        }
+    }
+
+    /// Poll every `interval`, giving up once `timeout` has elapsed.
+    ///
+    /// For waiting on a daemon or background task, e.g. with
+    /// [`PathAssert::assert_eventually`][crate::assert::PathAssert::assert_eventually].
+    pub fn poll(timeout: time::Duration, interval: time::Duration) -> Self {
+        Self::fixed(interval).timeout(timeout)
+    }
+
+    /// Wait `initial` after the first attempt, doubling the delay after each following one.
+    pub fn backoff(initial: time::Duration) -> Self {
+        Self {
+            initial,
+            ..Self::default()
+        }
+    }
+
+    /// Multiply the delay by `factor` after each attempt.
+    pub fn factor(mut self, factor: u32) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Never wait longer than `max_delay` between attempts.
+    pub fn max_delay(mut self, max_delay: time::Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Give up after `max_attempts`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Give up once `timeout` has elapsed since the first attempt.
+    pub fn timeout(mut self, timeout: time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Wait a random part, between half and all, of each delay, so parallel tests retrying the
+    /// same thing spread out.
+    pub fn jitter(mut self, yes: bool) -> Self {
+        self.jitter = yes;
+        self
+    }
+
+    /// Call `f` until it succeeds, returning the last error if attempts run out.
+    pub fn run<T, E, F>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut() -> Result<T, E>,
+    {
+        let mut attempts = self.start();
+        loop {
+            match f() {
+                Ok(value) => return Ok(value),
+                Err(err) if !attempts.next() => return Err(err),
+                Err(_) => {}
+            }
+        }
+    }
+
+    pub(crate) fn start(&self) -> Attempts<'_> {
+        Attempts {
+            retry: self,
+            start: time::Instant::now(),
+            attempt: 1,
+            delay: self.initial,
+        }
+    }
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            initial: time::Duration::from_millis(10),
+            factor: 2,
+            max_delay: time::Duration::from_secs(1),
+            max_attempts: None,
+            timeout: Some(time::Duration::from_secs(10)),
+            jitter: false,
+        }
+    }
+}
+
+/// The state of an operation being retried.
+pub(crate) struct Attempts<'r> {
+    retry: &'r Retry,
+    start: time::Instant,
+    attempt: u32,
+    delay: time::Duration,
+}
+
+impl Attempts<'_> {
+    /// Wait for the next attempt, returning `false` if there are none left.
+    pub(crate) fn next(&mut self) -> bool {
+        if self
+            .retry
+            .max_attempts
+            .map_or(false, |max| max <= self.attempt)
+        {
+            return false;
+        }
+        let mut delay = self.delay.min(self.retry.max_delay);
+        if self.retry.jitter {
+            let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+            hasher.write_u32(self.attempt);
+            delay = delay / 2 + delay.mul_f64((hasher.finish() % 1000) as f64 / 2000.0);
+        }
+        if let Some(timeout) = self.retry.timeout {
+            let left = match timeout.checked_sub(self.start.elapsed()) {
+                Some(left) if !left.is_zero() => left,
+                _ => return false,
+            };
+            delay = delay.min(left);
+        }
+        thread::sleep(delay);
+        self.attempt += 1;
+        self.delay = self.delay.saturating_mul(self.retry.factor);
+        true
+    }
+
+    /// How long since the first attempt.
+    #[cfg(feature = "glob")]
+    pub(crate) fn elapsed(&self) -> time::Duration {
+        self.start.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn max_attempts() {
+        let retry = Retry::fixed(time::Duration::ZERO).max_attempts(3);
+        let mut calls = 0;
+        let result: Result<(), _> = retry.run(|| {
+            calls += 1;
+            Err(calls)
+        });
+        assert_eq!(result, Err(3));
+    }
+
+    #[test]
+    fn timeout_ends_attempts() {
+        let retry = Retry::backoff(time::Duration::from_millis(1)).timeout(time::Duration::ZERO);
+        let mut attempts = retry.start();
+        assert!(!attempts.next());
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path;
+
+use super::errors::*;
+use super::TempDir;
+
+/// An entry recreated by the emitted setup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Entry {
+    Dir,
+    Text(String),
+    Binary(Vec<u8>),
+    Symlink { target: path::PathBuf, dir: bool },
+}
+
+/// A recorded entry, relative to the fixture root, with its current state.
+struct Recorded {
+    names: Vec<String>,
+    entry: Entry,
+    mode: Option<u32>,
+}
+
+impl TempDir {
+    /// Rust source recreating what was built through the fixture API, for turning an ad-hoc
+    /// setup into a reproducible, in-repo fixture.
+    ///
+    /// Every successful operation in the [operation log][TempDir::operation_log] is replayed
+    /// in order, as a write, directory, or symlink, with content read from the entries as they
+    /// are now.  Entries no longer present, or created outside of the fixture API, are left out.
+    /// On Unix, files with any executable bit set keep their mode.
+    ///
+    /// # Errors
+    ///
+    /// Fails if an entry can't be read, or its name isn't UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("src/lib.rs").write_str("pub fn answer() {}\n").unwrap();
+    /// tmp_dir.child("target").create_dir_all().unwrap();
+    ///
+    /// let code = tmp_dir.emit_setup_code().unwrap();
+    /// assert!(code.contains(r#"temp.child("src/lib.rs").write_str("pub fn answer() {}\n")"#));
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// ```
+    pub fn emit_setup_code(&self) -> Result<String, FixtureError> {
+        let mut code = String::from(
+            "use assert_fs::prelude::*;\n\nlet temp = assert_fs::TempDir::new().unwrap();\n",
+        );
+        for recorded in recorded(self)? {
+            let child = format!("temp.child({:?})", recorded.names.join("/"));
+            let _ = match &recorded.entry {
+                Entry::Dir => writeln!(code, "{}.create_dir_all().unwrap();", child),
+                Entry::Text(content) => {
+                    writeln!(code, "{}.write_str({:?}).unwrap();", child, content)
+                }
+                Entry::Binary(content) => writeln!(
+                    code,
+                    "{}.write_binary({}).unwrap();",
+                    child,
+                    byte_string(content)
+                ),
+                Entry::Symlink { target, dir } => writeln!(
+                    code,
+                    "{}.{}({:?}).unwrap();",
+                    child,
+                    if *dir {
+                        "symlink_to_dir"
+                    } else {
+                        "symlink_to_file"
+                    },
+                    target
+                ),
+            };
+            if let Some(mode) = recorded.mode {
+                let _ = writeln!(
+                    code,
+                    "#[cfg(unix)]\n{}.set_mode({:#o}).unwrap();",
+                    child, mode
+                );
+            }
+        }
+        Ok(code)
+    }
+
+    /// A JSON description of what was built through the fixture API, to replay with
+    /// [`PathPopulate::populate_from_str`][super::PathPopulate::populate_from_str] (requires
+    /// the `spec-json` feature).
+    ///
+    /// Entries are recorded as for [`TempDir::emit_setup_code`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if an entry can't be read, its name isn't UTF-8, or it's a file that isn't UTF-8,
+    /// which the description can't hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::TempDir;
+    ///
+    /// let tmp_dir = TempDir::new().unwrap();
+    /// tmp_dir.child("src/lib.rs").write_str("pub fn answer() {}\n").unwrap();
+    /// let spec = tmp_dir.emit_spec().unwrap();
+    ///
+    /// let replayed = TempDir::new().unwrap();
+    /// replayed.populate_from_str(&spec).unwrap();
+    /// replayed.child("src/lib.rs").assert("pub fn answer() {}\n");
+    ///
+    /// // Ensure deletion happens.
+    /// tmp_dir.close().unwrap();
+    /// replayed.close().unwrap();
+    /// ```
+    #[cfg(feature = "spec-json")]
+    pub fn emit_spec(&self) -> Result<String, FixtureError> {
+        use serde_json::{Map, Value};
+
+        let mut spec = Map::new();
+        for recorded in recorded(self)? {
+            let mut value = match recorded.entry {
+                Entry::Dir => Value::Object(Map::new()),
+                Entry::Text(content) => Value::String(content),
+                Entry::Binary(_) => {
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "binary files can't be described in JSON",
+                    );
+                    return Err(FixtureError::new(FixtureKind::Spec)
+                        .chain(err)
+                        .with_path(self.path().join(recorded.names.join("/"))));
+                }
+                Entry::Symlink { target, dir } => {
+                    let key = if dir { "$symlink_dir" } else { "$symlink" };
+                    let mut fields = Map::new();
+                    fields.insert(
+                        key.to_owned(),
+                        Value::String(target.to_string_lossy().into_owned()),
+                    );
+                    Value::Object(fields)
+                }
+            };
+            let (last, parents) = recorded
+                .names
+                .split_last()
+                .expect("recorded entries are within the root");
+            if let Some(mode) = recorded.mode {
+                let mut fields = Map::new();
+                fields.insert("$file".to_owned(), value);
+                fields.insert("$mode".to_owned(), Value::String(format!("{:o}", mode)));
+                value = Value::Object(fields);
+            }
+            let mut dir = &mut spec;
+            for name in parents {
+                let entry = dir
+                    .entry(name.clone())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                dir = match entry {
+                    Value::Object(children) => children,
+                    _ => unreachable!("parents are directories"),
+                };
+            }
+            dir.insert(last.clone(), value);
+        }
+        serde_json::to_string_pretty(&Value::Object(spec))
+            .chain(FixtureError::new(FixtureKind::Spec))
+    }
+}
+
+/// The entries created by successful operations on `temp`, in the order first created.
+///
+/// Directories are left out when something within them is recorded, as creating that creates
+/// them too.
+fn recorded(temp: &TempDir) -> Result<Vec<Recorded>, FixtureError> {
+    let root = temp.path();
+    let mut seen = HashSet::new();
+    let mut paths = Vec::new();
+    for op in temp.operation_log() {
+        if op.error().is_some() || !op.kind().creates_path() {
+            continue;
+        }
+        let mut created = vec![op.path().to_owned()];
+        if op.kind().creates_tree() {
+            created.extend(
+                crate::order::walk(op.path())
+                    .min_depth(1)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.into_path()),
+            );
+        }
+        for path in created {
+            let rel = match path.strip_prefix(root) {
+                Ok(rel) if !rel.as_os_str().is_empty() => rel.to_owned(),
+                _ => continue,
+            };
+            if path.symlink_metadata().is_ok() && seen.insert(rel.clone()) {
+                paths.push(rel);
+            }
+        }
+    }
+
+    let mut recorded = Vec::new();
+    for rel in &paths {
+        let path = root.join(rel);
+        let metadata = path
+            .symlink_metadata()
+            .chain(FixtureError::new(FixtureKind::ReadFile))
+            .map_err(|err| err.with_path(&path))?;
+        let entry = if metadata.file_type().is_symlink() {
+            let target = fs::read_link(&path)
+                .chain(FixtureError::new(FixtureKind::ReadFile))
+                .map_err(|err| err.with_path(&path))?;
+            Entry::Symlink {
+                target: within(root, rel, target),
+                dir: path.is_dir(),
+            }
+        } else if metadata.is_dir() {
+            if paths
+                .iter()
+                .any(|other| other != rel && other.starts_with(rel))
+            {
+                continue;
+            }
+            Entry::Dir
+        } else {
+            let content = fs::read(&path)
+                .chain(FixtureError::new(FixtureKind::ReadFile))
+                .map_err(|err| err.with_path(&path))?;
+            match String::from_utf8(content) {
+                Ok(text) if !text.contains('\0') => Entry::Text(text),
+                Ok(text) => Entry::Binary(text.into_bytes()),
+                Err(err) => Entry::Binary(err.into_bytes()),
+            }
+        };
+        let names = rel
+            .components()
+            .map(|c| {
+                c.as_os_str().to_str().map(str::to_owned).ok_or_else(|| {
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "the name isn't UTF-8",
+                    );
+                    FixtureError::new(FixtureKind::ReadFile)
+                        .chain(err)
+                        .with_path(&path)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        recorded.push(Recorded {
+            names,
+            mode: executable_mode(&metadata, &entry),
+            entry,
+        });
+    }
+    Ok(recorded)
+}
+
+/// `target` of the symlink at `rel`, relative to the link when it points within `root`.
+fn within(root: &path::Path, rel: &path::Path, target: path::PathBuf) -> path::PathBuf {
+    let target_rel = match target.strip_prefix(root) {
+        Ok(target_rel) => target_rel,
+        Err(_) => return target,
+    };
+    let depth = rel.components().count() - 1;
+    let mut relative: path::PathBuf = std::iter::repeat("..").take(depth).collect();
+    relative.push(target_rel);
+    relative
+}
+
+#[cfg(unix)]
+fn executable_mode(metadata: &fs::Metadata, entry: &Entry) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode() & 0o7777;
+    match entry {
+        Entry::Text(_) | Entry::Binary(_) if mode & 0o111 != 0 => Some(mode),
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+fn executable_mode(_metadata: &fs::Metadata, _entry: &Entry) -> Option<u32> {
+    None
+}
+
+/// `content` as a Rust byte string literal.
+fn byte_string(content: &[u8]) -> String {
+    let mut literal = String::from("b\"");
+    for &byte in content {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b'\n' => literal.push_str("\\n"),
+            b'\r' => literal.push_str("\\r"),
+            b'\t' => literal.push_str("\\t"),
+            0x20..=0x7e => literal.push(char::from(byte)),
+            _ => {
+                let _ = write!(literal, "\\x{:02x}", byte);
+            }
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn byte_string_escapes() {
+        assert_eq!(byte_string(b"a\"\\\n\x00\xff"), r#"b"a\"\\\n\x00\xff""#);
+    }
+
+    #[test]
+    fn within_relativizes_links_into_root() {
+        let root = path::Path::new("/tmp/root");
+        assert_eq!(
+            within(root, path::Path::new("a/link"), root.join("b/target.txt")),
+            path::Path::new("../b/target.txt")
+        );
+        assert_eq!(
+            within(root, path::Path::new("link"), "/etc/hosts".into()),
+            path::Path::new("/etc/hosts")
+        );
+    }
+}
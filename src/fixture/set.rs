@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt;
+
+use super::errors::*;
+use super::TempDir;
+
+/// Several fixtures, torn down together in reverse declaration order.
+///
+/// Interdependent fixtures, like a "remote" repository and a "local" checkout pointing at it,
+/// need a deterministic teardown order: later fixtures may depend on earlier ones, so they are
+/// torn down first.  This happens on [`FixtureSet::close`], or on drop, ignoring errors.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::FixtureSet;
+/// use assert_fs::prelude::*;
+///
+/// let mut fixtures = FixtureSet::new();
+/// fixtures.add_dir("remote", assert_fs::TempDir::new().unwrap());
+/// fixtures.add_dir("local", assert_fs::TempDir::new().unwrap());
+/// let remote = fixtures.dir("remote").unwrap().path().to_owned();
+/// fixtures.on_teardown("unregister remote", move || {
+///     // ... e.g. remove `remote` from a config file ...
+///     # let _ = remote;
+///     Ok(())
+/// });
+///
+/// fixtures.dir("local").unwrap().child("README").touch().unwrap();
+///
+/// // Runs the teardown, then closes "local", then "remote"
+/// fixtures.close().unwrap();
+/// ```
+#[derive(Default)]
+pub struct FixtureSet {
+    entries: Vec<(String, Entry)>,
+}
+
+enum Entry {
+    Dir(TempDir),
+    Guard(Box<dyn std::any::Any>),
+    Teardown(Box<dyn FnOnce() -> Result<(), FixtureError>>),
+}
+
+impl FixtureSet {
+    /// An empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Own `dir` as `name`, closing it on teardown.
+    pub fn add_dir<S>(&mut self, name: S, dir: TempDir) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.entries.push((name.into(), Entry::Dir(dir)));
+        self
+    }
+
+    /// Own `guard` as `name`, dropping it on teardown.
+    pub fn add_guard<S, G>(&mut self, name: S, guard: G) -> &mut Self
+    where
+        S: Into<String>,
+        G: 'static,
+    {
+        self.entries
+            .push((name.into(), Entry::Guard(Box::new(guard))));
+        self
+    }
+
+    /// Run `f` as `name` on teardown.
+    pub fn on_teardown<S, F>(&mut self, name: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: FnOnce() -> Result<(), FixtureError> + 'static,
+    {
+        self.entries
+            .push((name.into(), Entry::Teardown(Box::new(f))));
+        self
+    }
+
+    /// Access the directory added as `name`.
+    pub fn dir(&self, name: &str) -> Option<&TempDir> {
+        self.entries.iter().find_map(|(n, entry)| match entry {
+            Entry::Dir(dir) if n == name => Some(dir),
+            _ => None,
+        })
+    }
+
+    /// Tear down every fixture, in reverse order.
+    ///
+    /// # Errors
+    ///
+    /// Teardown continues past failures, returning an error naming every fixture that failed.
+    pub fn close(mut self) -> Result<(), FixtureError> {
+        let mut failures = Vec::new();
+        while let Some((name, entry)) = self.entries.pop() {
+            let result = match entry {
+                Entry::Dir(dir) => dir.close(),
+                Entry::Guard(guard) => {
+                    drop(guard);
+                    Ok(())
+                }
+                Entry::Teardown(f) => f(),
+            };
+            if let Err(err) = result {
+                failures.push((name, err));
+            }
+        }
+        match failures.first() {
+            Some((_, first)) => {
+                Err(FixtureError::new(first.kind()).chain(TeardownFailures(failures)))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for FixtureSet {
+    fn drop(&mut self) {
+        while let Some((_, entry)) = self.entries.pop() {
+            if let Entry::Teardown(f) = entry {
+                let _ = f();
+            }
+        }
+    }
+}
+
+impl fmt::Debug for FixtureSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+/// Every fixture that failed to tear down.
+#[derive(Debug)]
+struct TeardownFailures(Vec<(String, FixtureError)>);
+
+impl fmt::Display for TeardownFailures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, err)) in self.0.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "`{}`: {}", name, err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for TeardownFailures {}
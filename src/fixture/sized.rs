@@ -0,0 +1,333 @@
+use std::io;
+use std::path;
+use std::process;
+
+use super::errors::*;
+use super::TempDir;
+
+/// A [`TempDir`] backed by its own tiny filesystem, so writing past `size` fails with a real
+/// "no space left on device" error.
+///
+/// On Linux this mounts a size-limited `tmpfs`; on macOS, a RAM disk formatted as HFS+.  Both
+/// require the privileges to mount filesystems (usually root), so tests using this are best
+/// kept opt-in.  Other platforms are unsupported.
+///
+/// Without those privileges, [`SizedTempDir::fill`] instead fills the filesystem the directory is
+/// on until only `size` bytes are free; see [`TempDir::with_capacity_limit_or_fill`] to try both.
+///
+/// The filesystem is unmounted, or the space released, when the `SizedTempDir` is dropped; to
+/// handle errors, call [`SizedTempDir::close`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use assert_fs::prelude::*;
+/// use assert_fs::fixture::SizedTempDir;
+///
+/// let temp = SizedTempDir::new(1024 * 1024).unwrap();
+/// let big = vec![0; 2 * 1024 * 1024];
+/// assert!(temp.child("big.bin").write_binary(&big).is_err());
+/// temp.close().unwrap();
+/// ```
+pub struct SizedTempDir {
+    temp: Option<TempDir>,
+    device: Option<String>,
+    mounted: bool,
+    ballast: Option<tempfile::NamedTempFile>,
+}
+
+impl SizedTempDir {
+    /// Mount a filesystem holding at most `size` bytes as a new temporary directory.
+    ///
+    /// # Errors
+    ///
+    /// If the filesystem can not be created or mounted, e.g. for lack of privileges, `Err` is
+    /// returned.
+    pub fn new(size: u64) -> Result<Self, FixtureError> {
+        let temp = TempDir::new()?;
+        let mut sized = Self {
+            temp: Some(temp),
+            device: None,
+            mounted: false,
+            ballast: None,
+        };
+        sized.mount(size)?;
+        Ok(sized)
+    }
+
+    /// Create a new temporary directory, filling the filesystem it's on until only `size` bytes
+    /// are free.
+    ///
+    /// The space is taken by a hidden file next to the directory, preallocated so writes fail
+    /// with a real "no space left on device" error.  The limit is shared with everything else on
+    /// the filesystem, so it's only approximate: other processes writing, or the filesystem's
+    /// own bookkeeping, move it.  Prefer [`SizedTempDir::new`] where it's permitted.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `size` bytes are free, or the free space can't be determined (outside of
+    /// Unix), `Err` is returned.
+    pub fn fill(size: u64) -> Result<Self, FixtureError> {
+        let temp = TempDir::new()?;
+        let parent = temp
+            .path()
+            .parent()
+            .expect("temp directories have a parent")
+            .to_owned();
+        let available = available_space(&parent)?;
+        if available < size {
+            let err = io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "only {} bytes are free, fewer than the {} requested",
+                    available, size
+                ),
+            );
+            return Err(FixtureError::new(FixtureKind::Mount)
+                .chain(err)
+                .with_path(&parent));
+        }
+        let ballast = tempfile::Builder::new()
+            .prefix(".assert_fs-ballast-")
+            .tempfile_in(&parent)
+            .chain(FixtureError::new(FixtureKind::WriteFile))
+            .map_err(|err| err.with_path(&parent))?;
+        allocate(ballast.as_file(), available - size)
+            .chain(FixtureError::new(FixtureKind::WriteFile))
+            .map_err(|err| err.with_path(ballast.path()))?;
+        Ok(Self {
+            temp: Some(temp),
+            device: None,
+            mounted: false,
+            ballast: Some(ballast),
+        })
+    }
+
+    /// Whether the directory is backed by its own filesystem, rather than [filled][Self::fill].
+    pub fn is_mounted(&self) -> bool {
+        self.mounted
+    }
+
+    /// The underlying [`TempDir`].
+    pub fn temp_dir(&self) -> &TempDir {
+        self.temp.as_ref().expect("only taken on close")
+    }
+
+    /// Unmount the filesystem and remove the directory, returning a `Result`.
+    pub fn close(mut self) -> Result<(), FixtureError> {
+        self.unmount()?;
+        let temp = self.temp.take().expect("only taken on close");
+        temp.close()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn mount(&mut self, size: u64) -> Result<(), FixtureError> {
+        let path = self.temp_dir().path().to_owned();
+        run(process::Command::new("mount")
+            .args(["-t", "tmpfs", "-o"])
+            .arg(format!("size={}", size))
+            .arg("tmpfs")
+            .arg(&path))?;
+        self.mounted = true;
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn mount(&mut self, size: u64) -> Result<(), FixtureError> {
+        // `ram://` sizes are in 512-byte sectors
+        let sectors = (size + 511) / 512;
+        let device = run(process::Command::new("hdiutil")
+            .args(["attach", "-nomount"])
+            .arg(format!("ram://{}", sectors)))?;
+        let device = device.trim().to_owned();
+        self.device = Some(device.clone());
+        run(process::Command::new("newfs_hfs").arg(&device))?;
+        let path = self.temp_dir().path().to_owned();
+        run(process::Command::new("mount")
+            .args(["-t", "hfs"])
+            .arg(&device)
+            .arg(&path))?;
+        self.mounted = true;
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn mount(&mut self, _size: u64) -> Result<(), FixtureError> {
+        let err = io::Error::new(
+            io::ErrorKind::Other,
+            "size-limited filesystems are unsupported on this platform",
+        );
+        Err(FixtureError::new(FixtureKind::Mount).chain(err))
+    }
+
+    fn unmount(&mut self) -> Result<(), FixtureError> {
+        if self.mounted {
+            let path = self.temp_dir().path().to_owned();
+            run(process::Command::new("umount").arg(&path))?;
+            self.mounted = false;
+        }
+        if let Some(device) = self.device.take() {
+            run(process::Command::new("hdiutil").args(["detach", &device]))?;
+        }
+        if let Some(ballast) = self.ballast.take() {
+            ballast
+                .close()
+                .chain(FixtureError::new(FixtureKind::Cleanup))?;
+        }
+        Ok(())
+    }
+}
+
+impl TempDir {
+    /// Create a new temporary directory holding at most `bytes`, so writing past it fails with a
+    /// real "no space left on device" error (requires the `small-fs` feature).
+    ///
+    /// Mounts a filesystem of that size, see [`SizedTempDir::new`].
+    ///
+    /// # Errors
+    ///
+    /// If the filesystem can not be created or mounted, e.g. for lack of privileges, `Err` is
+    /// returned.  See [`TempDir::with_capacity_limit_or_fill`] to fall back instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::with_capacity_limit(64 * 1024).unwrap();
+    /// let err = temp.child("big.bin").write_binary(&[0; 1024 * 1024]).unwrap_err();
+    /// println!("{}", err);
+    /// temp.close().unwrap();
+    /// ```
+    pub fn with_capacity_limit(bytes: u64) -> Result<SizedTempDir, FixtureError> {
+        SizedTempDir::new(bytes)
+    }
+
+    /// Like [`TempDir::with_capacity_limit`], falling back to [filling][SizedTempDir::fill] the
+    /// filesystem the directory is on where mounting isn't permitted.
+    ///
+    /// Filling only approximates the limit; check [`SizedTempDir::is_mounted`] for which was
+    /// used.
+    ///
+    /// # Errors
+    ///
+    /// If neither works, the error from filling is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::with_capacity_limit_or_fill(64 * 1024).unwrap();
+    /// let err = temp.child("big.bin").write_binary(&[0; 1024 * 1024]).unwrap_err();
+    /// println!("mounted: {}, {}", temp.is_mounted(), err);
+    /// temp.close().unwrap();
+    /// ```
+    pub fn with_capacity_limit_or_fill(bytes: u64) -> Result<SizedTempDir, FixtureError> {
+        SizedTempDir::new(bytes).or_else(|_| SizedTempDir::fill(bytes))
+    }
+}
+
+impl Drop for SizedTempDir {
+    fn drop(&mut self) {
+        let _ = self.unmount();
+    }
+}
+
+impl AsRef<path::Path> for SizedTempDir {
+    fn as_ref(&self) -> &path::Path {
+        self.temp_dir().path()
+    }
+}
+
+impl std::ops::Deref for SizedTempDir {
+    type Target = TempDir;
+    #[inline]
+    fn deref(&self) -> &TempDir {
+        self.temp_dir()
+    }
+}
+
+/// The bytes available to unprivileged users on the filesystem holding `path`.
+#[cfg(unix)]
+fn available_space(path: &path::Path) -> Result<u64, FixtureError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(io::Error::from)
+        .chain(FixtureError::new(FixtureKind::Mount))
+        .map_err(|err| err.with_path(path))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `c_path` is NUL-terminated and `stat` is a valid out-pointer.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error())
+            .chain(FixtureError::new(FixtureKind::Mount))
+            .map_err(|err| err.with_path(path));
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn available_space(path: &path::Path) -> Result<u64, FixtureError> {
+    let err = io::Error::new(
+        io::ErrorKind::Other,
+        "free space can only be determined on Unix",
+    );
+    Err(FixtureError::new(FixtureKind::Mount)
+        .chain(err)
+        .with_path(path))
+}
+
+/// Reserve `len` bytes of disk for `file`, not just extend it sparsely.
+#[cfg(target_os = "linux")]
+fn allocate(file: &std::fs::File, len: u64) -> io::Result<()> {
+    use std::convert::TryFrom;
+    use std::os::unix::io::AsRawFd;
+
+    let off_len =
+        libc::off_t::try_from(len).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    // SAFETY: the descriptor is open for writing for the duration of the call.
+    match unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, off_len) } {
+        0 => Ok(()),
+        // Not every filesystem supports preallocation
+        libc::EOPNOTSUPP | libc::EINVAL => write_zeros(file, len),
+        errno => Err(io::Error::from_raw_os_error(errno)),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn allocate(file: &std::fs::File, len: u64) -> io::Result<()> {
+    write_zeros(file, len)
+}
+
+fn write_zeros(mut file: &std::fs::File, mut len: u64) -> io::Result<()> {
+    use std::io::Write;
+
+    let chunk = vec![0; 1024 * 1024];
+    while len > 0 {
+        let n = len.min(chunk.len() as u64) as usize;
+        file.write_all(&chunk[..n])?;
+        len -= n as u64;
+    }
+    file.sync_all()
+}
+
+fn run(command: &mut process::Command) -> Result<String, FixtureError> {
+    let output = command
+        .output()
+        .chain(FixtureError::new(FixtureKind::Mount))?;
+    if !output.status.success() {
+        let err = io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "`{:?}` failed with {}: {}",
+                command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        );
+        return Err(FixtureError::new(FixtureKind::Mount).chain(err));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,179 @@
+use std::fs;
+use std::io::Write;
+use std::path;
+use std::sync;
+use std::sync::atomic;
+use std::thread;
+use std::time;
+
+use super::errors::*;
+use super::ChildPath;
+use super::NamedTempFile;
+
+/// Write a file gradually at [`ChildPath`], simulating a slow producer.
+///
+pub trait FileWriteSlowly {
+    /// Write `data` in `chunk_size` pieces, sleeping `delay` after each one, on a background
+    /// thread.
+    ///
+    /// The file is created (empty) before this returns, and every chunk is flushed before the
+    /// delay, so consumers tailing the file see realistic partial content.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("server.log");
+    /// let writer = log
+    ///     .write_slowly(b"line 1\nline 2\n", 7, Duration::from_millis(10))
+    ///     .unwrap();
+    ///
+    /// // ... tail `log` while it grows ...
+    ///
+    /// writer.join().unwrap();
+    /// log.assert("line 1\nline 2\n");
+    /// temp.close().unwrap();
+    /// ```
+    fn write_slowly(
+        &self,
+        data: &[u8],
+        chunk_size: usize,
+        delay: time::Duration,
+    ) -> Result<SlowWrite, FixtureError>;
+
+    /// Write the text `data` in `chunk_size` byte pieces, like
+    /// [`FileWriteSlowly::write_slowly`].
+    ///
+    /// Chunks may split a multi-byte character, as a real producer's writes would.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("config.toml");
+    /// let writer = config
+    ///     .write_str_slowly("port = 8080\n", 4, Duration::from_millis(10))
+    ///     .unwrap();
+    ///
+    /// // ... check the reader copes with a half-written config ...
+    ///
+    /// writer.join().unwrap();
+    /// config.assert("port = 8080\n");
+    /// temp.close().unwrap();
+    /// ```
+    fn write_str_slowly(
+        &self,
+        data: &str,
+        chunk_size: usize,
+        delay: time::Duration,
+    ) -> Result<SlowWrite, FixtureError>;
+}
+
+impl FileWriteSlowly for ChildPath {
+    fn write_slowly(
+        &self,
+        data: &[u8],
+        chunk_size: usize,
+        delay: time::Duration,
+    ) -> Result<SlowWrite, FixtureError> {
+        write_slowly(self.path(), data, chunk_size, delay)
+    }
+
+    fn write_str_slowly(
+        &self,
+        data: &str,
+        chunk_size: usize,
+        delay: time::Duration,
+    ) -> Result<SlowWrite, FixtureError> {
+        write_slowly(self.path(), data.as_bytes(), chunk_size, delay)
+    }
+}
+
+impl FileWriteSlowly for NamedTempFile {
+    fn write_slowly(
+        &self,
+        data: &[u8],
+        chunk_size: usize,
+        delay: time::Duration,
+    ) -> Result<SlowWrite, FixtureError> {
+        write_slowly(self.path(), data, chunk_size, delay)
+    }
+
+    fn write_str_slowly(
+        &self,
+        data: &str,
+        chunk_size: usize,
+        delay: time::Duration,
+    ) -> Result<SlowWrite, FixtureError> {
+        write_slowly(self.path(), data.as_bytes(), chunk_size, delay)
+    }
+}
+
+/// A write in progress, created with [`FileWriteSlowly::write_slowly`].
+///
+/// Dropping the handle lets the write finish in the background.
+#[derive(Debug)]
+pub struct SlowWrite {
+    handle: thread::JoinHandle<Result<(), FixtureError>>,
+    finished: sync::Arc<atomic::AtomicBool>,
+}
+
+impl SlowWrite {
+    /// Whether every chunk has been written.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(atomic::Ordering::Acquire)
+    }
+
+    /// Wait for every chunk to be written.
+    pub fn join(self) -> Result<(), FixtureError> {
+        match self.handle.join() {
+            Ok(result) => result,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+fn write_slowly(
+    path: &path::Path,
+    data: &[u8],
+    chunk_size: usize,
+    delay: time::Duration,
+) -> Result<SlowWrite, FixtureError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+    }
+    let mut file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+    let data = data.to_owned();
+    let chunk_size = chunk_size.max(1);
+    let finished = sync::Arc::new(atomic::AtomicBool::new(false));
+    let handle = {
+        let finished = finished.clone();
+        thread::spawn(move || {
+            let result = write_chunks(&mut file, &data, chunk_size, delay);
+            finished.store(true, atomic::Ordering::Release);
+            result
+        })
+    };
+    Ok(SlowWrite { handle, finished })
+}
+
+fn write_chunks(
+    file: &mut fs::File,
+    data: &[u8],
+    chunk_size: usize,
+    delay: time::Duration,
+) -> Result<(), FixtureError> {
+    for chunk in data.chunks(chunk_size) {
+        file.write_all(chunk)
+            .and_then(|_| file.flush())
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
@@ -0,0 +1,1005 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path;
+use std::time;
+
+use sha2::Digest;
+
+use super::errors::*;
+use super::ChildPath;
+use super::TempDir;
+
+/// A captured state of a directory tree.
+///
+/// Created with [`PathSnapshot::snapshot`].  Alongside the structure of the tree, the snapshot
+/// stashes a copy of every file so the tree can later be restored with
+/// [`PathSnapshot::reset_to`].
+///
+/// Snapshots of large fixtures can be [saved][DirSnapshot::save] once, e.g. by a setup binary,
+/// and [loaded][DirSnapshot::load] by many test processes.
+#[derive(Debug)]
+pub struct DirSnapshot {
+    entries: BTreeMap<path::PathBuf, Entry>,
+    modified: BTreeMap<path::PathBuf, time::SystemTime>,
+    taken_at: time::SystemTime,
+    ignore: Vec<String>,
+    mode: CompareMode,
+    sparse: SparseMode,
+    stash: tempfile::TempDir,
+    materialized: bool,
+}
+
+/// How [`PathSnapshot::reset_to`] decides whether a file changed since the snapshot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompareMode {
+    /// Always hash the content of every file.
+    Strict,
+    /// Trust a matching size and modification time, only hashing content when either differs.
+    ///
+    /// This is an order of magnitude faster for large trees, but misses changes that keep both
+    /// the size and the modification time, e.g. rewrites within the filesystem's timestamp
+    /// granularity.
+    Sampled,
+}
+
+impl Default for CompareMode {
+    fn default() -> Self {
+        CompareMode::Strict
+    }
+}
+
+/// Whether sparse and dense files with identical bytes are considered the same.
+///
+/// A file is sparse when it occupies less space on disk than its length, because the
+/// filesystem doesn't allocate blocks for runs of zeroes ("holes").
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SparseMode {
+    /// Only compare the bytes of files.
+    Ignore,
+    /// A sparse file differs from a dense file with the same bytes.
+    ///
+    /// Resetting to the snapshot restores sparse files with holes again.  This is for tools,
+    /// like backup or copy utilities, that are expected to preserve sparseness.
+    Preserve,
+}
+
+impl Default for SparseMode {
+    fn default() -> Self {
+        SparseMode::Ignore
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Entry {
+    Dir,
+    File {
+        len: u64,
+        digest: Vec<u8>,
+        sparse: bool,
+    },
+    Symlink(path::PathBuf),
+}
+
+impl Entry {
+    fn matches(&self, other: &Entry, mode: SparseMode) -> bool {
+        match (self, other, mode) {
+            (
+                Entry::File { len, digest, .. },
+                Entry::File {
+                    len: other_len,
+                    digest: other_digest,
+                    ..
+                },
+                SparseMode::Ignore,
+            ) => len == other_len && digest == other_digest,
+            _ => self == other,
+        }
+    }
+}
+
+impl DirSnapshot {
+    /// The relative paths captured in the snapshot, in sorted order.
+    pub fn paths(&self) -> impl Iterator<Item = &path::Path> {
+        self.entries.keys().map(|p| p.as_path())
+    }
+
+    /// When the snapshot was taken, according to the fixture's [`Clock`][super::Clock].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::Clock;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let base = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    /// temp.set_clock(Clock::fixed(base));
+    /// assert_eq!(temp.snapshot().unwrap().taken_at(), base);
+    /// temp.close().unwrap();
+    /// ```
+    pub fn taken_at(&self) -> time::SystemTime {
+        self.taken_at
+    }
+
+    /// The copy of the tree, for snapshots taken with [`PathSnapshot::snapshot_materialized`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let snapshot = temp.snapshot_materialized().unwrap();
+    /// temp.assert_dir_eq(snapshot.materialized_path().unwrap());
+    /// temp.close().unwrap();
+    /// ```
+    pub fn materialized_path(&self) -> Option<&path::Path> {
+        self.materialized.then(|| self.stash.path())
+    }
+
+    /// Change how files are compared when resetting to this snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::CompareMode;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let snapshot = temp.snapshot().unwrap().compare_mode(CompareMode::Sampled);
+    ///
+    /// temp.child("foo.txt").write_str("modified!").unwrap();
+    /// temp.reset_to(&snapshot).unwrap();
+    /// temp.child("foo.txt").assert("initial");
+    /// temp.close().unwrap();
+    /// ```
+    pub fn compare_mode(mut self, mode: CompareMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Change whether sparseness is compared when resetting to, or
+    /// [diffing][DirSnapshot::differences] against, this snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::SparseMode;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let snapshot = temp.snapshot().unwrap().sparse_mode(SparseMode::Preserve);
+    /// temp.close().unwrap();
+    /// ```
+    pub fn sparse_mode(mut self, mode: SparseMode) -> Self {
+        self.sparse = mode;
+        self
+    }
+
+    /// The relative paths that differ between this snapshot and `other`, in sorted order.
+    ///
+    /// Files are compared according to this snapshot's [`SparseMode`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "glob")] {
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::SparseMode;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let src = temp.child("src");
+    /// src.child("disk.img").write_binary(&[0; 4096]).unwrap();
+    /// let backup = temp.child("backup");
+    /// backup.copy_from(&src, &["*"]).unwrap();
+    ///
+    /// let expected = src.snapshot().unwrap().sparse_mode(SparseMode::Preserve);
+    /// let actual = backup.snapshot().unwrap();
+    /// assert!(expected.differences(&actual).is_empty());
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    pub fn differences<'a>(&'a self, other: &'a DirSnapshot) -> Vec<&'a path::Path> {
+        let mut differences: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(rel, entry)| {
+                other
+                    .entries
+                    .get(*rel)
+                    .map(|other| !entry.matches(other, self.sparse))
+                    .unwrap_or(true)
+            })
+            .map(|(rel, _)| rel.as_path())
+            .chain(
+                other
+                    .entries
+                    .keys()
+                    .filter(|rel| !self.entries.contains_key(*rel))
+                    .map(|rel| rel.as_path()),
+            )
+            .collect();
+        differences.sort();
+        differences
+    }
+
+    /// Write the snapshot, including the stashed file contents, to `path` in a compact binary
+    /// format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::DirSnapshot;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let saved = assert_fs::NamedTempFile::new("baseline.snap").unwrap();
+    /// temp.snapshot().unwrap().save(saved.path()).unwrap();
+    ///
+    /// temp.child("foo.txt").write_str("modified").unwrap();
+    /// let snapshot = DirSnapshot::load(saved.path()).unwrap();
+    /// temp.reset_to(&snapshot).unwrap();
+    /// temp.child("foo.txt").assert("initial");
+    /// temp.close().unwrap();
+    /// ```
+    pub fn save<P>(&self, path: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        let file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        let mut writer = io::BufWriter::new(file);
+        self.write_to(&mut writer)
+            .and_then(|_| writer.flush())
+            .chain(FixtureError::new(FixtureKind::WriteFile))
+    }
+
+    /// Read a snapshot previously written with [`DirSnapshot::save`].
+    pub fn load<P>(path: P) -> Result<Self, FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        let file = fs::File::open(path).chain(FixtureError::new(FixtureKind::ReadFile))?;
+        let stash = tempfile::TempDir::new().chain(FixtureError::new(FixtureKind::CreateDir))?;
+        read_from(&mut io::BufReader::new(file), stash)
+            .chain(FixtureError::new(FixtureKind::ReadFile))
+    }
+
+    fn write_to(&self, writer: &mut dyn io::Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        write_time(writer, Some(self.taken_at))?;
+        write_u64(writer, self.ignore.len() as u64)?;
+        for pattern in &self.ignore {
+            write_bytes(writer, pattern.as_bytes())?;
+        }
+        write_u64(writer, self.entries.len() as u64)?;
+        for (rel, entry) in &self.entries {
+            match entry {
+                Entry::Dir => {
+                    writer.write_all(&[TAG_DIR])?;
+                    write_path(writer, rel)?;
+                }
+                Entry::File {
+                    len,
+                    digest,
+                    sparse,
+                } => {
+                    writer.write_all(&[TAG_FILE])?;
+                    write_path(writer, rel)?;
+                    write_bytes(writer, digest)?;
+                    writer.write_all(&[u8::from(*sparse)])?;
+                    write_time(writer, self.modified.get(rel).copied())?;
+                    write_u64(writer, *len)?;
+                    let mut data = fs::File::open(self.stash.path().join(rel))?;
+                    let copied = io::copy(&mut data, writer)?;
+                    if copied != *len {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("stashed `{}` changed size", rel.display()),
+                        ));
+                    }
+                }
+                Entry::Symlink(link) => {
+                    writer.write_all(&[TAG_SYMLINK])?;
+                    write_path(writer, rel)?;
+                    write_path(writer, link)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const MAGIC: &[u8] = b"assert_fs-snapshot-2\n";
+/// Without timestamps
+const MAGIC_V1: &[u8] = b"assert_fs-snapshot-1\n";
+const TAG_DIR: u8 = 0;
+const TAG_FILE: u8 = 1;
+const TAG_SYMLINK: u8 = 2;
+
+fn read_from(reader: &mut dyn io::Read, stash: tempfile::TempDir) -> io::Result<DirSnapshot> {
+    let mut magic = vec![0; MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    let timestamps = if magic == MAGIC {
+        true
+    } else if magic == MAGIC_V1 {
+        false
+    } else {
+        return Err(invalid_data("not an `assert_fs` snapshot"));
+    };
+    let taken_at = if timestamps { read_time(reader)? } else { None };
+    let mut ignore = Vec::new();
+    for _ in 0..read_u64(reader)? {
+        let pattern = String::from_utf8(read_bytes(reader)?)
+            .map_err(|_| invalid_data("non-UTF-8 ignore pattern"))?;
+        ignore.push(pattern);
+    }
+    let mut entries = BTreeMap::new();
+    let mut modified = BTreeMap::new();
+    for _ in 0..read_u64(reader)? {
+        let mut tag = [0; 1];
+        reader.read_exact(&mut tag)?;
+        let rel = read_path(reader)?;
+        if rel.is_absolute() || rel.components().any(|c| c == path::Component::ParentDir) {
+            return Err(invalid_data("entry escapes the snapshot root"));
+        }
+        let entry = match tag[0] {
+            TAG_DIR => {
+                fs::create_dir_all(stash.path().join(&rel))?;
+                Entry::Dir
+            }
+            TAG_FILE => {
+                let digest = read_bytes(reader)?;
+                let mut sparse = false;
+                if timestamps {
+                    let mut flag = [0; 1];
+                    reader.read_exact(&mut flag)?;
+                    sparse = flag[0] != 0;
+                    if let Some(mtime) = read_time(reader)? {
+                        modified.insert(rel.clone(), mtime);
+                    }
+                }
+                let len = read_u64(reader)?;
+                let mut data = fs::File::create(stash.path().join(&rel))?;
+                let copied = io::copy(&mut (&mut *reader).take(len), &mut data)?;
+                if copied != len {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                Entry::File {
+                    len,
+                    digest,
+                    sparse,
+                }
+            }
+            TAG_SYMLINK => Entry::Symlink(read_path(reader)?),
+            _ => return Err(invalid_data("unknown entry kind")),
+        };
+        entries.insert(rel, entry);
+    }
+    Ok(DirSnapshot {
+        entries,
+        modified,
+        taken_at: taken_at.unwrap_or(time::UNIX_EPOCH),
+        ignore,
+        mode: CompareMode::default(),
+        sparse: SparseMode::default(),
+        stash,
+        materialized: false,
+    })
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn write_u64(writer: &mut dyn io::Write, value: u64) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn read_u64(reader: &mut dyn io::Read) -> io::Result<u64> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn write_bytes(writer: &mut dyn io::Write, bytes: &[u8]) -> io::Result<()> {
+    write_u64(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+/// Seconds and nanoseconds since the Unix epoch, if known
+fn write_time(writer: &mut dyn io::Write, time: Option<time::SystemTime>) -> io::Result<()> {
+    match time.and_then(|t| t.duration_since(time::UNIX_EPOCH).ok()) {
+        Some(since) => {
+            writer.write_all(&[1])?;
+            write_u64(writer, since.as_secs())?;
+            write_u64(writer, u64::from(since.subsec_nanos()))
+        }
+        None => writer.write_all(&[0]),
+    }
+}
+
+fn read_time(reader: &mut dyn io::Read) -> io::Result<Option<time::SystemTime>> {
+    let mut known = [0; 1];
+    reader.read_exact(&mut known)?;
+    if known[0] == 0 {
+        return Ok(None);
+    }
+    let secs = read_u64(reader)?;
+    let nanos = read_u64(reader)?;
+    if nanos >= 1_000_000_000 {
+        return Err(invalid_data("invalid timestamp"));
+    }
+    let since = time::Duration::new(secs, nanos as u32);
+    time::UNIX_EPOCH
+        .checked_add(since)
+        .map(Some)
+        .ok_or_else(|| invalid_data("invalid timestamp"))
+}
+
+fn read_bytes(reader: &mut dyn io::Read) -> io::Result<Vec<u8>> {
+    let len = read_u64(reader)?;
+    let mut bytes = Vec::new();
+    (&mut *reader).take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+#[cfg(unix)]
+fn write_path(writer: &mut dyn io::Write, path: &path::Path) -> io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    // Raw bytes, so non-UTF-8 names survive
+    write_bytes(writer, path.as_os_str().as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_path(writer: &mut dyn io::Write, path: &path::Path) -> io::Result<()> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| invalid_data("non-Unicode paths can't be saved"))?;
+    // Always `/`-separated, so snapshots are portable
+    write_bytes(writer, path.replace('\\', "/").as_bytes())
+}
+
+#[cfg(unix)]
+fn read_path(reader: &mut dyn io::Read) -> io::Result<path::PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+
+    let bytes = read_bytes(reader)?;
+    Ok(path::PathBuf::from(std::ffi::OsString::from_vec(bytes)))
+}
+
+#[cfg(not(unix))]
+fn read_path(reader: &mut dyn io::Read) -> io::Result<path::PathBuf> {
+    let bytes = read_bytes(reader)?;
+    let path = String::from_utf8(bytes).map_err(|_| invalid_data("non-UTF-8 path"))?;
+    Ok(path::PathBuf::from(path))
+}
+
+/// A point to rewind a [`TempDir`] to, from [`TempDir::checkpoint`].
+///
+/// Holds a [`DirSnapshot`] of the tree, and how far the
+/// [operation log][TempDir::operation_log] had got.
+#[derive(Debug)]
+pub struct Checkpoint {
+    pub(super) snapshot: DirSnapshot,
+    pub(super) operations: usize,
+    pub(super) context: std::sync::Weak<super::context::Context>,
+}
+
+impl Checkpoint {
+    /// The snapshot of the tree at the checkpoint.
+    pub fn snapshot(&self) -> &DirSnapshot {
+        &self.snapshot
+    }
+}
+
+/// Capture and restore the state of a directory tree.
+///
+/// Useful for reusing one expensive fixture across parameterized test cases: populate it once,
+/// snapshot it, and reset back to the snapshot before each case.
+///
+pub trait PathSnapshot {
+    /// Capture the current state of the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let snapshot = temp.snapshot().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    fn snapshot(&self) -> Result<DirSnapshot, FixtureError>;
+
+    /// Capture the current state of the tree, skipping paths matching the glob `ignore`
+    /// patterns.
+    ///
+    /// The patterns are kept with the snapshot, so [`PathSnapshot::reset_to`] also leaves
+    /// matching paths alone.  This is useful for volatile files, like logs or caches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let snapshot = temp.snapshot_ignoring(&["**/*.log", ".cache/**"]).unwrap();
+    ///
+    /// temp.child("run.log").write_str("started").unwrap();
+    /// temp.reset_to(&snapshot).unwrap();
+    /// temp.child("run.log").assert("started");
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn snapshot_ignoring<S>(&self, ignore: &[S]) -> Result<DirSnapshot, FixtureError>
+    where
+        S: AsRef<str>;
+
+    /// Capture the current state of the tree as a full copy, next to it on the same
+    /// filesystem.
+    ///
+    /// The copy, at [`DirSnapshot::materialized_path`], keeps symlinks, permissions, and
+    /// modification times, so the tree can be diffed against it, e.g. with
+    /// [`PathAssertTree::assert_dir_eq`][crate::assert::PathAssertTree::assert_dir_eq], after
+    /// the code under test modified files in place.  Where the filesystem supports it, like
+    /// Btrfs, XFS, or APFS, files are cloned (reflinked), sharing storage until modified.
+    ///
+    /// Files are not hard linked, as the copy would then change along with the tree.  For a
+    /// [`ChildPath`], the copy is created in its parent directory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let db = temp.child("db.bin");
+    /// db.write_binary(b"\x00\x01\x02\x03").unwrap();
+    /// let snapshot = temp.snapshot_materialized().unwrap();
+    ///
+    /// // e.g. the code under test updates a record in place
+    /// db.patch_bytes(2, b"\xff").unwrap();
+    ///
+    /// let before = snapshot.materialized_path().unwrap().join("db.bin");
+    /// assert_eq!(std::fs::read(before).unwrap(), b"\x00\x01\x02\x03");
+    /// temp.reset_to(&snapshot).unwrap();
+    /// db.assert(&b"\x00\x01\x02\x03"[..]);
+    /// temp.close().unwrap();
+    /// ```
+    fn snapshot_materialized(&self) -> Result<DirSnapshot, FixtureError>;
+
+    /// Restore the tree to a previously captured state.
+    ///
+    /// Paths added since the snapshot are deleted, and modified or removed paths are restored.
+    /// Unchanged files are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use predicates::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("foo.txt").write_str("initial").unwrap();
+    /// let snapshot = temp.snapshot().unwrap();
+    ///
+    /// for case in &["one", "two"] {
+    ///     temp.reset_to(&snapshot).unwrap();
+    ///     temp.child("foo.txt").write_str(case).unwrap();
+    ///     temp.child("bar.txt").touch().unwrap();
+    /// }
+    ///
+    /// temp.reset_to(&snapshot).unwrap();
+    /// temp.child("foo.txt").assert("initial");
+    /// temp.child("bar.txt").assert(predicate::path::missing());
+    /// temp.close().unwrap();
+    /// ```
+    fn reset_to(&self, snapshot: &DirSnapshot) -> Result<(), FixtureError>;
+}
+
+impl PathSnapshot for TempDir {
+    fn snapshot(&self) -> Result<DirSnapshot, FixtureError> {
+        snapshot(self.path(), Vec::new())
+    }
+
+    fn snapshot_materialized(&self) -> Result<DirSnapshot, FixtureError> {
+        snapshot_materialized(self.path())
+    }
+
+    #[cfg(feature = "glob")]
+    fn snapshot_ignoring<S>(&self, ignore: &[S]) -> Result<DirSnapshot, FixtureError>
+    where
+        S: AsRef<str>,
+    {
+        let ignore = ignore.iter().map(|p| p.as_ref().to_owned()).collect();
+        snapshot(self.path(), ignore)
+    }
+
+    fn reset_to(&self, snapshot: &DirSnapshot) -> Result<(), FixtureError> {
+        reset_to(self.path(), snapshot)
+    }
+}
+
+impl PathSnapshot for ChildPath {
+    fn snapshot(&self) -> Result<DirSnapshot, FixtureError> {
+        snapshot(self.path(), Vec::new())
+    }
+
+    fn snapshot_materialized(&self) -> Result<DirSnapshot, FixtureError> {
+        snapshot_materialized(self.path())
+    }
+
+    #[cfg(feature = "glob")]
+    fn snapshot_ignoring<S>(&self, ignore: &[S]) -> Result<DirSnapshot, FixtureError>
+    where
+        S: AsRef<str>,
+    {
+        let ignore = ignore.iter().map(|p| p.as_ref().to_owned()).collect();
+        snapshot(self.path(), ignore)
+    }
+
+    fn reset_to(&self, snapshot: &DirSnapshot) -> Result<(), FixtureError> {
+        reset_to(self.path(), snapshot)
+    }
+}
+
+fn snapshot(root: &path::Path, ignore: Vec<String>) -> Result<DirSnapshot, FixtureError> {
+    let stash = tempfile::TempDir::new().chain(FixtureError::new(FixtureKind::CreateDir))?;
+    let taken_at = super::clock::now(root);
+    let (entries, modified) = capture(root, &ignore, None)?;
+    for (rel, entry) in &entries {
+        let target = stash.path().join(rel);
+        match entry {
+            Entry::Dir => {
+                fs::create_dir_all(&target).chain(FixtureError::new(FixtureKind::CreateDir))?;
+            }
+            Entry::File { .. } => {
+                fs::copy(root.join(rel), &target)
+                    .chain(FixtureError::new(FixtureKind::CopyFile))?;
+            }
+            Entry::Symlink(_) => {}
+        }
+    }
+    Ok(DirSnapshot {
+        entries,
+        modified,
+        taken_at,
+        ignore,
+        mode: CompareMode::default(),
+        sparse: SparseMode::default(),
+        stash,
+        materialized: false,
+    })
+}
+
+fn snapshot_materialized(root: &path::Path) -> Result<DirSnapshot, FixtureError> {
+    // Clones only work within a filesystem
+    let parent = root.parent().unwrap_or(root);
+    let stash = tempfile::Builder::new()
+        .prefix(".assert_fs-snapshot.")
+        .tempdir_in(parent)
+        .chain(FixtureError::new(FixtureKind::CreateDir))?;
+    let taken_at = super::clock::now(root);
+    let (entries, modified) = capture(root, &[], None)?;
+    let mut dirs = Vec::new();
+    for (rel, entry) in &entries {
+        let target = stash.path().join(rel);
+        match entry {
+            Entry::Dir => {
+                fs::create_dir_all(&target).chain(FixtureError::new(FixtureKind::CreateDir))?;
+                dirs.push(rel);
+            }
+            Entry::File { .. } => {
+                // `fs::copy` clones where the platform and filesystem support it
+                fs::copy(root.join(rel), &target)
+                    .chain(FixtureError::new(FixtureKind::CopyFile))?;
+                restore_modified(&target, modified.get(rel))?;
+            }
+            Entry::Symlink(link) => {
+                symlink(link, &target).chain(FixtureError::new(FixtureKind::Symlink))?;
+            }
+        }
+    }
+    // Deepest first, so creating children doesn't change a parent's modification time
+    for rel in dirs.into_iter().rev() {
+        let metadata =
+            fs::metadata(root.join(rel)).chain(FixtureError::new(FixtureKind::Permissions))?;
+        let target = stash.path().join(rel);
+        fs::set_permissions(&target, metadata.permissions())
+            .chain(FixtureError::new(FixtureKind::Permissions))?;
+        restore_modified(&target, metadata.modified().ok().as_ref())?;
+    }
+    Ok(DirSnapshot {
+        entries,
+        modified,
+        taken_at,
+        ignore: Vec::new(),
+        mode: CompareMode::default(),
+        sparse: SparseMode::default(),
+        stash,
+        materialized: true,
+    })
+}
+
+fn reset_to(root: &path::Path, snapshot: &DirSnapshot) -> Result<(), FixtureError> {
+    let baseline = match snapshot.mode {
+        CompareMode::Strict => None,
+        CompareMode::Sampled => Some(snapshot),
+    };
+    let (current, current_modified) = capture(root, &snapshot.ignore, baseline)?;
+
+    for (rel, entry) in current.iter().rev() {
+        let expected = snapshot.entries.get(rel);
+        if !expected
+            .map(|e| e.matches(entry, snapshot.sparse))
+            .unwrap_or(false)
+        {
+            remove(&root.join(rel))?;
+        }
+    }
+
+    for (rel, entry) in &snapshot.entries {
+        let target = root.join(rel);
+        if current
+            .get(rel)
+            .map(|c| entry.matches(c, snapshot.sparse))
+            .unwrap_or(false)
+        {
+            if current_modified.get(rel) != snapshot.modified.get(rel) {
+                restore_modified(&target, snapshot.modified.get(rel))?;
+            }
+            continue;
+        }
+        match entry {
+            Entry::Dir => {
+                fs::create_dir_all(&target).chain(FixtureError::new(FixtureKind::CreateDir))?;
+            }
+            Entry::File { sparse: true, .. } if snapshot.sparse == SparseMode::Preserve => {
+                copy_sparse(&snapshot.stash.path().join(rel), &target)
+                    .chain(FixtureError::new(FixtureKind::CopyFile))?;
+                restore_modified(&target, snapshot.modified.get(rel))?;
+            }
+            Entry::File { .. } => {
+                fs::copy(snapshot.stash.path().join(rel), &target)
+                    .chain(FixtureError::new(FixtureKind::CopyFile))?;
+                restore_modified(&target, snapshot.modified.get(rel))?;
+            }
+            Entry::Symlink(link) => {
+                symlink(link, &target).chain(FixtureError::new(FixtureKind::Symlink))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy `source` to `target`, leaving holes for blocks of zeroes.
+fn copy_sparse(source: &path::Path, target: &path::Path) -> io::Result<()> {
+    use std::io::Seek;
+
+    const BLOCK: usize = 4096;
+
+    let mut reader = fs::File::open(source)?;
+    let mut writer = fs::File::create(target)?;
+    mark_sparse(target)?;
+    let mut block = vec![0; BLOCK];
+    let mut len = 0;
+    loop {
+        let read = reader.read(&mut block)?;
+        if read == 0 {
+            break;
+        }
+        len += read as u64;
+        if block[..read].iter().all(|b| *b == 0) {
+            writer.seek(io::SeekFrom::Current(read as i64))?;
+        } else {
+            writer.write_all(&block[..read])?;
+        }
+    }
+    // Covers a trailing hole
+    writer.set_len(len)
+}
+
+#[cfg(windows)]
+fn mark_sparse(path: &path::Path) -> io::Result<()> {
+    // Windows only leaves holes in files flagged as sparse
+    let status = std::process::Command::new("fsutil")
+        .args(["sparse", "setflag"])
+        .arg(path)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "`fsutil sparse setflag` failed",
+        ))
+    }
+}
+
+#[cfg(not(windows))]
+fn mark_sparse(_path: &path::Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Whether the file occupies less space on disk than its length.
+#[cfg(unix)]
+fn is_sparse(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.blocks().saturating_mul(512) < metadata.len()
+}
+
+/// Whether the file is flagged as sparse.
+#[cfg(windows)]
+fn is_sparse(metadata: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    const FILE_ATTRIBUTE_SPARSE_FILE: u32 = 0x200;
+    metadata.file_attributes() & FILE_ATTRIBUTE_SPARSE_FILE != 0
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_sparse(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// Put back the recorded modification time, so timestamp-relative fixtures survive a reset.
+fn restore_modified(
+    path: &path::Path,
+    modified: Option<&time::SystemTime>,
+) -> Result<(), FixtureError> {
+    match modified {
+        Some(modified) => super::clock::set_modified(path, *modified, false),
+        None => Ok(()),
+    }
+}
+
+type Captured = (
+    BTreeMap<path::PathBuf, Entry>,
+    BTreeMap<path::PathBuf, time::SystemTime>,
+);
+
+/// Walk `root`, reusing digests from `baseline` for files whose size and modification time
+/// match it.
+fn capture(
+    root: &path::Path,
+    ignore: &[String],
+    baseline: Option<&DirSnapshot>,
+) -> Result<Captured, FixtureError> {
+    let ignored = ignored(root, ignore)?;
+    let mut entries = BTreeMap::new();
+    let mut modified = BTreeMap::new();
+    for entry in crate::order::walk(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !ignored.contains(e.path()))
+    {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        let rel = entry
+            .path()
+            .strip_prefix(root)
+            .expect("entries to be under `root`")
+            .to_owned();
+        let file_type = entry.file_type();
+        let captured = if file_type.is_dir() {
+            Entry::Dir
+        } else if file_type.is_symlink() {
+            let link = fs::read_link(entry.path()).chain(FixtureError::new(FixtureKind::Walk))?;
+            Entry::Symlink(link)
+        } else {
+            let metadata = entry
+                .metadata()
+                .chain(FixtureError::new(FixtureKind::ReadFile))?;
+            let mtime = metadata.modified().ok();
+            let sparse = is_sparse(&metadata);
+            let unchanged = baseline.and_then(|baseline| {
+                let entry = baseline.entries.get(&rel)?;
+                match entry {
+                    Entry::File { len, digest, .. }
+                        if *len == metadata.len()
+                            && mtime.is_some()
+                            && baseline.modified.get(&rel) == mtime.as_ref() =>
+                    {
+                        Some(Entry::File {
+                            len: *len,
+                            digest: digest.clone(),
+                            sparse,
+                        })
+                    }
+                    _ => None,
+                }
+            });
+            if let Some(mtime) = mtime {
+                modified.insert(rel.clone(), mtime);
+            }
+            match unchanged {
+                Some(entry) => entry,
+                None => {
+                    let data =
+                        fs::read(entry.path()).chain(FixtureError::new(FixtureKind::ReadFile))?;
+                    Entry::File {
+                        len: data.len() as u64,
+                        digest: sha2::Sha256::digest(&data).to_vec(),
+                        sparse,
+                    }
+                }
+            }
+        };
+        entries.insert(rel, captured);
+    }
+    Ok((entries, modified))
+}
+
+#[cfg(feature = "glob")]
+fn ignored(
+    root: &path::Path,
+    ignore: &[String],
+) -> Result<std::collections::HashSet<path::PathBuf>, FixtureError> {
+    if ignore.is_empty() || !root.exists() {
+        return Ok(Default::default());
+    }
+    let walker = globwalk::GlobWalkerBuilder::from_patterns(root, ignore)
+        .build()
+        .chain(FixtureError::new(FixtureKind::Walk))?;
+    let mut ignored = std::collections::HashSet::new();
+    for entry in walker {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        ignored.insert(entry.into_path());
+    }
+    Ok(ignored)
+}
+
+#[cfg(not(feature = "glob"))]
+fn ignored(
+    _root: &path::Path,
+    _ignore: &[String],
+) -> Result<std::collections::HashSet<path::PathBuf>, FixtureError> {
+    Ok(Default::default())
+}
+
+fn remove(path: &path::Path) -> Result<(), FixtureError> {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        // Already removed along with its parent directory.
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(FixtureError::new(FixtureKind::Remove).chain(err)),
+    };
+    if metadata.is_dir() {
+        fs::remove_dir_all(path).chain(FixtureError::new(FixtureKind::Remove))
+    } else {
+        fs::remove_file(path).chain(FixtureError::new(FixtureKind::Remove))
+    }
+}
+
+#[cfg(windows)]
+fn symlink(target: &path::Path, link: &path::Path) -> io::Result<()> {
+    if link
+        .parent()
+        .map(|p| p.join(target))
+        .map(|t| t.is_dir())
+        .unwrap_or(false)
+    {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(not(windows))]
+fn symlink(target: &path::Path, link: &path::Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
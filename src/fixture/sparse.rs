@@ -0,0 +1,194 @@
+use std::fs;
+use std::io;
+use std::io::Seek;
+use std::io::Write;
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+use super::NamedTempFile;
+
+/// The content of a sparse file, for [`FileWriteSparse::write_sparse`]: data at some offsets,
+/// and holes everywhere else.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::SparseLayout;
+///
+/// // 1 GiB, with a header and a trailer
+/// let layout = SparseLayout::new(1 << 30)
+///     .data(0, b"HEADER")
+///     .data((1 << 30) - 7, b"TRAILER");
+/// assert_eq!(layout.len(), 1 << 30);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SparseLayout {
+    len: u64,
+    extents: Vec<(u64, Vec<u8>)>,
+}
+
+impl SparseLayout {
+    /// A file of `len` bytes, all hole.
+    pub fn new(len: u64) -> Self {
+        Self {
+            len,
+            extents: Vec::new(),
+        }
+    }
+
+    /// Write `data` at `offset`.
+    ///
+    /// Data past the end of the file extends it, and later data overwrites earlier data it
+    /// overlaps.
+    pub fn data(mut self, offset: u64, data: &[u8]) -> Self {
+        self.len = self.len.max(offset + data.len() as u64);
+        self.extents.push((offset, data.to_vec()));
+        self
+    }
+
+    /// The file's logical size, in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The ranges not covered by any data, in order.
+    fn holes(&self) -> Vec<(u64, u64)> {
+        let mut covered: Vec<(u64, u64)> = self
+            .extents
+            .iter()
+            .filter(|(_, data)| !data.is_empty())
+            .map(|(offset, data)| (*offset, offset + data.len() as u64))
+            .collect();
+        covered.sort_unstable();
+        let mut holes = Vec::new();
+        let mut pos = 0;
+        for (start, end) in covered {
+            if pos < start {
+                holes.push((pos, start));
+            }
+            pos = pos.max(end);
+        }
+        if pos < self.len {
+            holes.push((pos, self.len));
+        }
+        holes
+    }
+}
+
+/// Write a sparse file at [`ChildPath`].
+pub trait FileWriteSparse {
+    /// Write a file as `layout` describes, creating it and any missing parent directories.
+    ///
+    /// Data is written at its offset, and the rest left as holes, which take no space on
+    /// filesystems supporting sparse files.  On Linux, holes are also punched with
+    /// `FALLOC_FL_PUNCH_HOLE`, in case the filesystem allocated them anyway.
+    ///
+    /// See [`PathAssertMetadata::assert_sparse`][crate::assert::PathAssertMetadata::assert_sparse]
+    /// to check a copy kept its holes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::SparseLayout;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let image = temp.child("disk.img");
+    /// image
+    ///     .write_sparse(&SparseLayout::new(64 << 20).data(0, b"MBR"))
+    ///     .unwrap();
+    /// assert_eq!(image.metadata().unwrap().len(), 64 << 20);
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_sparse(&self, layout: &SparseLayout) -> Result<(), FixtureError>;
+}
+
+impl FileWriteSparse for ChildPath {
+    fn write_sparse(&self, layout: &SparseLayout) -> Result<(), FixtureError> {
+        write_sparse(self.path(), layout)
+    }
+}
+
+impl FileWriteSparse for NamedTempFile {
+    fn write_sparse(&self, layout: &SparseLayout) -> Result<(), FixtureError> {
+        write_sparse(self.path(), layout)
+    }
+}
+
+fn write_sparse(path: &path::Path, layout: &SparseLayout) -> Result<(), FixtureError> {
+    record(OperationKind::WriteSparse, path, Some(layout.len), || {
+        super::tools::ensure_parent_dir(path)?;
+        let mut file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        file.set_len(layout.len)
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        for (offset, data) in &layout.extents {
+            file.seek(io::SeekFrom::Start(*offset))
+                .and_then(|_| file.write_all(data))
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        }
+        for (start, end) in layout.holes() {
+            punch_hole(&file, start, end - start)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn punch_hole(file: &fs::File, offset: u64, len: u64) -> io::Result<()> {
+    use std::convert::TryFrom;
+    use std::os::unix::io::AsRawFd;
+
+    let to_off =
+        |n: u64| libc::off_t::try_from(n).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput));
+    let (offset, len) = (to_off(offset)?, to_off(len)?);
+    // SAFETY: the descriptor is open for writing for the duration of the call.
+    let ret = unsafe {
+        libc::fallocate(
+            file.as_raw_fd(),
+            libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+            offset,
+            len,
+        )
+    };
+    if ret == 0 {
+        return Ok(());
+    }
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        // The range was never written, so is a hole wherever the filesystem supports them
+        Some(libc::EOPNOTSUPP) | Some(libc::ENOSYS) => Ok(()),
+        _ => Err(err),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn punch_hole(_file: &fs::File, _offset: u64, _len: u64) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn holes_skip_data() {
+        let layout = SparseLayout::new(100)
+            .data(10, &[1; 10])
+            .data(15, &[2; 10])
+            .data(50, &[])
+            .data(90, &[3; 20]);
+        assert_eq!(layout.len(), 110);
+        assert_eq!(layout.holes(), vec![(0, 10), (25, 90)]);
+        assert_eq!(SparseLayout::new(8).holes(), vec![(0, 8)]);
+    }
+}
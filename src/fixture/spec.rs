@@ -0,0 +1,205 @@
+use std::error::Error;
+use std::fmt;
+
+use serde_json::Value;
+
+use super::errors::*;
+use super::ChildPath;
+use super::TempDir;
+use super::TreeBuilder;
+
+/// Populate [`TempDir`] from a JSON description of a tree.
+///
+/// The description is an object mapping names to entries:
+/// - a string is a file with that content
+/// - an object is a directory, with its entries
+/// - `{"$symlink": "target"}` is a symlink to a file, `{"$symlink_dir": "target"}` to a directory
+/// - `{"$file": "content", "$mode": "755"}` or `{"$dir": {...}, "$mode": "700"}` also sets the
+///   permissions, in octal, on Unix
+///
+/// Names prefixed with `unix:` or `windows:` are only created on that platform, e.g.
+/// `"unix:run.sh"`.  A bare `"unix:"` or `"windows:"` name is a section, whose entries are
+/// added to the enclosing directory on that platform.
+///
+/// Requires the `spec-json` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.populate_from_str(
+///     r##"{
+///         "src": { "lib.rs": "pub fn answer() -> u32 { 42 }" },
+///         "empty_dir": {},
+///         "unix:run.sh": { "$file": "#!/bin/sh\n", "$mode": "755" },
+///         "windows:": {
+///             "run.cmd": "@echo off\r\n"
+///         }
+///     }"##,
+/// )
+/// .unwrap();
+/// temp.child("src/lib.rs").assert("pub fn answer() -> u32 { 42 }");
+/// temp.close().unwrap();
+/// ```
+pub trait PathPopulate {
+    /// Create the tree described by the JSON `spec`.
+    fn populate_from_str(&self, spec: &str) -> Result<(), FixtureError>;
+}
+
+impl PathPopulate for TempDir {
+    fn populate_from_str(&self, spec: &str) -> Result<(), FixtureError> {
+        populate_from_str(self.path(), spec)
+    }
+}
+
+impl PathPopulate for ChildPath {
+    fn populate_from_str(&self, spec: &str) -> Result<(), FixtureError> {
+        populate_from_str(self.path(), spec)
+    }
+}
+
+/// A description that isn't a valid tree.
+#[derive(Debug)]
+struct SpecError(String);
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for SpecError {}
+
+fn invalid(message: String) -> FixtureError {
+    FixtureError::new(FixtureKind::Spec).chain(SpecError(message))
+}
+
+fn populate_from_str(root: &std::path::Path, spec: &str) -> Result<(), FixtureError> {
+    let spec: Value = serde_json::from_str(spec).chain(FixtureError::new(FixtureKind::Spec))?;
+    let entries = match spec {
+        Value::Object(entries) => entries,
+        other => return Err(invalid(format!("expected an object, found `{}`", other))),
+    };
+    let mut builder = TreeBuilder::new(root);
+    populate(&mut builder, &entries);
+    builder.finish()
+}
+
+fn populate<'b>(
+    builder: &'b mut TreeBuilder,
+    entries: &serde_json::Map<String, Value>,
+) -> &'b mut TreeBuilder {
+    for (key, entry) in entries {
+        let name = match platform(key) {
+            Some((true, name)) => name,
+            Some((false, _)) => continue,
+            None => key.as_str(),
+        };
+        if name.is_empty() {
+            match entry {
+                Value::Object(children) => {
+                    populate(builder, children);
+                }
+                other => {
+                    builder.fail(
+                        key,
+                        invalid(format!(
+                            "expected an object for a section, found `{}`",
+                            other
+                        )),
+                    );
+                }
+            }
+            continue;
+        }
+        match entry {
+            Value::String(content) => {
+                builder.file(name, content);
+            }
+            Value::Object(fields) if fields.keys().any(|k| k.starts_with('$')) => {
+                special(builder, name, fields);
+            }
+            Value::Object(children) => {
+                builder.dir(name, |d| populate(d, children));
+            }
+            other => {
+                builder.fail(
+                    name,
+                    invalid(format!("expected a string or object, found `{}`", other)),
+                );
+            }
+        }
+    }
+    builder
+}
+
+/// Split a `unix:` or `windows:` prefix off of `key`, with whether it applies to this platform.
+fn platform(key: &str) -> Option<(bool, &str)> {
+    if let Some(name) = key.strip_prefix("unix:") {
+        Some((cfg!(unix), name))
+    } else {
+        key.strip_prefix("windows:")
+            .map(|name| (cfg!(windows), name))
+    }
+}
+
+/// Create an entry described with `$` fields.
+fn special(builder: &mut TreeBuilder, name: &str, fields: &serde_json::Map<String, Value>) {
+    match (
+        fields.get("$file"),
+        fields.get("$dir"),
+        fields.get("$symlink"),
+        fields.get("$symlink_dir"),
+    ) {
+        (Some(Value::String(content)), None, None, None) => {
+            builder.file(name, content);
+        }
+        (None, Some(Value::Object(children)), None, None) => {
+            builder.dir(name, |d| populate(d, children));
+        }
+        (None, None, Some(Value::String(target)), None) => {
+            builder.symlink_to_file(name, target);
+        }
+        (None, None, None, Some(Value::String(target))) => {
+            builder.symlink_to_dir(name, target);
+        }
+        _ => {
+            builder.fail(
+                name,
+                invalid(
+                    "expected one of `$file`, `$dir`, `$symlink`, or `$symlink_dir`".to_owned(),
+                ),
+            );
+            return;
+        }
+    }
+    match fields.get("$mode") {
+        #[cfg(unix)]
+        Some(Value::String(mode)) => match u32::from_str_radix(mode, 8) {
+            Ok(mode) => {
+                builder.mode(name, mode);
+            }
+            Err(err) => {
+                builder.fail(
+                    name,
+                    invalid(format!("invalid `$mode` `{}`: {}", mode, err)),
+                );
+            }
+        },
+        // Permissions don't map to other platforms
+        #[cfg(not(unix))]
+        Some(Value::String(_)) => {}
+        Some(other) => {
+            builder.fail(
+                name,
+                invalid(format!(
+                    "expected an octal string for `$mode`, found `{}`",
+                    other
+                )),
+            );
+        }
+        None => {}
+    }
+}
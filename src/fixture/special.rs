@@ -0,0 +1,120 @@
+use std::ffi;
+use std::os::unix::ffi::OsStrExt;
+use std::path;
+
+use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
+use super::ChildPath;
+
+/// The longest path, in bytes, a unix socket can be bound to (`sun_path`, excluding the
+/// terminating NUL).
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub const MAX_SOCKET_PATH_LEN: usize = 107;
+/// The longest path, in bytes, a unix socket can be bound to (`sun_path`, excluding the
+/// terminating NUL).
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub const MAX_SOCKET_PATH_LEN: usize = 103;
+
+/// Create named pipes and unix sockets at [`ChildPath`] (Unix only).
+///
+/// See [`PathAssertMetadata::assert_is_fifo`][crate::assert::PathAssertMetadata::assert_is_fifo]
+/// and [`PathAssertMetadata::assert_is_socket`][crate::assert::PathAssertMetadata::assert_is_socket]
+/// to check what the program created.
+pub trait PathSpecialFile {
+    /// Create a named pipe (FIFO) at [`ChildPath`] with the permission bits `mode`, creating
+    /// any missing parent directories.
+    ///
+    /// The process umask applies to `mode`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let commands = temp.child("run/commands");
+    /// commands.mkfifo(0o600).unwrap();
+    /// commands.assert_is_fifo();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn mkfifo(&self, mode: u32) -> Result<(), FixtureError>;
+
+    /// Prepare [`ChildPath`] for a unix socket to be bound to, creating any missing parent
+    /// directories.
+    ///
+    /// Nothing is created at the path itself, as binding fails if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Fails if something exists at the path, or it's longer than [`MAX_SOCKET_PATH_LEN`],
+    /// which deeply nested temporary directories can easily be.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let socket = temp.child("run/daemon.sock");
+    /// socket.reserve_socket().unwrap();
+    ///
+    /// // ... start the daemon, listening on `socket` ...
+    /// # let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+    ///
+    /// socket.assert_is_socket();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn reserve_socket(&self) -> Result<(), FixtureError>;
+}
+
+impl PathSpecialFile for ChildPath {
+    fn mkfifo(&self, mode: u32) -> Result<(), FixtureError> {
+        mkfifo(self.path(), mode)
+    }
+
+    fn reserve_socket(&self) -> Result<(), FixtureError> {
+        reserve_socket(self.path())
+    }
+}
+
+fn mkfifo(path: &path::Path, mode: u32) -> Result<(), FixtureError> {
+    record(OperationKind::Mkfifo, path, None, || {
+        super::tools::ensure_parent_dir(path)?;
+        let c_path = ffi::CString::new(path.as_os_str().as_bytes())
+            .map_err(std::io::Error::from)
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        // SAFETY: `c_path` is a valid NUL-terminated string for the duration of the call
+        let result = unsafe { libc::mkfifo(c_path.as_ptr(), mode as libc::mode_t) };
+        if result != 0 {
+            return Err(std::io::Error::last_os_error())
+                .chain(FixtureError::new(FixtureKind::WriteFile));
+        }
+        Ok(())
+    })
+}
+
+fn reserve_socket(path: &path::Path) -> Result<(), FixtureError> {
+    let len = path.as_os_str().len();
+    if MAX_SOCKET_PATH_LEN < len {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "{} bytes is longer than sockets allow ({} bytes), use a shorter path",
+                len, MAX_SOCKET_PATH_LEN
+            ),
+        );
+        return Err(FixtureError::new(FixtureKind::WriteFile)
+            .chain(err)
+            .with_path(path));
+    }
+    if path.symlink_metadata().is_ok() {
+        let err = std::io::Error::new(std::io::ErrorKind::AlreadyExists, "path already exists");
+        return Err(FixtureError::new(FixtureKind::WriteFile)
+            .chain(err)
+            .with_path(path));
+    }
+    super::tools::ensure_parent_dir(path).map_err(|err| err.with_path(path))
+}
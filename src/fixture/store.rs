@@ -0,0 +1,212 @@
+use std::fs;
+use std::io;
+use std::path;
+use std::time;
+
+use sha2::Digest;
+
+use super::errors::*;
+
+/// A persistent, content-addressed cache of fixture assets, shared between test processes.
+///
+/// Downloading, extracting, or generating large fixtures in every test is slow.  The store keeps
+/// them across runs:
+/// - blobs, added with [`FixtureStore::insert`], are addressed by the SHA-256 of their content
+/// - trees, created with [`FixtureStore::get_or_create`], are addressed by the SHA-256 of a
+///   key describing how they were made, like a URL or a generator version
+///
+/// Entries are added atomically, so concurrent processes never see partial entries.  Using an
+/// entry marks it as recently used; [`FixtureStore::gc`] removes entries that haven't been.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "glob")] {
+/// use assert_fs::fixture::FixtureStore;
+/// use assert_fs::prelude::*;
+/// use std::time::Duration;
+///
+/// # let cache = assert_fs::TempDir::new().unwrap();
+/// // Usually `FixtureStore::in_target_dir()`, to be shared between runs
+/// let store = FixtureStore::open(cache.path()).unwrap();
+/// let corpus = store
+///     .get_or_create("corpus-v1", |dir| {
+///         // ... e.g. download and extract an archive ...
+///         std::fs::write(dir.join("seed.bin"), b"\x00\x01").unwrap();
+///         Ok(())
+///     })
+///     .unwrap();
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.copy_from(&corpus, &["*"]).unwrap();
+/// temp.close().unwrap();
+///
+/// store.gc(Duration::from_secs(30 * 24 * 60 * 60)).unwrap();
+/// # cache.close().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FixtureStore {
+    root: path::PathBuf,
+}
+
+impl FixtureStore {
+    /// Open the store at `root`, creating it if needed.
+    pub fn open<P>(root: P) -> Result<Self, FixtureError>
+    where
+        P: Into<path::PathBuf>,
+    {
+        let root = root.into();
+        fs::create_dir_all(root.join(STAGING)).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(Self { root })
+    }
+
+    /// Open the store at `$ASSERT_FS_STORE_DIR`, falling back to `assert_fs-store` in Cargo's
+    /// target directory.
+    ///
+    /// # Errors
+    ///
+    /// Fails if neither location is known, e.g. when not running under Cargo.
+    pub fn in_target_dir() -> Result<Self, FixtureError> {
+        let root = match std::env::var_os("ASSERT_FS_STORE_DIR") {
+            Some(root) => path::PathBuf::from(root),
+            None => match super::dir::target_dir() {
+                Some(target) => target.join("assert_fs-store"),
+                None => {
+                    let err = io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "no target directory, set `ASSERT_FS_STORE_DIR`",
+                    );
+                    return Err(FixtureError::new(FixtureKind::CreateDir).chain(err));
+                }
+            },
+        };
+        Self::open(root)
+    }
+
+    /// The store's location.
+    pub fn path(&self) -> &path::Path {
+        &self.root
+    }
+
+    /// Add `data` as a blob, returning its SHA-256.
+    pub fn insert(&self, data: &[u8]) -> Result<String, FixtureError> {
+        let hash = sha256(data);
+        let dest = self.entry(BLOBS, &hash);
+        if touch(&dest) {
+            return Ok(hash);
+        }
+        let mut staged = tempfile::NamedTempFile::new_in(self.root.join(STAGING))
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        io::Write::write_all(&mut staged, data).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        create_parent(&dest)?;
+        staged
+            .persist(&dest)
+            .map_err(|err| err.error)
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        Ok(hash)
+    }
+
+    /// The path of the blob with the SHA-256 `hash`, if present.
+    pub fn get(&self, hash: &str) -> Option<path::PathBuf> {
+        let hash = hash.to_ascii_lowercase();
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let path = self.entry(BLOBS, &hash);
+        if touch(&path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// The path of the directory created for `key`, calling `create` to populate it if absent.
+    ///
+    /// `create` is given an empty staging directory, which is moved into the store once it
+    /// succeeds.  If several processes race to create the same key, one wins and the others'
+    /// directories are discarded.
+    pub fn get_or_create<F>(&self, key: &str, create: F) -> Result<path::PathBuf, FixtureError>
+    where
+        F: FnOnce(&path::Path) -> Result<(), FixtureError>,
+    {
+        let dest = self.entry(TREES, &sha256(key.as_bytes()));
+        if touch(&dest) {
+            return Ok(dest);
+        }
+        let staged = tempfile::TempDir::new_in(self.root.join(STAGING))
+            .chain(FixtureError::new(FixtureKind::CreateDir))?;
+        create(staged.path())?;
+        create_parent(&dest)?;
+        let staged = staged.into_path();
+        if let Err(err) = fs::rename(&staged, &dest) {
+            let _ = fs::remove_dir_all(&staged);
+            if !dest.is_dir() {
+                return Err(FixtureError::new(FixtureKind::CreateDir).chain(err));
+            }
+        }
+        Ok(dest)
+    }
+
+    /// Remove entries not used within `max_age`, returning how many were removed.
+    pub fn gc(&self, max_age: time::Duration) -> Result<usize, FixtureError> {
+        let now = time::SystemTime::now();
+        let mut removed = 0;
+        for kind in [BLOBS, TREES, STAGING] {
+            let depth = if kind == STAGING { 1 } else { 2 };
+            let dir = self.root.join(kind);
+            if !dir.exists() {
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(&dir)
+                .min_depth(depth)
+                .max_depth(depth)
+            {
+                let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+                let unused = entry
+                    .metadata()
+                    .ok()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .map_or(false, |age| max_age < age);
+                if !unused {
+                    continue;
+                }
+                if entry.file_type().is_dir() {
+                    fs::remove_dir_all(entry.path())
+                } else {
+                    fs::remove_file(entry.path())
+                }
+                .chain(FixtureError::new(FixtureKind::Remove))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn entry(&self, kind: &str, hash: &str) -> path::PathBuf {
+        self.root.join(kind).join(&hash[..2]).join(hash)
+    }
+}
+
+const BLOBS: &str = "blobs";
+const TREES: &str = "trees";
+const STAGING: &str = "staging";
+
+fn sha256(data: &[u8]) -> String {
+    crate::hex::encode(&sha2::Sha256::digest(data))
+}
+
+/// Mark `path` as recently used, returning whether it exists.
+fn touch(path: &path::Path) -> bool {
+    if !path.exists() {
+        return false;
+    }
+    let _ = super::clock::set_modified(path, time::SystemTime::now(), false);
+    true
+}
+
+fn create_parent(path: &path::Path) -> Result<(), FixtureError> {
+    let parent = path.parent().expect("entries are within the store");
+    fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))
+}
@@ -0,0 +1,481 @@
+use std::fs;
+use std::path;
+
+use super::errors::*;
+use super::ChildPath;
+use super::TempDir;
+
+/// The longest path, in bytes, the platform accepts (excluding the terminating NUL).
+#[cfg(target_os = "linux")]
+pub const MAX_PATH_LEN: usize = 4095;
+/// The longest path, in bytes, the platform accepts (excluding the terminating NUL).
+#[cfg(windows)]
+pub const MAX_PATH_LEN: usize = 259;
+/// The longest path, in bytes, the platform accepts (excluding the terminating NUL).
+#[cfg(not(any(target_os = "linux", windows)))]
+pub const MAX_PATH_LEN: usize = 1023;
+
+/// The longest path component (file name), in bytes, most filesystems accept.
+pub const MAX_COMPONENT_LEN: usize = 255;
+
+/// File names that Windows reserves or rejects, though most other platforms accept them.
+pub const WINDOWS_SPECIAL_NAMES: &[&str] = &[
+    "CON",
+    "PRN",
+    "AUX",
+    "NUL",
+    "COM1",
+    "LPT1",
+    "aux.txt",
+    "nul.tar.gz",
+    "trailing.",
+    "trailing ",
+    "a<b",
+    "a>b",
+    "a:b",
+    "a\"b",
+    "a|b",
+    "a?b",
+    "a*b",
+];
+
+/// File names with characters that must be percent-encoded in URLs, or that URL handling
+/// commonly mangles.
+///
+/// `?` is rejected by Windows, see [`PathStress::try_url_unsafe_names`].
+pub const URL_UNSAFE_NAMES: &[&str] = &[
+    "with space.txt",
+    "100%.txt",
+    "%20literal.txt",
+    "hash#fragment.txt",
+    "query?x=1.txt",
+    "amp&semi;.txt",
+    "plus+sign.txt",
+    "brackets[1].txt",
+    "at@host.txt",
+    "caf\u{e9}.txt",
+];
+
+/// What the platform did when asked to create a file by name, see
+/// [`PathStress::try_special_name`].
+pub enum NameOutcome {
+    /// A file was created under exactly the requested name.
+    Created(ChildPath),
+    /// Creation succeeded but no entry with the requested name exists, e.g. because it opened a
+    /// device or the name was trimmed.
+    Altered(ChildPath),
+    /// The platform refused the name.
+    Rejected(FixtureError),
+}
+
+impl std::fmt::Debug for NameOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameOutcome::Created(child) => f.debug_tuple("Created").field(&child.path()).finish(),
+            NameOutcome::Altered(child) => f.debug_tuple("Altered").field(&child.path()).finish(),
+            NameOutcome::Rejected(err) => f.debug_tuple("Rejected").field(err).finish(),
+        }
+    }
+}
+
+impl NameOutcome {
+    /// Whether the file exists under exactly the requested name.
+    pub fn is_created(&self) -> bool {
+        matches!(self, NameOutcome::Created(_))
+    }
+}
+
+/// What the filesystem did when asked to create two names that differ only in case, see
+/// [`PathStress::try_case_collision`].
+pub enum CaseOutcome {
+    /// Both files exist side by side, as on most Linux filesystems.
+    Distinct(ChildPath, ChildPath),
+    /// The second name resolved to the first file, as on default macOS and Windows
+    /// filesystems, which are case-insensitive but case-preserving.  Holds the first file, under
+    /// the name the filesystem stored.
+    Collided(ChildPath),
+    /// The platform refused to create either file.
+    Rejected(FixtureError),
+}
+
+impl std::fmt::Debug for CaseOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaseOutcome::Distinct(first, second) => f
+                .debug_tuple("Distinct")
+                .field(&first.path())
+                .field(&second.path())
+                .finish(),
+            CaseOutcome::Collided(child) => f.debug_tuple("Collided").field(&child.path()).finish(),
+            CaseOutcome::Rejected(err) => f.debug_tuple("Rejected").field(err).finish(),
+        }
+    }
+}
+
+impl CaseOutcome {
+    /// Whether both files exist side by side.
+    pub fn is_distinct(&self) -> bool {
+        matches!(self, CaseOutcome::Distinct(..))
+    }
+}
+
+/// Generate paths that stress path-handling code.
+///
+pub trait PathStress {
+    /// Create a chain of `depth` nested directories, each named with `component_len` characters,
+    /// returning the innermost directory.
+    ///
+    /// `component_len` is capped at [`MAX_COMPONENT_LEN`], and the chain stops early rather than
+    /// exceeding [`MAX_PATH_LEN`], so the result is the deepest path the platform can handle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let deep = temp.make_deep_path(50, 8).unwrap();
+    /// deep.child("leaf.txt").touch().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    fn make_deep_path(&self, depth: usize, component_len: usize)
+        -> Result<ChildPath, FixtureError>;
+
+    /// Create a file whose full path is exactly `len` bytes long, creating intermediate
+    /// directories as needed.
+    ///
+    /// Paths beyond [`MAX_PATH_LEN`] are still attempted, so the caller gets the error the
+    /// platform reports for them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::MAX_PATH_LEN;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let under = temp.child("under").make_path_of_len(MAX_PATH_LEN - 1).unwrap();
+    /// let exact = temp.child("exact").make_path_of_len(MAX_PATH_LEN).unwrap();
+    /// assert_eq!(exact.path().as_os_str().len(), MAX_PATH_LEN);
+    /// assert!(temp.child("over").make_path_of_len(MAX_PATH_LEN + 1).is_err());
+    /// temp.close().unwrap();
+    /// ```
+    fn make_path_of_len(&self, len: usize) -> Result<ChildPath, FixtureError>;
+
+    /// Attempt to create an empty file called `name`, reporting how the platform handled it.
+    ///
+    /// Useful for checking a tool's own file name sanitization against real OS behaviour.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::NameOutcome;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// match temp.try_special_name("aux.txt") {
+    ///     NameOutcome::Created(path) => assert!(cfg!(not(windows)), "{}", path.display()),
+    ///     NameOutcome::Altered(_) | NameOutcome::Rejected(_) => assert!(cfg!(windows)),
+    /// }
+    /// temp.close().unwrap();
+    /// ```
+    fn try_special_name(&self, name: &str) -> NameOutcome;
+
+    /// Attempt to create each of [`WINDOWS_SPECIAL_NAMES`], see
+    /// [`PathStress::try_special_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// for (name, outcome) in temp.try_windows_special_names() {
+    ///     println!("{:?}: {:?}", name, outcome);
+    /// }
+    /// temp.close().unwrap();
+    /// ```
+    fn try_windows_special_names(&self) -> Vec<(&'static str, NameOutcome)>;
+
+    /// Create an empty file whose name is not valid Unicode, returning its path.
+    ///
+    /// On Unix the name contains invalid UTF-8 bytes; on Windows, an unpaired UTF-16 surrogate.
+    /// Filesystems that only store Unicode names, like APFS, reject it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// if let Ok(file) = temp.make_non_unicode_file() {
+    ///     assert!(file.path().to_str().is_none());
+    /// }
+    /// temp.close().unwrap();
+    /// ```
+    fn make_non_unicode_file(&self) -> Result<ChildPath, FixtureError>;
+
+    /// Attempt to create empty files called `name` and `other`, names which differ only in
+    /// case, reporting whether the filesystem kept them apart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use assert_fs::fixture::CaseOutcome;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// match temp.try_case_collision("Readme.md", "README.md") {
+    ///     CaseOutcome::Distinct(..) => {
+    ///         // ... expect the tool to report a collision when copying to Windows ...
+    ///     }
+    ///     CaseOutcome::Collided(existing) => {
+    ///         assert_eq!(existing.file_name().unwrap(), "Readme.md");
+    ///     }
+    ///     CaseOutcome::Rejected(err) => panic!("{}", err),
+    /// }
+    /// temp.close().unwrap();
+    /// ```
+    fn try_case_collision(&self, name: &str, other: &str) -> CaseOutcome;
+
+    /// Attempt to create each of [`URL_UNSAFE_NAMES`], see [`PathStress::try_special_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// for (name, outcome) in temp.try_url_unsafe_names() {
+    ///     if outcome.is_created() {
+    ///         // ... convert to a `file://` URL and back ...
+    ///         # let _ = name;
+    ///     }
+    /// }
+    /// temp.close().unwrap();
+    /// ```
+    fn try_url_unsafe_names(&self) -> Vec<(&'static str, NameOutcome)>;
+}
+
+impl PathStress for TempDir {
+    fn make_deep_path(
+        &self,
+        depth: usize,
+        component_len: usize,
+    ) -> Result<ChildPath, FixtureError> {
+        make_deep_path(self.path(), depth, component_len)
+    }
+
+    fn make_path_of_len(&self, len: usize) -> Result<ChildPath, FixtureError> {
+        make_path_of_len(self.path(), len)
+    }
+
+    fn try_special_name(&self, name: &str) -> NameOutcome {
+        try_special_name(self.path(), name)
+    }
+
+    fn try_windows_special_names(&self) -> Vec<(&'static str, NameOutcome)> {
+        try_windows_special_names(self.path())
+    }
+
+    fn make_non_unicode_file(&self) -> Result<ChildPath, FixtureError> {
+        make_non_unicode_file(self.path())
+    }
+
+    fn try_case_collision(&self, name: &str, other: &str) -> CaseOutcome {
+        try_case_collision(self.path(), name, other)
+    }
+
+    fn try_url_unsafe_names(&self) -> Vec<(&'static str, NameOutcome)> {
+        try_url_unsafe_names(self.path())
+    }
+}
+
+impl PathStress for ChildPath {
+    fn make_deep_path(
+        &self,
+        depth: usize,
+        component_len: usize,
+    ) -> Result<ChildPath, FixtureError> {
+        make_deep_path(self.path(), depth, component_len)
+    }
+
+    fn make_path_of_len(&self, len: usize) -> Result<ChildPath, FixtureError> {
+        make_path_of_len(self.path(), len)
+    }
+
+    fn try_special_name(&self, name: &str) -> NameOutcome {
+        try_special_name(self.path(), name)
+    }
+
+    fn try_windows_special_names(&self) -> Vec<(&'static str, NameOutcome)> {
+        try_windows_special_names(self.path())
+    }
+
+    fn make_non_unicode_file(&self) -> Result<ChildPath, FixtureError> {
+        make_non_unicode_file(self.path())
+    }
+
+    fn try_case_collision(&self, name: &str, other: &str) -> CaseOutcome {
+        try_case_collision(self.path(), name, other)
+    }
+
+    fn try_url_unsafe_names(&self) -> Vec<(&'static str, NameOutcome)> {
+        try_url_unsafe_names(self.path())
+    }
+}
+
+fn path_len(path: &path::Path) -> usize {
+    path.as_os_str().len()
+}
+
+fn make_deep_path(
+    root: &path::Path,
+    depth: usize,
+    component_len: usize,
+) -> Result<ChildPath, FixtureError> {
+    let component = "d".repeat(component_len.clamp(1, MAX_COMPONENT_LEN));
+    let mut path = root.to_owned();
+    for _ in 0..depth {
+        // Leave room for the separator
+        if MAX_PATH_LEN < path_len(&path) + 1 + component.len() {
+            break;
+        }
+        path.push(&component);
+    }
+    fs::create_dir_all(&path).chain(FixtureError::new(FixtureKind::CreateDir))?;
+    Ok(ChildPath::new(path))
+}
+
+pub(super) fn make_path_of_len(root: &path::Path, len: usize) -> Result<ChildPath, FixtureError> {
+    // Each component costs its length plus a separator
+    let mut rest = len.saturating_sub(path_len(root));
+    if rest < 2 {
+        let err = std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("`{}` is already {} bytes", root.display(), path_len(root)),
+        );
+        return Err(FixtureError::new(FixtureKind::WriteFile).chain(err));
+    }
+
+    let mut path = root.to_owned();
+    while 1 + MAX_COMPONENT_LEN < rest {
+        // Leave room for at least a one byte file name
+        let n = MAX_COMPONENT_LEN.min(rest - 3);
+        path.push("d".repeat(n));
+        rest -= 1 + n;
+    }
+    let parent = path.clone();
+    path.push("f".repeat(rest - 1));
+
+    fs::create_dir_all(&parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+    fs::File::create(&path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+    Ok(ChildPath::new(path))
+}
+
+fn try_special_name(root: &path::Path, name: &str) -> NameOutcome {
+    if let Err(err) = fs::create_dir_all(root).chain(FixtureError::new(FixtureKind::CreateDir)) {
+        return NameOutcome::Rejected(err);
+    }
+    let path = root.join(name);
+    if let Err(err) = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .chain(FixtureError::new(FixtureKind::WriteFile))
+    {
+        return NameOutcome::Rejected(err);
+    }
+    // Windows may silently open a device or trim the name, so check what actually landed
+    let listed = fs::read_dir(root)
+        .map(|entries| entries.flatten().any(|entry| entry.file_name() == name))
+        .unwrap_or(false);
+    if listed {
+        NameOutcome::Created(ChildPath::new(path))
+    } else {
+        NameOutcome::Altered(ChildPath::new(path))
+    }
+}
+
+fn try_windows_special_names(root: &path::Path) -> Vec<(&'static str, NameOutcome)> {
+    WINDOWS_SPECIAL_NAMES
+        .iter()
+        .map(|name| (*name, try_special_name(root, name)))
+        .collect()
+}
+
+fn try_url_unsafe_names(root: &path::Path) -> Vec<(&'static str, NameOutcome)> {
+    URL_UNSAFE_NAMES
+        .iter()
+        .map(|name| (*name, try_special_name(root, name)))
+        .collect()
+}
+
+#[cfg(unix)]
+fn non_unicode_name() -> std::ffi::OsString {
+    use std::os::unix::ffi::OsStringExt;
+
+    std::ffi::OsString::from_vec(b"non-unicode-\xff\xfe.txt".to_vec())
+}
+
+#[cfg(windows)]
+fn non_unicode_name() -> std::ffi::OsString {
+    use std::os::windows::ffi::OsStringExt;
+
+    let mut wide: Vec<u16> = "non-unicode-".encode_utf16().collect();
+    wide.push(0xD800);
+    wide.extend(".txt".encode_utf16());
+    std::ffi::OsString::from_wide(&wide)
+}
+
+fn make_non_unicode_file(root: &path::Path) -> Result<ChildPath, FixtureError> {
+    fs::create_dir_all(root).chain(FixtureError::new(FixtureKind::CreateDir))?;
+    let path = root.join(non_unicode_name());
+    fs::File::create(&path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+    Ok(ChildPath::new(path))
+}
+
+fn try_case_collision(root: &path::Path, name: &str, other: &str) -> CaseOutcome {
+    let first = match try_special_name(root, name) {
+        NameOutcome::Created(first) | NameOutcome::Altered(first) => first,
+        NameOutcome::Rejected(err) => return CaseOutcome::Rejected(err),
+    };
+    let second = root.join(other);
+    match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&second)
+    {
+        Ok(_) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+            return CaseOutcome::Collided(stored_name(root, name).unwrap_or(first));
+        }
+        Err(err) => {
+            return CaseOutcome::Rejected(FixtureError::new(FixtureKind::WriteFile).chain(err))
+        }
+    }
+    // Double check against the listing, in case the filesystem folded the names anyway
+    let names: Vec<_> = fs::read_dir(root)
+        .map(|entries| entries.flatten().map(|entry| entry.file_name()).collect())
+        .unwrap_or_default();
+    if names.iter().any(|n| n == name) && names.iter().any(|n| n == other) {
+        CaseOutcome::Distinct(first, ChildPath::new(second))
+    } else {
+        CaseOutcome::Collided(stored_name(root, name).unwrap_or(first))
+    }
+}
+
+/// The entry of `root` matching `name` ignoring case, under the name the filesystem stored.
+fn stored_name(root: &path::Path, name: &str) -> Option<ChildPath> {
+    fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .map(|n| n.to_lowercase() == name.to_lowercase())
+                .unwrap_or(false)
+        })
+        .map(|entry| ChildPath::new(entry.path()))
+}
@@ -0,0 +1,281 @@
+use std::fmt;
+use std::path;
+
+use super::errors::*;
+use super::ChildPath;
+use super::NamedTempFile;
+#[cfg(feature = "glob")]
+use super::TempDir;
+
+/// Name of the variable always set to the fixture's own path.
+const TEMP_DIR_VAR: &str = "temp_dir";
+
+/// Values to substitute for `{{name}}` placeholders.
+///
+/// `{{temp_dir}}` is always available, as the path of the [`TempDir`]
+/// containing the file being written, or the directory being written to when outside of one.
+/// Setting it overrides the built-in value.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::TemplateVars;
+///
+/// let vars = TemplateVars::new().set("port", 8080).set("name", "server");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplateVars {
+    vars: Vec<(String, String)>,
+}
+
+impl TemplateVars {
+    /// No variables, besides `{{temp_dir}}`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Substitute `value` for `{{key}}`.
+    pub fn set<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: fmt::Display,
+    {
+        let key = key.into();
+        let value = value.to_string();
+        match self.vars.iter_mut().find(|(k, _)| *k == key) {
+            Some(var) => var.1 = value,
+            None => self.vars.push((key, value)),
+        }
+        self
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.vars
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Write text with `{{name}}` placeholders filled in to [`ChildPath`] or [`NamedTempFile`].
+///
+/// For config files that need the fixture's own path, or a port number, without `format!`
+/// plumbing.  Whitespace inside the braces is ignored, and unknown names are an error.
+pub trait FileWriteTemplated {
+    /// Render `template` with `vars` and write it to the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::TemplateVars;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("app.toml");
+    /// let vars = TemplateVars::new().set("port", 8080);
+    /// config
+    ///     .write_str_templated("data = \"{{temp_dir}}/data\"\nport = {{ port }}\n", &vars)
+    ///     .unwrap();
+    /// config.assert(format!(
+    ///     "data = \"{}/data\"\nport = 8080\n",
+    ///     temp.path().display()
+    /// ));
+    /// temp.close().unwrap();
+    /// ```
+    fn write_str_templated(&self, template: &str, vars: &TemplateVars) -> Result<(), FixtureError>;
+}
+
+impl FileWriteTemplated for ChildPath {
+    fn write_str_templated(&self, template: &str, vars: &TemplateVars) -> Result<(), FixtureError> {
+        write_str_templated(self.path(), template, vars)
+    }
+}
+
+impl FileWriteTemplated for NamedTempFile {
+    fn write_str_templated(&self, template: &str, vars: &TemplateVars) -> Result<(), FixtureError> {
+        write_str_templated(self.path(), template, vars)
+    }
+}
+
+/// Copy files into [`TempDir`], filling in `{{name}}` placeholders in text files.
+///
+/// Files that aren't UTF-8 text are copied as-is.
+#[cfg(feature = "glob")]
+pub trait PathCopyTemplated {
+    /// Copy the entries of `source` matching the glob `patterns`, like
+    /// [`PathCopy::copy_from`][super::PathCopy::copy_from], rendering text files with `vars`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use assert_fs::fixture::TemplateVars;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let vars = TemplateVars::new().set("port", 8080);
+    /// temp.copy_from_templated("tests/fixtures/config", &["*.toml"], &vars)
+    ///     .unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    fn copy_from_templated<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        vars: &TemplateVars,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>;
+}
+
+#[cfg(feature = "glob")]
+impl PathCopyTemplated for TempDir {
+    fn copy_from_templated<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        vars: &TemplateVars,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_from_templated(self.path(), source.as_ref(), patterns, vars)
+    }
+}
+
+#[cfg(feature = "glob")]
+impl PathCopyTemplated for ChildPath {
+    fn copy_from_templated<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        vars: &TemplateVars,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_from_templated(self.path(), source.as_ref(), patterns, vars)
+    }
+}
+
+fn write_str_templated(
+    path: &path::Path,
+    template: &str,
+    vars: &TemplateVars,
+) -> Result<(), FixtureError> {
+    let dir = path.parent().unwrap_or(path);
+    let data = render(template, vars, &temp_dir(dir)).map_err(|err| err.with_path(path))?;
+    super::tools::write_str(path, &data)
+}
+
+#[cfg(feature = "glob")]
+fn copy_from_templated<S>(
+    target: &path::Path,
+    source: &path::Path,
+    patterns: &[S],
+    vars: &TemplateVars,
+) -> Result<(), FixtureError>
+where
+    S: AsRef<str>,
+{
+    super::tools::copy_files(target, source, patterns, super::CopyOptions::default())?;
+    let temp_dir = temp_dir(target);
+    let source = source
+        .canonicalize()
+        .chain(FixtureError::new(FixtureKind::Walk))?;
+    for entry in crate::order::glob(&source, patterns)
+        .build()
+        .chain(FixtureError::new(FixtureKind::Walk))?
+    {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(&source)
+            .expect("entries to be under `source`");
+        let target_path = target.join(rel);
+        let data = std::fs::read(entry.path())
+            .chain(FixtureError::new(FixtureKind::ReadFile))
+            .map_err(|err| err.with_path(entry.path()))?;
+        let template = match String::from_utf8(data) {
+            Ok(template) if !template.contains('\0') => template,
+            _ => continue,
+        };
+        let data = render(&template, vars, &temp_dir).map_err(|err| err.with_path(entry.path()))?;
+        std::fs::write(&target_path, data)
+            .chain(FixtureError::new(FixtureKind::WriteFile))
+            .map_err(|err| err.with_path(&target_path))?;
+    }
+    Ok(())
+}
+
+/// The value of `{{temp_dir}}` for writes to `dir`.
+fn temp_dir(dir: &path::Path) -> path::PathBuf {
+    super::oplog::fixture_root(dir).unwrap_or_else(|| dir.to_owned())
+}
+
+/// Fill in the `{{name}}` placeholders in `template`.
+fn render(
+    template: &str,
+    vars: &TemplateVars,
+    temp_dir: &path::Path,
+) -> Result<String, FixtureError> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = match after.find("}}") {
+            Some(end) => end,
+            None => return Err(template_error("unclosed `{{`".to_owned())),
+        };
+        let name = after[..end].trim();
+        match vars.get(name) {
+            Some(value) => rendered.push_str(value),
+            None if name == TEMP_DIR_VAR => rendered.push_str(&temp_dir.to_string_lossy()),
+            None => return Err(template_error(format!("unknown variable `{}`", name))),
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+fn template_error(message: String) -> FixtureError {
+    let err = std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    FixtureError::new(FixtureKind::Template).chain(err)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_substitutes() {
+        let vars = TemplateVars::new()
+            .set("port", 8080)
+            .set("host", "localhost");
+        let temp_dir = path::Path::new("/tmp/fixture");
+        assert_eq!(
+            render("{{host}}:{{ port }} in {{temp_dir}}", &vars, temp_dir).unwrap(),
+            "localhost:8080 in /tmp/fixture"
+        );
+        assert_eq!(
+            render("no placeholders", &vars, temp_dir).unwrap(),
+            "no placeholders"
+        );
+    }
+
+    #[test]
+    fn render_rejects_unknown_and_unclosed() {
+        let vars = TemplateVars::new();
+        let temp_dir = path::Path::new("/tmp/fixture");
+        let err = render("{{missing}}", &vars, temp_dir).unwrap_err();
+        assert_eq!(err.kind(), FixtureKind::Template);
+        assert!(render("{{port", &vars, temp_dir).is_err());
+    }
+}
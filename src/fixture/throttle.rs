@@ -0,0 +1,54 @@
+use std::path;
+use std::sync;
+use std::thread;
+use std::time;
+
+/// Limits the rate of writes within a fixture.
+#[derive(Debug, Default)]
+pub(crate) struct Limit {
+    bytes_per_second: Option<u64>,
+    // When the writes so far would have finished at the limited rate
+    busy_until: Option<time::Instant>,
+}
+
+impl Limit {
+    pub(crate) fn set(&mut self, bytes_per_second: Option<u64>) {
+        self.bytes_per_second = bytes_per_second.map(|rate| rate.max(1));
+        self.busy_until = None;
+    }
+
+    /// Account for `bytes` written, returning when the caller may continue.
+    fn consume(&mut self, bytes: u64) -> Option<time::Instant> {
+        let rate = self.bytes_per_second?;
+        let now = time::Instant::now();
+        let start = self.busy_until.filter(|until| now < *until).unwrap_or(now);
+        let cost = time::Duration::from_secs_f64(bytes as f64 / rate as f64);
+        let until = start + cost;
+        self.busy_until = Some(until);
+        Some(until)
+    }
+}
+
+pub(crate) type Shared = sync::Mutex<Limit>;
+
+/// Sleep as long as writing `bytes` to `path` takes under the innermost fixture's limit.
+pub(crate) fn throttle(path: &path::Path, bytes: u64) {
+    if let Some(context) = super::context::innermost(path) {
+        wait(&context.write_limit, bytes);
+    }
+}
+
+/// Sleep as long as writing `bytes` takes under `limit`.
+pub(crate) fn wait(limit: &Shared, bytes: u64) {
+    let until = limit
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .consume(bytes);
+    if let Some(until) = until {
+        // Sleep outside of the lock, so concurrent writers queue up rather than block
+        let now = time::Instant::now();
+        if now < until {
+            thread::sleep(until - now);
+        }
+    }
+}
@@ -5,6 +5,8 @@ use std::io::Write;
 use std::path;
 
 use super::errors::*;
+use super::oplog::record;
+use super::oplog::OperationKind;
 use super::ChildPath;
 use super::NamedTempFile;
 use super::TempDir;
@@ -12,15 +14,17 @@ use super::TempDir;
 /// Create empty directories at [`ChildPath`].
 ///
 pub trait PathCreateDir {
-    /// Create an empty file at [`ChildPath`].
+    /// Create an empty directory at [`ChildPath`], along with any missing parents.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use assert_fs::prelude::*;
+    /// use predicates::prelude::*;
     ///
     /// let temp = assert_fs::TempDir::new().unwrap();
-    /// temp.child("subdir").create_dir_all().unwrap();
+    /// temp.child("nested/empty/dir").create_dir_all().unwrap();
+    /// temp.child("nested/empty/dir").assert(predicate::path::is_dir());
     /// temp.close().unwrap();
     /// ```
     ///
@@ -33,10 +37,127 @@ impl PathCreateDir for ChildPath {
     }
 }
 
+/// Remove files and directories at [`ChildPath`] or [`NamedTempFile`].
+///
+pub trait PathRemove {
+    /// Remove the file at [`ChildPath`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("config.toml");
+    /// config.touch().unwrap();
+    /// config.remove_file().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    #[doc(alias = "rm")]
+    fn remove_file(&self) -> Result<(), FixtureError>;
+
+    /// Remove the empty directory at [`ChildPath`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let subdir = temp.child("subdir");
+    /// subdir.create_dir_all().unwrap();
+    /// subdir.remove_dir().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn remove_dir(&self) -> Result<(), FixtureError>;
+
+    /// Remove the file or directory, and all of its contents, at [`ChildPath`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let subdir = temp.child("subdir");
+    /// subdir.child("foo.txt").touch().unwrap();
+    /// subdir.remove_all().unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    #[doc(alias = "rm_rf")]
+    fn remove_all(&self) -> Result<(), FixtureError>;
+}
+
+impl PathRemove for ChildPath {
+    fn remove_file(&self) -> Result<(), FixtureError> {
+        remove_file(self.path())
+    }
+
+    fn remove_dir(&self) -> Result<(), FixtureError> {
+        remove_dir(self.path())
+    }
+
+    fn remove_all(&self) -> Result<(), FixtureError> {
+        remove_all(self.path())
+    }
+}
+
+impl PathRemove for NamedTempFile {
+    fn remove_file(&self) -> Result<(), FixtureError> {
+        remove_file(self.path())
+    }
+
+    fn remove_dir(&self) -> Result<(), FixtureError> {
+        remove_dir(self.path())
+    }
+
+    fn remove_all(&self) -> Result<(), FixtureError> {
+        remove_all(self.path())
+    }
+}
+
+/// Empty the directory at [`TempDir`] or [`ChildPath`], keeping the directory itself.
+///
+pub trait PathClear {
+    /// Remove everything within the directory, so paths to it captured earlier stay valid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use predicates::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("cache/entry.bin").touch().unwrap();
+    /// temp.child("config.toml").touch().unwrap();
+    /// temp.clear().unwrap();
+    /// temp.assert(predicate::path::is_dir());
+    /// temp.child("config.toml").assert(predicate::path::missing());
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn clear(&self) -> Result<(), FixtureError>;
+}
+
+impl PathClear for TempDir {
+    fn clear(&self) -> Result<(), FixtureError> {
+        clear(self.path())
+    }
+}
+
+impl PathClear for ChildPath {
+    fn clear(&self) -> Result<(), FixtureError> {
+        clear(self.path())
+    }
+}
+
 /// Create empty files at [`ChildPath`].
 ///
 pub trait FileTouch {
-    /// Create an empty file at [`ChildPath`].
+    /// Create an empty file at [`ChildPath`], creating any missing parent directories.
     ///
     /// # Examples
     ///
@@ -66,7 +187,7 @@ impl FileTouch for NamedTempFile {
 /// Write a binary file at [`ChildPath`].
 ///
 pub trait FileWriteBin {
-    /// Write a binary file at [`ChildPath`].
+    /// Write a binary file at [`ChildPath`], creating any missing parent directories.
     ///
     /// # Examples
     ///
@@ -99,7 +220,7 @@ impl FileWriteBin for NamedTempFile {
 /// Write a text file at [`ChildPath`].
 ///
 pub trait FileWriteStr {
-    /// Write a text file at [`ChildPath`].
+    /// Write a text file at [`ChildPath`], creating any missing parent directories.
     ///
     /// # Examples
     ///
@@ -115,6 +236,27 @@ pub trait FileWriteStr {
     /// ```
     ///
     fn write_str(&self, data: &str) -> Result<(), FixtureError>;
+
+    /// Write a text file at [`ChildPath`], with every line ending converted to `line_ending`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::LineEnding;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let script = temp.child("run.cmd");
+    /// script
+    ///     .write_str_with("@echo off\necho hi\n", LineEnding::CrLf)
+    ///     .unwrap();
+    /// script.assert("@echo off\r\necho hi\r\n");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_str_with(&self, data: &str, line_ending: LineEnding) -> Result<(), FixtureError> {
+        self.write_str(&line_ending.apply(data))
+    }
 }
 
 impl FileWriteStr for ChildPath {
@@ -129,45 +271,88 @@ impl FileWriteStr for NamedTempFile {
     }
 }
 
-/// Write (copy) a file to [`ChildPath`].
+/// Replace a file at [`ChildPath`] in a single step, so readers never see it partially written.
 ///
-pub trait FileWriteFile {
-    /// Write (copy) a file to [`ChildPath`].
+/// See [`FileWriteSlowly`][super::FileWriteSlowly] for the opposite, a file that grows over time.
+///
+pub trait FileWriteAtomic {
+    /// Write `data` to a temporary file beside [`ChildPath`], then rename it into place,
+    /// creating any missing parent directories.
+    ///
+    /// Readers see either the previous content or `data`, never a mix, though on Windows
+    /// replacing a file that's open elsewhere fails.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::path::Path;
     /// use assert_fs::prelude::*;
     ///
     /// let temp = assert_fs::TempDir::new().unwrap();
-    /// temp
-    ///    .child("foo.txt")
-    ///    .write_file(Path::new("Cargo.toml"))
-    ///    .unwrap();
+    /// let config = temp.child("config.toml");
+    /// config.write_str("port = 8080\n").unwrap();
+    ///
+    /// // ... start a process watching `config` ...
+    ///
+    /// config.write_str_atomic("port = 9090\n").unwrap();
+    /// config.assert("port = 9090\n");
     /// temp.close().unwrap();
     /// ```
     ///
-    fn write_file(&self, data: &path::Path) -> Result<(), FixtureError>;
+    fn write_binary_atomic(&self, data: &[u8]) -> Result<(), FixtureError>;
+
+    /// Write the text `data` in a single step, like [`FileWriteAtomic::write_binary_atomic`].
+    fn write_str_atomic(&self, data: &str) -> Result<(), FixtureError>;
 }
 
-impl FileWriteFile for ChildPath {
-    fn write_file(&self, data: &path::Path) -> Result<(), FixtureError> {
-        write_file(self.path(), data)
+impl FileWriteAtomic for ChildPath {
+    fn write_binary_atomic(&self, data: &[u8]) -> Result<(), FixtureError> {
+        write_atomic(self.path(), data)
+    }
+
+    fn write_str_atomic(&self, data: &str) -> Result<(), FixtureError> {
+        write_atomic(self.path(), data.as_bytes())
     }
 }
 
-impl FileWriteFile for NamedTempFile {
-    fn write_file(&self, data: &path::Path) -> Result<(), FixtureError> {
-        write_file(self.path(), data)
+/// The line ending [`FileWriteStr::write_str_with`] writes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LineEnding {
+    /// `\r\n` on Windows, `\n` elsewhere.
+    Native,
+    /// `\n`.
+    Lf,
+    /// `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// The characters ending each line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Native if cfg!(windows) => "\r\n",
+            LineEnding::Native | LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// `text` with every `\n` or `\r\n` replaced by this line ending.
+    pub fn apply(self, text: &str) -> String {
+        let text = crate::assert::normalize_line_endings(text);
+        match self.as_str() {
+            "\n" => text.into_owned(),
+            ending => text.replace('\n', ending),
+        }
     }
 }
 
-/// Copy files into [`TempDir`].
+/// Append to a file at [`ChildPath`].
 ///
-pub trait PathCopy {
-    /// Copy files and directories into the current path from the `source` according to the glob
-    /// `patterns`.
+/// Useful to simulate a file, like a log, growing between runs of the code under test.
+///
+pub trait FileAppend {
+    /// Append binary data to a file at [`ChildPath`], creating it and any missing parent
+    /// directories.
     ///
     /// # Examples
     ///
@@ -175,39 +360,81 @@ pub trait PathCopy {
     /// use assert_fs::prelude::*;
     ///
     /// let temp = assert_fs::TempDir::new().unwrap();
-    /// temp.copy_from(".", &["*.rs"]).unwrap();
+    /// let data = temp.child("data.bin");
+    /// data.append_binary(b"\x00").unwrap();
+    /// data.append_binary(b"\x01").unwrap();
+    /// data.assert(&b"\x00\x01"[..]);
     /// temp.close().unwrap();
     /// ```
-    fn copy_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-        S: AsRef<str>;
+    ///
+    fn append_binary(&self, data: &[u8]) -> Result<(), FixtureError>;
+
+    /// Append text to a file at [`ChildPath`], creating it and any missing parent directories.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("logs/app.log");
+    /// log.append_str("started\n").unwrap();
+    /// // ... run the code under test ...
+    /// log.append_str("rotated\n").unwrap();
+    /// log.assert("started\nrotated\n");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn append_str(&self, data: &str) -> Result<(), FixtureError>;
 }
 
-impl PathCopy for TempDir {
-    fn copy_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-        S: AsRef<str>,
-    {
-        copy_files(self.path(), source.as_ref(), patterns)
+impl FileAppend for ChildPath {
+    fn append_binary(&self, data: &[u8]) -> Result<(), FixtureError> {
+        record(
+            OperationKind::AppendBinary,
+            self.path(),
+            Some(data.len() as u64),
+            || append_bytes(self.path(), data),
+        )
+    }
+
+    fn append_str(&self, data: &str) -> Result<(), FixtureError> {
+        record(
+            OperationKind::AppendStr,
+            self.path(),
+            Some(data.len() as u64),
+            || append_bytes(self.path(), data.as_bytes()),
+        )
     }
 }
 
-impl PathCopy for ChildPath {
-    fn copy_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-        S: AsRef<str>,
-    {
-        copy_files(self.path(), source.as_ref(), patterns)
+impl FileAppend for NamedTempFile {
+    fn append_binary(&self, data: &[u8]) -> Result<(), FixtureError> {
+        record(
+            OperationKind::AppendBinary,
+            self.path(),
+            Some(data.len() as u64),
+            || append_bytes(self.path(), data),
+        )
+    }
+
+    fn append_str(&self, data: &str) -> Result<(), FixtureError> {
+        record(
+            OperationKind::AppendStr,
+            self.path(),
+            Some(data.len() as u64),
+            || append_bytes(self.path(), data.as_bytes()),
+        )
     }
 }
 
-/// Create a symlink to the target
+/// Write formatted text to a file at [`ChildPath`].
 ///
-pub trait SymlinkToFile {
-    /// Create a symlink to the target
+/// Named so the [`write!`] and [`writeln!`] macros can be used directly on the path, for
+/// generated config fixtures without a `format!` at each call site.
+///
+pub trait FileWriteFmt {
+    /// Write formatted text to a file at [`ChildPath`], replacing any existing content.
     ///
     /// # Examples
     ///
@@ -215,40 +442,34 @@ pub trait SymlinkToFile {
     /// use assert_fs::prelude::*;
     ///
     /// let temp = assert_fs::TempDir::new().unwrap();
-    /// let real_file = temp.child("real_file");
-    /// real_file.touch().unwrap();
-    ///
-    /// temp.child("link_file").symlink_to_file(real_file.path()).unwrap();
-    ///
+    /// let port = 8080;
+    /// writeln!(temp.child("config.toml"), "port = {}", port).unwrap();
+    /// temp.child("config.toml").assert("port = 8080\n");
     /// temp.close().unwrap();
     /// ```
-    fn symlink_to_file<P>(&self, target: P) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>;
+    ///
+    fn write_fmt(&self, args: std::fmt::Arguments<'_>) -> Result<(), FixtureError>;
 }
 
-impl SymlinkToFile for ChildPath {
-    fn symlink_to_file<P>(&self, target: P) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-    {
-        symlink_to_file(self.path(), target.as_ref())
+impl FileWriteFmt for ChildPath {
+    fn write_fmt(&self, args: std::fmt::Arguments<'_>) -> Result<(), FixtureError> {
+        write_fmt(self.path(), args)
     }
 }
 
-impl SymlinkToFile for NamedTempFile {
-    fn symlink_to_file<P>(&self, target: P) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-    {
-        symlink_to_file(self.path(), target.as_ref())
+impl FileWriteFmt for NamedTempFile {
+    fn write_fmt(&self, args: std::fmt::Arguments<'_>) -> Result<(), FixtureError> {
+        write_fmt(self.path(), args)
     }
 }
 
-/// Create a symlink to the target
+/// Write an executable script at [`ChildPath`].
 ///
-pub trait SymlinkToDir {
-    /// Create a symlink to the target
+pub trait FileWriteScript {
+    /// Write an executable script at [`ChildPath`], returning the path of the script.
+    ///
+    /// On Unix, the file is marked as executable; include a shebang in `body`.  On Windows, a
+    /// `.cmd` extension is added when the path has none; write `body` as a batch script.
     ///
     /// # Examples
     ///
@@ -256,129 +477,2248 @@ pub trait SymlinkToDir {
     /// use assert_fs::prelude::*;
     ///
     /// let temp = assert_fs::TempDir::new().unwrap();
-    /// let real_dir = temp.child("real_dir");
-    /// real_dir.create_dir_all().unwrap();
+    /// let body = if cfg!(windows) {
+    ///     "@echo fake-git 1.0"
+    /// } else {
+    ///     "#!/bin/sh\necho fake-git 1.0\n"
+    /// };
+    /// let script = temp.child("bin/git").write_script(body).unwrap();
+    /// println!("{}", script.path().display());
+    /// temp.close().unwrap();
+    /// ```
     ///
-    /// temp.child("link_dir").symlink_to_dir(real_dir.path()).unwrap();
+    fn write_script(&self, body: &str) -> Result<ChildPath, FixtureError>;
+}
+
+impl FileWriteScript for ChildPath {
+    fn write_script(&self, body: &str) -> Result<ChildPath, FixtureError> {
+        write_script(self.path(), body).map(ChildPath::new)
+    }
+}
+
+/// Change the permissions of [`ChildPath`].
+///
+/// See [`PathAssertMetadata::assert_mode`][crate::assert::PathAssertMetadata::assert_mode] to
+/// check them.
+///
+pub trait PathPermissions {
+    /// Set the Unix permission bits, e.g. `0o644` for a non-executable script.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let script = temp.child("bin/tool");
+    /// script.write_str("#!/bin/sh\n").unwrap();
+    /// script.set_mode(0o644).unwrap();
+    /// script.assert_mode(0o644);
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    fn set_mode(&self, mode: u32) -> Result<(), FixtureError>;
+
+    /// Set, or clear, the read-only flag.
+    ///
+    /// On Unix, this removes, or adds back, the owner's write permission.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
     ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let config = temp.child("config.toml");
+    /// config.write_str("locked = true\n").unwrap();
+    /// config.set_readonly(true).unwrap();
+    /// assert!(config.metadata().unwrap().permissions().readonly());
     /// temp.close().unwrap();
     /// ```
-    fn symlink_to_dir<P>(&self, target: P) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>;
+    fn set_readonly(&self, readonly: bool) -> Result<(), FixtureError>;
 }
 
-impl SymlinkToDir for ChildPath {
-    fn symlink_to_dir<P>(&self, target: P) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-    {
-        symlink_to_dir(self.path(), target.as_ref())
+impl PathPermissions for ChildPath {
+    #[cfg(unix)]
+    fn set_mode(&self, mode: u32) -> Result<(), FixtureError> {
+        set_mode(self.path(), mode)
     }
-}
 
-impl SymlinkToDir for TempDir {
-    fn symlink_to_dir<P>(&self, target: P) -> Result<(), FixtureError>
-    where
-        P: AsRef<path::Path>,
-    {
-        symlink_to_dir(self.path(), target.as_ref())
+    fn set_readonly(&self, readonly: bool) -> Result<(), FixtureError> {
+        set_readonly(self.path(), readonly)
     }
 }
 
-fn ensure_parent_dir(path: &path::Path) -> Result<(), FixtureError> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+impl PathPermissions for NamedTempFile {
+    #[cfg(unix)]
+    fn set_mode(&self, mode: u32) -> Result<(), FixtureError> {
+        set_mode(self.path(), mode)
     }
-    Ok(())
-}
 
-fn create_dir_all(path: &path::Path) -> Result<(), FixtureError> {
-    fs::create_dir_all(path).chain(FixtureError::new(FixtureKind::CreateDir))?;
-    Ok(())
+    fn set_readonly(&self, readonly: bool) -> Result<(), FixtureError> {
+        set_readonly(self.path(), readonly)
+    }
 }
 
-fn touch(path: &path::Path) -> Result<(), FixtureError> {
-    ensure_parent_dir(path)?;
-    fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
-    Ok(())
-}
+/// Modify an existing file at [`ChildPath`] in-place.
+///
+/// Useful for staging corrupted or partially-written files, like torn writes or truncated
+/// downloads.
+///
+pub trait FilePatch {
+    /// Truncate (or extend with zeros) the file at [`ChildPath`] to `len` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let download = temp.child("download.bin");
+    /// download.write_str("To be or not to be...").unwrap();
+    /// download.truncate(5).unwrap();
+    /// download.assert("To be");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn truncate(&self, len: u64) -> Result<(), FixtureError>;
 
-fn write_binary(path: &path::Path, data: &[u8]) -> Result<(), FixtureError> {
-    ensure_parent_dir(path)?;
-    let mut file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
-    file.write_all(data)
-        .chain(FixtureError::new(FixtureKind::WriteFile))?;
-    Ok(())
+    /// Overwrite the file at [`ChildPath`] with `data`, starting at `offset`.
+    ///
+    /// The file is extended if `data` goes past its end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let record = temp.child("record.txt");
+    /// record.write_str("To be or not to be...").unwrap();
+    /// record.patch_bytes(3, b"XX").unwrap();
+    /// record.assert("To XX or not to be...");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn patch_bytes(&self, offset: u64, data: &[u8]) -> Result<(), FixtureError>;
 }
 
-fn write_str(path: &path::Path, data: &str) -> Result<(), FixtureError> {
+impl FilePatch for ChildPath {
+    fn truncate(&self, len: u64) -> Result<(), FixtureError> {
+        truncate(self.path(), len)
+    }
+
+    fn patch_bytes(&self, offset: u64, data: &[u8]) -> Result<(), FixtureError> {
+        patch_bytes(self.path(), offset, data)
+    }
+}
+
+impl FilePatch for NamedTempFile {
+    fn truncate(&self, len: u64) -> Result<(), FixtureError> {
+        truncate(self.path(), len)
+    }
+
+    fn patch_bytes(&self, offset: u64, data: &[u8]) -> Result<(), FixtureError> {
+        patch_bytes(self.path(), offset, data)
+    }
+}
+
+/// Write (copy) a file to [`ChildPath`].
+///
+pub trait FileWriteFile {
+    /// Write (copy) a file to [`ChildPath`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp
+    ///    .child("foo.txt")
+    ///    .write_file(Path::new("Cargo.toml"))
+    ///    .unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_file(&self, data: &path::Path) -> Result<(), FixtureError>;
+}
+
+impl FileWriteFile for ChildPath {
+    fn write_file(&self, data: &path::Path) -> Result<(), FixtureError> {
+        write_file(self.path(), data)
+    }
+}
+
+impl FileWriteFile for NamedTempFile {
+    fn write_file(&self, data: &path::Path) -> Result<(), FixtureError> {
+        write_file(self.path(), data)
+    }
+}
+
+/// Write large files at [`ChildPath`] without buffering their content in memory.
+///
+pub trait FileWriteStream {
+    /// Write everything read from `reader` to the file, creating it and any missing parent
+    /// directories, returning the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let zeros = temp.child("zeros.bin");
+    /// let written = zeros.write_from(std::io::repeat(0).take(1024)).unwrap();
+    /// assert_eq!(written, 1024);
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_from<R>(&self, reader: R) -> Result<u64, FixtureError>
+    where
+        R: std::io::Read;
+
+    /// Write a file of `len` bytes, repeating `fill_pattern`, creating it and any missing
+    /// parent directories.
+    ///
+    /// An empty or all-zero `fill_pattern` only sets the file's length, which is fast and
+    /// creates a sparse file on filesystems that support them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let disk = temp.child("disk.img");
+    /// disk.write_file_of_size(1 << 30, &[]).unwrap();
+    /// assert_eq!(disk.metadata().unwrap().len(), 1 << 30);
+    ///
+    /// let pattern = temp.child("pattern.bin");
+    /// pattern.write_file_of_size(5, b"ab").unwrap();
+    /// pattern.assert("ababa");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn write_file_of_size(&self, len: u64, fill_pattern: &[u8]) -> Result<(), FixtureError>;
+}
+
+impl FileWriteStream for ChildPath {
+    fn write_from<R>(&self, reader: R) -> Result<u64, FixtureError>
+    where
+        R: std::io::Read,
+    {
+        write_from(self.path(), reader)
+    }
+
+    fn write_file_of_size(&self, len: u64, fill_pattern: &[u8]) -> Result<(), FixtureError> {
+        write_file_of_size(self.path(), len, fill_pattern)
+    }
+}
+
+impl FileWriteStream for NamedTempFile {
+    fn write_from<R>(&self, reader: R) -> Result<u64, FixtureError>
+    where
+        R: std::io::Read,
+    {
+        write_from(self.path(), reader)
+    }
+
+    fn write_file_of_size(&self, len: u64, fill_pattern: &[u8]) -> Result<(), FixtureError> {
+        write_file_of_size(self.path(), len, fill_pattern)
+    }
+}
+
+/// Spool a stream into a file at [`ChildPath`] in the background.
+///
+pub trait FileCapture {
+    /// Copy everything read from `reader`, like a child process's stdout, into the file,
+    /// creating it and any missing parent directories.
+    ///
+    /// The file is created before returning; the copy runs on a separate thread until `reader`
+    /// reaches end-of-file.  Join the returned handle before asserting on the file's content.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("out.txt");
+    /// // e.g. `child.stdout.take().unwrap()` of a spawned process
+    /// let stdout = std::io::Cursor::new(b"line 1\nline 2\n".to_vec());
+    /// let copied = output.capture_from(stdout).unwrap().join().unwrap().unwrap();
+    /// assert_eq!(copied, 14);
+    /// output.assert("line 1\nline 2\n");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn capture_from<R>(
+        &self,
+        reader: R,
+    ) -> Result<std::thread::JoinHandle<Result<u64, FixtureError>>, FixtureError>
+    where
+        R: std::io::Read + Send + 'static;
+}
+
+impl FileCapture for ChildPath {
+    fn capture_from<R>(
+        &self,
+        reader: R,
+    ) -> Result<std::thread::JoinHandle<Result<u64, FixtureError>>, FixtureError>
+    where
+        R: std::io::Read + Send + 'static,
+    {
+        record(OperationKind::Capture, self.path(), None, || {
+            capture_from(self.path(), reader)
+        })
+    }
+}
+
+impl FileCapture for NamedTempFile {
+    fn capture_from<R>(
+        &self,
+        reader: R,
+    ) -> Result<std::thread::JoinHandle<Result<u64, FixtureError>>, FixtureError>
+    where
+        R: std::io::Read + Send + 'static,
+    {
+        record(OperationKind::Capture, self.path(), None, || {
+            capture_from(self.path(), reader)
+        })
+    }
+}
+
+/// Read a file at [`ChildPath`].
+///
+/// Errors name the file that couldn't be read.
+///
+pub trait FileRead {
+    /// Read the file's content.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("out.bin");
+    /// output.write_binary(b"\x00\x01").unwrap();
+    /// assert_eq!(output.read_binary().unwrap(), b"\x00\x01");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn read_binary(&self) -> Result<Vec<u8>, FixtureError>;
+
+    /// Read the file's content as UTF-8 text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let output = temp.child("version.txt");
+    /// output.write_str("1.2.3\n").unwrap();
+    /// let version: String = output.read_str().unwrap().trim().to_owned();
+    /// assert_eq!(version, "1.2.3");
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn read_str(&self) -> Result<String, FixtureError>;
+
+    /// Read the file's content as lines of UTF-8 text, without line endings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let log = temp.child("run.log");
+    /// log.write_str("started\r\nstopped\n").unwrap();
+    /// assert_eq!(log.read_lines().unwrap(), ["started", "stopped"]);
+    /// temp.close().unwrap();
+    /// ```
+    ///
+    fn read_lines(&self) -> Result<Vec<String>, FixtureError>;
+}
+
+impl FileRead for ChildPath {
+    fn read_binary(&self) -> Result<Vec<u8>, FixtureError> {
+        read_binary(self.path())
+    }
+
+    fn read_str(&self) -> Result<String, FixtureError> {
+        read_str(self.path())
+    }
+
+    fn read_lines(&self) -> Result<Vec<String>, FixtureError> {
+        read_str(self.path()).map(|data| data.lines().map(str::to_owned).collect())
+    }
+}
+
+impl FileRead for NamedTempFile {
+    fn read_binary(&self) -> Result<Vec<u8>, FixtureError> {
+        read_binary(self.path())
+    }
+
+    fn read_str(&self) -> Result<String, FixtureError> {
+        read_str(self.path())
+    }
+
+    fn read_lines(&self) -> Result<Vec<String>, FixtureError> {
+        read_str(self.path()).map(|data| data.lines().map(str::to_owned).collect())
+    }
+}
+
+/// Copy files into [`TempDir`].
+///
+pub trait PathCopy {
+    /// Copy files and directories into the current path from the `source` according to the glob
+    /// `patterns`.
+    ///
+    /// With the `parallel` feature, files are copied across threads, which speeds up large
+    /// trees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.copy_from(".", &["*.rs"]).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn copy_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>;
+
+    /// Like [`PathCopy::copy_from`], controlling how entries are copied with `options`.
+    ///
+    /// Defaults to [`PathCopy::copy_from_with_report`], discarding the report.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::CopyOptions;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let options = CopyOptions::new().preserve_symlinks(true).preserve_mtime(true);
+    /// temp.copy_from_with(".", &["*.rs"], options).unwrap();
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn copy_from_with<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        options: CopyOptions,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        self.copy_from_with_report(source, patterns, options)
+            .map(|_| ())
+    }
+
+    /// Like [`PathCopy::copy_from_with`], returning what was copied.
+    ///
+    /// Useful to check the fixture itself was set up as intended, e.g. that `patterns` didn't
+    /// silently match nothing because `source` moved.
+    ///
+    /// Defaults to copying into a scratch directory, for the report, and from there with
+    /// [`PathCopy::copy_from`], which can't preserve symlinks or modification times, or resolve
+    /// conflicts other than by overwriting; those options fail unless this is overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::CopyOptions;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let report = temp
+    ///     .copy_from_with_report(".", &["src/*.rs"], CopyOptions::new())
+    ///     .unwrap();
+    /// assert!(report.files() > 0, "{}", report);
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn copy_from_with_report<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        options: CopyOptions,
+    ) -> Result<CopyReport, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        if options.preserve_symlinks
+            || options.preserve_mtime
+            || options.on_conflict != ConflictPolicy::Overwrite
+        {
+            return Err(unsupported(
+                "preserving symlinks or modification times, or resolving conflicts other than \
+                 by overwriting, requires overriding `PathCopy::copy_from_with_report`",
+            ));
+        }
+        let scratch = TempDir::new()?;
+        let report = copy_files(scratch.path(), source.as_ref(), patterns, options)?;
+        self.copy_from(scratch.path(), &["**"])?;
+        scratch.close()?;
+        Ok(report)
+    }
+
+    /// Copy from each of `sources`, a source and its glob patterns, in order, layering them
+    /// into one tree.
+    ///
+    /// Files from a later source conflict with those of earlier ones, and with files already
+    /// in the destination, and are resolved by [`CopyOptions::on_conflict`]: overwritten by
+    /// default, so later sources override earlier ones.
+    ///
+    /// Defaults to [`PathCopy::copy_from_with_report`] for each source, adding up the reports.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::{ConflictPolicy, CopyOptions};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let base = assert_fs::TempDir::new().unwrap();
+    /// base.child("config.toml").write_str("debug = false").unwrap();
+    /// base.child("data/users.json").write_str("[]").unwrap();
+    /// let overrides = assert_fs::TempDir::new().unwrap();
+    /// overrides.child("config.toml").write_str("debug = true").unwrap();
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let sources = [(base.path(), ["**"]), (overrides.path(), ["**"])];
+    /// temp.copy_from_many(&sources, CopyOptions::new()).unwrap();
+    /// temp.child("config.toml").assert("debug = true");
+    /// temp.child("data/users.json").assert("[]");
+    ///
+    /// let strict = assert_fs::TempDir::new().unwrap();
+    /// let options = CopyOptions::new().on_conflict(ConflictPolicy::Error);
+    /// assert!(strict.copy_from_many(&sources, options).is_err());
+    ///
+    /// temp.close().unwrap();
+    /// strict.close().unwrap();
+    /// base.close().unwrap();
+    /// overrides.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn copy_from_many<P, I, S>(
+        &self,
+        sources: &[(P, I)],
+        options: CopyOptions,
+    ) -> Result<CopyReport, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        I: AsRef<[S]>,
+        S: AsRef<str>,
+    {
+        let mut report = CopyReport::default();
+        for (source, patterns) in sources {
+            report.add(self.copy_from_with_report(source, patterns.as_ref(), options)?);
+        }
+        Ok(report)
+    }
+
+    /// Make the current path mirror the files in `source` matching the glob `patterns`.
+    ///
+    /// Unlike [`PathCopy::copy_from`], files whose content is unchanged are left alone and
+    /// entries not matched in `source` are removed, making it cheap to refresh a long-lived
+    /// sandbox.
+    ///
+    /// None of the other methods can remove entries, so the default fails; implementors must
+    /// override it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("stale.rs").touch().unwrap();
+    /// temp.sync_from(".", &["src/*.rs"]).unwrap();
+    /// temp.child("stale.rs").assert(predicates::path::missing());
+    /// temp.child("src/lib.rs").assert(predicates::path::exists());
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn sync_from<P, S>(&self, _source: P, _patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        Err(unsupported(
+            "syncing requires overriding `PathCopy::sync_from`",
+        ))
+    }
+
+    /// Mirror the files in `source` matching the glob `patterns` as symlinks into the current
+    /// path.
+    ///
+    /// Directories are created for real, while each file becomes a symlink to its canonical
+    /// path in `source`.  This is a near-free alternative to [`PathCopy::copy_from`] for
+    /// fixtures that are never modified, and for testing tools that must handle link farms.
+    ///
+    /// Defaults to building the farm in a scratch directory and copying it over with
+    /// [`PathCopy::copy_from_with`], preserving the symlinks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.link_farm_from(".", &["src/*.rs"]).unwrap();
+    /// assert!(temp.child("src/lib.rs").symlink_metadata().unwrap().file_type().is_symlink());
+    /// temp.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn link_farm_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        let scratch = TempDir::new()?;
+        link_farm(scratch.path(), source.as_ref(), patterns)?;
+        let options = CopyOptions::new().preserve_symlinks(true);
+        self.copy_from_with(scratch.path(), &["**"], options)?;
+        scratch.close()
+    }
+
+    /// Copy the current file, or directory and all of its contents, out to `dest`.
+    ///
+    /// Parent directories of `dest` are created as needed.
+    ///
+    /// None of the other methods can read the current path, so the default fails; implementors
+    /// must override it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let out = temp.child("out");
+    /// out.child("report.txt").write_str("All good").unwrap();
+    ///
+    /// let artifacts = assert_fs::TempDir::new().unwrap();
+    /// out.copy_to(artifacts.child("run-1")).unwrap();
+    /// artifacts.child("run-1/report.txt").assert("All good");
+    ///
+    /// temp.close().unwrap();
+    /// artifacts.close().unwrap();
+    /// ```
+    fn copy_to<P>(&self, _dest: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        Err(unsupported(
+            "copying out requires overriding `PathCopy::copy_to`",
+        ))
+    }
+
+    /// Copy files and directories out to `dest` according to the glob `patterns`, the inverse
+    /// of [`PathCopy::copy_from`].
+    ///
+    /// For exporting a test's output, e.g. for upload as a CI artifact.  `dest` and its parents
+    /// are created as needed.
+    ///
+    /// Defaults to [`PathCopy::copy_to`] a scratch directory, exporting the matches from there.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("out/report.txt").write_str("All good").unwrap();
+    /// temp.child("out/scratch.tmp").touch().unwrap();
+    ///
+    /// let artifacts = assert_fs::TempDir::new().unwrap();
+    /// temp.export_to(artifacts.child("run-1"), &["**/*.txt"]).unwrap();
+    /// artifacts.child("run-1/out/report.txt").assert("All good");
+    /// artifacts.child("run-1/out/scratch.tmp").assert(predicates::path::missing());
+    ///
+    /// temp.close().unwrap();
+    /// artifacts.close().unwrap();
+    /// ```
+    #[cfg(feature = "glob")]
+    fn export_to<P, S>(&self, dest: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        let scratch = TempDir::new()?;
+        self.copy_to(scratch.path())?;
+        export_files(scratch.path(), dest.as_ref(), patterns)?;
+        scratch.close()
+    }
+}
+
+impl PathCopy for TempDir {
+    #[cfg(feature = "glob")]
+    fn copy_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_files(
+            self.path(),
+            source.as_ref(),
+            patterns,
+            CopyOptions::default(),
+        )?;
+        Ok(())
+    }
+
+    #[cfg(feature = "glob")]
+    fn copy_from_with<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        options: CopyOptions,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_files(self.path(), source.as_ref(), patterns, options)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "glob")]
+    fn copy_from_with_report<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        options: CopyOptions,
+    ) -> Result<CopyReport, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_files(self.path(), source.as_ref(), patterns, options)
+    }
+
+    #[cfg(feature = "glob")]
+    fn copy_from_many<P, I, S>(
+        &self,
+        sources: &[(P, I)],
+        options: CopyOptions,
+    ) -> Result<CopyReport, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        I: AsRef<[S]>,
+        S: AsRef<str>,
+    {
+        copy_many(self.path(), sources, options)
+    }
+
+    #[cfg(feature = "glob")]
+    fn sync_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        sync_files(self.path(), source.as_ref(), patterns)
+    }
+
+    #[cfg(feature = "glob")]
+    fn link_farm_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        link_farm(self.path(), source.as_ref(), patterns)
+    }
+
+    fn copy_to<P>(&self, dest: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        copy_to(self.path(), dest.as_ref())
+    }
+
+    #[cfg(feature = "glob")]
+    fn export_to<P, S>(&self, dest: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        export_files(self.path(), dest.as_ref(), patterns)
+    }
+}
+
+impl PathCopy for ChildPath {
+    #[cfg(feature = "glob")]
+    fn copy_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_files(
+            self.path(),
+            source.as_ref(),
+            patterns,
+            CopyOptions::default(),
+        )?;
+        Ok(())
+    }
+
+    #[cfg(feature = "glob")]
+    fn copy_from_with<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        options: CopyOptions,
+    ) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_files(self.path(), source.as_ref(), patterns, options)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "glob")]
+    fn copy_from_with_report<P, S>(
+        &self,
+        source: P,
+        patterns: &[S],
+        options: CopyOptions,
+    ) -> Result<CopyReport, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        copy_files(self.path(), source.as_ref(), patterns, options)
+    }
+
+    #[cfg(feature = "glob")]
+    fn copy_from_many<P, I, S>(
+        &self,
+        sources: &[(P, I)],
+        options: CopyOptions,
+    ) -> Result<CopyReport, FixtureError>
+    where
+        P: AsRef<path::Path>,
+        I: AsRef<[S]>,
+        S: AsRef<str>,
+    {
+        copy_many(self.path(), sources, options)
+    }
+
+    #[cfg(feature = "glob")]
+    fn sync_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        sync_files(self.path(), source.as_ref(), patterns)
+    }
+
+    #[cfg(feature = "glob")]
+    fn link_farm_from<P, S>(&self, source: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        link_farm(self.path(), source.as_ref(), patterns)
+    }
+
+    fn copy_to<P>(&self, dest: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        copy_to(self.path(), dest.as_ref())
+    }
+
+    #[cfg(feature = "glob")]
+    fn export_to<P, S>(&self, dest: P, patterns: &[S]) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+        S: AsRef<str>,
+    {
+        export_files(self.path(), dest.as_ref(), patterns)
+    }
+}
+
+/// How [`PathCopy::copy_from_with`] copies entries.
+///
+/// The default, used by [`PathCopy::copy_from`], follows symlinks, copying what they point to,
+/// and preserves file permissions but not modification times.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CopyOptions {
+    preserve_symlinks: bool,
+    preserve_permissions: bool,
+    preserve_mtime: bool,
+    require_matches: bool,
+    respect_ignore_files: bool,
+    on_conflict: ConflictPolicy,
+}
+
+impl CopyOptions {
+    /// The default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recreate symlinks as symlinks, with the same target, rather than copying what they point
+    /// to.
+    pub fn preserve_symlinks(mut self, yes: bool) -> Self {
+        self.preserve_symlinks = yes;
+        self
+    }
+
+    /// Copy the permissions of files and directories, like the executable bit on Unix.
+    pub fn preserve_permissions(mut self, yes: bool) -> Self {
+        self.preserve_permissions = yes;
+        self
+    }
+
+    /// Copy the modification times of files and directories.
+    pub fn preserve_mtime(mut self, yes: bool) -> Self {
+        self.preserve_mtime = yes;
+        self
+    }
+
+    /// Fail if any pattern, other than an exclusion (`!`), matches nothing in `source`.
+    ///
+    /// Catches typos in patterns, or a moved `source`, while setting up the fixture rather
+    /// than in a confusing failure later in the test.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "glob")] {
+    /// use assert_fs::fixture::{CopyOptions, FixtureKind};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let options = CopyOptions::new().require_matches(true);
+    /// let err = temp
+    ///     .copy_from_with(".", &["src/*.rs", "src/*.rsx"], options)
+    ///     .unwrap_err();
+    /// assert_eq!(err.kind(), FixtureKind::NoMatch);
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    pub fn require_matches(mut self, yes: bool) -> Self {
+        self.require_matches = yes;
+        self
+    }
+
+    /// Skip entries ignored by `.gitignore` or `.ignore` files within `source`, and `.git`
+    /// directories, like `git` and `ripgrep` do.
+    ///
+    /// Ignored directories, like `target/`, aren't walked at all, so copying a whole project stays
+    /// fast.  Ignore files above `source`, and global git configuration, don't apply; use `!`
+    /// patterns for rules of your own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "glob")] {
+    /// use assert_fs::fixture::CopyOptions;
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let options = CopyOptions::new().respect_ignore_files(true);
+    /// temp.copy_from_with(env!("CARGO_MANIFEST_DIR"), &["**", "!*.orig"], options)
+    ///     .unwrap();
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    pub fn respect_ignore_files(mut self, yes: bool) -> Self {
+        self.respect_ignore_files = yes;
+        self
+    }
+
+    /// What to do when a file or symlink being copied already exists in the destination.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "glob")] {
+    /// use assert_fs::fixture::{ConflictPolicy, CopyOptions};
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("src/lib.rs").write_str("// local").unwrap();
+    /// let options = CopyOptions::new().on_conflict(ConflictPolicy::SkipExisting);
+    /// temp.copy_from_with(".", &["src/*.rs"], options).unwrap();
+    /// temp.child("src/lib.rs").assert("// local");
+    /// temp.close().unwrap();
+    /// # }
+    /// ```
+    pub fn on_conflict(mut self, policy: ConflictPolicy) -> Self {
+        self.on_conflict = policy;
+        self
+    }
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            preserve_symlinks: false,
+            preserve_permissions: true,
+            preserve_mtime: false,
+            require_matches: false,
+            respect_ignore_files: false,
+            on_conflict: ConflictPolicy::default(),
+        }
+    }
+}
+
+/// What [`PathCopy::copy_from_with`] does with a file or symlink that already exists in the
+/// destination.
+///
+/// Directories are merged, so never conflict.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConflictPolicy {
+    /// Fail, before copying any files.
+    Error,
+    /// Keep the existing entry, listing the one not copied in [`CopyReport::skipped`].
+    SkipExisting,
+    /// Replace the existing file.
+    Overwrite,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        ConflictPolicy::Overwrite
+    }
+}
+
+/// How [`TempDir::clone_from`] creates each file.
+///
+/// Reflinks and hard links make cloning large fixtures nearly free, but have caveats: reflinks
+/// are only supported by some filesystems, like Btrfs, XFS and APFS, and require the `reflink`
+/// feature, while hard links share their content with `source`, so writing to them modifies
+/// the original.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CloneStrategy {
+    /// Use the first of a reflink, a hard link, or a copy that works for each file.
+    Auto,
+    /// Use a reflink where supported, otherwise a copy, so files never share content with
+    /// `source`.
+    ReflinkOrCopy,
+    /// A copy-on-write clone, sharing storage until either side is modified.
+    Reflink,
+    /// A hard link, sharing content with `source`.
+    HardLink,
+    /// A plain copy.
+    Copy,
+}
+
+impl Default for CloneStrategy {
+    fn default() -> Self {
+        CloneStrategy::Auto
+    }
+}
+
+/// What [`PathCopy::copy_from_with_report`] copied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CopyReport {
+    files: usize,
+    dirs: usize,
+    symlinks: usize,
+    bytes: u64,
+    skipped: Vec<path::PathBuf>,
+}
+
+impl CopyReport {
+    /// How many files were copied.
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    /// How many directories were created.
+    pub fn dirs(&self) -> usize {
+        self.dirs
+    }
+
+    /// How many symlinks were recreated.
+    pub fn symlinks(&self) -> usize {
+        self.symlinks
+    }
+
+    /// How many bytes of file content were copied.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Matched entries that weren't copied, like sockets or devices, or ones kept from the
+    /// destination by [`ConflictPolicy::SkipExisting`], relative to `source`.
+    pub fn skipped(&self) -> &[path::PathBuf] {
+        &self.skipped
+    }
+
+    #[cfg(feature = "glob")]
+    fn add(&mut self, other: CopyReport) {
+        self.files += other.files;
+        self.dirs += other.dirs;
+        self.symlinks += other.symlinks;
+        self.bytes += other.bytes;
+        self.skipped.extend(other.skipped);
+    }
+}
+
+impl std::fmt::Display for CopyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "copied {} files ({} bytes), {} directories, {} symlinks",
+            self.files, self.bytes, self.dirs, self.symlinks
+        )?;
+        for skipped in &self.skipped {
+            write!(f, "\nskipped {}", skipped.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Create a symlink to the target
+///
+pub trait SymlinkToFile {
+    /// Create a symlink to the target, creating any missing parent directories
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let real_file = temp.child("real_file");
+    /// real_file.touch().unwrap();
+    ///
+    /// temp.child("link_file").symlink_to_file(real_file.path()).unwrap();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    fn symlink_to_file<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>;
+}
+
+impl SymlinkToFile for ChildPath {
+    fn symlink_to_file<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        symlink_to_file(self.path(), target.as_ref())
+    }
+}
+
+impl SymlinkToFile for NamedTempFile {
+    fn symlink_to_file<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        symlink_to_file(self.path(), target.as_ref())
+    }
+}
+
+/// Create a symlink to the target
+///
+pub trait SymlinkToDir {
+    /// Create a symlink to the target, creating any missing parent directories
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let real_dir = temp.child("real_dir");
+    /// real_dir.create_dir_all().unwrap();
+    ///
+    /// temp.child("link_dir").symlink_to_dir(real_dir.path()).unwrap();
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    fn symlink_to_dir<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>;
+}
+
+impl SymlinkToDir for ChildPath {
+    fn symlink_to_dir<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        symlink_to_dir(self.path(), target.as_ref())
+    }
+}
+
+impl SymlinkToDir for TempDir {
+    fn symlink_to_dir<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        symlink_to_dir(self.path(), target.as_ref())
+    }
+}
+
+/// Create a hard link to an existing file
+///
+pub trait HardLinkTo {
+    /// Create a hard link to `target`, creating any missing parent directories
+    ///
+    /// Both paths then refer to the same file, as checked by
+    /// [`PathAssertMetadata::assert_same_file_as`][crate::assert::PathAssertMetadata::assert_same_file_as].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let original = temp.child("original.bin");
+    /// original.write_str("shared").unwrap();
+    ///
+    /// let link = temp.child("backup/link.bin");
+    /// link.hard_link_to(original.path()).unwrap();
+    /// link.assert_same_file_as(original.path());
+    ///
+    /// temp.close().unwrap();
+    /// ```
+    fn hard_link_to<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>;
+}
+
+impl HardLinkTo for ChildPath {
+    fn hard_link_to<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        hard_link_to(self.path(), target.as_ref())
+    }
+}
+
+impl HardLinkTo for NamedTempFile {
+    fn hard_link_to<P>(&self, target: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        hard_link_to(self.path(), target.as_ref())
+    }
+}
+
+fn capture_from<R>(
+    path: &path::Path,
+    mut reader: R,
+) -> Result<std::thread::JoinHandle<Result<u64, FixtureError>>, FixtureError>
+where
+    R: std::io::Read + Send + 'static,
+{
+    ensure_parent_dir(path)?;
+    let file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+    let handle = std::thread::Builder::new()
+        .name("assert_fs-capture".to_owned())
+        .spawn(move || {
+            let mut file = std::io::BufWriter::new(file);
+            let copied = std::io::copy(&mut reader, &mut file)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            file.flush()
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            Ok(copied)
+        })
+        .chain(FixtureError::new(FixtureKind::WriteFile))?;
+    Ok(handle)
+}
+
+pub(super) fn hard_link_to(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::HardLinkTo, link, None, || {
+        ensure_parent_dir(link)?;
+        fs::hard_link(target, link).chain(FixtureError::new(FixtureKind::HardLink))
+    })
+}
+
+pub(super) fn ensure_parent_dir(path: &path::Path) -> Result<(), FixtureError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+    }
+    Ok(())
+}
+
+pub(super) fn create_dir_all(path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::CreateDirAll, path, None, || {
+        fs::create_dir_all(path).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        Ok(())
+    })
+}
+
+pub(super) fn remove_file(path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::RemoveFile, path, None, || {
+        retry_writable(path, || fs::remove_file(path)).chain(FixtureError::new(FixtureKind::Remove))
+    })
+}
+
+pub(super) fn remove_dir(path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::RemoveDir, path, None, || {
+        retry_writable(path, || fs::remove_dir(path)).chain(FixtureError::new(FixtureKind::Remove))
+    })
+}
+
+pub(super) fn remove_all(path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::RemoveAll, path, None, || remove_tree(path))
+}
+
+fn clear(path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::Clear, path, None, || {
+        for entry in fs::read_dir(path).chain(FixtureError::new(FixtureKind::Remove))? {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Remove))?;
+            remove_tree(&entry.path()).map_err(|err| err.with_path(entry.path()))?;
+        }
+        Ok(())
+    })
+}
+
+fn remove_tree(path: &path::Path) -> Result<(), FixtureError> {
+    let metadata = path
+        .symlink_metadata()
+        .chain(FixtureError::new(FixtureKind::Remove))?;
+    if !metadata.is_dir() {
+        return retry_writable(path, || fs::remove_file(path))
+            .chain(FixtureError::new(FixtureKind::Remove));
+    }
+    if fs::remove_dir_all(path).is_ok() {
+        return Ok(());
+    }
+    // Read-only entries block removal on Windows
+    for entry in walkdir::WalkDir::new(path).into_iter().flatten() {
+        let _ = make_writable(entry.path());
+    }
+    fs::remove_dir_all(path).chain(FixtureError::new(FixtureKind::Remove))
+}
+
+fn retry_writable<F>(path: &path::Path, op: F) -> std::io::Result<()>
+where
+    F: Fn() -> std::io::Result<()>,
+{
+    match op() {
+        Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+            if make_writable(path).unwrap_or(false) {
+                op()
+            } else {
+                Err(err)
+            }
+        }
+        result => result,
+    }
+}
+
+/// Clear the read-only flag, returning whether it was set.
+fn make_writable(path: &path::Path) -> std::io::Result<bool> {
+    let metadata = path.symlink_metadata()?;
+    let mut permissions = metadata.permissions();
+    if !permissions.readonly() || metadata.file_type().is_symlink() {
+        return Ok(false);
+    }
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)?;
+    Ok(true)
+}
+
+#[cfg(unix)]
+fn set_mode(path: &path::Path, mode: u32) -> Result<(), FixtureError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    record(OperationKind::SetMode, path, None, || {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .chain(FixtureError::new(FixtureKind::Permissions))
+    })
+}
+
+fn set_readonly(path: &path::Path, readonly: bool) -> Result<(), FixtureError> {
+    record(OperationKind::SetReadonly, path, None, || {
+        let mut permissions = path
+            .metadata()
+            .chain(FixtureError::new(FixtureKind::Permissions))?
+            .permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(readonly);
+        fs::set_permissions(path, permissions).chain(FixtureError::new(FixtureKind::Permissions))
+    })
+}
+
+pub(super) fn touch(path: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::Touch, path, Some(0), || {
+        ensure_parent_dir(path)?;
+        fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        Ok(())
+    })
+}
+
+pub(super) fn write_binary(path: &path::Path, data: &[u8]) -> Result<(), FixtureError> {
+    record(
+        OperationKind::WriteBinary,
+        path,
+        Some(data.len() as u64),
+        || write_bytes(path, data),
+    )
+}
+
+pub(super) fn write_str(path: &path::Path, data: &str) -> Result<(), FixtureError> {
+    record(
+        OperationKind::WriteStr,
+        path,
+        Some(data.len() as u64),
+        || write_bytes(path, data.as_bytes()),
+    )
+}
+
+fn write_fmt(path: &path::Path, args: std::fmt::Arguments<'_>) -> Result<(), FixtureError> {
+    let mut written = 0;
+    let result = write_counted(path, args, &mut written);
+    // Recorded once written, as the length is only known then
+    record(OperationKind::WriteFmt, path, Some(written), || result)
+}
+
+fn write_counted(
+    path: &path::Path,
+    args: std::fmt::Arguments<'_>,
+    written: &mut u64,
+) -> Result<(), FixtureError> {
+    ensure_parent_dir(path)?;
+    let file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+    let mut file = CountingWriter {
+        inner: std::io::BufWriter::new(file),
+        written,
+    };
+    file.write_fmt(args)
+        .and_then(|_| file.flush())
+        .chain(FixtureError::new(FixtureKind::WriteFile))
+}
+
+/// Count the bytes written through `inner`.
+struct CountingWriter<'a, W> {
+    inner: W,
+    written: &'a mut u64,
+}
+
+impl<W: Write> Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        *self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn write_atomic(path: &path::Path, data: &[u8]) -> Result<(), FixtureError> {
+    record(
+        OperationKind::WriteAtomic,
+        path,
+        Some(data.len() as u64),
+        || {
+            ensure_parent_dir(path)?;
+            let dir = path.parent().unwrap_or_else(|| path::Path::new("."));
+            let mut temp = tempfile::Builder::new()
+                .prefix(".assert_fs-")
+                .suffix(".tmp")
+                .tempfile_in(dir)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            temp.write_all(data)
+                .and_then(|_| temp.as_file().sync_all())
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            temp.persist(path)
+                .map_err(|err| err.error)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            Ok(())
+        },
+    )
+}
+
+fn write_bytes(path: &path::Path, data: &[u8]) -> Result<(), FixtureError> {
+    ensure_parent_dir(path)?;
+    let mut file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+    file.write_all(data)
+        .chain(FixtureError::new(FixtureKind::WriteFile))?;
+    Ok(())
+}
+
+fn append_bytes(path: &path::Path, data: &[u8]) -> Result<(), FixtureError> {
     ensure_parent_dir(path)?;
-    write_binary(path, data.as_bytes()).chain(FixtureError::new(FixtureKind::WriteFile))
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)
+        .chain(FixtureError::new(FixtureKind::WriteFile))?;
+    file.write_all(data)
+        .chain(FixtureError::new(FixtureKind::WriteFile))?;
+    Ok(())
+}
+
+fn write_from<R>(path: &path::Path, mut reader: R) -> Result<u64, FixtureError>
+where
+    R: std::io::Read,
+{
+    record(OperationKind::WriteFrom, path, None, || {
+        ensure_parent_dir(path)?;
+        let file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        let mut file = std::io::BufWriter::new(file);
+        let written = std::io::copy(&mut reader, &mut file)
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        file.flush()
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        Ok(written)
+    })
+}
+
+fn write_file_of_size(
+    path: &path::Path,
+    len: u64,
+    fill_pattern: &[u8],
+) -> Result<(), FixtureError> {
+    /// Bytes written per call when filling with a pattern.
+    const CHUNK: usize = 64 * 1024;
+
+    record(OperationKind::WriteOfSize, path, Some(len), || {
+        ensure_parent_dir(path)?;
+        let mut file = fs::File::create(path).chain(FixtureError::new(FixtureKind::WriteFile))?;
+        if fill_pattern.iter().all(|b| *b == 0) {
+            file.set_len(len)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            return Ok(());
+        }
+        let chunk: Vec<u8> = fill_pattern
+            .iter()
+            .copied()
+            .cycle()
+            .take(CHUNK.max(fill_pattern.len()) / fill_pattern.len() * fill_pattern.len())
+            .collect();
+        let mut left = len;
+        while left != 0 {
+            let n = left.min(chunk.len() as u64) as usize;
+            file.write_all(&chunk[..n])
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            left -= n as u64;
+        }
+        Ok(())
+    })
+}
+
+fn truncate(path: &path::Path, len: u64) -> Result<(), FixtureError> {
+    record(OperationKind::Truncate, path, None, || {
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        file.set_len(len)
+            .chain(FixtureError::new(FixtureKind::WriteFile))?;
+        Ok(())
+    })
+}
+
+fn patch_bytes(path: &path::Path, offset: u64, data: &[u8]) -> Result<(), FixtureError> {
+    use std::io::Seek;
+
+    record(
+        OperationKind::PatchBytes,
+        path,
+        Some(data.len() as u64),
+        || {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .open(path)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            file.seek(std::io::SeekFrom::Start(offset))
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            file.write_all(data)
+                .chain(FixtureError::new(FixtureKind::WriteFile))?;
+            Ok(())
+        },
+    )
+}
+
+#[cfg(windows)]
+fn write_script(path: &path::Path, body: &str) -> Result<path::PathBuf, FixtureError> {
+    let path = if path.extension().is_none() {
+        path.with_extension("cmd")
+    } else {
+        path.to_owned()
+    };
+    record(
+        OperationKind::WriteScript,
+        &path,
+        Some(body.len() as u64),
+        || write_bytes(&path, body.as_bytes()),
+    )?;
+    Ok(path)
+}
+
+#[cfg(not(windows))]
+fn write_script(path: &path::Path, body: &str) -> Result<path::PathBuf, FixtureError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    record(
+        OperationKind::WriteScript,
+        path,
+        Some(body.len() as u64),
+        || {
+            write_bytes(path, body.as_bytes())?;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+                .chain(FixtureError::new(FixtureKind::Permissions))
+        },
+    )?;
+    Ok(path.to_owned())
+}
+
+/// Name `path` in read errors.
+pub(crate) fn read_error(path: &path::Path, err: std::io::Error) -> FixtureError {
+    FixtureError::new(FixtureKind::ReadFile)
+        .chain(err)
+        .with_path(path)
+}
+
+pub(super) fn read_binary(path: &path::Path) -> Result<Vec<u8>, FixtureError> {
+    fs::read(path).map_err(|err| read_error(path, err))
+}
+
+pub(super) fn read_str(path: &path::Path) -> Result<String, FixtureError> {
+    fs::read_to_string(path).map_err(|err| read_error(path, err))
+}
+
+pub(super) fn write_file(path: &path::Path, data: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::WriteFile, path, None, || {
+        copy_file(path, data)
+    })
+}
+
+/// The error from a [`PathCopy`] default that can't do what's asked through the other methods.
+fn unsupported(reason: &'static str) -> FixtureError {
+    let err = std::io::Error::new(std::io::ErrorKind::Unsupported, reason);
+    FixtureError::new(FixtureKind::CopyFile).chain(err)
 }
 
-fn write_file(path: &path::Path, data: &path::Path) -> Result<(), FixtureError> {
+fn copy_file(path: &path::Path, data: &path::Path) -> Result<(), FixtureError> {
     ensure_parent_dir(path)?;
     fs::copy(data, path).chain(FixtureError::new(FixtureKind::CopyFile))?;
     Ok(())
 }
 
-fn copy_files<S>(
+#[cfg(feature = "glob")]
+pub(super) fn copy_files<S>(
+    target: &path::Path,
+    source: &path::Path,
+    patterns: &[S],
+    options: CopyOptions,
+) -> Result<CopyReport, FixtureError>
+where
+    S: AsRef<str>,
+{
+    record(OperationKind::CopyFrom, target, None, || {
+        let mut report = CopyReport::default();
+        let mut progress = super::progress::Tracker::new(OperationKind::CopyFrom, target);
+        // `walkdir`, on Windows, seems to convert "." into "" which then fails.
+        let source = source
+            .canonicalize()
+            .chain(FixtureError::new(FixtureKind::Walk))?;
+        if options.require_matches {
+            require_matches(&source, patterns, options)?;
+        }
+        // Applied once the walk is done, so copying children doesn't undo them, or isn't
+        // blocked by a read-only directory
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+        let mut ignore_files = IgnoreFiles::new(&source);
+        for entry in crate::order::glob(&source, patterns)
+            .follow_links(!options.preserve_symlinks)
+            .build()
+            .chain(FixtureError::new(FixtureKind::Walk))?
+            .filter(|entry| match entry {
+                Ok(entry) if options.respect_ignore_files => {
+                    !ignore_files.is_excluded(entry.path(), entry.file_type().is_dir())
+                }
+                _ => true,
+            })
+        {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(&source)
+                .expect("entries to be under `source`");
+            let target_path = target.join(rel);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target_path)
+                    .chain(FixtureError::new(FixtureKind::CreateDir))?;
+                dirs.push((entry.path().to_owned(), target_path));
+                report.dirs += 1;
+            } else if entry.file_type().is_file() {
+                files.push((entry.path().to_owned(), target_path));
+            } else if entry.file_type().is_symlink() {
+                links.push((entry.path().to_owned(), target_path));
+            } else {
+                report.skipped.push(rel.to_owned());
+            }
+        }
+        if options.on_conflict != ConflictPolicy::Overwrite {
+            for entries in [&mut files, &mut links] {
+                resolve_conflicts(entries, &source, options.on_conflict, &mut report)?;
+            }
+        }
+        // Parents of matched files that weren't matched themselves, once each
+        let parents: std::collections::BTreeSet<_> = files
+            .iter()
+            .map(|(_, target_path)| target_path.parent().expect("at least `target` exists"))
+            .collect();
+        for parent in parents {
+            fs::create_dir_all(parent).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        }
+        for bytes in copy_all(&files, options, &mut progress)? {
+            report.files += 1;
+            report.bytes += bytes;
+        }
+        for (source_link, target_path) in &links {
+            let link = fs::read_link(source_link).chain(FixtureError::new(FixtureKind::Symlink))?;
+            if source_link.is_dir() {
+                symlink_to_dir(target_path, &link)?;
+            } else {
+                symlink_to_file(target_path, &link)?;
+            }
+            report.symlinks += 1;
+        }
+        // Deepest first, so a parent's mtime is set after its children are
+        for (source_dir, target_dir) in dirs.iter().rev() {
+            if options.preserve_mtime {
+                copy_mtime(source_dir, target_dir)?;
+            }
+            if options.preserve_permissions {
+                let permissions = fs::metadata(source_dir)
+                    .chain(FixtureError::new(FixtureKind::Permissions))?
+                    .permissions();
+                fs::set_permissions(target_dir, permissions)
+                    .chain(FixtureError::new(FixtureKind::Permissions))?;
+            }
+        }
+        Ok(report)
+    })
+}
+
+#[cfg(feature = "glob")]
+fn copy_many<P, I, S>(
+    target: &path::Path,
+    sources: &[(P, I)],
+    options: CopyOptions,
+) -> Result<CopyReport, FixtureError>
+where
+    P: AsRef<path::Path>,
+    I: AsRef<[S]>,
+    S: AsRef<str>,
+{
+    let mut report = CopyReport::default();
+    for (source, patterns) in sources {
+        let copied = copy_files(target, source.as_ref(), patterns.as_ref(), options)?;
+        report.add(copied);
+    }
+    Ok(report)
+}
+
+/// Drop `entries`, a source and target path, whose target exists, or fail on the first, as
+/// `policy` says.
+#[cfg(feature = "glob")]
+fn resolve_conflicts(
+    entries: &mut Vec<(path::PathBuf, path::PathBuf)>,
+    source: &path::Path,
+    policy: ConflictPolicy,
+    report: &mut CopyReport,
+) -> Result<(), FixtureError> {
+    let mut result = Ok(());
+    entries.retain(|(source_path, target_path)| {
+        if result.is_err() || target_path.symlink_metadata().is_err() {
+            return true;
+        }
+        match policy {
+            ConflictPolicy::Error => {
+                let err = std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    "the destination already exists",
+                );
+                result = Err(FixtureError::new(FixtureKind::CopyFile)
+                    .chain(err)
+                    .with_path(target_path));
+                true
+            }
+            ConflictPolicy::SkipExisting => {
+                let rel = source_path
+                    .strip_prefix(source)
+                    .expect("entries to be under `source`");
+                report.skipped.push(rel.to_owned());
+                false
+            }
+            ConflictPolicy::Overwrite => true,
+        }
+    });
+    result
+}
+
+/// The `.gitignore` and `.ignore` files within a tree, loaded as the walk reaches them.
+#[cfg(feature = "glob")]
+struct IgnoreFiles {
+    root: path::PathBuf,
+    dirs: std::collections::HashMap<path::PathBuf, ignore::gitignore::Gitignore>,
+    excluded_dirs: std::collections::HashMap<path::PathBuf, bool>,
+}
+
+#[cfg(feature = "glob")]
+impl IgnoreFiles {
+    fn new(root: &path::Path) -> Self {
+        Self {
+            root: root.to_owned(),
+            dirs: Default::default(),
+            excluded_dirs: Default::default(),
+        }
+    }
+
+    /// Whether `path`, within the root, is ignored or within an ignored directory.
+    fn is_excluded(&mut self, path: &path::Path, is_dir: bool) -> bool {
+        if let Some(parent) = path.parent() {
+            if parent != self.root && parent.starts_with(&self.root) {
+                let parent = parent.to_owned();
+                let excluded = match self.excluded_dirs.get(&parent) {
+                    Some(excluded) => *excluded,
+                    None => {
+                        let excluded = self.is_excluded(&parent, true);
+                        self.excluded_dirs.insert(parent, excluded);
+                        excluded
+                    }
+                };
+                if excluded {
+                    return true;
+                }
+            }
+        }
+        self.is_ignored(path, is_dir)
+    }
+
+    /// Whether `path`, within the root, is ignored, not counting its parent directories.
+    fn is_ignored(&mut self, path: &path::Path, is_dir: bool) -> bool {
+        if is_dir && path.file_name() == Some(".git".as_ref()) {
+            return true;
+        }
+        // The deepest ignore file with a matching rule decides
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(&self.root) {
+                break;
+            }
+            let rules = self
+                .dirs
+                .entry(dir.to_owned())
+                .or_insert_with(|| Self::load(dir));
+            match rules.matched(path, is_dir) {
+                ignore::Match::None => {}
+                ignore::Match::Ignore(_) => return true,
+                ignore::Match::Whitelist(_) => return false,
+            }
+        }
+        false
+    }
+
+    fn load(dir: &path::Path) -> ignore::gitignore::Gitignore {
+        let mut builder = ignore::gitignore::GitignoreBuilder::new(dir);
+        for name in [".gitignore", ".ignore"] {
+            let file = dir.join(name);
+            if file.is_file() {
+                // Like `git`, skip invalid lines rather than failing
+                let _ = builder.add(file);
+            }
+        }
+        builder
+            .build()
+            .unwrap_or_else(|_| ignore::gitignore::Gitignore::empty())
+    }
+}
+
+/// Copy each of `files`, a source and target path, returning the bytes copied for each.
+#[cfg(all(feature = "glob", not(feature = "parallel")))]
+fn copy_all(
+    files: &[(path::PathBuf, path::PathBuf)],
+    options: CopyOptions,
+    progress: &mut super::progress::Tracker,
+) -> Result<Vec<u64>, FixtureError> {
+    files
+        .iter()
+        .map(|(source, target)| {
+            let bytes = copy_one(source, target, options)?;
+            progress.file(bytes);
+            Ok(bytes)
+        })
+        .collect()
+}
+
+/// Copy each of `files`, a source and target path, across threads, returning the bytes
+/// copied for each.
+#[cfg(all(feature = "glob", feature = "parallel"))]
+fn copy_all(
+    files: &[(path::PathBuf, path::PathBuf)],
+    options: CopyOptions,
+    progress: &mut super::progress::Tracker,
+) -> Result<Vec<u64>, FixtureError> {
+    use rayon::prelude::*;
+
+    let progress = std::sync::Mutex::new(progress);
+    files
+        .par_iter()
+        .map(|(source, target)| {
+            let bytes = copy_one(source, target, options)?;
+            progress
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .file(bytes);
+            Ok(bytes)
+        })
+        .collect()
+}
+
+#[cfg(feature = "glob")]
+fn copy_one(
+    source: &path::Path,
     target: &path::Path,
+    options: CopyOptions,
+) -> Result<u64, FixtureError> {
+    let bytes = if options.preserve_permissions {
+        fs::copy(source, target)
+    } else {
+        fs::File::open(source).and_then(|mut from| {
+            let mut to = fs::File::create(target)?;
+            std::io::copy(&mut from, &mut to)
+        })
+    }
+    .chain(FixtureError::new(FixtureKind::CopyFile))?;
+    if options.preserve_mtime {
+        copy_mtime(source, target)?;
+    }
+    Ok(bytes)
+}
+
+/// Fail on the first pattern matching nothing in `source`.
+#[cfg(feature = "glob")]
+fn require_matches<S>(
     source: &path::Path,
     patterns: &[S],
+    options: CopyOptions,
 ) -> Result<(), FixtureError>
 where
     S: AsRef<str>,
 {
-    // `walkdir`, on Windows, seems to convert "." into "" which then fails.
-    let source = source
-        .canonicalize()
-        .chain(FixtureError::new(FixtureKind::Walk))?;
-    for entry in globwalk::GlobWalkerBuilder::from_patterns(&source, patterns)
-        .follow_links(true)
-        .build()
-        .chain(FixtureError::new(FixtureKind::Walk))?
-    {
-        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
-        let rel = entry
-            .path()
-            .strip_prefix(&source)
-            .expect("entries to be under `source`");
-        let target_path = target.join(rel);
-        if entry.file_type().is_dir() {
-            fs::create_dir_all(target_path).chain(FixtureError::new(FixtureKind::CreateDir))?;
-        } else if entry.file_type().is_file() {
-            fs::create_dir_all(target_path.parent().expect("at least `target` exists"))
-                .chain(FixtureError::new(FixtureKind::CreateDir))?;
-            fs::copy(entry.path(), target_path).chain(FixtureError::new(FixtureKind::CopyFile))?;
+    for pattern in patterns.iter().map(|p| p.as_ref()) {
+        if pattern.starts_with('!') {
+            continue;
+        }
+        let found = crate::order::glob(source, &[pattern])
+            .follow_links(!options.preserve_symlinks)
+            .build()
+            .chain(FixtureError::new(FixtureKind::Walk))?
+            .any(|entry| entry.is_ok());
+        if !found {
+            let err = std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("`{}` matched nothing in `{}`", pattern, source.display()),
+            );
+            return Err(FixtureError::new(FixtureKind::NoMatch).chain(err));
         }
     }
     Ok(())
 }
 
+#[cfg(feature = "glob")]
+fn copy_mtime(source: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
+    let modified = fs::metadata(source)
+        .and_then(|m| m.modified())
+        .chain(FixtureError::new(FixtureKind::Timestamp))?;
+    super::clock::set_modified(target, modified, false)
+}
+
+#[cfg(feature = "glob")]
+fn link_farm<S>(
+    target: &path::Path,
+    source: &path::Path,
+    patterns: &[S],
+) -> Result<(), FixtureError>
+where
+    S: AsRef<str>,
+{
+    record(OperationKind::LinkFarmFrom, target, None, || {
+        // `walkdir`, on Windows, seems to convert "." into "" which then fails.
+        let source = source
+            .canonicalize()
+            .chain(FixtureError::new(FixtureKind::Walk))?;
+        for entry in crate::order::glob(&source, patterns)
+            .follow_links(true)
+            .build()
+            .chain(FixtureError::new(FixtureKind::Walk))?
+        {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(&source)
+                .expect("entries to be under `source`");
+            let target_path = target.join(rel);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(target_path).chain(FixtureError::new(FixtureKind::CreateDir))?;
+            } else if entry.file_type().is_file() {
+                symlink_to_file(&target_path, entry.path())?;
+            }
+        }
+        Ok(())
+    })
+}
+
+pub(super) fn clone_tree(
+    target: &path::Path,
+    source: &path::Path,
+    strategy: CloneStrategy,
+) -> Result<(), FixtureError> {
+    record(OperationKind::CloneFrom, target, None, || {
+        let mut progress = super::progress::Tracker::new(OperationKind::CloneFrom, target);
+        // `walkdir`, on Windows, seems to convert "." into "" which then fails.
+        let source = source
+            .canonicalize()
+            .chain(FixtureError::new(FixtureKind::Walk))?;
+        for entry in crate::order::walk(&source).min_depth(1) {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(&source)
+                .expect("entries to be under `source`");
+            let target_path = target.join(rel);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target_path)
+                    .chain(FixtureError::new(FixtureKind::CreateDir))?;
+            } else if entry.file_type().is_symlink() {
+                let link =
+                    fs::read_link(entry.path()).chain(FixtureError::new(FixtureKind::Symlink))?;
+                if entry.path().is_dir() {
+                    symlink_to_dir(&target_path, &link)?;
+                } else {
+                    symlink_to_file(&target_path, &link)?;
+                }
+            } else {
+                clone_file(entry.path(), &target_path, strategy)?;
+                let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                progress.file(bytes);
+            }
+        }
+        Ok(())
+    })
+}
+
+fn clone_file(
+    source: &path::Path,
+    target: &path::Path,
+    strategy: CloneStrategy,
+) -> Result<(), FixtureError> {
+    match strategy {
+        CloneStrategy::Auto => reflink(source, target)
+            .or_else(|_| fs::hard_link(source, target))
+            .or_else(|_| fs::copy(source, target).map(|_| ()))
+            .chain(FixtureError::new(FixtureKind::CopyFile)),
+        CloneStrategy::ReflinkOrCopy => reflink(source, target)
+            .or_else(|_| fs::copy(source, target).map(|_| ()))
+            .chain(FixtureError::new(FixtureKind::CopyFile)),
+        CloneStrategy::Reflink => {
+            reflink(source, target).chain(FixtureError::new(FixtureKind::CopyFile))
+        }
+        CloneStrategy::HardLink => {
+            fs::hard_link(source, target).chain(FixtureError::new(FixtureKind::HardLink))
+        }
+        CloneStrategy::Copy => fs::copy(source, target)
+            .map(|_| ())
+            .chain(FixtureError::new(FixtureKind::CopyFile)),
+    }
+}
+
+#[cfg(feature = "reflink")]
+fn reflink(source: &path::Path, target: &path::Path) -> std::io::Result<()> {
+    reflink_copy::reflink(source, target)
+}
+
+#[cfg(not(feature = "reflink"))]
+fn reflink(_source: &path::Path, _target: &path::Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "reflinks require the `reflink` feature",
+    ))
+}
+
+#[cfg(feature = "glob")]
+fn sync_files<S>(
+    target: &path::Path,
+    source: &path::Path,
+    patterns: &[S],
+) -> Result<(), FixtureError>
+where
+    S: AsRef<str>,
+{
+    record(OperationKind::SyncFrom, target, None, || {
+        let mut progress = super::progress::Tracker::new(OperationKind::SyncFrom, target);
+        // `walkdir`, on Windows, seems to convert "." into "" which then fails.
+        let source = source
+            .canonicalize()
+            .chain(FixtureError::new(FixtureKind::Walk))?;
+        let mut keep = std::collections::HashSet::new();
+        for entry in crate::order::glob(&source, patterns)
+            .follow_links(true)
+            .build()
+            .chain(FixtureError::new(FixtureKind::Walk))?
+        {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(&source)
+                .expect("entries to be under `source`");
+            let target_path = target.join(rel);
+            if entry.file_type().is_dir() {
+                if target_path.is_file() {
+                    fs::remove_file(&target_path).chain(FixtureError::new(FixtureKind::Remove))?;
+                }
+                fs::create_dir_all(&target_path)
+                    .chain(FixtureError::new(FixtureKind::CreateDir))?;
+            } else if entry.file_type().is_file() {
+                if target_path.is_dir() {
+                    fs::remove_dir_all(&target_path)
+                        .chain(FixtureError::new(FixtureKind::Remove))?;
+                }
+                if !same_content(entry.path(), &target_path) {
+                    fs::create_dir_all(target_path.parent().expect("at least `target` exists"))
+                        .chain(FixtureError::new(FixtureKind::CreateDir))?;
+                    let bytes = fs::copy(entry.path(), &target_path)
+                        .chain(FixtureError::new(FixtureKind::CopyFile))?;
+                    progress.file(bytes);
+                }
+            } else {
+                continue;
+            }
+            keep.extend(rel.ancestors().map(path::Path::to_owned));
+        }
+
+        if !target.exists() {
+            return Ok(());
+        }
+        for entry in crate::order::walk(target).min_depth(1).contents_first(true) {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(target)
+                .expect("entries to be under `target`");
+            if keep.contains(rel) {
+                continue;
+            }
+            if entry.file_type().is_dir() {
+                fs::remove_dir(entry.path())
+            } else {
+                fs::remove_file(entry.path())
+            }
+            .chain(FixtureError::new(FixtureKind::Remove))?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(feature = "glob")]
+fn same_content(left: &path::Path, right: &path::Path) -> bool {
+    match (left.metadata(), right.metadata()) {
+        (Ok(l), Ok(r)) if l.is_file() && r.is_file() && l.len() == r.len() => {}
+        _ => return false,
+    }
+    match (fs::read(left), fs::read(right)) {
+        (Ok(l), Ok(r)) => l == r,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "glob")]
+fn export_files<S>(
+    source: &path::Path,
+    dest: &path::Path,
+    patterns: &[S],
+) -> Result<(), FixtureError>
+where
+    S: AsRef<str>,
+{
+    record(OperationKind::ExportTo, source, None, || {
+        fs::create_dir_all(dest).chain(FixtureError::new(FixtureKind::CreateDir))?;
+        copy_files(dest, source, patterns, CopyOptions::default())?;
+        Ok(())
+    })
+}
+
+fn copy_to(source: &path::Path, dest: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::CopyTo, source, None, || {
+        if !source.is_dir() {
+            return copy_file(dest, source);
+        }
+        for entry in crate::order::walk(source).follow_links(true) {
+            let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+            let rel = entry
+                .path()
+                .strip_prefix(source)
+                .expect("entries to be under `source`");
+            let target_path = dest.join(rel);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(target_path).chain(FixtureError::new(FixtureKind::CreateDir))?;
+            } else if entry.file_type().is_file() {
+                fs::copy(entry.path(), target_path)
+                    .chain(FixtureError::new(FixtureKind::CopyFile))?;
+            }
+        }
+        Ok(())
+    })
+}
+
 #[cfg(windows)]
-fn symlink_to_file(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
-    std::os::windows::fs::symlink_file(target, link)
-        .chain(FixtureError::new(FixtureKind::Symlink))?;
-    Ok(())
+pub(super) fn symlink_to_file(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::SymlinkToFile, link, None, || {
+        ensure_parent_dir(link)?;
+        std::os::windows::fs::symlink_file(target, link)
+            .chain(FixtureError::new(FixtureKind::Symlink))
+    })
 }
 
 #[cfg(windows)]
-fn symlink_to_dir(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
-    std::os::windows::fs::symlink_dir(target, link)
-        .chain(FixtureError::new(FixtureKind::Symlink))?;
-    Ok(())
+pub(super) fn symlink_to_dir(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::SymlinkToDir, link, None, || {
+        ensure_parent_dir(link)?;
+        std::os::windows::fs::symlink_dir(target, link)
+            .chain(FixtureError::new(FixtureKind::Symlink))
+    })
 }
 
 #[cfg(not(windows))]
-fn symlink_to_file(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
-    std::os::unix::fs::symlink(target, link).chain(FixtureError::new(FixtureKind::Symlink))?;
-    Ok(())
+pub(super) fn symlink_to_file(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::SymlinkToFile, link, None, || {
+        ensure_parent_dir(link)?;
+        std::os::unix::fs::symlink(target, link).chain(FixtureError::new(FixtureKind::Symlink))
+    })
 }
 
 #[cfg(not(windows))]
-fn symlink_to_dir(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
-    std::os::unix::fs::symlink(target, link).chain(FixtureError::new(FixtureKind::Symlink))?;
-    Ok(())
+pub(super) fn symlink_to_dir(link: &path::Path, target: &path::Path) -> Result<(), FixtureError> {
+    record(OperationKind::SymlinkToDir, link, None, || {
+        ensure_parent_dir(link)?;
+        std::os::unix::fs::symlink(target, link).chain(FixtureError::new(FixtureKind::Symlink))
+    })
 }
@@ -0,0 +1,41 @@
+use std::path;
+use std::sync::atomic;
+
+use super::context::Context;
+use super::FixtureError;
+use super::OperationKind;
+
+pub(crate) type Shared = atomic::AtomicBool;
+
+/// Emit a `tracing` event for the operation on `path`, if its innermost fixture `context` traces.
+pub(crate) fn emit<T>(
+    context: Option<&Context>,
+    kind: OperationKind,
+    path: &path::Path,
+    bytes: Option<u64>,
+    result: &Result<T, FixtureError>,
+) {
+    let enabled = context
+        .map(|context| context.trace.load(atomic::Ordering::Relaxed))
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    match result {
+        Ok(_) => tracing::debug!(
+            target: "assert_fs",
+            operation = %kind,
+            path = %path.display(),
+            bytes,
+            "fixture operation"
+        ),
+        Err(err) => tracing::warn!(
+            target: "assert_fs",
+            operation = %kind,
+            path = %path.display(),
+            bytes,
+            error = %err,
+            "fixture operation failed"
+        ),
+    }
+}
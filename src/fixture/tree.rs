@@ -0,0 +1,188 @@
+use std::fmt::Write;
+use std::fs;
+use std::path;
+
+use sha2::Digest;
+
+use super::errors::*;
+use super::ChildPath;
+use super::TempDir;
+
+/// Render a directory tree as stable text, to compare with a snapshot testing library.
+///
+/// Entries are sorted by name and indented by depth.  Directories end in `/`, symlinks show
+/// their target with `/` separators, and files show their size or content hash.  The rendering
+/// doesn't include the fixture's own path and [redacts][crate::redact] secrets, so it is the
+/// same across runs and platforms.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::fixture::RenderOptions;
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.child("src/lib.rs").write_str("pub fn answer() {}\n").unwrap();
+/// temp.child("README.md").write_str("# Answer\r\n").unwrap();
+///
+/// let rendered = temp
+///     .render_tree(RenderOptions::new().inline_content(64))
+///     .unwrap();
+/// assert_eq!(
+///     rendered,
+///     "README.md (10 bytes)\n  | # Answer\nsrc/\n  lib.rs (19 bytes)\n    | pub fn answer() {}\n"
+/// );
+/// temp.close().unwrap();
+/// ```
+pub trait PathRenderTree {
+    /// Render the entries within the current path.
+    fn render_tree(&self, options: RenderOptions) -> Result<String, FixtureError>;
+
+    /// List the current path and the entries within it, with file sizes, for debugging.
+    ///
+    /// Unlike [`PathRenderTree::render_tree`], the listing starts with the full path, and
+    /// unreadable entries are shown inline rather than failing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// temp.child("src/lib.rs").write_str("pub fn answer() {}\n").unwrap();
+    ///
+    /// let listing = temp.tree();
+    /// assert!(listing.ends_with("  src/\n    lib.rs (19 bytes)\n"));
+    /// println!("{}", listing);
+    /// temp.close().unwrap();
+    /// ```
+    fn tree(&self) -> String;
+}
+
+impl PathRenderTree for TempDir {
+    fn render_tree(&self, options: RenderOptions) -> Result<String, FixtureError> {
+        render_tree(self.path(), options)
+    }
+
+    fn tree(&self) -> String {
+        render_listing(self.path(), usize::MAX)
+    }
+}
+
+impl PathRenderTree for ChildPath {
+    fn render_tree(&self, options: RenderOptions) -> Result<String, FixtureError> {
+        render_tree(self.path(), options)
+    }
+
+    fn tree(&self) -> String {
+        render_listing(self.path(), usize::MAX)
+    }
+}
+
+/// What [`PathRenderTree::render_tree`] shows for each file.
+///
+/// By default, only the size of files is shown.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    hashes: bool,
+    inline_content: usize,
+}
+
+impl RenderOptions {
+    /// The default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the SHA-256 of each file's content, rather than its size.
+    pub fn hashes(mut self, yes: bool) -> Self {
+        self.hashes = yes;
+        self
+    }
+
+    /// Show the content of UTF-8 files up to `max_bytes`, below their name, with `\r\n`
+    /// normalized to `\n`.
+    pub fn inline_content(mut self, max_bytes: usize) -> Self {
+        self.inline_content = max_bytes;
+        self
+    }
+}
+
+fn render_tree(root: &path::Path, options: RenderOptions) -> Result<String, FixtureError> {
+    let mut rendered = String::new();
+    for entry in walkdir::WalkDir::new(root).min_depth(1).sort_by_file_name() {
+        let entry = entry.chain(FixtureError::new(FixtureKind::Walk))?;
+        let indent = "  ".repeat(entry.depth() - 1);
+        let name = entry.file_name().to_string_lossy();
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            let _ = writeln!(rendered, "{}{}/", indent, name);
+        } else if file_type.is_symlink() {
+            let target =
+                fs::read_link(entry.path()).chain(FixtureError::new(FixtureKind::ReadFile))?;
+            let target = target
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let _ = writeln!(rendered, "{}{} -> {}", indent, name, target);
+        } else {
+            let data = fs::read(entry.path()).chain(FixtureError::new(FixtureKind::ReadFile))?;
+            if options.hashes {
+                let sha256 = crate::hex::encode(&sha2::Sha256::digest(&data));
+                let _ = writeln!(rendered, "{}{} sha256:{}", indent, name, sha256);
+            } else {
+                let _ = writeln!(rendered, "{}{} ({} bytes)", indent, name, data.len());
+            }
+            if data.len() <= options.inline_content {
+                if let Ok(content) = std::str::from_utf8(&data) {
+                    for line in content.lines() {
+                        let _ = writeln!(rendered, "{}  | {}", indent, line);
+                    }
+                }
+            }
+        }
+    }
+    Ok(crate::redact::apply(rendered))
+}
+
+/// Render `root` as an indented listing, stopping after `max_entries`.
+pub(crate) fn render_listing(root: &path::Path, max_entries: usize) -> String {
+    let mut rendered = format!("{}\n", root.display());
+    let mut walker = walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter();
+    let mut count = 0;
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                let _ = writeln!(rendered, "  <{}>", err);
+                continue;
+            }
+        };
+        if count == max_entries {
+            let remaining = walker.count() + 1;
+            let _ = writeln!(rendered, "  ... {} more entries", remaining);
+            break;
+        }
+        count += 1;
+
+        let indent = "  ".repeat(entry.depth());
+        let name = entry.file_name().to_string_lossy();
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            let _ = writeln!(rendered, "{}{}/", indent, name);
+        } else if file_type.is_symlink() {
+            let target = std::fs::read_link(entry.path())
+                .map(|t| t.display().to_string())
+                .unwrap_or_else(|_| "?".to_owned());
+            let _ = writeln!(rendered, "{}{} -> {}", indent, name, target);
+        } else {
+            let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let _ = writeln!(rendered, "{}{} ({} bytes)", indent, name, len);
+        }
+    }
+    crate::redact::apply(rendered)
+}
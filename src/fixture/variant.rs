@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::path;
+
+use super::errors::*;
+use super::ChildPath;
+use super::FileWriteBin;
+use super::PathCreateDir;
+use super::TempDir;
+
+/// A layout of files and directories, to instantiate as a fixture.
+///
+/// Matrix-style tests often need several slightly different layouts.  Define the base layout
+/// once, then derive each variant from it by overriding files or dropping paths, rather than
+/// repeating the whole fixture definition.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::fixture::FixtureVariant;
+/// use predicates::prelude::*;
+///
+/// let base = FixtureVariant::new("base")
+///     .file("Cargo.toml", "[package]\nname = \"demo\"\n")
+///     .file("src/lib.rs", "")
+///     .dir("target");
+/// let variants = [
+///     base.clone(),
+///     base.variant("no-target").without("target"),
+///     base.variant("bin").without("src/lib.rs").file("src/main.rs", "fn main() {}"),
+/// ];
+///
+/// for variant in &variants {
+///     let temp = variant.instantiate().unwrap();
+///     temp.child("Cargo.toml").assert(predicate::path::is_file());
+///     temp.close().unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FixtureVariant {
+    name: String,
+    entries: BTreeMap<path::PathBuf, Option<Vec<u8>>>,
+}
+
+impl FixtureVariant {
+    /// An empty layout, called `name`.
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// A copy of this layout, called `name`, to apply mutations to.
+    pub fn variant<S>(&self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// The name of the layout, for identifying the variant in test output.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add, or override, a file at the relative `path`.
+    pub fn file<P, C>(mut self, path: P, content: C) -> Self
+    where
+        P: AsRef<path::Path>,
+        C: AsRef<[u8]>,
+    {
+        self.entries
+            .insert(path.as_ref().to_owned(), Some(content.as_ref().to_owned()));
+        self
+    }
+
+    /// Add an empty directory at the relative `path`.
+    pub fn dir<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<path::Path>,
+    {
+        self.entries.insert(path.as_ref().to_owned(), None);
+        self
+    }
+
+    /// Drop the file or directory at the relative `path`, including everything under it.
+    pub fn without<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<path::Path>,
+    {
+        let path = path.as_ref();
+        self.entries.retain(|entry, _| !entry.starts_with(path));
+        self
+    }
+
+    /// The relative paths in the layout, in sorted order.
+    pub fn paths(&self) -> impl Iterator<Item = &path::Path> {
+        self.entries.keys().map(|p| p.as_path())
+    }
+
+    /// Create the layout in a new [`TempDir`].
+    pub fn instantiate(&self) -> Result<TempDir, FixtureError> {
+        let temp = TempDir::new()?;
+        self.write_to(temp.path())?;
+        Ok(temp)
+    }
+
+    /// Create the layout under `root`.
+    pub fn write_to<P>(&self, root: P) -> Result<(), FixtureError>
+    where
+        P: AsRef<path::Path>,
+    {
+        let root = root.as_ref();
+        for (rel, content) in &self.entries {
+            let child = ChildPath::new(root.join(rel));
+            match content {
+                Some(content) => child.write_binary(content)?,
+                None => child.create_dir_all()?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use std::path;
+use std::time;
+
+use super::errors::*;
+use super::ChildPath;
+use super::Retry;
+use super::TempDir;
+
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
+/// Wait for files to appear within [`TempDir`], by polling.
+///
+/// Polling needs no filesystem notification support, so it works in containers and on network
+/// filesystems.
+///
+pub trait PathWait {
+    /// Poll until at least one path matches the glob `pattern`, returning the matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FixtureKind::Timeout`] if nothing matched within `timeout`; the cause lists the
+    /// tree as it was on the last poll.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let out = temp.child("out/result.json");
+    /// std::thread::spawn(move || out.write_str("{}").unwrap());
+    ///
+    /// let matches = temp.wait_until("out/*.json", Duration::from_secs(10)).unwrap();
+    /// assert_eq!(matches.len(), 1);
+    /// temp.close().unwrap();
+    /// ```
+    fn wait_until(
+        &self,
+        pattern: &str,
+        timeout: time::Duration,
+    ) -> Result<Vec<ChildPath>, FixtureError>;
+
+    /// Like [`PathWait::wait_until`], polling according to `retry`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::fixture::Retry;
+    /// use assert_fs::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let out = temp.child("out/result.json");
+    /// std::thread::spawn(move || out.write_str("{}").unwrap());
+    ///
+    /// let retry = Retry::backoff(Duration::from_millis(1)).timeout(Duration::from_secs(10));
+    /// let matches = temp.wait_until_with("out/*.json", &retry).unwrap();
+    /// assert_eq!(matches.len(), 1);
+    /// temp.close().unwrap();
+    /// ```
+    fn wait_until_with(&self, pattern: &str, retry: &Retry)
+        -> Result<Vec<ChildPath>, FixtureError>;
+}
+
+impl PathWait for TempDir {
+    fn wait_until(
+        &self,
+        pattern: &str,
+        timeout: time::Duration,
+    ) -> Result<Vec<ChildPath>, FixtureError> {
+        wait_until(
+            self.path(),
+            pattern,
+            &Retry::fixed(POLL_INTERVAL).timeout(timeout),
+        )
+    }
+
+    fn wait_until_with(
+        &self,
+        pattern: &str,
+        retry: &Retry,
+    ) -> Result<Vec<ChildPath>, FixtureError> {
+        wait_until(self.path(), pattern, retry)
+    }
+}
+
+impl PathWait for ChildPath {
+    fn wait_until(
+        &self,
+        pattern: &str,
+        timeout: time::Duration,
+    ) -> Result<Vec<ChildPath>, FixtureError> {
+        wait_until(
+            self.path(),
+            pattern,
+            &Retry::fixed(POLL_INTERVAL).timeout(timeout),
+        )
+    }
+
+    fn wait_until_with(
+        &self,
+        pattern: &str,
+        retry: &Retry,
+    ) -> Result<Vec<ChildPath>, FixtureError> {
+        wait_until(self.path(), pattern, retry)
+    }
+}
+
+fn wait_until(
+    root: &path::Path,
+    pattern: &str,
+    retry: &Retry,
+) -> Result<Vec<ChildPath>, FixtureError> {
+    let mut attempts = retry.start();
+    loop {
+        let matches = find(root, pattern)?;
+        if !matches.is_empty() {
+            return Ok(matches);
+        }
+        if !attempts.next() {
+            let err = std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!(
+                    "nothing matched `{}` after {:?}\n{}",
+                    pattern,
+                    attempts.elapsed(),
+                    super::tree::render_listing(root, 100)
+                ),
+            );
+            return Err(FixtureError::new(FixtureKind::Timeout).chain(err));
+        }
+    }
+}
+
+fn find(root: &path::Path, pattern: &str) -> Result<Vec<ChildPath>, FixtureError> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+    let walker = crate::order::glob(root, &[pattern])
+        .build()
+        .chain(FixtureError::new(FixtureKind::Walk))?;
+    // Entries may disappear while walking; only keep what could be read.
+    let matches = walker
+        .filter_map(|entry| entry.ok())
+        .map(|entry| ChildPath::new(entry.into_path()))
+        .collect();
+    Ok(matches)
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::path;
+use std::sync;
+
+use notify::Watcher as _;
+
+use super::errors::*;
+use super::ChildPath;
+use super::Retry;
+use super::TempDir;
+
+/// Record filesystem events within [`TempDir`] or [`ChildPath`].
+///
+/// For tools driven by file watching, to assert on what they changed without racy sleeps.
+///
+pub trait PathWatch {
+    /// Start recording create, modify, and remove events under the path, until the returned
+    /// handle is dropped.
+    ///
+    /// Events are reported by the platform's watching API, like inotify or FSEvents, which may
+    /// coalesce or delay them.  Assertions on the handle wait for the expected event to arrive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use assert_fs::prelude::*;
+    ///
+    /// let temp = assert_fs::TempDir::new().unwrap();
+    /// let events = temp.watch().unwrap();
+    ///
+    /// // ... run the code under test ...
+    /// temp.child("out/result.json").write_str("{}").unwrap();
+    ///
+    /// events.assert_created("out/result.json");
+    /// temp.close().unwrap();
+    /// ```
+    fn watch(&self) -> Result<WatchEvents, FixtureError>;
+}
+
+impl PathWatch for TempDir {
+    fn watch(&self) -> Result<WatchEvents, FixtureError> {
+        WatchEvents::start(self.path())
+    }
+}
+
+impl PathWatch for ChildPath {
+    fn watch(&self) -> Result<WatchEvents, FixtureError> {
+        WatchEvents::start(self.path())
+    }
+}
+
+/// What happened to a path, as recorded by [`WatchEvents`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FsEventKind {
+    /// The path was created, or renamed into place.
+    Created,
+    /// The path's content or metadata changed.
+    Modified,
+    /// The path was removed, or renamed away.
+    Removed,
+}
+
+impl fmt::Display for FsEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FsEventKind::Created => write!(f, "created"),
+            FsEventKind::Modified => write!(f, "modified"),
+            FsEventKind::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+/// A filesystem event recorded by [`WatchEvents`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FsEvent {
+    kind: FsEventKind,
+    path: path::PathBuf,
+}
+
+impl FsEvent {
+    /// What happened.
+    pub fn kind(&self) -> FsEventKind {
+        self.kind
+    }
+
+    /// Where it happened, relative to the watched path.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+}
+
+impl fmt::Display for FsEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.path.display())
+    }
+}
+
+/// Filesystem events recorded under a path, created with [`PathWatch::watch`].
+///
+/// Recording stops when dropped.
+pub struct WatchEvents {
+    root: path::PathBuf,
+    events: sync::Arc<sync::Mutex<Vec<FsEvent>>>,
+    retry: Retry,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl WatchEvents {
+    fn start(root: &path::Path) -> Result<Self, FixtureError> {
+        let events = sync::Arc::new(sync::Mutex::new(Vec::new()));
+        // Platforms may report canonical paths, e.g. macOS's `/private/var` for `/var`
+        let roots = [
+            root.to_owned(),
+            root.canonicalize().unwrap_or_else(|_| root.to_owned()),
+        ];
+        let recorded = sync::Arc::clone(&events);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    record(&roots, &recorded, event);
+                }
+            })
+            .chain(FixtureError::new(FixtureKind::Watch))?;
+        watcher
+            .watch(root, notify::RecursiveMode::Recursive)
+            .chain(FixtureError::new(FixtureKind::Watch))?;
+        Ok(Self {
+            root: root.to_owned(),
+            events,
+            retry: Retry::default(),
+            _watcher: watcher,
+        })
+    }
+
+    /// How long assertions wait for an expected event, defaulting to [`Retry::default`].
+    pub fn retry(mut self, retry: Retry) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The events recorded so far, in the order they were reported.
+    pub fn events(&self) -> Vec<FsEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Forget the events recorded so far, e.g. between steps of a test.
+    pub fn clear(&self) {
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Assert `path`, relative to the watched path, was created.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if no such event arrives before the retry gives up, listing the events that
+    /// did.
+    #[track_caller]
+    pub fn assert_created<P>(&self, path: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        self.assert_event(FsEventKind::Created, path.as_ref());
+        self
+    }
+
+    /// Assert `path`, relative to the watched path, was modified.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if no such event arrives before the retry gives up, listing the events that
+    /// did.
+    #[track_caller]
+    pub fn assert_modified<P>(&self, path: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        self.assert_event(FsEventKind::Modified, path.as_ref());
+        self
+    }
+
+    /// Assert `path`, relative to the watched path, was removed.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if no such event arrives before the retry gives up, listing the events that
+    /// did.
+    #[track_caller]
+    pub fn assert_removed<P>(&self, path: P) -> &Self
+    where
+        P: AsRef<path::Path>,
+    {
+        self.assert_event(FsEventKind::Removed, path.as_ref());
+        self
+    }
+
+    #[track_caller]
+    fn assert_event(&self, kind: FsEventKind, path: &path::Path) {
+        let expected = FsEvent {
+            kind,
+            path: path.to_owned(),
+        };
+        let found = self
+            .retry
+            .run(|| {
+                if self.events().contains(&expected) {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            })
+            .is_ok();
+        if found {
+            return;
+        }
+        let palette = crate::Palette::current();
+        let observed: Vec<_> = self
+            .events()
+            .iter()
+            .map(|event| format!("\n  {}", event))
+            .collect();
+        crate::report::fail(format!(
+            "Expected filesystem event not observed\n{}={}\n{}={}\n{}:{}",
+            palette.key.paint("expected"),
+            palette.value.paint(&expected),
+            palette.key.paint("path"),
+            palette.value.paint(self.root.display()),
+            palette.key.paint("observed"),
+            if observed.is_empty() {
+                " none".to_owned()
+            } else {
+                observed.concat()
+            },
+        ));
+    }
+}
+
+impl fmt::Debug for WatchEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchEvents")
+            .field("root", &self.root)
+            .field("events", &self.events())
+            .finish_non_exhaustive()
+    }
+}
+
+fn record(roots: &[path::PathBuf], events: &sync::Mutex<Vec<FsEvent>>, event: notify::Event) {
+    use notify::event::{EventKind, ModifyKind, RenameMode};
+
+    let kinds: &[FsEventKind] = match event.kind {
+        EventKind::Create(_) => &[FsEventKind::Created],
+        EventKind::Remove(_) => &[FsEventKind::Removed],
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => &[FsEventKind::Removed],
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => &[FsEventKind::Created],
+        // Both paths of a rename, in order
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            &[FsEventKind::Removed, FsEventKind::Created]
+        }
+        EventKind::Modify(_) => &[FsEventKind::Modified],
+        _ => return,
+    };
+    let mut events = events.lock().unwrap_or_else(|e| e.into_inner());
+    for (i, path) in event.paths.iter().enumerate() {
+        let rel = match roots.iter().find_map(|root| path.strip_prefix(root).ok()) {
+            Some(rel) => rel,
+            None => continue,
+        };
+        let kind = kinds[i.min(kinds.len() - 1)];
+        events.push(FsEvent {
+            kind,
+            path: rel.to_owned(),
+        });
+        if kind == FsEventKind::Created && path.is_dir() {
+            created_within(path, rel, &mut events);
+        }
+    }
+}
+
+/// Record the entries already in the new directory `path`, as they may have been created
+/// before the directory itself was watched.
+fn created_within(path: &path::Path, rel: &path::Path, events: &mut Vec<FsEvent>) {
+    for entry in crate::order::walk(path).min_depth(1).into_iter().flatten() {
+        let entry_rel = match entry.path().strip_prefix(path) {
+            Ok(entry_rel) => rel.join(entry_rel),
+            Err(_) => continue,
+        };
+        let event = FsEvent {
+            kind: FsEventKind::Created,
+            path: entry_rel,
+        };
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::path;
+
+use super::errors::*;
+use super::ChildPath;
+use super::PathChild;
+use super::PathCreateDir;
+use super::TempDir;
+
+/// Several related, named directories sharing a single lifecycle.
+///
+/// Tools that sync, copy or back up data usually need more than one root in every test (e.g.
+/// `src`, `dest` and `cache`).  Each root is created side-by-side in one [`TempDir`], so
+/// persisting or cleaning up the workspace applies to all of them together.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::fixture::Workspace;
+///
+/// let workspace = Workspace::new(&["src", "dest"]).unwrap();
+/// workspace.root("src").child("foo.txt").touch().unwrap();
+///
+/// // ... sync `src` into `dest` ...
+///
+/// workspace.close().unwrap();
+/// ```
+pub struct Workspace {
+    temp: TempDir,
+    names: Vec<String>,
+}
+
+impl Workspace {
+    /// Create a workspace with an empty directory for each of `names`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a name is not a valid [`PathChild::child`].
+    pub fn new<S>(names: &[S]) -> Result<Self, FixtureError>
+    where
+        S: AsRef<str>,
+    {
+        let temp = TempDir::new()?;
+        let names: Vec<_> = names.iter().map(|n| n.as_ref().to_owned()).collect();
+        let workspace = Self { temp, names };
+        for name in &workspace.names {
+            workspace.root(name).create_dir_all()?;
+        }
+        Ok(workspace)
+    }
+
+    /// Access one of the roots of the workspace.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `name` was not passed to [`Workspace::new`].
+    #[track_caller]
+    pub fn root(&self, name: &str) -> ChildPath {
+        assert!(
+            self.names.iter().any(|n| n == name),
+            "Unknown workspace root `{}`, expected one of {:?}",
+            name,
+            self.names
+        );
+        self.temp.child(name)
+    }
+
+    /// The names of the roots, in the order they were declared.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|n| n.as_str())
+    }
+
+    /// Accesses the [`Path`][std::path::Path] containing all of the roots.
+    pub fn path(&self) -> &path::Path {
+        self.temp.path()
+    }
+
+    /// Conditionally persist every root for debug purposes.
+    ///
+    /// Note: this operation is not reversible, i.e. `into_persistent_if(false)` is a no-op.
+    pub fn into_persistent_if(self, yes: bool) -> Self {
+        if !yes {
+            return self;
+        }
+
+        self.into_persistent()
+    }
+
+    /// Persist every root for debug purposes.
+    ///
+    /// Note: this operation is not reversible, i.e. `into_persistent_if(false)` is a no-op.
+    pub fn into_persistent(self) -> Self {
+        let Self { temp, names } = self;
+        let temp = temp.into_persistent();
+        Self { temp, names }
+    }
+
+    /// Closes and removes every root, returning a `Result`.
+    pub fn close(self) -> Result<(), FixtureError> {
+        self.temp.close()
+    }
+}
+
+impl AsRef<path::Path> for Workspace {
+    fn as_ref(&self) -> &path::Path {
+        self.path()
+    }
+}
@@ -0,0 +1,20 @@
+use std::fmt::Write;
+
+/// Lowercase hex encoding of `bytes`, like a SHA-256 digest in a manifest.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_pads_bytes() {
+        assert_eq!(encode(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+    }
+}
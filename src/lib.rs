@@ -12,12 +12,44 @@
 //! - [`write_binary`][`FileWriteBin`] a [`ChildPath`] or [`NamedTempFile`]
 //! - [`write_str`][`FileWriteStr`] a [`ChildPath`] or [`NamedTempFile`]
 //! - [`write_file`][`FileWriteFile`] a [`ChildPath`] or [`NamedTempFile`]
-//! - [`copy_from`][`PathCopy`] a pristine folder to a [`ChildPath`] or [`TempDir`]
+//! - [`write!`][`FileWriteFmt`] formatted text to a [`ChildPath`] or [`NamedTempFile`]
+//! - [`write_random_bytes`][`FileGenerate`], `write_random_text` or `write_zeros` of a given size
+//!   to a [`ChildPath`] or [`NamedTempFile`], the same for the same seed
+//! - [`write_sparse`][`FileWriteSparse`] a file with holes to a [`ChildPath`] or
+//!   [`NamedTempFile`]
+//! - [`write_str_templated`][`FileWriteTemplated`] text with `{{name}}` placeholders filled in to
+//!   a [`ChildPath`] or [`NamedTempFile`]
+//! - [`copy_from`][`PathCopy`] a pristine folder to a [`ChildPath`] or [`TempDir`] (requires the
+//!   default `glob` feature)
+//! - [`copy_from_mapped`][`PathCopyMapped`] a folder, skipping, renaming, or rewriting entries
+//!   along the way (requires the default `glob` feature)
 //! - [`symlink_to_file`][`SymlinkToFile`] a [`ChildPath`] or [`NamedTempFile`]
 //! - [`symlink_to_dir`][`SymlinkToDir`] a [`ChildPath`] or [`TempDir`]
+//! - [`hard_link_to`][`HardLinkTo`] a [`ChildPath`] or [`NamedTempFile`]
+//! - [`write_corpus`][`PathCorpus`] a fuzzing corpus to a [`ChildPath`] or [`TempDir`]
+//! - [`populate_from_str`][`PathPopulate`] a [`ChildPath`] or [`TempDir`] from a JSON description
+//!   of the tree (requires the `spec-json` feature)
+//! - `init_git_repo` in a [`ChildPath`] or [`TempDir`], to `commit_all` and leave tracked,
+//!   untracked, and dirty files in (requires the `git` feature)
+//! - `write_str_async`, `read_str_async`, `copy_from_async` and friends for async tests, backed
+//!   by `tokio::fs` (requires the `async` feature)
+//! - `trace` every operation on a [`TempDir`] as a `tracing` event (requires
+//!   the `tracing` feature)
+//! - `tree`, a `proptest` strategy for arbitrary directory trees to `materialize` into a
+//!   [`TempDir`] (requires the `proptest` feature)
 //!
 //! Validating
 //! - [`assert`][`PathAssert`] a [`ChildPath`], [`TempDir`], or [`NamedTempFile`]
+//! - `assert_async` the same, from async tests (requires the `async` feature)
+//! - `assert_json`, `assert_toml`, or `assert_yaml` a [`ChildPath`] or [`NamedTempFile`],
+//!   ignoring formatting and key order (requires the `structured-json`, `structured-toml`, or
+//!   `structured-yaml` feature)
+//! - `write_manifest` of a [`TempDir`] or [`ChildPath`], with the size, mode, and hash of every
+//!   entry, and `assert_manifest` it later, as a compact golden tree
+//! - `set_xattr` and `assert_xattr` extended attributes of a [`ChildPath`] or [`NamedTempFile`],
+//!   as NTFS alternate data streams on Windows (requires the `xattr` feature)
+//! - `lock_exclusive` or `lock_shared` a [`ChildPath`] or [`NamedTempFile`] while the code under
+//!   test runs, and `assert_locked` or `assert_unlocked` it (requires the `lock` feature)
 //!
 //! ## Example
 //!
@@ -44,9 +76,17 @@
 //! [`FileWriteBin`]: fixture::FileWriteBin
 //! [`FileWriteStr`]: fixture::FileWriteStr
 //! [`FileWriteFile`]: fixture::FileWriteFile
+//! [`FileWriteFmt`]: fixture::FileWriteFmt
+//! [`FileGenerate`]: fixture::FileGenerate
+//! [`FileWriteSparse`]: fixture::FileWriteSparse
+//! [`FileWriteTemplated`]: fixture::FileWriteTemplated
 //! [`SymlinkToDir`]: fixture::SymlinkToDir
+//! [`HardLinkTo`]: fixture::HardLinkTo
 //! [`SymlinkToFile`]: fixture::SymlinkToFile
 //! [`PathCopy`]: fixture::PathCopy
+//! [`PathCopyMapped`]: fixture::PathCopyMapped
+//! [`PathCorpus`]: fixture::PathCorpus
+//! [`PathPopulate`]: fixture::PathPopulate
 //! [`PathAssert`]: assert::PathAssert
 //! [dir-diff]: https://crates.io/crates/dir-diff
 
@@ -54,11 +94,31 @@
 
 pub mod assert;
 pub mod fixture;
+pub mod order;
+pub mod redact;
+pub mod report;
 
 // Pulling this in for convenience-sake
 #[doc(inline)]
 pub use crate::fixture::TempDir;
 
+/// Run a test with a fresh [`TempDir`], kept on failure (requires the `macros` feature).
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+/// use assert_fs::TempDir;
+///
+/// #[assert_fs::fixture]
+/// fn writes_report(temp: &TempDir) {
+///     temp.child("report.txt").write_str("ok").unwrap();
+///     temp.child("report.txt").assert("ok");
+/// }
+/// ```
+#[cfg(feature = "macros")]
+pub use assert_fs_macros::fixture;
+
 // Pulling this in for convenience-sake
 #[doc(inline)]
 pub use crate::fixture::NamedTempFile;
@@ -66,19 +126,98 @@ pub use crate::fixture::NamedTempFile;
 /// Extension traits that are useful to have available.
 pub mod prelude {
     pub use crate::assert::PathAssert;
+    #[cfg(feature = "async")]
+    pub use crate::assert::PathAssertAsync;
+    pub use crate::assert::PathAssertBytes;
+    #[cfg(feature = "checksum")]
+    pub use crate::assert::PathAssertDigest;
+    pub use crate::assert::PathAssertDuplicates;
+    pub use crate::assert::PathAssertEncoding;
+    pub use crate::assert::PathAssertEntry;
+    pub use crate::assert::PathAssertExactCase;
+    pub use crate::assert::PathAssertGolden;
+    #[cfg(feature = "sniff")]
+    pub use crate::assert::PathAssertKind;
+    #[cfg(feature = "lock")]
+    pub use crate::assert::PathAssertLock;
+    pub use crate::assert::PathAssertMetadata;
+    pub use crate::assert::PathAssertSimilar;
+    pub use crate::assert::PathAssertSorted;
+    #[cfg(any(
+        feature = "structured-json",
+        feature = "structured-toml",
+        feature = "structured-yaml"
+    ))]
+    pub use crate::assert::PathAssertStructured;
+    pub use crate::assert::PathAssertTree;
+    #[cfg(feature = "xattr")]
+    pub use crate::assert::PathAssertXattr;
+    #[cfg(feature = "sniff")]
+    pub use crate::assert::PathFindKind;
+    pub use crate::fixture::FileAppend;
+    pub use crate::fixture::FileCapture;
+    pub use crate::fixture::FileGenerate;
+    #[cfg(feature = "lock")]
+    pub use crate::fixture::FileLock;
+    pub use crate::fixture::FilePatch;
+    pub use crate::fixture::FileRead;
+    #[cfg(feature = "async")]
+    pub use crate::fixture::FileReadAsync;
+    pub use crate::fixture::FileTimestamp;
     pub use crate::fixture::FileTouch;
+    #[cfg(feature = "async")]
+    pub use crate::fixture::FileWriteAsync;
+    pub use crate::fixture::FileWriteAtomic;
     pub use crate::fixture::FileWriteBin;
     pub use crate::fixture::FileWriteFile;
+    pub use crate::fixture::FileWriteFmt;
+    pub use crate::fixture::FileWriteScript;
+    pub use crate::fixture::FileWriteSlowly;
+    pub use crate::fixture::FileWriteSparse;
     pub use crate::fixture::FileWriteStr;
+    pub use crate::fixture::FileWriteStream;
+    pub use crate::fixture::FileWriteTemplated;
+    pub use crate::fixture::HardLinkTo;
     pub use crate::fixture::PathChild;
+    pub use crate::fixture::PathClear;
+    pub use crate::fixture::PathContend;
     pub use crate::fixture::PathCopy;
+    #[cfg(all(feature = "async", feature = "glob"))]
+    pub use crate::fixture::PathCopyAsync;
+    #[cfg(feature = "glob")]
+    pub use crate::fixture::PathCopyMapped;
+    #[cfg(feature = "glob")]
+    pub use crate::fixture::PathCopyTemplated;
+    pub use crate::fixture::PathCorpus;
     pub use crate::fixture::PathCreateDir;
+    #[cfg(any(feature = "archive-tar", feature = "archive-zip"))]
+    pub use crate::fixture::PathExtract;
+    #[cfg(feature = "git")]
+    pub use crate::fixture::PathGitRepo;
+    pub use crate::fixture::PathManifest;
+    pub use crate::fixture::PathMetadataSnapshot;
+    pub use crate::fixture::PathPermissions;
+    #[cfg(feature = "spec-json")]
+    pub use crate::fixture::PathPopulate;
+    pub use crate::fixture::PathRemove;
+    pub use crate::fixture::PathRenderTree;
+    pub use crate::fixture::PathSnapshot;
+    #[cfg(unix)]
+    pub use crate::fixture::PathSpecialFile;
+    pub use crate::fixture::PathStress;
+    #[cfg(feature = "glob")]
+    pub use crate::fixture::PathWait;
+    #[cfg(feature = "watch")]
+    pub use crate::fixture::PathWatch;
+    #[cfg(feature = "xattr")]
+    pub use crate::fixture::PathXattr;
     pub use crate::fixture::SymlinkToDir;
     pub use crate::fixture::SymlinkToFile;
 }
 
 mod color;
 use color::Palette;
+mod hex;
 
 #[macro_use]
 extern crate doc_comment;
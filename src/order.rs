@@ -0,0 +1,86 @@
+//! Order of directory listings.
+//!
+//! Filesystems list directory entries in arbitrary order, which can differ between machines.
+//! So tests and snapshots don't flake, every API that walks, globs, diffs, or dumps a tree
+//! visits entries sorted by file name, a directory's entries right after the directory itself.
+//! For example, [`PathChild::children`][crate::fixture::PathChild::children] yields matches in
+//! that order, and failure messages list offending paths in it.
+//!
+//! Sorting means reading each directory in full before visiting it.  For very large trees,
+//! where order doesn't matter, it can be turned off process-wide with [`set_sorted`], or by
+//! setting the [`UNSORTED_ENV`] environment variable.  Rendered trees and snapshots, which are
+//! compared against stored copies, are always sorted.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "glob")] {
+//! use assert_fs::prelude::*;
+//!
+//! let temp = assert_fs::TempDir::new().unwrap();
+//! temp.child("b.txt").touch().unwrap();
+//! temp.child("a.txt").touch().unwrap();
+//!
+//! let names: Vec<_> = temp
+//!     .children("*.txt")
+//!     .map(|child| child.path().file_name().unwrap().to_owned())
+//!     .collect();
+//! assert_eq!(names, ["a.txt", "b.txt"]);
+//!
+//! temp.close().unwrap();
+//! # }
+//! ```
+
+use std::path;
+use std::sync::atomic;
+
+/// Environment variable that, when set to anything but `0`, turns sorting off.
+pub const UNSORTED_ENV: &str = "ASSERT_FS_UNSORTED";
+
+const UNSET: u8 = 0;
+const SORTED: u8 = 1;
+const UNSORTED: u8 = 2;
+
+static MODE: atomic::AtomicU8 = atomic::AtomicU8::new(UNSET);
+
+/// Turn sorting on or off for the rest of the process, overriding [`UNSORTED_ENV`].
+pub fn set_sorted(yes: bool) {
+    let mode = if yes { SORTED } else { UNSORTED };
+    MODE.store(mode, atomic::Ordering::Relaxed);
+}
+
+/// Whether listings are sorted.
+pub fn is_sorted() -> bool {
+    match MODE.load(atomic::Ordering::Relaxed) {
+        SORTED => true,
+        UNSORTED => false,
+        _ => !std::env::var_os(UNSORTED_ENV)
+            .map(|v| !v.is_empty() && v != "0")
+            .unwrap_or(false),
+    }
+}
+
+/// Walk `root`, in sorted order unless turned off.
+pub(crate) fn walk(root: &path::Path) -> walkdir::WalkDir {
+    let walker = walkdir::WalkDir::new(root);
+    if is_sorted() {
+        walker.sort_by_file_name()
+    } else {
+        walker
+    }
+}
+
+/// Walk the entries under `root` matching the glob `patterns`, in sorted order unless turned
+/// off.
+#[cfg(feature = "glob")]
+pub(crate) fn glob<S>(root: &path::Path, patterns: &[S]) -> globwalk::GlobWalkerBuilder
+where
+    S: AsRef<str>,
+{
+    let walker = globwalk::GlobWalkerBuilder::from_patterns(root, patterns);
+    if is_sorted() {
+        walker.sort_by(|a, b| a.file_name().cmp(b.file_name()))
+    } else {
+        walker
+    }
+}
@@ -0,0 +1,82 @@
+//! Mask secrets in failure output.
+//!
+//! Fixtures sometimes contain credentials, like tokens written into config files.  Registered
+//! secrets are replaced with `[REDACTED]` in every assertion failure message and fixture tree
+//! dump, so CI logs from failing tests don't leak them.
+//!
+//! Secrets are registered process-wide, as tests commonly run on many threads.
+//!
+//! # Examples
+//!
+//! ```rust,should_panic
+//! use assert_fs::prelude::*;
+//!
+//! let token = "ghp_1234567890";
+//! assert_fs::redact::add(token);
+//!
+//! let temp = assert_fs::TempDir::new().unwrap();
+//! let config = temp.child("config.toml");
+//! config.write_str(&format!("token = \"{}\"\n", token)).unwrap();
+//!
+//! // The failure message shows `token = "[REDACTED]"`
+//! config.assert("token = \"other\"\n");
+//! ```
+
+use std::sync;
+
+/// What secrets are replaced with.
+pub const MASK: &str = "[REDACTED]";
+
+enum Pattern {
+    Exact(String),
+    #[cfg(feature = "redact-regex")]
+    Regex(regex::Regex),
+}
+
+static PATTERNS: once_cell::sync::Lazy<sync::RwLock<Vec<Pattern>>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// Mask every occurrence of `secret` in failure output.
+pub fn add<S>(secret: S)
+where
+    S: Into<String>,
+{
+    let secret = secret.into();
+    if secret.is_empty() {
+        return;
+    }
+    PATTERNS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Pattern::Exact(secret));
+}
+
+/// Mask every match of the regular expression `pattern` in failure output.
+///
+/// # Errors
+///
+/// If `pattern` is not a valid regular expression, `Err` is returned.
+#[cfg(feature = "redact-regex")]
+pub fn add_regex(pattern: &str) -> Result<(), regex::Error> {
+    let regex = regex::Regex::new(pattern)?;
+    PATTERNS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Pattern::Regex(regex));
+    Ok(())
+}
+
+/// Stop masking all registered secrets.
+pub fn clear() {
+    PATTERNS.write().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Mask all registered secrets in `text`.
+pub(crate) fn apply(text: String) -> String {
+    let patterns = PATTERNS.read().unwrap_or_else(|e| e.into_inner());
+    patterns.iter().fold(text, |text, pattern| match pattern {
+        Pattern::Exact(secret) => text.replace(secret.as_str(), MASK),
+        #[cfg(feature = "redact-regex")]
+        Pattern::Regex(regex) => regex.replace_all(&text, MASK).into_owned(),
+    })
+}
@@ -0,0 +1,329 @@
+//! Control how assertion failures are reported.
+//!
+//! Failure messages can carry whole file contents and diffs.  To keep one failing golden test
+//! from blowing up CI log limits, messages over budget are truncated with a marker, and the
+//! full message is written to a file whose path is included instead.
+//!
+//! The budget defaults to 200 lines and 64 KiB, and can be changed with
+//! [`set_max_lines`] and [`set_max_bytes`], or the `ASSERT_FS_MAX_LINES` and
+//! `ASSERT_FS_MAX_BYTES` environment variables.
+//!
+//! When an artifacts directory is set, with [`set_artifacts_dir`] or the
+//! `ASSERT_FS_ARTIFACTS_DIR` environment variable, content assertions also write the complete
+//! expected and actual data there, alongside the full failure message, so CI users can download
+//! them.
+//!
+//! Failures within a [`context`] are labeled with it, so a loop over many cases reports which
+//! case broke.
+
+use std::cell::RefCell;
+use std::fs;
+use std::io::Write;
+use std::path;
+use std::sync;
+use std::sync::atomic;
+
+const DEFAULT_MAX_LINES: usize = 200;
+const DEFAULT_MAX_BYTES: usize = 64 * 1024;
+
+// `0` means unset, falling back to the environment and then the default
+static MAX_LINES: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+static MAX_BYTES: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+
+/// Limit failure messages to `max` lines, `usize::MAX` for no limit.
+pub fn set_max_lines(max: usize) {
+    MAX_LINES.store(max.max(1), atomic::Ordering::Relaxed);
+}
+
+/// Limit failure messages to `max` bytes, `usize::MAX` for no limit.
+pub fn set_max_bytes(max: usize) {
+    MAX_BYTES.store(max.max(1), atomic::Ordering::Relaxed);
+}
+
+static ARTIFACTS_DIR: once_cell::sync::Lazy<sync::RwLock<Option<path::PathBuf>>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// Write the full data of failed assertions under `dir`.
+pub fn set_artifacts_dir<P>(dir: P)
+where
+    P: Into<path::PathBuf>,
+{
+    *ARTIFACTS_DIR.write().unwrap_or_else(|e| e.into_inner()) = Some(dir.into());
+}
+
+fn artifacts_dir() -> Option<path::PathBuf> {
+    let dir = ARTIFACTS_DIR
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    dir.or_else(|| std::env::var_os("ASSERT_FS_ARTIFACTS_DIR").map(path::PathBuf::from))
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// Label assertion failures on this thread with `label`, until the returned guard is dropped.
+///
+/// Contexts nest, with failures showing all active labels, outermost first.
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// for run in 1..=2 {
+///     let _context = assert_fs::report::context(format!("after sync run {}", run));
+///     temp.child("out.txt").write_str("synced").unwrap();
+///     temp.child("out.txt").assert("synced");
+/// }
+/// temp.close().unwrap();
+/// ```
+pub fn context<S>(label: S) -> ContextGuard
+where
+    S: Into<String>,
+{
+    CONTEXT.with(|context| context.borrow_mut().push(label.into()));
+    ContextGuard {
+        _thread: std::marker::PhantomData,
+    }
+}
+
+/// Run `f`, labeling its assertion failures with `label`.
+///
+/// See [`context`].
+///
+/// # Examples
+///
+/// ```rust
+/// use assert_fs::prelude::*;
+///
+/// let temp = assert_fs::TempDir::new().unwrap();
+/// temp.child("out.txt").write_str("synced").unwrap();
+/// assert_fs::report::with_context("after first sync", || {
+///     temp.child("out.txt").assert("synced");
+/// });
+/// temp.close().unwrap();
+/// ```
+pub fn with_context<S, F, R>(label: S, f: F) -> R
+where
+    S: Into<String>,
+    F: FnOnce() -> R,
+{
+    let _guard = context(label);
+    f()
+}
+
+/// Removes its label from assertion failures when dropped.
+///
+/// Created with [`context`].
+#[must_use = "the context is removed when the guard is dropped"]
+#[derive(Debug)]
+pub struct ContextGuard {
+    // Labels are per-thread, so the guard must be dropped on the thread that created it
+    _thread: std::marker::PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        // `try_with`, as the thread-local may already be gone during thread teardown
+        let _ = CONTEXT.try_with(|context| context.borrow_mut().pop());
+    }
+}
+
+/// The active context labels, as a header for failure messages.
+fn context_header() -> Option<String> {
+    CONTEXT
+        .try_with(|context| {
+            let context = context.borrow();
+            if context.is_empty() {
+                None
+            } else {
+                Some(format!("context={}\n", context.join(" > ")))
+            }
+        })
+        .ok()
+        .flatten()
+}
+
+fn max_lines() -> usize {
+    budget(&MAX_LINES, "ASSERT_FS_MAX_LINES", DEFAULT_MAX_LINES)
+}
+
+fn max_bytes() -> usize {
+    budget(&MAX_BYTES, "ASSERT_FS_MAX_BYTES", DEFAULT_MAX_BYTES)
+}
+
+fn budget(setting: &atomic::AtomicUsize, var: &str, default: usize) -> usize {
+    match setting.load(atomic::Ordering::Relaxed) {
+        0 => std::env::var(var)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v != 0)
+            .unwrap_or(default),
+        max => max,
+    }
+}
+
+/// Panic with `message`, after masking secrets and enforcing the output budget.
+#[track_caller]
+pub(crate) fn fail(message: String) -> ! {
+    fail_with(message, Vec::new())
+}
+
+/// Like [`fail`], also exporting the named `artifacts` when an artifacts directory is set.
+#[track_caller]
+pub(crate) fn fail_with(message: String, artifacts: Vec<(&'static str, Vec<u8>)>) -> ! {
+    let message = match context_header() {
+        Some(header) => header + &message,
+        None => message,
+    };
+    let message = crate::redact::apply(message);
+    let exported = if artifacts.is_empty() {
+        None
+    } else {
+        export_artifacts(&message, artifacts)
+    };
+    let mut output = match truncate(&message, max_lines(), max_bytes()) {
+        // The artifacts already include the full message
+        Some(truncated) if exported.is_some() => truncated,
+        Some(truncated) => match export(&message) {
+            Some(path) => format!("{}\nfull output={}", truncated, path.display()),
+            None => truncated,
+        },
+        None => message,
+    };
+    if let Some(dir) = exported {
+        output.push_str(&format!("\nartifacts={}", dir.display()));
+    }
+    panic!("{}", output)
+}
+
+/// Shorten `message` to the budget, returning `None` if it already fits.
+fn truncate(message: &str, max_lines: usize, max_bytes: usize) -> Option<String> {
+    let total = message.lines().count();
+    if total <= max_lines && message.len() <= max_bytes {
+        return None;
+    }
+
+    let mut kept = String::new();
+    let mut count = 0;
+    for line in message.lines().take(max_lines) {
+        if max_bytes < kept.len() + line.len() + 1 {
+            let mut end = max_bytes.saturating_sub(kept.len());
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            kept.push_str(&line[..end]);
+            kept.push('\n');
+            count += 1;
+            break;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+        count += 1;
+    }
+    kept.push_str(&format!(
+        "\u{2026} {} more lines truncated \u{2026}",
+        group_digits(total - count)
+    ));
+    Some(kept)
+}
+
+/// Write the full `message` to a file that outlives the test.
+fn export(message: &str) -> Option<std::path::PathBuf> {
+    let mut file = tempfile::Builder::new()
+        .prefix("assert_fs-failure-")
+        .suffix(".txt")
+        .tempfile()
+        .ok()?;
+    file.write_all(message.as_bytes()).ok()?;
+    let (_, path) = file.keep().ok()?;
+    Some(path)
+}
+
+/// Write `message` and `artifacts` into a fresh directory under the artifacts directory.
+fn export_artifacts(
+    message: &str,
+    artifacts: Vec<(&'static str, Vec<u8>)>,
+) -> Option<path::PathBuf> {
+    let root = artifacts_dir()?;
+    fs::create_dir_all(&root).ok()?;
+    let prefix = match crate::fixture::current_test_name() {
+        Some(name) => format!("{}-", name),
+        None => "assert_fs-".to_owned(),
+    };
+    let dir = tempfile::Builder::new()
+        .prefix(&prefix)
+        .tempdir_in(&root)
+        .ok()?
+        .into_path();
+    fs::write(dir.join("failure.txt"), message).ok()?;
+    for (name, data) in artifacts {
+        let data = match String::from_utf8(data) {
+            Ok(text) => crate::redact::apply(text).into_bytes(),
+            Err(err) => err.into_bytes(),
+        };
+        fs::write(dir.join(name), data).ok()?;
+    }
+    Some(dir)
+}
+
+fn group_digits(n: usize) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i != 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncate_fits() {
+        assert_eq!(truncate("a\nb\n", 2, 100), None);
+    }
+
+    #[test]
+    fn truncate_lines() {
+        let message = "a\nb\nc\nd";
+        assert_eq!(
+            truncate(message, 2, 100).unwrap(),
+            "a\nb\n\u{2026} 2 more lines truncated \u{2026}"
+        );
+    }
+
+    #[test]
+    fn truncate_bytes() {
+        let message = "abcdef\nghi";
+        assert_eq!(
+            truncate(message, 10, 4).unwrap(),
+            "abcd\n\u{2026} 1 more lines truncated \u{2026}"
+        );
+    }
+
+    #[test]
+    fn context_nests() {
+        assert_eq!(context_header(), None);
+        let _outer = context("case 3");
+        {
+            let _inner = context("second run");
+            assert_eq!(context_header().unwrap(), "context=case 3 > second run\n");
+        }
+        assert_eq!(context_header().unwrap(), "context=case 3\n");
+    }
+
+    #[test]
+    fn group_digits_thousands() {
+        assert_eq!(group_digits(12), "12");
+        assert_eq!(group_digits(12345), "12,345");
+        assert_eq!(group_digits(123456), "123,456");
+    }
+}
@@ -21,3 +21,517 @@ fn verify_failure_output() {
     let f = assert_fs::fixture::ChildPath::new("Cargo.toml");
     f.assert("Not real content");
 }
+
+#[test]
+fn assert_dir_eq_reports_differences() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let expected = temp.child("expected");
+    expected.child("same.txt").write_str("same\n").unwrap();
+    expected.child("changed.txt").write_str("old\n").unwrap();
+    expected.child("gone/nested.txt").touch().unwrap();
+    let actual = temp.child("actual");
+    actual.child("same.txt").write_str("same\n").unwrap();
+    actual.child("changed.txt").write_str("new\n").unwrap();
+    actual.child("added.txt").touch().unwrap();
+
+    expected.assert_dir_eq(&expected);
+    let err = std::panic::catch_unwind(|| {
+        actual.assert_dir_eq(&expected);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("gone"), "{}", message);
+    assert!(!message.contains("nested.txt"), "{}", message);
+    assert!(message.contains("added.txt"), "{}", message);
+    assert!(message.contains("changed.txt"), "{}", message);
+    assert!(
+        message.contains("@@ -1,1 +1,1 @@\n-old\n+new"),
+        "{}",
+        message
+    );
+    assert!(!message.contains("same.txt"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn assert_no_open_handles_finds_leaks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let log = temp.child("logs/app.log");
+    log.touch().unwrap();
+
+    let file = std::fs::File::open(log.path()).unwrap();
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.assert_no_open_handles();
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("app.log"), "{}", message);
+
+    drop(file);
+    temp.assert_no_open_handles();
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn assert_only_lists_unexpected_files() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("expected.txt").touch().unwrap();
+    temp.child("logs/run.log").touch().unwrap();
+    temp.child("logs/core.dump").touch().unwrap();
+    temp.child("stray.tmp").touch().unwrap();
+    temp.child("empty").create_dir_all().unwrap();
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.assert_only(&["expected.txt", "logs/*.log"]);
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("stray.tmp"), "{}", message);
+    assert!(message.contains("core.dump"), "{}", message);
+    assert!(!message.contains("expected.txt"), "{}", message);
+
+    temp.assert_only(&["expected.txt", "logs", "*.tmp"]);
+    temp.child("missing.tmp").assert_missing();
+    temp.close().unwrap();
+}
+
+#[test]
+fn assert_tree_snapshot_shows_diff() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let out = temp.child("out");
+    out.child("report.txt").write_str("ok\n").unwrap();
+    let snap = temp.child("out.snap");
+    snap.write_str(
+        "report.txt sha256:dc51b8c96c2d745df3bd5590d990230a482fd247123599548e0632fdbf97fc22\n  | ok\n",
+    )
+    .unwrap();
+    out.assert_tree_snapshot(&snap);
+
+    out.child("extra.log").touch().unwrap();
+    let err = std::panic::catch_unwind(|| {
+        out.assert_tree_snapshot(&snap);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("extra.log"), "{}", message);
+
+    let missing = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        out.assert_tree_snapshot(temp.child("missing.snap"));
+    }))
+    .unwrap_err();
+    let message = missing.downcast_ref::<String>().unwrap();
+    assert!(
+        message.contains("ASSERT_FS_SNAPSHOT=overwrite"),
+        "{}",
+        message
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn assert_bytes_dumps_difference() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output = temp.child("out.bin");
+    let mut expected: Vec<u8> = (0..64).collect();
+    output.write_binary(&expected).unwrap();
+    output.assert_bytes(&expected);
+
+    expected[40] = 0xff;
+    let err = std::panic::catch_unwind(|| {
+        output.assert_bytes(&expected);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("offset 0x28"), "{}", message);
+    assert!(message.contains("> 00000020"), "{}", message);
+    assert!(message.contains(" ff "), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn streamed_comparison_reports_offset() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let expected = temp.child("expected.bin");
+    expected.write_random_bytes(300_000, 1).unwrap();
+    let output = temp.child("out.bin");
+    output.write_random_bytes(300_000, 1).unwrap();
+    output.assert_same_content_as(expected.path());
+    output.assert_content_from(std::fs::File::open(&expected).unwrap());
+
+    let mut content = std::fs::read(&expected).unwrap();
+    content[200_000] ^= 0xff;
+    output.write_binary(&content).unwrap();
+    let err = std::panic::catch_unwind(|| {
+        output.assert_same_content_as(expected.path());
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("offset 0x30d40"), "{}", message);
+    assert!(message.contains("> 00030d40"), "{}", message);
+
+    content.truncate(250_000);
+    let err = std::panic::catch_unwind(|| {
+        output.assert_content_from(&content[..]);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("offset 0x3d090"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "checksum")]
+fn assert_digest_reports_mismatch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output = temp.child("dist/app.txt");
+    output.write_str("abc").unwrap();
+    output.assert_sha256("BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+    output.assert_md5("900150983cd24fb0d6963f7d28e17f72");
+
+    let err = std::panic::catch_unwind(|| {
+        output.assert_md5("d41d8cd98f00b204e9800998ecf8427e");
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(
+        message.contains("900150983cd24fb0d6963f7d28e17f72"),
+        "{}",
+        message
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn assert_matches_glob_set_reports_both_sides() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let dist = temp.child("dist");
+    dist.child("app.js").touch().unwrap();
+    dist.child("app.css").touch().unwrap();
+    dist.assert_matches_glob_set(&["app.js", "*.css"]);
+
+    let err = std::panic::catch_unwind(|| {
+        dist.assert_matches_glob_set(&["app.js", "index.html"]);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("index.html"), "{}", message);
+    assert!(message.contains("app.css"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn assert_similar_reports_ratio_and_hunk() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output = temp.child("out.log");
+    output.write_str("start\nstep 1\nstep 2\ndone\n").unwrap();
+    output.assert_similar("start\nstep 1\nstep 3\ndone\n", 0.25);
+
+    let err = std::panic::catch_unwind(|| {
+        output.assert_similar("start\nstep 9\nstep 8\ndone\n", 0.25);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("diff_ratio=0.500"), "{}", message);
+    assert!(
+        message.contains("-step 9\n-step 8\n+step 1\n+step 2\n"),
+        "{}",
+        message
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn assert_eventually_polls_until_written() {
+    use assert_fs::fixture::Retry;
+    use std::time::Duration;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let status = temp.child("status");
+    let writer = assert_fs::fixture::ChildPath::new(status.path());
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        writer.write_str("ready").unwrap();
+    });
+
+    let retry = Retry::poll(Duration::from_secs(10), Duration::from_millis(10));
+    status.assert_eventually("ready", &retry);
+
+    let err = std::panic::catch_unwind(|| {
+        let retry = Retry::poll(Duration::from_millis(30), Duration::from_millis(10));
+        status.assert_eventually("stopped", &retry);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("ready"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn assert_exact_case_checks_stored_names() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("docs/readme.md").touch().unwrap();
+    temp.child("docs/readme.md").assert_exact_case();
+
+    let err = std::panic::catch_unwind(|| {
+        temp.child("Docs/readme.md").assert_exact_case();
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    if temp.is_case_sensitive().unwrap() {
+        assert!(message.contains("does not exist"), "{}", message);
+    } else {
+        assert!(message.contains("differs in case"), "{}", message);
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn normalized_ignores_line_endings() {
+    use assert_fs::assert::StrContentPathPredicate;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output = temp.child("output.txt");
+    output.write_str("one\r\ntwo\n").unwrap();
+    output.assert(StrContentPathPredicate::normalized("one\ntwo\r\n"));
+
+    let err = std::panic::catch_unwind(|| {
+        output.assert(StrContentPathPredicate::normalized("one\nthree\n"));
+    });
+    assert!(err.is_err());
+
+    temp.close().unwrap();
+}
+
+#[cfg(feature = "structured-json")]
+#[test]
+fn assert_json_compares_structure() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let manifest = temp.child("manifest.json");
+    manifest
+        .write_str(r#"{"deps": [{"name": "a", "version": "1.0"}], "v": 2}"#)
+        .unwrap();
+    manifest.assert_json(
+        r#"{
+            "v": 2,
+            "deps": [{ "version": "1.0", "name": "a" }]
+        }"#,
+    );
+
+    let err = std::panic::catch_unwind(|| {
+        manifest.assert_json(r#"{"deps": [{"name": "a", "version": "2.0"}], "v": 2}"#);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("/deps/0/version"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn assert_size_measures_files_and_dirs() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("cache/a.bin").write_binary(&[0; 100]).unwrap();
+    temp.child("cache/nested/b.bin")
+        .write_binary(&[0; 24])
+        .unwrap();
+
+    temp.child("cache/a.bin").assert_size(100);
+    temp.child("cache").assert_size(124);
+    temp.assert_size_between(100, 200);
+    assert_eq!(temp.total_size().unwrap(), 124);
+
+    let err = std::panic::catch_unwind(|| {
+        temp.child("cache").assert_size_between(0, 123);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("124 bytes"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn assert_file_types_and_metadata() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("releases/v2/app.log").touch().unwrap();
+    temp.child("current").symlink_to_dir("releases/v2").unwrap();
+    let script = temp.child("bin/run").write_script("exit 0").unwrap();
+
+    temp.child("releases/v2/app.log")
+        .assert_is_file()
+        .assert_is_empty();
+    temp.child("releases").assert_is_dir();
+    temp.child("current")
+        .assert_is_symlink()
+        .assert_is_symlink_to("releases/v2")
+        .assert_is_dir();
+    script.assert_is_executable();
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.child("current").assert_is_symlink_to("releases/v1");
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("releases/v2"), "{}", message);
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.child("releases/v2").assert_is_empty();
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("app.log"), "{}", message);
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.child("releases/v2/app.log").assert_is_executable();
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("not executable"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn missing_path_failures_list_nearest_dir() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("out/report.txt").write_str("ok").unwrap();
+    assert!(temp.tree().contains("report.txt (2 bytes)"));
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.child("out/nested/summary.txt").assert("ok");
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("existing"), "{}", message);
+    assert!(message.contains("report.txt (2 bytes)"), "{}", message);
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.child("out/summary.txt").assert_is_file();
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("report.txt (2 bytes)"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn text_failures_show_line_diff() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let expected: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+    let config = temp.child("config.ini");
+    config
+        .write_str(&expected.replace("line 10\n", "changed\n"))
+        .unwrap();
+
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        config.assert(expected.as_str());
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("@@ -7,7 +7,7 @@"), "{}", message);
+    assert!(message.contains("-line 10"), "{}", message);
+    assert!(message.contains("+changed"), "{}", message);
+    assert!(!message.contains("line 2\n"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn sparse_files_keep_holes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let layout = assert_fs::fixture::SparseLayout::new(16 << 20)
+        .data(0, b"HEADER")
+        .data((16 << 20) - 7, b"TRAILER");
+    let sparse = temp.child("sparse.img");
+    sparse.write_sparse(&layout).unwrap();
+    let content = std::fs::read(sparse.path()).unwrap();
+    assert_eq!(content.len(), 16 << 20);
+    assert!(content.starts_with(b"HEADER"));
+    assert!(content.ends_with(b"TRAILER"));
+    sparse.assert_sparse();
+
+    let dense = temp.child("dense.img");
+    dense.write_zeros(64 * 1024).unwrap();
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        dense.assert_sparse();
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("bytes allocated"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn same_path_resolves_before_comparing() {
+    let temp = assert_fs::TempDir::new_canonical().unwrap();
+    let config = temp.child("etc/app.toml");
+    config.touch().unwrap();
+    assert_eq!(config.canonicalized().unwrap().path(), config.path());
+
+    config.assert_same_path(temp.path().join("etc/./../etc/app.toml"));
+    #[cfg(unix)]
+    {
+        temp.child("link").symlink_to_dir("etc").unwrap();
+        config.assert_same_path(temp.path().join("link/app.toml"));
+    }
+
+    temp.child("etc/other.toml").touch().unwrap();
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        config.assert_same_path(temp.child("etc/other.toml"));
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("Unexpected path"), "{}", message);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn manifest_reports_added_removed_and_changed() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let out = temp.child("out");
+    out.child("keep.txt").write_str("same").unwrap();
+    out.child("change.txt").write_str("before").unwrap();
+    out.child("remove.txt").touch().unwrap();
+    let manifest = out.child("expected.manifest");
+    out.write_manifest(&manifest).unwrap();
+    out.assert_manifest(&manifest);
+    assert!(!std::fs::read_to_string(&manifest)
+        .unwrap()
+        .contains("expected.manifest"));
+
+    out.child("change.txt").write_str("after!").unwrap();
+    out.child("remove.txt").remove_file().unwrap();
+    out.child("add/new.txt").touch().unwrap();
+    let err = std::panic::catch_unwind(|| {
+        out.assert_manifest(&manifest);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("add, add/new.txt"), "{}", message);
+    assert!(message.contains("remove.txt"), "{}", message);
+    assert!(
+        message.contains("change.txt") && message.contains("\ncontent"),
+        "{}",
+        message
+    );
+    assert!(!message.contains("keep.txt"), "{}", message);
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,2076 @@
+#[test]
+fn temp_dir_for_test_is_named() {
+    let temp = assert_fs::TempDir::for_test().unwrap();
+    let name = temp.path().file_name().unwrap().to_str().unwrap();
+    assert!(name.starts_with("temp_dir_for_test_is_named."), "{}", name);
+    temp.close().unwrap();
+}
+
+#[test]
+fn scratch_is_reset_between_runs() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::scratch().unwrap();
+    temp.child("leftover.txt")
+        .assert(predicates::path::missing());
+    temp.child("leftover.txt").touch().unwrap();
+    let temp = assert_fs::TempDir::scratch().unwrap();
+    temp.child("leftover.txt")
+        .assert(predicates::path::missing());
+    std::fs::remove_dir_all(temp.path()).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn write_script_is_executable() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let script = temp
+        .child("bin/hello")
+        .write_script("#!/bin/sh\necho hello\n")
+        .unwrap();
+    let output = std::process::Command::new(script.path()).output().unwrap();
+    assert_eq!(output.stdout, b"hello\n");
+    temp.close().unwrap();
+}
+
+#[test]
+fn operation_log_records_fixture_api() {
+    use assert_fs::fixture::OperationKind;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("foo.txt").write_str("Hello").unwrap();
+    temp.child("foo.txt").remove_file().unwrap();
+    let log = temp.operation_log();
+    let kinds: Vec<_> = log.iter().map(|op| op.kind()).collect();
+    assert_eq!(kinds, [OperationKind::WriteStr, OperationKind::RemoveFile]);
+    assert_eq!(log[0].bytes(), Some(5));
+    assert!(log.iter().all(|op| op.error().is_none()));
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "archive-zstd")]
+fn extract_tar_zst() {
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("data/hello.txt").write_str("Hello").unwrap();
+    let archive = assert_fs::NamedTempFile::new("fixture.tar.zst").unwrap();
+    {
+        let file = std::fs::File::create(archive.path()).unwrap();
+        let encoder = zstd::Encoder::new(file, 0).unwrap().auto_finish();
+        let mut builder = tar::Builder::new(encoder);
+        builder
+            .append_dir_all("data", source.child("data"))
+            .unwrap();
+        builder.into_inner().unwrap();
+    }
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.extract_from(archive.path()).unwrap();
+    temp.child("data/hello.txt").assert("Hello");
+
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn sync_from_mirrors_source() {
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("data/kept.txt").write_str("new").unwrap();
+    source
+        .child("data/skipped.bin")
+        .write_str("ignored")
+        .unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("data/kept.txt").write_str("old").unwrap();
+    temp.child("data/extra.txt").write_str("extra").unwrap();
+    temp.child("stale/nested.txt").write_str("stale").unwrap();
+    temp.sync_from(source.path(), &["**/*.txt"]).unwrap();
+
+    temp.child("data/kept.txt").assert("new");
+    temp.child("data/extra.txt")
+        .assert(predicates::path::missing());
+    temp.child("data/skipped.bin")
+        .assert(predicates::path::missing());
+    temp.child("stale").assert(predicates::path::missing());
+
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+fn windows_special_names_outcome() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let outcomes = temp.try_windows_special_names();
+    assert_eq!(
+        outcomes.len(),
+        assert_fs::fixture::WINDOWS_SPECIAL_NAMES.len()
+    );
+    for (name, outcome) in outcomes {
+        if cfg!(unix) {
+            assert!(outcome.is_created(), "{:?}: {:?}", name, outcome);
+        }
+    }
+    temp.close().unwrap();
+}
+
+#[test]
+fn read_only_restores_on_drop() {
+    use assert_fs::fixture::ReadOnlyMode;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("foo/bar.txt").write_str("Hello").unwrap();
+    {
+        let guard = temp.read_only().unwrap();
+        // Root ignores permissions, so only a remount is guaranteed to reject writes
+        if guard.mode() == ReadOnlyMode::Remount {
+            assert!(temp.child("foo/bar.txt").write_str("Bye").is_err());
+        }
+    }
+    temp.child("foo/bar.txt").write_str("Bye").unwrap();
+    temp.child("foo/bar.txt").assert("Bye");
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn fault_helpers_restore_permissions() {
+    use assert_fs::fixture::fault;
+    use assert_fs::prelude::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let secret = temp.child("locked/secret.txt");
+    secret.write_str("Hello").unwrap();
+    let mode = |path: &std::path::Path| path.metadata().unwrap().permissions().mode() & 0o777;
+    {
+        let _unreadable = fault::make_unreadable(&secret).unwrap();
+        let _readonly = fault::make_dir_readonly(temp.child("locked")).unwrap();
+        assert_eq!(mode(secret.path()) & 0o444, 0);
+        assert_eq!(mode(temp.child("locked").path()) & 0o222, 0);
+    }
+    assert_ne!(mode(secret.path()) & 0o400, 0);
+    assert_ne!(mode(temp.child("locked").path()) & 0o200, 0);
+    assert!(fault::make_dir_readonly(&secret).is_err());
+
+    fault::dangling_symlink(temp.child("current"), "releases/v2").unwrap();
+    assert!(temp.child("current").symlink_metadata().is_ok());
+    assert!(!temp.child("current").exists());
+    assert!(fault::dangling_symlink(temp.child("link"), "locked").is_err());
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn special_files_are_created() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let pipe = temp.child("run/commands");
+    pipe.mkfifo(0o600).unwrap();
+    pipe.assert_is_fifo();
+    assert!(pipe.mkfifo(0o600).is_err());
+    let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        temp.child("run").assert_is_fifo();
+    }))
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.contains("directory"), "{}", message);
+
+    let socket = temp.child("run/daemon.sock");
+    socket.reserve_socket().unwrap();
+    socket.assert_missing();
+    let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+    socket.assert_is_socket();
+    assert!(socket.reserve_socket().is_err());
+
+    let long = temp.child("d".repeat(assert_fs::fixture::MAX_SOCKET_PATH_LEN));
+    assert!(long.reserve_socket().is_err());
+    temp.close().unwrap();
+}
+
+#[test]
+fn names_create_tricky_children() {
+    use assert_fs::fixture::names;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let space = names::trailing_space(temp.child("dir"), "name").unwrap();
+    assert_eq!(space.file_name().unwrap(), "name ");
+    let dot = names::trailing_dot(temp.child("dir"), "name").unwrap();
+    assert_eq!(dot.file_name().unwrap(), "name.");
+    assert!(names::emoji(&temp).unwrap().exists());
+    let long = names::long_path(&temp).unwrap();
+    assert!(long.exists());
+    assert_eq!(long.as_os_str().len(), names::LONG_PATH_LEN);
+    match names::normalization_pair(&temp) {
+        Ok((nfc, nfd)) => assert_ne!(nfc.path(), nfd.path()),
+        Err(err) => assert!(cfg!(target_os = "macos"), "{}", err),
+    }
+    match names::non_utf8(&temp) {
+        Ok(file) => assert!(file.to_str().is_none()),
+        Err(err) => assert!(cfg!(not(target_os = "linux")), "{}", err),
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn non_unicode_names_are_handled() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let file = match temp.make_non_unicode_file() {
+        Ok(file) => file,
+        // e.g. APFS
+        Err(_) => return,
+    };
+    file.write_str("same").unwrap();
+    temp.child("other.txt").write_str("same").unwrap();
+
+    assert_eq!(temp.snapshot().unwrap().paths().count(), 2);
+    let saved = assert_fs::NamedTempFile::new("non-unicode.snap").unwrap();
+    temp.snapshot().unwrap().save(saved.path()).unwrap();
+    file.remove_file().unwrap();
+    let snapshot = assert_fs::fixture::DirSnapshot::load(saved.path()).unwrap();
+    temp.reset_to(&snapshot).unwrap();
+    file.assert("same");
+
+    temp.assert_duplicates(&[&["non-unicode-\u{fffd}\u{fffd}.txt", "other.txt"]]);
+    temp.close().unwrap();
+}
+
+#[test]
+fn name_policy_is_deterministic() {
+    use assert_fs::fixture::NamePolicy;
+    use assert_fs::prelude::*;
+
+    let names = || {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.set_name_policy(NamePolicy::seeded(7).prefix("gen-"));
+        let names: Vec<_> = (0..3)
+            .map(|_| {
+                let child = temp.unique_child(".txt");
+                child.touch().unwrap();
+                child.file_name().unwrap().to_owned()
+            })
+            .collect();
+        temp.close().unwrap();
+        names
+    };
+    let first = names();
+    assert_eq!(first, names());
+    assert!(first[0].to_str().unwrap().starts_with("gen-"));
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.set_name_policy(NamePolicy::counter());
+    temp.child("0").touch().unwrap();
+    assert_eq!(temp.unique_child("").file_name().unwrap(), "1");
+    temp.close().unwrap();
+}
+
+#[test]
+fn clock_drives_relative_timestamps() {
+    use assert_fs::fixture::Clock;
+    use assert_fs::prelude::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let base = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.set_clock(Clock::fixed(base));
+    let input = temp.child("input.txt");
+    input.write_str("data").unwrap();
+    input.touch_ago(Duration::from_secs(2 * 60 * 60)).unwrap();
+    temp.child("output.txt").touch_ago(Duration::ZERO).unwrap();
+
+    let modified = |name: &str| temp.child(name).metadata().unwrap().modified().unwrap();
+    assert_eq!(
+        modified("output.txt")
+            .duration_since(modified("input.txt"))
+            .unwrap(),
+        Duration::from_secs(2 * 60 * 60)
+    );
+    input.assert("data");
+
+    let snapshot = temp.snapshot().unwrap();
+    assert_eq!(snapshot.taken_at(), base);
+    input.touch_at(base).unwrap();
+    temp.reset_to(&snapshot).unwrap();
+    assert_eq!(
+        modified("input.txt"),
+        base - Duration::from_secs(2 * 60 * 60)
+    );
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn sparse_mode_restores_holes() {
+    use assert_fs::fixture::SparseMode;
+    use assert_fs::prelude::*;
+    use std::os::unix::fs::MetadataExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let image = temp.child("disk.img");
+    std::fs::File::create(image.path())
+        .unwrap()
+        .set_len(1 << 20)
+        .unwrap();
+    let allocated = |path: &std::path::Path| path.metadata().unwrap().blocks() * 512;
+    if allocated(image.path()) >= 1 << 20 {
+        // Filesystem without sparse file support
+        return;
+    }
+    let snapshot = temp.snapshot().unwrap().sparse_mode(SparseMode::Preserve);
+
+    image.write_binary(&vec![0; 1 << 20]).unwrap();
+    assert_eq!(
+        temp.snapshot().unwrap().differences(&snapshot),
+        Vec::<&std::path::Path>::new()
+    );
+    assert_eq!(
+        snapshot.differences(&temp.snapshot().unwrap()),
+        vec![std::path::Path::new("disk.img")]
+    );
+
+    temp.reset_to(&snapshot).unwrap();
+    assert!(allocated(image.path()) < 1 << 20);
+    assert_eq!(image.metadata().unwrap().len(), 1 << 20);
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn symlinks_create_parent_dirs() {
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let real_file = temp.child("real_file");
+    real_file.write_str("Hello").unwrap();
+    let real_dir = temp.child("real_dir");
+    real_dir.create_dir_all().unwrap();
+
+    let file_link = temp.child("links/nested/file");
+    file_link.symlink_to_file(real_file.path()).unwrap();
+    assert!(file_link
+        .symlink_metadata()
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    file_link.assert("Hello");
+
+    let dir_link = temp.child("links/dir");
+    dir_link.symlink_to_dir(real_dir.path()).unwrap();
+    assert!(dir_link
+        .symlink_metadata()
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    dir_link.assert(predicate::path::is_dir().follow_links(true));
+    temp.close().unwrap();
+}
+
+#[test]
+fn clear_keeps_root() {
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cache = temp.child("cache");
+    cache
+        .child("nested/entry.bin")
+        .write_binary(b"data")
+        .unwrap();
+    cache.child("readonly.txt").touch().unwrap();
+    cache.child("readonly.txt").set_readonly(true).unwrap();
+    cache.clear().unwrap();
+    cache.assert(predicate::path::is_dir());
+    assert_eq!(std::fs::read_dir(cache.path()).unwrap().count(), 0);
+
+    temp.child("config.toml").touch().unwrap();
+    temp.clear().unwrap();
+    temp.assert(predicate::path::is_dir());
+    temp.child("config.toml").assert(predicate::path::missing());
+    assert!(temp.child("missing").clear().is_err());
+    temp.close().unwrap();
+}
+
+#[test]
+fn atomic_and_slow_writes() {
+    use assert_fs::prelude::*;
+    use std::time::Duration;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let config = temp.child("etc/config.toml");
+    config.write_str_atomic("port = 8080\n").unwrap();
+    config.assert("port = 8080\n");
+    config.write_binary_atomic(b"port = 9090\n").unwrap();
+    config.assert("port = 9090\n");
+    // No temporary files left behind
+    assert_eq!(
+        std::fs::read_dir(temp.child("etc").path()).unwrap().count(),
+        1
+    );
+
+    let log = temp.child("server.log");
+    let writer = log
+        .write_str_slowly("listening\n", 3, Duration::from_millis(1))
+        .unwrap();
+    writer.join().unwrap();
+    log.assert("listening\n");
+    temp.close().unwrap();
+}
+
+#[test]
+fn write_fmt_interpolates() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let config = temp.child("nested/config.toml");
+    write!(config, "name = {:?}", "demo").unwrap();
+    config.assert("name = \"demo\"");
+    writeln!(config, "port = {}", 8080).unwrap();
+    config.assert("port = 8080\n");
+    config.write_fmt(format_args!("[server]\n")).unwrap();
+    config.assert("[server]\n");
+    temp.close().unwrap();
+}
+
+#[test]
+fn dedup_writer_skips_rewritten_paths() {
+    use assert_fs::fixture::DedupWriter;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut writer = DedupWriter::new();
+    writer.write_str(&temp.child("a.txt"), "old").unwrap();
+    writer.write_str(&temp.child("a.txt"), "new").unwrap();
+    writer.write_str(&temp.child("b.txt"), "old").unwrap();
+    temp.child("a.txt").assert("new");
+    temp.child("b.txt").assert("old");
+
+    let mut writer = DedupWriter::new().break_links(false);
+    writer.write_str(&temp.child("c.txt"), "old").unwrap();
+    writer.write_str(&temp.child("d.txt"), "old").unwrap();
+    // Written in-place, so through the link to `c.txt` as well
+    writer.write_str(&temp.child("d.txt"), "new").unwrap();
+    writer.write_str(&temp.child("e.txt"), "old").unwrap();
+    temp.child("e.txt").assert("old");
+    temp.close().unwrap();
+}
+
+#[test]
+fn writes_create_parent_dirs() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("a/b/empty.txt").touch().unwrap();
+    temp.child("c/d/text.txt").write_str("text").unwrap();
+    temp.child("e/f/data.bin")
+        .write_binary(b"\x00\x01")
+        .unwrap();
+
+    temp.child("a/b/empty.txt").assert("");
+    temp.child("c/d/text.txt").assert("text");
+    temp.child("e/f/data.bin").assert(b"\x00\x01" as &[u8]);
+    temp.close().unwrap();
+}
+
+#[test]
+fn case_collision_outcome() {
+    use assert_fs::fixture::CaseOutcome;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    match temp.try_case_collision("Readme.md", "README.md") {
+        CaseOutcome::Distinct(first, second) => {
+            first.assert("");
+            second.assert("");
+            assert_eq!(temp.read_dir().unwrap().count(), 2);
+        }
+        CaseOutcome::Collided(existing) => {
+            assert_eq!(existing.file_name().unwrap(), "Readme.md");
+            assert_eq!(temp.read_dir().unwrap().count(), 1);
+        }
+        CaseOutcome::Rejected(err) => panic!("{}", err),
+    }
+    temp.close().unwrap();
+}
+
+#[test]
+fn persist_on_failure_keeps_fixture() {
+    use assert_fs::prelude::*;
+
+    let path = std::thread::spawn(|| {
+        let temp = assert_fs::TempDir::new().unwrap().persist_on_failure();
+        temp.child("foo.txt").write_str("Hello").unwrap();
+        let path = temp.path().to_owned();
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _temp = temp;
+            panic!("simulated failure");
+        }))
+        .unwrap_err();
+        path
+    })
+    .join()
+    .unwrap();
+    assert!(path.join("foo.txt").exists());
+    std::fs::remove_dir_all(&path).unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap().persist_on_failure();
+    let path = temp.path().to_owned();
+    drop(temp);
+    assert!(!path.exists());
+}
+
+#[test]
+#[cfg(unix)]
+fn cleanup_policy_handles_read_only_dirs() {
+    use assert_fs::fixture::CleanupPolicy;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new()
+        .unwrap()
+        .cleanup_policy(CleanupPolicy::ForceChmodThenDelete);
+    temp.child("locked/file.txt").touch().unwrap();
+    temp.child("locked").set_mode(0o500).unwrap();
+    let path = temp.path().to_owned();
+    temp.close().unwrap();
+    assert!(!path.exists());
+
+    let temp = assert_fs::TempDir::new()
+        .unwrap()
+        .cleanup_policy(CleanupPolicy::Strict);
+    temp.child("locked/file.txt").touch().unwrap();
+    temp.child("locked").set_mode(0o500).unwrap();
+    let path = temp.path().to_owned();
+    let dropped = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || drop(temp)));
+    // Permissions don't stop root
+    if path.exists() {
+        let err = dropped.unwrap_err();
+        let message = err.downcast_ref::<String>().unwrap();
+        assert!(message.contains("file.txt"), "{}", message);
+        let unlocked = assert_fs::fixture::ChildPath::new(path.join("locked"));
+        unlocked.set_mode(0o700).unwrap();
+        std::fs::remove_dir_all(&path).unwrap();
+    } else {
+        dropped.unwrap();
+    }
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn on_progress_counts_copied_files() {
+    use assert_fs::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    let fixture = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixture");
+    let temp = assert_fs::TempDir::new().unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    temp.on_progress(move |progress| sink.lock().unwrap().push(*progress));
+    temp.copy_from(&fixture, &["*.txt"]).unwrap();
+
+    let seen = seen.lock().unwrap();
+    let last = seen.last().unwrap();
+    assert_eq!(last.kind(), assert_fs::fixture::OperationKind::CopyFrom);
+    assert_eq!(last.files(), 1);
+    assert_eq!(
+        last.bytes(),
+        std::fs::metadata(fixture.join("hello.txt")).unwrap().len()
+    );
+    temp.close().unwrap();
+}
+
+#[test]
+fn set_write_rate_slows_writes() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.set_write_rate(Some(10_000));
+    let start = std::time::Instant::now();
+    temp.child("a.bin").write_binary(&[0; 1000]).unwrap();
+    temp.child("b.bin").write_binary(&[0; 1000]).unwrap();
+    assert!(start.elapsed() >= std::time::Duration::from_millis(200));
+    temp.close().unwrap();
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn trace_emits_event_per_operation() {
+    use assert_fs::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Count(Arc<AtomicUsize>);
+
+    impl tracing::Subscriber for Count {
+        fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+            metadata.target() == "assert_fs"
+        }
+        fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, _: &tracing::Event<'_>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    let events = Arc::new(AtomicUsize::new(0));
+    let temp = assert_fs::TempDir::new().unwrap();
+    tracing::subscriber::with_default(Count(events.clone()), || {
+        temp.child("quiet.txt").write_str("a").unwrap();
+        temp.trace(true);
+        temp.child("a.txt").write_str("a").unwrap();
+        temp.child("b").touch().unwrap();
+        temp.trace(false);
+        temp.child("quiet.txt").write_str("b").unwrap();
+    });
+    assert_eq!(events.load(Ordering::SeqCst), 2);
+    temp.close().unwrap();
+}
+
+#[test]
+fn is_case_sensitive_matches_lookup() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let sensitive = temp.is_case_sensitive().unwrap();
+    temp.child("readme.md").touch().unwrap();
+    assert_eq!(temp.child("README.md").exists(), !sensitive);
+    assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 1);
+    temp.close().unwrap();
+}
+
+#[cfg(feature = "glob")]
+#[test]
+fn templated_fixtures_substitute_vars() {
+    use assert_fs::fixture::{FixtureKind, TemplateVars};
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source
+        .child("app.toml")
+        .write_str("root = \"{{temp_dir}}\"\nport = {{port}}\n")
+        .unwrap();
+    source
+        .child("blob.bin")
+        .write_binary(b"{{port}}\0")
+        .unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let vars = TemplateVars::new().set("port", 8080);
+    temp.copy_from_templated(source.path(), &["*"], &vars)
+        .unwrap();
+    temp.child("app.toml").assert(format!(
+        "root = \"{}\"\nport = 8080\n",
+        temp.path().display()
+    ));
+    temp.child("blob.bin").assert(&b"{{port}}\0"[..]);
+
+    temp.child("motd")
+        .write_str_templated("port {{ port }}", &vars)
+        .unwrap();
+    temp.child("motd").assert("port 8080");
+    let err = temp
+        .child("bad")
+        .write_str_templated("{{host}}", &vars)
+        .unwrap_err();
+    assert_eq!(err.kind(), FixtureKind::Template);
+
+    source.close().unwrap();
+    temp.close().unwrap();
+}
+
+#[test]
+fn child_path_composes_with_std() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let child = temp.child("foo.txt");
+    child.write_str("Hello").unwrap();
+
+    assert_eq!(std::fs::read_to_string(&child).unwrap(), "Hello");
+    assert_eq!(child.clone(), child);
+    assert_ne!(temp.child("bar.txt"), child);
+    assert!(format!("{:?}", child).contains("foo.txt"));
+    assert_eq!(child.extension().unwrap(), "txt");
+    let path: std::path::PathBuf = child.into();
+    assert_eq!(path, temp.path().join("foo.txt"));
+    temp.close().unwrap();
+}
+
+#[test]
+fn write_str_with_converts_line_endings() {
+    use assert_fs::fixture::LineEnding;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let file = temp.child("foo.txt");
+    file.write_str_with("a\r\nb\n", LineEnding::Lf).unwrap();
+    file.assert("a\nb\n");
+    file.write_str_with("a\r\nb\n", LineEnding::CrLf).unwrap();
+    file.assert("a\r\nb\r\n");
+    file.write_str_with("a\nb", LineEnding::Native).unwrap();
+    file.assert(format!("a{}b", LineEnding::Native.as_str()));
+    temp.close().unwrap();
+}
+
+#[test]
+fn build_declares_tree() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::build(|b| {
+        b.dir("src", |d| {
+            d.file("main.rs", "fn main() {}").dir("bin", |d| d)
+        })
+        .file("Cargo.toml", "[package]\n")
+    })
+    .unwrap();
+    temp.child("src/main.rs").assert("fn main() {}");
+    temp.child("src/bin").assert(predicates::path::is_dir());
+    temp.child("Cargo.toml").assert("[package]\n");
+    temp.close().unwrap();
+
+    let err = match assert_fs::TempDir::build(|b| {
+        b.file("blocker", "")
+            .file("blocker/a.txt", "")
+            .file("blocker/b.txt", "")
+    }) {
+        Ok(_) => panic!("files within a file should fail"),
+        Err(err) => err,
+    };
+    let message = err.to_string();
+    assert!(
+        message.contains("a.txt") && message.contains("b.txt"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+#[cfg(feature = "spec-json")]
+fn populate_from_json_spec() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.populate_from_str(
+        r##"{
+            "src": { "lib.rs": "pub fn f() {}" },
+            "empty_dir": {},
+            "bin": { "$file": "#!/bin/sh\n", "$mode": "755" }
+        }"##,
+    )
+    .unwrap();
+    temp.child("src/lib.rs").assert("pub fn f() {}");
+    temp.child("empty_dir").assert(predicates::path::is_dir());
+    temp.child("bin").assert("#!/bin/sh\n");
+    #[cfg(unix)]
+    temp.child("bin").assert_no_mode_bits(0o022);
+
+    let err = temp.populate_from_str(r#"{ "bad": 42 }"#).unwrap_err();
+    assert_eq!(err.kind(), assert_fs::fixture::FixtureKind::Spec);
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "spec-json")]
+fn populate_from_json_spec_per_platform() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.populate_from_str(
+        r#"{
+            "unix:run.sh": "unix",
+            "windows:run.cmd": "windows",
+            "unix:": { "lib": { "unix:": { "libfoo.so": "" } } },
+            "windows:": { "foo.dll": "" }
+        }"#,
+    )
+    .unwrap();
+    let (present, absent) = if cfg!(windows) {
+        (["run.cmd", "foo.dll"], ["run.sh", "lib"])
+    } else {
+        (["run.sh", "lib/libfoo.so"], ["run.cmd", "foo.dll"])
+    };
+    for name in present {
+        temp.child(name).assert(predicates::path::exists());
+    }
+    for name in absent {
+        temp.child(name).assert(predicates::path::missing());
+    }
+
+    let err = temp.populate_from_str(r#"{ "unix:": "x" }"#);
+    assert_eq!(err.is_err(), cfg!(unix));
+    temp.close().unwrap();
+}
+
+#[test]
+fn with_layout_populates_tree() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::with_layout(|t| {
+        t.file("a.txt", "x");
+        t.empty_dir("b/c");
+    })
+    .unwrap();
+    temp.child("a.txt").assert("x");
+    temp.child("b/c").assert(predicates::path::is_dir());
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "archive-gzip")]
+fn extract_tar_gz_from_reader() {
+    use assert_fs::fixture::ArchiveFormat;
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("data/hello.txt").write_str("Hello").unwrap();
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder
+        .append_dir_all("data", source.child("data"))
+        .unwrap();
+    let archive = builder.into_inner().unwrap().finish().unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.extract_from_reader(archive.as_slice(), ArchiveFormat::TarGz)
+        .unwrap();
+    temp.child("data/hello.txt").assert("Hello");
+
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+#[cfg(all(unix, feature = "glob"))]
+fn link_farm_mirrors_source() {
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("data/a.txt").write_str("a").unwrap();
+    source.child("data/b.bin").write_str("b").unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.link_farm_from(source.path(), &["**/*.txt"]).unwrap();
+    let link = temp.child("data/a.txt");
+    assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(
+        std::fs::read_link(&link).unwrap(),
+        source.path().canonicalize().unwrap().join("data/a.txt")
+    );
+    link.assert("a");
+    temp.child("data/b.bin").assert(predicates::path::missing());
+
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+fn fixture_set_tears_down_in_reverse() {
+    use assert_fs::fixture::{FixtureError, FixtureKind, FixtureSet};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let mut fixtures = FixtureSet::new();
+    fixtures.add_dir("remote", assert_fs::TempDir::new().unwrap());
+    let remote = fixtures.dir("remote").unwrap().path().to_owned();
+    for name in ["first", "second"] {
+        let order = order.clone();
+        fixtures.on_teardown(name, move || {
+            order.borrow_mut().push(name);
+            Err(FixtureError::new(FixtureKind::Cleanup))
+        });
+    }
+
+    let err = fixtures.close().unwrap_err();
+    assert_eq!(*order.borrow(), ["second", "first"]);
+    assert!(!remote.exists());
+    let message = err.to_string();
+    assert!(
+        message.contains("`first`") && message.contains("`second`"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn set_readonly_round_trips() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let config = temp.child("config.toml");
+    config.write_str("locked = true\n").unwrap();
+    config.set_readonly(true).unwrap();
+    assert!(config.metadata().unwrap().permissions().readonly());
+    config.set_readonly(false).unwrap();
+    assert!(!config.metadata().unwrap().permissions().readonly());
+
+    #[cfg(unix)]
+    {
+        config.set_mode(0o640).unwrap();
+        config.assert_mode(0o640);
+    }
+    temp.close().unwrap();
+}
+
+#[test]
+fn url_unsafe_names_round_trip() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let outcomes = temp.try_url_unsafe_names();
+    assert_eq!(outcomes.len(), assert_fs::fixture::URL_UNSAFE_NAMES.len());
+    if cfg!(unix) {
+        assert!(outcomes.iter().all(|(_, outcome)| outcome.is_created()));
+        temp.assert_has_url_entry("with%20space.txt")
+            .assert_has_url_entry("100%25.txt")
+            .assert_has_url_entry("hash%23fragment.txt")
+            .assert_has_url_entry("query%3Fx=1.txt");
+    }
+    temp.close().unwrap();
+}
+
+#[test]
+fn set_times_orders_files() {
+    use assert_fs::prelude::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let input = temp.child("main.c");
+    input.write_str("int main;").unwrap();
+    let output = temp.child("main.o");
+    output.write_str("obj").unwrap();
+
+    let base = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    output.set_mtime(base).unwrap();
+    input
+        .set_times(base, base + Duration::from_secs(1))
+        .unwrap();
+    input.assert_newer_than(&output);
+    output.assert_older_than(&input);
+    assert_eq!(input.metadata().unwrap().accessed().unwrap(), base);
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(all(unix, feature = "glob"))]
+fn copy_from_with_preserves_links_and_metadata() {
+    use assert_fs::fixture::CopyOptions;
+    use assert_fs::prelude::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let source = assert_fs::TempDir::new().unwrap();
+    let script = source.child("bin/run.sh");
+    script.write_script("echo hi").unwrap();
+    let modified = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    script.set_mtime(modified).unwrap();
+    source
+        .child("latest")
+        .symlink_to_file("bin/run.sh")
+        .unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let options = CopyOptions::new()
+        .preserve_symlinks(true)
+        .preserve_mtime(true);
+    temp.copy_from_with(source.path(), &["**"], options)
+        .unwrap();
+
+    let copied = temp.child("bin/run.sh").metadata().unwrap();
+    assert_eq!(copied.permissions().mode() & 0o777, 0o755);
+    assert_eq!(copied.modified().unwrap(), modified);
+    let link = temp.child("latest");
+    assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(
+        std::fs::read_link(link.path()).unwrap(),
+        std::path::Path::new("bin/run.sh")
+    );
+
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn retry_policy_waits_for_output() {
+    use assert_fs::fixture::Retry;
+    use assert_fs::prelude::*;
+    use std::time::Duration;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let writer = temp.child("out/result.json");
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        writer.write_str("{}").unwrap();
+    });
+
+    let retry = Retry::backoff(Duration::from_millis(1))
+        .jitter(true)
+        .timeout(Duration::from_secs(10));
+    let matches = temp.wait_until_with("out/*.json", &retry).unwrap();
+    assert_eq!(matches.len(), 1);
+    temp.child("out/result.json")
+        .assert_eventually("{}", &retry);
+
+    let missing = Retry::fixed(Duration::from_millis(1)).max_attempts(3);
+    assert!(temp.wait_until_with("*.txt", &missing).is_err());
+
+    temp.close_with_retry(&retry).unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn children_yields_matches_in_order() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("out/b.log").touch().unwrap();
+    temp.child("out/a.log").touch().unwrap();
+    temp.child("out/nested/c.log").touch().unwrap();
+    temp.child("out/d.txt").touch().unwrap();
+
+    let names: Vec<_> = temp
+        .children("**/*.log")
+        .map(|child| child.strip_prefix(temp.path()).unwrap().to_owned())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            std::path::Path::new("out/a.log"),
+            std::path::Path::new("out/b.log"),
+            std::path::Path::new("out/nested/c.log"),
+        ]
+    );
+    assert_eq!(temp.child("missing").children("*").count(), 0);
+    temp.close().unwrap();
+}
+
+#[test]
+fn verify_setup_flags_untracked_entries() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/lib.rs").write_str("").unwrap();
+    temp.child("empty").create_dir_all().unwrap();
+    temp.child("gone.txt").touch().unwrap();
+    temp.child("gone.txt").remove_file().unwrap();
+    temp.verify_setup().unwrap();
+
+    std::fs::write(temp.path().join("src/sneaky.rs"), "").unwrap();
+    std::fs::create_dir(temp.path().join("stray")).unwrap();
+    let err = temp.verify_setup().unwrap_err().to_string();
+    assert!(err.contains("sneaky.rs"), "{}", err);
+    assert!(err.contains("stray"), "{}", err);
+    assert!(!err.contains("lib.rs"), "{}", err);
+    temp.close().unwrap();
+}
+
+#[test]
+fn render_tree_is_stable() {
+    use assert_fs::fixture::RenderOptions;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("b/empty.txt").touch().unwrap();
+    temp.child("a.txt").write_str("abc").unwrap();
+
+    let rendered = temp.render_tree(RenderOptions::new()).unwrap();
+    assert_eq!(rendered, "a.txt (3 bytes)\nb/\n  empty.txt (0 bytes)\n");
+
+    let hashed = temp.render_tree(RenderOptions::new().hashes(true)).unwrap();
+    assert!(
+        hashed.starts_with(
+            "a.txt sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n"
+        ),
+        "{}",
+        hashed
+    );
+    temp.close().unwrap();
+}
+
+#[test]
+fn fixture_store_reuses_entries() {
+    use assert_fs::fixture::FixtureStore;
+    use assert_fs::prelude::*;
+    use std::time::Duration;
+
+    let cache = assert_fs::TempDir::new().unwrap();
+    let store = FixtureStore::open(cache.path()).unwrap();
+
+    let hash = store.insert(b"abc").unwrap();
+    assert_eq!(
+        hash,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(std::fs::read(store.get(&hash).unwrap()).unwrap(), b"abc");
+    assert_eq!(store.get(&"0".repeat(64)), None);
+
+    let mut calls = 0;
+    for _ in 0..2 {
+        let tree = store
+            .get_or_create("generated-v1", |dir| {
+                calls += 1;
+                std::fs::write(dir.join("seed.bin"), b"\x00").unwrap();
+                Ok(())
+            })
+            .unwrap();
+        assert!(tree.join("seed.bin").is_file());
+    }
+    assert_eq!(calls, 1);
+
+    assert_eq!(store.gc(Duration::from_secs(60)).unwrap(), 0);
+    let old = std::time::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    let blob = assert_fs::fixture::ChildPath::new(store.get(&hash).unwrap());
+    blob.set_mtime(old).unwrap();
+    let tree = store.get_or_create("generated-v1", |_| Ok(())).unwrap();
+    assert_fs::fixture::ChildPath::new(tree)
+        .set_mtime(old)
+        .unwrap();
+    assert_eq!(store.gc(Duration::from_secs(60)).unwrap(), 2);
+    assert_eq!(store.get(&hash), None);
+
+    cache.close().unwrap();
+}
+
+#[test]
+fn read_helpers_name_the_file() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output = temp.child("out.txt");
+    output.write_str("a\nb\n").unwrap();
+    assert_eq!(output.read_str().unwrap(), "a\nb\n");
+    assert_eq!(output.read_binary().unwrap(), b"a\nb\n");
+    assert_eq!(output.read_lines().unwrap(), ["a", "b"]);
+
+    let err = temp.child("missing.txt").read_str().unwrap_err();
+    assert_eq!(err.kind(), assert_fs::fixture::FixtureKind::ReadFile);
+    assert!(err.to_string().contains("missing.txt"), "{}", err);
+    temp.close().unwrap();
+}
+
+#[test]
+fn append_creates_and_extends() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let log = temp.child("logs/app.log");
+    log.append_str("one\n").unwrap();
+    log.append_binary(b"two\n").unwrap();
+    log.assert("one\ntwo\n");
+    temp.close().unwrap();
+}
+
+#[test]
+fn capture_from_spools_process_output() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output = temp.child("logs/stdout.txt");
+    let stdout = std::io::Cursor::new(b"first\nsecond\n".to_vec());
+    let handle = output.capture_from(stdout).unwrap();
+    assert_eq!(handle.join().unwrap().unwrap(), 13);
+    output.assert("first\nsecond\n");
+    temp.close().unwrap();
+}
+
+#[test]
+fn leak_for_process_lifetime_keeps_dir() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("cache.txt").write_str("shared").unwrap();
+    let path = temp.leak_for_process_lifetime();
+    assert!(path.join("cache.txt").exists());
+    assert!(assert_fs::TempDir::leaked().contains(&path));
+    std::fs::remove_dir_all(path).unwrap();
+}
+
+#[test]
+fn generated_content_is_seeded() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let first = temp.child("first.bin");
+    first.write_random_bytes(100_003, 1).unwrap();
+    let second = temp.child("second.bin");
+    second.write_random_bytes(100_003, 1).unwrap();
+    let other = temp.child("other.bin");
+    other.write_random_bytes(100_003, 2).unwrap();
+    let content = std::fs::read(first.path()).unwrap();
+    assert_eq!(content.len(), 100_003);
+    assert_eq!(content, std::fs::read(second.path()).unwrap());
+    assert_ne!(content, std::fs::read(other.path()).unwrap());
+
+    let text = temp.child("text/lines.txt");
+    text.write_random_text(50, 1).unwrap();
+    let lines = std::fs::read_to_string(text.path()).unwrap();
+    assert_eq!(lines.lines().count(), 50);
+    assert!(lines.ends_with('\n'));
+    text.write_random_text(50, 1).unwrap();
+    text.assert(lines.as_str());
+
+    let zeros = temp.child("zeros.bin");
+    zeros.write_zeros(70_000).unwrap();
+    assert_eq!(std::fs::read(zeros.path()).unwrap(), vec![0; 70_000]);
+    temp.close().unwrap();
+}
+
+#[test]
+fn write_file_of_size_repeats_pattern() {
+    use assert_fs::prelude::*;
+    use std::io::Read;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let sparse = temp.child("data/sparse.bin");
+    sparse.write_file_of_size(100_000, &[0, 0]).unwrap();
+    assert_eq!(std::fs::read(sparse.path()).unwrap(), vec![0; 100_000]);
+
+    let filled = temp.child("data/filled.bin");
+    filled.write_file_of_size(100_001, b"abc").unwrap();
+    let content = std::fs::read(filled.path()).unwrap();
+    assert_eq!(content.len(), 100_001);
+    assert!(content.chunks(3).all(|c| b"abc".starts_with(c)));
+
+    let streamed = temp.child("data/streamed.bin");
+    let written = streamed
+        .write_from(std::io::repeat(b'x').take(70_000))
+        .unwrap();
+    assert_eq!(written, 70_000);
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn copied_tree_lists_in_order() {
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    for name in ["c.txt", "a/z.txt", "b.txt", "a/y.txt"] {
+        source.child(name).touch().unwrap();
+    }
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.copy_from(source.path(), &["**"]).unwrap();
+    let names: Vec<_> = temp
+        .children("**/*.txt")
+        .map(|child| {
+            let rel = child.path().strip_prefix(temp.path()).unwrap();
+            rel.to_str().unwrap().replace('\\', "/")
+        })
+        .collect();
+    assert_eq!(names, ["a/y.txt", "a/z.txt", "b.txt", "c.txt"]);
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+fn builder_names_and_places_dir() {
+    let parent = assert_fs::TempDir::new().unwrap();
+    let temp = assert_fs::TempDir::builder()
+        .prefix("case.")
+        .suffix(".fixture")
+        .parent(parent.path())
+        .create()
+        .unwrap();
+    assert_eq!(temp.path().parent().unwrap(), parent.path());
+    let name = temp.path().file_name().unwrap().to_str().unwrap();
+    assert!(name.starts_with("case."), "{}", name);
+    assert!(name.ends_with(".fixture"), "{}", name);
+    temp.close().unwrap();
+    parent.close().unwrap();
+}
+
+#[test]
+fn hard_link_to_shares_content() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let original = temp.child("data/original.bin");
+    original.write_str("v1").unwrap();
+    let link = temp.child("backup/link.bin");
+    link.hard_link_to(original.path()).unwrap();
+    link.assert_same_file_as(original.path());
+    original.write_str("v2").unwrap();
+    link.assert("v2");
+
+    let err = temp
+        .child("dangling.bin")
+        .hard_link_to(temp.child("missing.bin").path())
+        .unwrap_err();
+    assert_eq!(err.kind(), assert_fs::fixture::FixtureKind::HardLink);
+    temp.close().unwrap();
+}
+
+#[test]
+fn metadata_snapshot_compares_paths() {
+    use assert_fs::fixture::EntryKind;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let a = temp.child("a.txt");
+    a.write_str("same").unwrap();
+    let b = temp.child("b.txt");
+    b.write_str("same").unwrap();
+    b.set_mtime(a.metadata().unwrap().modified().unwrap())
+        .unwrap();
+
+    let a_meta = a.metadata_snapshot().unwrap().hashed().unwrap();
+    let b_meta = b.metadata_snapshot().unwrap().hashed().unwrap();
+    assert_eq!(a_meta.kind(), EntryKind::File);
+    assert_eq!(a_meta.len(), 4);
+    assert_eq!(a_meta, b_meta);
+
+    b.write_str("different").unwrap();
+    let b_meta = b.metadata_snapshot().unwrap().hashed().unwrap();
+    assert!(a_meta.differences(&b_meta).contains(&"sha256"));
+    assert_eq!(temp.metadata_snapshot().unwrap().kind(), EntryKind::Dir);
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn copy_from_with_report_counts_entries() {
+    use assert_fs::fixture::CopyOptions;
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("a.txt").write_str("abc").unwrap();
+    source.child("nested/b.txt").write_str("de").unwrap();
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let report = temp
+        .copy_from_with_report(source.path(), &["**/*.txt"], CopyOptions::new())
+        .unwrap();
+    assert_eq!(report.files(), 2, "{}", report);
+    assert_eq!(report.bytes(), 5, "{}", report);
+    assert!(report.skipped().is_empty(), "{}", report);
+
+    let report = temp
+        .copy_from_with_report(source.path(), &["*.md"], CopyOptions::new())
+        .unwrap();
+    assert_eq!(report.files(), 0, "{}", report);
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[cfg(feature = "glob")]
+#[test]
+fn copy_from_many_layers_with_conflict_policy() {
+    use assert_fs::fixture::{ConflictPolicy, CopyOptions, FixtureKind};
+    use assert_fs::prelude::*;
+
+    let base = assert_fs::TempDir::new().unwrap();
+    base.child("config.toml").write_str("base").unwrap();
+    base.child("data/a.txt").write_str("a").unwrap();
+    let overrides = assert_fs::TempDir::new().unwrap();
+    overrides
+        .child("config.toml")
+        .write_str("override")
+        .unwrap();
+    overrides.child("data/b.txt").write_str("b").unwrap();
+    let sources = [(base.path(), ["**"]), (overrides.path(), ["**"])];
+
+    let layered = assert_fs::TempDir::new().unwrap();
+    let report = layered
+        .copy_from_many(&sources, CopyOptions::new())
+        .unwrap();
+    assert_eq!(report.files(), 4, "{}", report);
+    layered.child("config.toml").assert("override");
+    layered.child("data/a.txt").assert("a");
+    layered.child("data/b.txt").assert("b");
+
+    let kept = assert_fs::TempDir::new().unwrap();
+    let options = CopyOptions::new().on_conflict(ConflictPolicy::SkipExisting);
+    let report = kept.copy_from_many(&sources, options).unwrap();
+    assert_eq!(report.files(), 3, "{}", report);
+    assert_eq!(
+        report.skipped(),
+        &[std::path::PathBuf::from("config.toml")],
+        "{}",
+        report
+    );
+    kept.child("config.toml").assert("base");
+
+    let strict = assert_fs::TempDir::new().unwrap();
+    let options = CopyOptions::new().on_conflict(ConflictPolicy::Error);
+    let err = strict.copy_from_many(&sources, options).unwrap_err();
+    assert_eq!(err.kind(), FixtureKind::CopyFile);
+    assert_eq!(err.path(), Some(strict.child("config.toml").path()));
+    strict.child("config.toml").assert("base");
+    strict
+        .child("data/b.txt")
+        .assert(predicates::path::missing());
+
+    layered.close().unwrap();
+    kept.close().unwrap();
+    strict.close().unwrap();
+    base.close().unwrap();
+    overrides.close().unwrap();
+}
+
+#[cfg(feature = "glob")]
+#[test]
+fn copy_from_mapped_relocates_and_rewrites() {
+    use assert_fs::fixture::CopyAction;
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("old/a.txt").write_str("a").unwrap();
+    source.child("old/nested/b.txt").write_str("b").unwrap();
+    source.child("secret.key").write_str("hunter2").unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut seen = Vec::new();
+    temp.copy_from_mapped(source.path(), &["**"], |entry| {
+        seen.push(entry.path().to_owned());
+        match entry.path().to_str().unwrap() {
+            "old" => CopyAction::Rename("new".into()),
+            "secret.key" => CopyAction::RenameRewrite("redacted.key".into(), b"xxx".to_vec()),
+            _ => CopyAction::Copy,
+        }
+    })
+    .unwrap();
+    temp.child("new/a.txt").assert("a");
+    temp.child("new/nested/b.txt").assert("b");
+    temp.child("redacted.key").assert("xxx");
+    temp.child("old").assert(predicate::path::missing());
+    temp.child("secret.key").assert(predicate::path::missing());
+    assert_eq!(seen.len(), 5);
+
+    let escape = assert_fs::TempDir::new().unwrap();
+    assert!(escape
+        .copy_from_mapped(source.path(), &["*.key"], |_| {
+            CopyAction::Rename("../outside.key".into())
+        })
+        .is_err());
+
+    source.close().unwrap();
+    temp.close().unwrap();
+    escape.close().unwrap();
+}
+
+#[cfg(feature = "glob")]
+#[test]
+fn copy_from_respects_ignore_files() {
+    use assert_fs::fixture::CopyOptions;
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source
+        .child(".gitignore")
+        .write_str("target/\n*.log\n!keep.log\n")
+        .unwrap();
+    source.child("src/lib.rs").touch().unwrap();
+    source
+        .child("src/.ignore")
+        .write_str("generated.rs\n")
+        .unwrap();
+    source.child("src/generated.rs").touch().unwrap();
+    source.child("target/debug/app").touch().unwrap();
+    source.child("debug.log").touch().unwrap();
+    source.child("keep.log").touch().unwrap();
+    source.child(".git/HEAD").touch().unwrap();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let options = CopyOptions::new().respect_ignore_files(true);
+    temp.copy_from_with(source.path(), &["**"], options)
+        .unwrap();
+    temp.child(".gitignore").assert(predicate::path::exists());
+    temp.child("src/lib.rs").assert(predicate::path::exists());
+    temp.child("keep.log").assert(predicate::path::exists());
+    temp.child("src/generated.rs")
+        .assert(predicate::path::missing());
+    temp.child("target").assert(predicate::path::missing());
+    temp.child("debug.log").assert(predicate::path::missing());
+    temp.child(".git").assert(predicate::path::missing());
+
+    let all = assert_fs::TempDir::new().unwrap();
+    all.copy_from(source.path(), &["**"]).unwrap();
+    all.child("target/debug/app")
+        .assert(predicate::path::exists());
+
+    source.close().unwrap();
+    temp.close().unwrap();
+    all.close().unwrap();
+}
+
+#[cfg(feature = "glob")]
+#[test]
+fn copy_from_copies_large_trees() {
+    use assert_fs::fixture::CopyOptions;
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    for dir in 0..10 {
+        for file in 0..50 {
+            source
+                .child(format!("d{}/f{}.txt", dir, file))
+                .write_str(&format!("{}/{}", dir, file))
+                .unwrap();
+        }
+    }
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let report = temp
+        .copy_from_with_report(source.path(), &["**/*.txt"], CopyOptions::new())
+        .unwrap();
+    assert_eq!(report.files(), 500, "{}", report);
+    temp.child("d7/f42.txt").assert("7/42");
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+fn clone_from_uses_each_strategy() {
+    use assert_fs::fixture::CloneStrategy;
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("a.txt").write_str("abc").unwrap();
+    source.child("nested/b.txt").write_str("de").unwrap();
+
+    for strategy in [
+        CloneStrategy::Auto,
+        CloneStrategy::HardLink,
+        CloneStrategy::Copy,
+    ] {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.clone_from(source.path(), strategy).unwrap();
+        temp.child("a.txt").assert("abc");
+        temp.child("nested/b.txt").assert("de");
+        temp.close().unwrap();
+    }
+    source.close().unwrap();
+}
+
+#[test]
+fn fixture_cache_builds_once() {
+    use assert_fs::fixture::{FixtureCache, TempDir};
+    use assert_fs::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+    static TREE: FixtureCache = FixtureCache::new("tree", |dir: &TempDir| {
+        BUILDS.fetch_add(1, Ordering::SeqCst);
+        dir.child("nested/a.txt").write_str("abc")
+    });
+
+    let first = TREE.instantiate().unwrap();
+    let second = TREE.instantiate().unwrap();
+    first.child("nested/a.txt").write_str("changed").unwrap();
+    second.child("nested/a.txt").assert("abc");
+    let pristine = TREE.path().unwrap().join("nested/a.txt");
+    assert_eq!(std::fs::read_to_string(pristine).unwrap(), "abc");
+    assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+    first.close().unwrap();
+    second.close().unwrap();
+}
+
+#[test]
+fn snapshot_materialized_survives_in_place_edits() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("data/records.bin")
+        .write_binary(b"abcd")
+        .unwrap();
+    temp.child("data/index.txt").write_str("1\n").unwrap();
+    let snapshot = temp.snapshot_materialized().unwrap();
+    let copy = snapshot.materialized_path().unwrap();
+    temp.assert_dir_eq(copy);
+
+    temp.child("data/records.bin").patch_bytes(1, b"X").unwrap();
+    temp.child("data/index.txt").append_str("2\n").unwrap();
+    assert_eq!(
+        std::fs::read(copy.join("data/records.bin")).unwrap(),
+        b"abcd"
+    );
+
+    temp.reset_to(&snapshot).unwrap();
+    temp.assert_dir_eq(copy);
+    assert!(temp.snapshot().unwrap().materialized_path().is_none());
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn copy_from_with_require_matches_names_pattern() {
+    use assert_fs::fixture::CopyOptions;
+    use assert_fs::fixture::FixtureKind;
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source.child("a.txt").touch().unwrap();
+    let temp = assert_fs::TempDir::new().unwrap();
+    let options = CopyOptions::new().require_matches(true);
+
+    temp.copy_from_with(source.path(), &["*.txt", "!b.txt"], options)
+        .unwrap();
+    let err = temp
+        .copy_from_with(source.path(), &["*.txt", "*.jsn"], options)
+        .unwrap_err();
+    assert_eq!(err.kind(), FixtureKind::NoMatch);
+    assert!(
+        err.to_string().contains("`*.jsn` matched nothing"),
+        "{}",
+        err
+    );
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[tokio::test]
+#[cfg(all(feature = "async", feature = "glob"))]
+async fn async_write_copy_and_assert() {
+    use assert_fs::prelude::*;
+
+    let source = assert_fs::TempDir::new().unwrap();
+    source
+        .child("data/a.txt")
+        .write_str_async("one\ntwo\n")
+        .await
+        .unwrap();
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.copy_from_async(source.path(), &["**/*.txt"])
+        .await
+        .unwrap();
+
+    let copied = temp.child("data/a.txt");
+    assert_eq!(copied.read_lines_async().await.unwrap(), ["one", "two"]);
+    copied.assert_async("one\ntwo\n").await;
+    assert!(temp.child("missing.txt").read_str_async().await.is_err());
+    temp.close().unwrap();
+    source.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "watch")]
+fn watch_records_created_and_removed() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("stale.txt").touch().unwrap();
+    let events = temp.watch().unwrap();
+
+    temp.child("out").create_dir_all().unwrap();
+    temp.child("out/result.json").write_str("{}").unwrap();
+    std::fs::remove_file(temp.child("stale.txt").path()).unwrap();
+
+    events
+        .assert_created("out/result.json")
+        .assert_removed("stale.txt");
+    drop(events);
+    temp.close().unwrap();
+}
+
+#[test]
+fn ops_work_on_plain_paths() {
+    use assert_fs::fixture::ops;
+    use assert_fs::fixture::OperationKind;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let config = temp.path().join("etc/app.toml");
+    ops::write_str(&config, "port = 8080\n").unwrap();
+    ops::touch(temp.path().join("logs/app.log")).unwrap();
+    assert_eq!(ops::read_str(&config).unwrap(), "port = 8080\n");
+    ops::remove_all(temp.path().join("logs")).unwrap();
+    assert!(!temp.path().join("logs").exists());
+
+    let kinds: Vec<_> = temp.operation_log().iter().map(|op| op.kind()).collect();
+    assert_eq!(
+        kinds,
+        [
+            OperationKind::WriteStr,
+            OperationKind::Touch,
+            OperationKind::RemoveAll
+        ]
+    );
+    temp.close().unwrap();
+}
+
+#[test]
+fn named_temp_file_supports_file_traits() {
+    use assert_fs::fixture::NamedTempFile;
+    use assert_fs::prelude::*;
+
+    let dir = assert_fs::TempDir::new().unwrap();
+    let file = NamedTempFile::new_in(dir.path(), "report.txt").unwrap();
+    assert!(file.path().starts_with(dir.path()));
+
+    file.write_str("ok\n").unwrap();
+    assert_eq!(file.read_str().unwrap(), "ok\n");
+    file.remove_file().unwrap();
+    assert!(!file.path().exists());
+
+    file.close().unwrap();
+    dir.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "glob")]
+fn export_to_filters_by_pattern() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("out/report.txt").write_str("ok").unwrap();
+    temp.child("out/nested/log.txt").write_str("log").unwrap();
+    temp.child("out/core.dump").touch().unwrap();
+
+    let artifacts = assert_fs::TempDir::new().unwrap();
+    let dest = artifacts.child("ci/run-1");
+    temp.child("out").export_to(&dest, &["**/*.txt"]).unwrap();
+
+    dest.child("report.txt").assert("ok");
+    dest.child("nested/log.txt").assert("log");
+    assert!(!dest.child("core.dump").exists());
+    temp.close().unwrap();
+    artifacts.close().unwrap();
+}
+
+#[test]
+fn env_guard_restores_previous_value() {
+    let outer = assert_fs::TempDir::new().unwrap();
+    let temp = assert_fs::TempDir::new().unwrap();
+    {
+        let outer_guard = outer.env_guard("ASSERT_FS_TEST_HOME");
+        assert_eq!(outer_guard.previous(), None);
+        {
+            let guard = temp.env_guard("ASSERT_FS_TEST_HOME");
+            let _unset = temp.env_guard("ASSERT_FS_TEST_CONFIG");
+            assert_eq!(guard.previous(), Some(outer.path().as_os_str()));
+            assert_eq!(
+                std::env::var_os("ASSERT_FS_TEST_HOME").unwrap(),
+                temp.path()
+            );
+        }
+        assert_eq!(
+            std::env::var_os("ASSERT_FS_TEST_HOME").unwrap(),
+            outer.path()
+        );
+        assert_eq!(std::env::var_os("ASSERT_FS_TEST_CONFIG"), None);
+    }
+    assert_eq!(std::env::var_os("ASSERT_FS_TEST_HOME"), None);
+    temp.close().unwrap();
+    outer.close().unwrap();
+}
+
+#[cfg(feature = "macros")]
+#[assert_fs::fixture]
+fn fixture_macro_injects_temp_dir(temp: &assert_fs::TempDir) {
+    use assert_fs::prelude::*;
+
+    temp.child("report.txt").write_str("ok").unwrap();
+    temp.child("report.txt").assert("ok");
+}
+
+#[test]
+fn errors_name_operation_and_path() {
+    use assert_fs::fixture::FixtureKind;
+    use assert_fs::fixture::OperationKind;
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("blocker").touch().unwrap();
+    let target = temp.child("blocker/config.toml");
+    let err = target.write_str("a = 1").unwrap_err();
+
+    assert_eq!(err.kind(), FixtureKind::CreateDir);
+    assert_eq!(err.operation(), Some(OperationKind::WriteStr));
+    assert_eq!(err.path(), Some(target.path()));
+    let message = err.to_string();
+    assert!(message.contains("Operation: write_str"), "{}", message);
+    assert!(message.contains("config.toml"), "{}", message);
+    temp.close().unwrap();
+}
+
+#[cfg(feature = "xattr")]
+#[test]
+fn xattrs_round_trip() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let file = temp.child("photo.jpg");
+    file.touch().unwrap();
+    if file.set_xattr("user.origin", b"camera").is_err() {
+        // e.g. a `tmpfs` without user xattrs
+        return;
+    }
+    file.assert_no_xattr("user.comment");
+    file.assert_xattr("user.origin", b"camera");
+    assert_eq!(file.xattr("user.origin").unwrap(), Some(b"camera".to_vec()));
+    file.set_xattr("user.origin", b"scanner").unwrap();
+    file.assert_xattr("user.origin", b"scanner");
+    temp.close().unwrap();
+}
+
+#[cfg(feature = "lock")]
+#[test]
+fn locks_conflict_until_dropped() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let lock_file = temp.child("run/app.lock");
+    lock_file.assert_unlocked();
+
+    let first = lock_file.lock_shared().unwrap();
+    let second = lock_file.lock_shared().unwrap();
+    assert!(!first.is_exclusive());
+    lock_file.assert_locked();
+    assert!(lock_file.lock_exclusive().is_err());
+    drop((first, second));
+
+    let exclusive = lock_file.lock_exclusive().unwrap();
+    assert_eq!(exclusive.path(), lock_file.path());
+    assert!(lock_file.lock_shared().is_err());
+    lock_file.assert_locked();
+    drop(exclusive);
+    lock_file.assert_unlocked();
+    temp.close().unwrap();
+}
+
+#[test]
+fn path_handles_outlive_temp_dir() {
+    use assert_fs::prelude::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<assert_fs::TempDir>();
+    assert_send_sync::<assert_fs::fixture::ChildPath>();
+    assert_send_sync::<assert_fs::fixture::PathHandle>();
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let handle = temp.path_handle();
+    let path = handle.path().to_owned();
+    let workers: Vec<_> = (0..4)
+        .map(|i| {
+            let handle = handle.clone();
+            std::thread::spawn(move || {
+                handle
+                    .child(format!("{}.txt", i))
+                    .write_str("done")
+                    .unwrap();
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    temp.close().unwrap();
+    assert!(path.join("3.txt").exists());
+    drop(handle);
+    assert!(!path.exists());
+}
+
+#[test]
+#[should_panic(expected = "Invalid child path")]
+fn path_handle_child_rejects_escapes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.path_handle().child("../escaped.txt");
+}
+
+#[test]
+fn emit_setup_code_replays_operations() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("config/app.toml")
+        .write_str("name = \"demo\"\n")
+        .unwrap();
+    temp.child("data.bin").write_binary(b"\x00\x01").unwrap();
+    temp.child("cache").create_dir_all().unwrap();
+    temp.child("logs").create_dir_all().unwrap();
+    temp.child("logs/out.txt").touch().unwrap();
+    temp.child("gone.txt").touch().unwrap();
+    temp.child("gone.txt").remove_file().unwrap();
+
+    let code = temp.emit_setup_code().unwrap();
+    let expected = r#"use assert_fs::prelude::*;
+
+let temp = assert_fs::TempDir::new().unwrap();
+temp.child("config/app.toml").write_str("name = \"demo\"\n").unwrap();
+temp.child("data.bin").write_binary(b"\x00\x01").unwrap();
+temp.child("cache").create_dir_all().unwrap();
+temp.child("logs/out.txt").write_str("").unwrap();
+"#;
+    assert_eq!(code, expected);
+    temp.close().unwrap();
+}
+
+#[cfg(feature = "spec-json")]
+#[test]
+fn emit_spec_round_trips() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/lib.rs")
+        .write_str("pub fn f() {}\n")
+        .unwrap();
+    temp.child("empty").create_dir_all().unwrap();
+    let spec = temp.emit_spec().unwrap();
+
+    let replayed = assert_fs::TempDir::new().unwrap();
+    replayed.populate_from_str(&spec).unwrap();
+    replayed.child("src/lib.rs").assert("pub fn f() {}\n");
+    assert!(replayed.child("empty").is_dir());
+    temp.close().unwrap();
+    replayed.close().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn commands_run_in_fixture() {
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let status = temp
+        .command("sh")
+        .args(["-c", "touch ran.txt"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    temp.child("ran.txt").assert(predicate::path::is_file());
+
+    let status = temp
+        .isolation()
+        .home()
+        .command("sh")
+        .unwrap()
+        .args(["-c", "touch \"$HOME/ran.txt\""])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    temp.child(".home/ran.txt")
+        .assert(predicate::path::is_file());
+    temp.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "git")]
+fn git_repo_states() {
+    use assert_fs::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo = temp.init_git_repo().unwrap();
+    repo.child("src/lib.rs")
+        .write_str("pub fn answer() {}\n")
+        .unwrap();
+    repo.child("README.md").write_str("# Demo\n").unwrap();
+    let first = repo.commit_all("Initial commit").unwrap();
+    assert_eq!(first.len(), 40);
+
+    repo.child("README.md").write_str("# Changed\n").unwrap();
+    repo.child("notes.txt").touch().unwrap();
+    repo.child("staged.txt").touch().unwrap();
+    repo.add("staged.txt").unwrap();
+    assert_eq!(
+        repo.git(["status", "--porcelain"]).unwrap(),
+        " M README.md\nA  staged.txt\n?? notes.txt\n"
+    );
+    assert_eq!(
+        repo.git(["rev-parse", "--abbrev-ref", "HEAD"]).unwrap(),
+        "main\n"
+    );
+
+    // Fixed identity and dates give the same commit ids on every run
+    let other = assert_fs::TempDir::new().unwrap();
+    let same = other.init_git_repo().unwrap();
+    same.child("src/lib.rs")
+        .write_str("pub fn answer() {}\n")
+        .unwrap();
+    same.child("README.md").write_str("# Demo\n").unwrap();
+    assert_eq!(same.commit_all("Initial commit").unwrap(), first);
+
+    temp.verify_setup().unwrap();
+    temp.close().unwrap();
+    other.close().unwrap();
+}
+
+#[test]
+fn restore_rewinds_to_checkpoint() {
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("data/a.txt").write_str("a").unwrap();
+    temp.child("data/b.txt").write_str("b").unwrap();
+    let base = temp.checkpoint().unwrap();
+
+    temp.child("data/a.txt").write_str("changed").unwrap();
+    std::fs::remove_file(temp.child("data/b.txt").path()).unwrap();
+    temp.child("data/c.txt").write_str("c").unwrap();
+    let later = temp.checkpoint().unwrap();
+
+    temp.restore(&base).unwrap();
+    temp.child("data/a.txt").assert("a");
+    temp.child("data/b.txt").assert("b");
+    temp.child("data/c.txt").assert(predicate::path::missing());
+    assert_eq!(temp.operation_log().len(), 2);
+    temp.verify_setup().unwrap();
+
+    temp.restore(&later).unwrap();
+    temp.child("data/a.txt").assert("changed");
+    temp.child("data/b.txt").assert(predicate::path::missing());
+    temp.child("data/c.txt").assert("c");
+
+    let other = assert_fs::TempDir::new().unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = other.restore(&base);
+    }));
+    assert!(result.is_err());
+
+    temp.close().unwrap();
+    other.close().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn different_fs_fixtures_cross_devices() {
+    use assert_fs::prelude::*;
+    use std::os::unix::fs::MetadataExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let other = match assert_fs::TempDir::new_on_different_fs_than(&temp) {
+        Ok(other) => other,
+        Err(err) => {
+            assert!(err.to_string().contains(assert_fs::fixture::OTHER_FS_ENV));
+            return;
+        }
+    };
+    assert_ne!(
+        temp.path().metadata().unwrap().dev(),
+        other.path().metadata().unwrap().dev()
+    );
+
+    let source = temp.child("data.bin");
+    source.write_binary(b"payload").unwrap();
+    let err = std::fs::rename(source.path(), other.child("data.bin").path()).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EXDEV));
+
+    temp.close().unwrap();
+    other.close().unwrap();
+}
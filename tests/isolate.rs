@@ -0,0 +1,28 @@
+// Changes the working directory of the whole process, so it runs in its own test binary rather
+// than alongside tests that use relative paths.
+
+use assert_fs::prelude::*;
+
+#[test]
+fn run_isolated_restores_environment() {
+    let cwd = std::env::current_dir().unwrap();
+    let temp = assert_fs::TempDir::new().unwrap();
+    let seen = temp
+        .isolation()
+        .env("ASSERT_FS_ISOLATED", "yes")
+        .run(|| {
+            std::fs::write("out.txt", "data").unwrap();
+            (
+                std::env::var("ASSERT_FS_ISOLATED").unwrap(),
+                std::env::temp_dir(),
+            )
+        })
+        .unwrap();
+    assert_eq!(seen.0, "yes");
+    assert!(seen.1.starts_with(temp.path()));
+    temp.child("out.txt").assert("data");
+
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+    assert!(std::env::var_os("ASSERT_FS_ISOLATED").is_none());
+    temp.close().unwrap();
+}